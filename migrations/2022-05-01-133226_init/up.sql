@@ -9,7 +9,7 @@ CREATE TABLE "tasks" (
     description TEXT,
     duration INTEGER,
     due_date BIGINT,
-    project_id INTEGER,
+    project_id UUID,
     task_list TEXT[],
     updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
     created_at TIMESTAMP NOT NULL DEFAULT NOW()
@@ -5,8 +5,8 @@ CREATE TABLE "users" (
     email TEXT NOT NULL UNIQUE,
     password_hash TEXT NOT NULL,
     role TEXT NOT NULL,
-    access_token VARCHAR(512) DEFAULT "",
-    fcm_token VARCHAR(512) DEFAULT "",
+    access_token VARCHAR(512) DEFAULT '',
+    fcm_token VARCHAR(512) DEFAULT '',
     last_login TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     updated_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
     created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
@@ -1,3 +1,3 @@
 -- Your SQL goes here
 ALTER TABLE tasks
-ADD COLUMN user_id UUID NOT NULL;
+ADD COLUMN IF NOT EXISTS user_id UUID NOT NULL;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE users
+DROP COLUMN tokens_revoked_at;
+
+DROP TABLE "revoked_tokens";
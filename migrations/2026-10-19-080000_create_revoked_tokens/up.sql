@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE "revoked_tokens" (
+    jti UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    expires_at TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX revoked_tokens_expires_at_idx ON revoked_tokens (expires_at);
+
+ALTER TABLE users
+ADD COLUMN tokens_revoked_at TIMESTAMP;
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE users
+ADD COLUMN refresh_token VARCHAR(512) NOT NULL DEFAULT '';
+
+DROP TABLE "session_refresh_tokens";
+DROP TABLE "user_sessions";
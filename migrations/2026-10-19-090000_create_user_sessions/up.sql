@@ -0,0 +1,28 @@
+-- Your SQL goes here
+CREATE TABLE "user_sessions" (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    device_name TEXT,
+    ip_address TEXT,
+    user_agent TEXT,
+    revoked_at TIMESTAMP,
+    last_used_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX user_sessions_user_id_idx ON user_sessions (user_id);
+
+-- Every refresh rotates the token, all tokens of a session form one rotation family
+CREATE TABLE "session_refresh_tokens" (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    session_id UUID NOT NULL REFERENCES user_sessions (id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    rotated_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX session_refresh_tokens_session_id_idx ON session_refresh_tokens (session_id);
+
+ALTER TABLE users
+DROP COLUMN IF EXISTS refresh_token;
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+CREATE TABLE "account_tokens" (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    purpose TEXT NOT NULL,
+    token_hash TEXT NOT NULL UNIQUE,
+    expires_at TIMESTAMP NOT NULL,
+    used_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX account_tokens_user_id_purpose_idx ON account_tokens (user_id, purpose);
+
+ALTER TABLE users
+ADD COLUMN email_verified_at TIMESTAMP;
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE "mfa_role_requirements";
+DROP TABLE "mfa_challenges";
+DROP TABLE "mfa_recovery_codes";
+DROP TABLE "user_mfa";
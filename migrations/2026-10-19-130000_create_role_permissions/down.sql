@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+UPDATE api_keys SET permissions = '{}';
+
+DROP TABLE "role_permissions";
@@ -0,0 +1,28 @@
+-- Your SQL goes here
+CREATE TABLE "role_permissions" (
+    role TEXT NOT NULL,
+    permission TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (role, permission)
+);
+
+INSERT INTO role_permissions (role, permission)
+SELECT role, permission
+FROM (VALUES ('customer'), ('author'), ('admin'), ('super_admin')) AS roles (role)
+CROSS JOIN (VALUES ('tasks:read'), ('tasks:write'), ('tasks:delete'), ('account:read'), ('account:write'), ('account:delete')) AS permissions (permission);
+
+INSERT INTO role_permissions (role, permission)
+SELECT role, permission
+FROM (VALUES ('admin'), ('super_admin')) AS roles (role)
+CROSS JOIN (VALUES ('tasks:read:any'), ('tasks:write:any'), ('tasks:delete:any'), ('users:read'), ('users:write'), ('users:role'), ('users:delete'), ('mfa:manage')) AS permissions (permission);
+
+INSERT INTO role_permissions (role, permission) VALUES ('super_admin', 'permissions:manage');
+
+-- keys scoped with the former "read:<role>-tasks" strings get the grants of their owner's role
+UPDATE api_keys
+SET permissions = COALESCE((
+    SELECT array_agg(role_permissions.permission)
+    FROM role_permissions
+    JOIN users ON users.role = role_permissions.role
+    WHERE users.id = api_keys.user_id
+), '{}');
@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- Failed sign-in counters per email address, registered or not, and per client IP
+CREATE TABLE "login_throttles" (
+    scope TEXT NOT NULL,
+    subject TEXT NOT NULL,
+    failed_count INTEGER NOT NULL DEFAULT 0,
+    last_failed_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    locked_until TIMESTAMP,
+    PRIMARY KEY (scope, subject)
+);
+
+CREATE TABLE "audit_events" (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    event TEXT NOT NULL,
+    user_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    actor_id UUID REFERENCES users (id) ON DELETE SET NULL,
+    ip_address TEXT,
+    details TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX audit_events_user_id_idx ON audit_events (user_id);
+CREATE INDEX audit_events_event_idx ON audit_events (event, created_at);
@@ -0,0 +1,47 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX tasks_user_id_priority_idx;
+
+-- Back to free text, with the labels the former payloads wrote
+ALTER TABLE tasks
+    ALTER COLUMN typ DROP NOT NULL,
+    ALTER COLUMN typ DROP DEFAULT,
+    ALTER COLUMN typ TYPE TEXT USING (
+        CASE typ
+            WHEN 'personal' THEN 'Personal'
+            WHEN 'work' THEN 'Work'
+            ELSE 'None'
+        END
+    );
+
+ALTER TABLE tasks
+    ALTER COLUMN priority DROP NOT NULL,
+    ALTER COLUMN priority DROP DEFAULT,
+    ALTER COLUMN priority TYPE TEXT USING (
+        CASE priority
+            WHEN 'low' THEN 'Low'
+            WHEN 'medium' THEN 'Medium'
+            WHEN 'high' THEN 'High'
+            ELSE 'None'
+        END
+    );
+
+ALTER TABLE tasks
+    ALTER COLUMN status DROP NOT NULL,
+    ALTER COLUMN status DROP DEFAULT,
+    ALTER COLUMN status TYPE TEXT USING (
+        CASE status
+            WHEN 'to_do' THEN 'ToDo: (None)'
+            WHEN 'to_do_not_started' THEN 'ToDo: (Not Started)'
+            WHEN 'to_do_document' THEN 'ToDo: (Document)'
+            WHEN 'to_do_bug' THEN 'ToDo: (Bug)'
+            WHEN 'in_progress' THEN 'In Progress: (None)'
+            WHEN 'in_progress_doing' THEN 'In Progress: (Doing)'
+            WHEN 'in_progress_testing' THEN 'In Progress: (Testing)'
+            WHEN 'completed' THEN 'Completed'
+            ELSE 'None'
+        END
+    );
+
+DROP TYPE task_status;
+DROP TYPE task_priority;
+DROP TYPE task_type;
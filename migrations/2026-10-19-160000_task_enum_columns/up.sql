@@ -0,0 +1,63 @@
+-- Your SQL goes here
+-- Task type, priority and status become Postgres enums. Values are declared in rank order, so ORDER BY priority
+-- sorts none < low < medium < high
+CREATE TYPE task_type AS ENUM ('none', 'personal', 'work');
+CREATE TYPE task_priority AS ENUM ('none', 'low', 'medium', 'high');
+CREATE TYPE task_status AS ENUM (
+    'none',
+    'to_do',
+    'to_do_not_started',
+    'to_do_document',
+    'to_do_bug',
+    'in_progress',
+    'in_progress_doing',
+    'in_progress_testing',
+    'completed'
+);
+
+-- Existing values were free text written by the former payload labels ("Work", "ToDo: (Not Started)",
+-- "In Progress: (Doing)"...). They are compared lowercased without spaces or punctuation, anything else
+-- (NULL, unknown text) becomes 'none'
+ALTER TABLE tasks
+    ALTER COLUMN typ TYPE task_type USING (
+        CASE regexp_replace(lower(typ), '[^a-z]', '', 'g')
+            WHEN 'personal' THEN 'personal'
+            WHEN 'work' THEN 'work'
+            ELSE 'none'
+        END
+    )::task_type,
+    ALTER COLUMN typ SET DEFAULT 'none',
+    ALTER COLUMN typ SET NOT NULL;
+
+ALTER TABLE tasks
+    ALTER COLUMN priority TYPE task_priority USING (
+        CASE regexp_replace(lower(priority), '[^a-z]', '', 'g')
+            WHEN 'low' THEN 'low'
+            WHEN 'medium' THEN 'medium'
+            WHEN 'high' THEN 'high'
+            ELSE 'none'
+        END
+    )::task_priority,
+    ALTER COLUMN priority SET DEFAULT 'none',
+    ALTER COLUMN priority SET NOT NULL;
+
+ALTER TABLE tasks
+    ALTER COLUMN status TYPE task_status USING (
+        CASE regexp_replace(lower(status), '[^a-z]', '', 'g')
+            WHEN 'todo' THEN 'to_do'
+            WHEN 'todonone' THEN 'to_do'
+            WHEN 'todonotstarted' THEN 'to_do_not_started'
+            WHEN 'tododocument' THEN 'to_do_document'
+            WHEN 'todobug' THEN 'to_do_bug'
+            WHEN 'inprogress' THEN 'in_progress'
+            WHEN 'inprogressnone' THEN 'in_progress'
+            WHEN 'inprogressdoing' THEN 'in_progress_doing'
+            WHEN 'inprogresstesting' THEN 'in_progress_testing'
+            WHEN 'completed' THEN 'completed'
+            ELSE 'none'
+        END
+    )::task_status,
+    ALTER COLUMN status SET DEFAULT 'none',
+    ALTER COLUMN status SET NOT NULL;
+
+CREATE INDEX tasks_user_id_priority_idx ON tasks (user_id, priority);
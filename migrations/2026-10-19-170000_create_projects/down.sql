@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS tasks_project_id_idx;
+
+DROP TABLE IF EXISTS "projects";
@@ -0,0 +1,11 @@
+-- Your SQL goes here
+-- Declared in the schema since the beginning but never created, tasks without a project keep the nil id
+CREATE TABLE IF NOT EXISTS "projects" (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name TEXT NOT NULL,
+    description TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS tasks_project_id_idx ON tasks (project_id);
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+-- One secret calendar feed URL per user, only the digest of its token is kept
+CREATE TABLE "calendar_feeds" (
+    user_id UUID PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE "import_skipped_items";
+DROP TABLE "import_jobs";
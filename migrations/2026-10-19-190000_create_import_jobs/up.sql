@@ -0,0 +1,27 @@
+-- Your SQL goes here
+-- Imports running in the background and their report, whichever instance runs them
+CREATE TABLE "import_jobs" (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    source TEXT NOT NULL CHECK (source IN ('trello', 'todoist', 'notion')),
+    state TEXT NOT NULL DEFAULT 'queued' CHECK (state IN ('queued', 'running', 'completed', 'failed')),
+    total INTEGER NOT NULL DEFAULT 0,
+    processed INTEGER NOT NULL DEFAULT 0,
+    project_ids UUID[] NOT NULL DEFAULT '{}',
+    task_ids UUID[] NOT NULL DEFAULT '{}',
+    error TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- One import at a time per user
+CREATE UNIQUE INDEX import_jobs_unfinished_user_id_idx ON import_jobs (user_id) WHERE state IN ('queued', 'running');
+
+-- What the export holds that has no place in a task, in file order
+CREATE TABLE "import_skipped_items" (
+    job_id UUID NOT NULL REFERENCES import_jobs (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    item TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    PRIMARY KEY (job_id, position)
+);
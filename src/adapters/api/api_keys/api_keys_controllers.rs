@@ -0,0 +1,73 @@
+use crate::{
+    adapters::api::{
+        api_keys::{
+            api_keys_mappers::ApiKeyPresenterMapper,
+            api_keys_payloads::{ApiKeyCreatePayload, ApiKeyDataPayload},
+            api_keys_presenters::ApiKeyPresenter,
+        },
+        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{
+            api_key::{create_api_key_usecase::CreateApiKeyUseCase, get_all_api_keys_usecase::GetAllApiKeysUseCase, revoke_one_api_key_usecase::RevokeOneApiKeyUseCase},
+            interfaces::AbstractUseCase,
+        },
+        utils::access_control::{
+            extractors::{authorized::{require, Authorized}, signed_in::SignedIn},
+        },
+    },
+    domain::{api_key_entity::ApiKeyEntity, error::ApiError},
+};
+use actix_web::{delete, get, post, web, HttpResponse};
+use reqwest::StatusCode;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_all_api_keys_own).service(create_api_key_own).service(revoke_one_api_key_own);
+}
+
+#[get("/all_own")]
+async fn get_all_api_keys_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountRead>>) -> Result<HttpResponse, ErrorResponse> {
+    let api_key_payload = ApiKeyDataPayload::new(None, Some(auth.sub.clone()));
+    let get_all_api_keys_usecase = GetAllApiKeysUseCase::new(&api_key_payload, &data.api_keys_repository);
+    let api_keys: Result<Vec<ApiKeyEntity>, ApiError> = get_all_api_keys_usecase.execute().await;
+
+    match api_keys {
+        Ok(datas) => Ok(SuccessResponse::new(
+            StatusCode::OK,
+            "API keys retrieved successfully",
+            datas.into_iter().map(ApiKeyPresenterMapper::to_api).collect::<Vec<ApiKeyPresenter>>(),
+        )
+        .to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[post("/one_own")]
+async fn create_api_key_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountWrite>>, path: web::Json<ApiKeyCreatePayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut api_key_payload = path.into_inner();
+    api_key_payload.user_id = Some(auth.sub.clone());
+    let create_api_key_usecase = CreateApiKeyUseCase::new(&api_key_payload, &data.api_keys_repository);
+
+    match create_api_key_usecase.execute().await {
+        Ok(api_key) => Ok(SuccessResponse::new(
+            StatusCode::CREATED,
+            "API key created successfully, it will not be shown again",
+            ApiKeyPresenterMapper::to_api(api_key),
+        )
+        .to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[delete("/one_own")]
+async fn revoke_one_api_key_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountWrite>>, path: web::Json<ApiKeyDataPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut api_key_payload = path.into_inner();
+    api_key_payload.user_id = Some(auth.sub.clone());
+    let revoke_one_api_key_usecase = RevokeOneApiKeyUseCase::new(&api_key_payload, &data.api_keys_repository);
+
+    match revoke_one_api_key_usecase.execute().await {
+        Ok(api_key) => Ok(SuccessResponse::new(StatusCode::OK, "API key revoked successfully", ApiKeyPresenterMapper::to_api(api_key)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::api_key_entity::ApiKeyEntity;
+
+use super::api_keys_payloads::ApiKeyPayload;
+use super::api_keys_presenters::ApiKeyPresenter;
+
+pub struct ApiKeyPresenterMapper {}
+
+impl ApiMapper<ApiKeyEntity, ApiKeyPresenter, ApiKeyPayload> for ApiKeyPresenterMapper {
+    fn to_api(entity: ApiKeyEntity) -> ApiKeyPresenter {
+        ApiKeyPresenter {
+            api_key_id: entity.id,
+            name: entity.name,
+            prefix: entity.prefix,
+            key: entity.key,
+            permissions: entity.permissions,
+            expires_at: entity.expires_at.map(naive_datetime_to_unixtimemillis),
+            last_used_at: entity.last_used_at.map(naive_datetime_to_unixtimemillis),
+            revoked_at: entity.revoked_at.map(naive_datetime_to_unixtimemillis),
+            created_at: naive_datetime_to_unixtimemillis(entity.created_at),
+        }
+    }
+
+    fn to_entity(_payload: ApiKeyPayload) -> ApiKeyEntity {
+        panic!("not implemented");
+    }
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    // Get the Unix timestamp in seconds and convert to milliseconds
+    datetime.and_utc().timestamp_millis()
+}
@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ApiKeyCreatePayload {
+    #[serde(skip_deserializing)]
+    pub user_id: Option<String>,
+    pub name: String,
+    // Defaults to every permission of the owner's role
+    pub permissions: Option<Vec<String>>,
+    // Unix time in milliseconds, never expires when missing
+    pub expires_at: Option<i64>,
+}
+
+impl ApiKeyCreatePayload {
+    pub fn new(user_id: Option<String>, name: String, permissions: Option<Vec<String>>, expires_at: Option<i64>) -> Self {
+        ApiKeyCreatePayload {
+            user_id,
+            name,
+            permissions,
+            expires_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct ApiKeyDataPayload {
+    pub api_key_id: Option<String>,
+    pub user_id: Option<String>,
+}
+
+impl ApiKeyDataPayload {
+    pub fn new(api_key_id: Option<String>, user_id: Option<String>) -> Self {
+        ApiKeyDataPayload { api_key_id, user_id }
+    }
+}
+
+pub struct ApiKeyPayload {}
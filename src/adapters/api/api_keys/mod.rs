@@ -0,0 +1,4 @@
+pub mod api_keys_controllers;
+pub mod api_keys_mappers;
+pub mod api_keys_payloads;
+pub mod api_keys_presenters;
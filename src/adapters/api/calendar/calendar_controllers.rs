@@ -0,0 +1,158 @@
+use crate::{
+    adapters::api::{
+        calendar::{
+            calendar_formats::{calendar, CALENDAR_CONTENT_TYPE},
+            calendar_mappers::CalendarFeedPresenterMapper,
+            calendar_payloads::{CalendarFeedOwnerPayload, CalendarFeedQueryPayload, CalendarFeedTokenPayload},
+            calendar_presenters::CalendarFeedPresenter,
+        },
+        shared::{
+            app_state::AppState,
+            error_presenter::{ErrorPresenter, ErrorResponse},
+            success_presenter::{EmptySuccessResponse, SuccessResponse},
+            validated_json::ValidatedQuery,
+        },
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{
+            calendar::{
+                get_calendar_feed_tasks_usecase::GetCalendarFeedTasksUseCase, regenerate_calendar_feed_usecase::RegenerateCalendarFeedUseCase,
+                revoke_calendar_feed_usecase::RevokeCalendarFeedUseCase,
+            },
+            interfaces::AbstractUseCase,
+        },
+        utils::access_control::extractors::authorized::{require, Authorized},
+    },
+    domain::calendar_feed_entity::CalendarFeedEntity,
+};
+use actix_web::{delete, get, http::header::CacheControl, http::header::CacheDirective, post, web, HttpResponse};
+use chrono::Utc;
+use reqwest::StatusCode;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(regenerate_own_feed, revoke_own_feed, get_feed, regenerate_feed, revoke_feed))]
+pub struct CalendarApi;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(regenerate_own_feed)
+        .service(revoke_own_feed)
+        .service(get_feed)
+        .service(regenerate_feed)
+        .service(revoke_feed);
+}
+
+#[utoipa::path(
+    tag = "calendar",
+    summary = "Create or replace the calendar feed URL of the caller",
+    description = "The previous URL stops working. Calendar apps subscribe to `feed_url` without credentials, keep it secret.",
+    responses(
+        (status = 200, description = "Success, `code` is 201", body = SuccessResponse<CalendarFeedPresenter>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/feed_own")]
+async fn regenerate_own_feed(data: web::Data<AppState>, auth: Authorized<require::TasksRead>) -> Result<HttpResponse, ErrorResponse> {
+    let owner_payload = CalendarFeedOwnerPayload::from_user_id(auth.sub.clone());
+    let regenerate_calendar_feed_usecase = RegenerateCalendarFeedUseCase::new(&owner_payload, &data.calendar_feeds_repository);
+
+    match regenerate_calendar_feed_usecase.execute().await {
+        Ok(feed) => Ok(SuccessResponse::new(StatusCode::CREATED, "Calendar feed created successfully", feed_presenter(&data, feed)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "calendar",
+    summary = "Revoke the calendar feed URL of the caller",
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "No calendar feed", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[delete("/feed_own")]
+async fn revoke_own_feed(data: web::Data<AppState>, auth: Authorized<require::TasksRead>) -> Result<HttpResponse, ErrorResponse> {
+    let owner_payload = CalendarFeedOwnerPayload::from_user_id(auth.sub.clone());
+    revoke(&data, &owner_payload).await
+}
+
+#[utoipa::path(
+    tag = "calendar",
+    summary = "iCalendar feed of the tasks with a due date",
+    description = "Public, the token is the credential. Every task of the feed owner with a due date, as VTODO entries \
+        or, with `kind=event`, as VEVENT entries lasting the task duration in minutes.",
+    params(CalendarFeedTokenPayload, CalendarFeedQueryPayload),
+    responses(
+        (status = 200, description = "RFC 5545 calendar", body = String, content_type = "text/calendar"),
+        (status = 404, description = "Unknown or revoked token", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    )
+)]
+#[get("/feed/{token}.ics")]
+async fn get_feed(data: web::Data<AppState>, path: web::Path<CalendarFeedTokenPayload>, query: ValidatedQuery<CalendarFeedQueryPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let query = query.into_inner();
+    let get_calendar_feed_tasks_usecase = GetCalendarFeedTasksUseCase::new(&path.token, &query, &data.calendar_feeds_repository, &data.tasks_repository);
+    let tasks = get_calendar_feed_tasks_usecase.execute().await.map_err(ErrorResponse::map_io_error)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type(CALENDAR_CONTENT_TYPE)
+        .insert_header(CacheControl(vec![CacheDirective::Private, CacheDirective::NoCache]))
+        .body(calendar(&tasks, query.kind.unwrap_or_default(), &data.app_name, Utc::now().naive_utc())))
+}
+
+#[utoipa::path(
+    tag = "calendar",
+    summary = "Replace a calendar feed URL knowing only its token",
+    description = "For a leaked URL, no sign in needed. The old URL stops working.",
+    params(CalendarFeedTokenPayload),
+    responses(
+        (status = 200, description = "Success, `code` is 201", body = SuccessResponse<CalendarFeedPresenter>),
+        (status = 404, description = "Unknown or revoked token", body = ErrorPresenter),
+    )
+)]
+#[post("/feed/{token}/regenerate")]
+async fn regenerate_feed(data: web::Data<AppState>, path: web::Path<CalendarFeedTokenPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let owner_payload = CalendarFeedOwnerPayload::from_token(path.into_inner().token);
+    let regenerate_calendar_feed_usecase = RegenerateCalendarFeedUseCase::new(&owner_payload, &data.calendar_feeds_repository);
+
+    match regenerate_calendar_feed_usecase.execute().await {
+        Ok(feed) => Ok(SuccessResponse::new(StatusCode::CREATED, "Calendar feed created successfully", feed_presenter(&data, feed)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "calendar",
+    summary = "Revoke a calendar feed URL knowing only its token",
+    params(CalendarFeedTokenPayload),
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 404, description = "Unknown or revoked token", body = ErrorPresenter),
+    )
+)]
+#[delete("/feed/{token}")]
+async fn revoke_feed(data: web::Data<AppState>, path: web::Path<CalendarFeedTokenPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let owner_payload = CalendarFeedOwnerPayload::from_token(path.into_inner().token);
+    revoke(&data, &owner_payload).await
+}
+
+async fn revoke(data: &AppState, owner_payload: &CalendarFeedOwnerPayload) -> Result<HttpResponse, ErrorResponse> {
+    let revoke_calendar_feed_usecase = RevokeCalendarFeedUseCase::new(owner_payload, &data.calendar_feeds_repository);
+
+    match revoke_calendar_feed_usecase.execute().await {
+        Ok(_) => Ok(SuccessResponse::new(StatusCode::OK, "Calendar feed revoked successfully", ()).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+fn feed_presenter(data: &AppState, feed: CalendarFeedEntity) -> CalendarFeedPresenter {
+    let mut presenter = CalendarFeedPresenterMapper::to_api(feed);
+    presenter.feed_url = format!("{}{}", data.settings.app.base_url.trim_end_matches('/'), presenter.feed_url);
+    presenter
+}
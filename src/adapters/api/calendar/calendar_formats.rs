@@ -0,0 +1,216 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::{
+    adapters::api::calendar::calendar_payloads::CalendarEntryKind,
+    domain::task_entity::{TaskEntity, TaskPriority, TaskStatus, TaskType},
+};
+
+pub const CALENDAR_CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+// RFC 5545 3.1, longer content lines are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+// Writes the tasks as an RFC 5545 calendar. Tasks carry no recurrence, so no entry has an RRULE
+pub fn calendar(tasks: &[TaskEntity], kind: CalendarEntryKind, app_name: &str, now: NaiveDateTime) -> String {
+    let mut out = String::new();
+    line(&mut out, "BEGIN", "VCALENDAR");
+    line(&mut out, "VERSION", "2.0");
+    line(&mut out, "PRODID", &format!("-//{}//Tasks//EN", escape(app_name)));
+    line(&mut out, "CALSCALE", "GREGORIAN");
+    line(&mut out, "METHOD", "PUBLISH");
+    line(&mut out, "X-WR-CALNAME", &escape(&format!("{} tasks", app_name)));
+    for task in tasks {
+        match kind {
+            CalendarEntryKind::Todo => todo(&mut out, task, now),
+            CalendarEntryKind::Event => event(&mut out, task, now),
+        }
+    }
+    line(&mut out, "END", "VCALENDAR");
+    out
+}
+
+fn todo(out: &mut String, task: &TaskEntity, now: NaiveDateTime) {
+    line(out, "BEGIN", "VTODO");
+    common(out, task, now);
+    line(out, "DUE", &millis_to_utc(task.due_date));
+    line(out, "STATUS", todo_status(task.status));
+    if task.status == TaskStatus::Completed {
+        line(out, "PERCENT-COMPLETE", "100");
+    }
+    description(out, task, None);
+    line(out, "END", "VTODO");
+}
+
+// Events have no status of their own fitting a task, it goes in the description instead
+fn event(out: &mut String, task: &TaskEntity, now: NaiveDateTime) {
+    line(out, "BEGIN", "VEVENT");
+    common(out, task, now);
+    line(out, "DTSTART", &millis_to_utc(task.due_date));
+    if task.duration > 0 {
+        line(out, "DTEND", &millis_to_utc(task.due_date + i64::from(task.duration) * 60_000));
+    }
+    line(out, "TRANSP", "TRANSPARENT");
+    description(out, task, Some(task.status));
+    line(out, "END", "VEVENT");
+}
+
+fn common(out: &mut String, task: &TaskEntity, now: NaiveDateTime) {
+    line(out, "UID", &format!("{}@tasktracker", task.id));
+    line(out, "DTSTAMP", &datetime_to_utc(now));
+    line(out, "CREATED", &datetime_to_utc(task.created_at));
+    line(out, "LAST-MODIFIED", &datetime_to_utc(task.updated_at));
+    line(out, "SUMMARY", &escape(&task.title));
+    if let Some(priority) = priority(task.priority) {
+        line(out, "PRIORITY", priority);
+    }
+    if task.typ != TaskType::None {
+        line(out, "CATEGORIES", &escape(task.typ.as_str()));
+    }
+}
+
+fn description(out: &mut String, task: &TaskEntity, status: Option<TaskStatus>) {
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(status) = status {
+        parts.push(format!("Status: {}", status.as_str()));
+    }
+    if !task.description.is_empty() {
+        parts.push(task.description.clone());
+    }
+    if !task.task_list.is_empty() {
+        parts.push(task.task_list.iter().map(|item| format!("- {}", item)).collect::<Vec<_>>().join("\n"));
+    }
+    if !parts.is_empty() {
+        line(out, "DESCRIPTION", &escape(&parts.join("\n\n")));
+    }
+}
+
+fn todo_status(status: TaskStatus) -> &'static str {
+    match status {
+        TaskStatus::InProgress(_) => "IN-PROCESS",
+        TaskStatus::Completed => "COMPLETED",
+        TaskStatus::None | TaskStatus::ToDo(_) => "NEEDS-ACTION",
+    }
+}
+
+// 1 is the highest and 9 the lowest, 0 or no property means undefined
+fn priority(priority: TaskPriority) -> Option<&'static str> {
+    match priority {
+        TaskPriority::High => Some("1"),
+        TaskPriority::Medium => Some("5"),
+        TaskPriority::Low => Some("9"),
+        TaskPriority::None => None,
+    }
+}
+
+fn millis_to_utc(millis: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(millis).unwrap_or_default().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn datetime_to_utc(datetime: NaiveDateTime) -> String {
+    datetime.and_utc().format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// TEXT values, RFC 5545 3.3.11
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Folds on character boundaries, a continuation line starts with a space that counts towards its length
+fn line(out: &mut String, name: &str, value: &str) {
+    let content = format!("{}:{}", name, value);
+    let mut octets = 0;
+    for c in content.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TaskEntityBuilder;
+    use crate::domain::task_entity::TaskStatusInProgress;
+
+    fn task() -> TaskEntity {
+        TaskEntityBuilder::new("task1")
+            .title("Ship, then rest; maybe")
+            .typ(TaskType::Work)
+            .priority(TaskPriority::High)
+            .status(TaskStatus::InProgress(TaskStatusInProgress::Doing))
+            .description("Notes")
+            .duration(90)
+            .due_date(1_700_086_400_000)
+            .task_list(&["draft"])
+            .created_at(DateTime::from_timestamp(1_700_000_000, 0).unwrap().naive_utc())
+            .build()
+    }
+
+    #[test]
+    fn test_should_write_tasks_as_todos() {
+        // given a task in progress
+        let now = DateTime::from_timestamp(1_700_000_100, 0).unwrap().naive_utc();
+
+        // when writing a calendar of todos
+        let ics = calendar(&[task()], CalendarEntryKind::Todo, "Tasks", now);
+
+        // then every line ends with CRLF and the task fields are mapped
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        for expected in [
+            "BEGIN:VTODO",
+            "UID:task1@tasktracker",
+            "DTSTAMP:20231114T221500Z",
+            "SUMMARY:Ship\\, then rest\\; maybe",
+            "DUE:20231115T221320Z",
+            "STATUS:IN-PROCESS",
+            "PRIORITY:1",
+            "CATEGORIES:work",
+            "DESCRIPTION:Notes\\n\\n- draft",
+        ] {
+            assert!(ics.contains(&format!("{}\r\n", expected)), "missing {}", expected);
+        }
+    }
+
+    #[test]
+    fn test_should_write_tasks_as_events_lasting_their_duration() {
+        // given a task of 90 minutes
+        let now = DateTime::from_timestamp(1_700_000_100, 0).unwrap().naive_utc();
+
+        // when writing a calendar of events
+        let ics = calendar(&[task()], CalendarEntryKind::Event, "Tasks", now);
+
+        // then the event ends 90 minutes after the due date and the status is described
+        assert!(ics.contains("DTSTART:20231115T221320Z\r\nDTEND:20231115T234320Z\r\n"));
+        assert!(ics.contains("DESCRIPTION:Status: in_progress_doing\\n\\nNotes\\n\\n- draft\r\n"));
+        assert!(!ics.contains("VTODO"));
+    }
+
+    #[test]
+    fn test_should_fold_long_lines_on_character_boundaries() {
+        // given a title of multi-byte characters
+        let mut out = String::new();
+
+        // when writing it
+        line(&mut out, "SUMMARY", &"é".repeat(80));
+
+        // then no line is over 75 octets and unfolding gives the value back
+        assert!(out.split("\r\n").all(|folded| folded.len() <= MAX_LINE_OCTETS));
+        assert_eq!(out.replace("\r\n ", ""), format!("SUMMARY:{}\r\n", "é".repeat(80)));
+    }
+}
@@ -0,0 +1,29 @@
+use chrono::NaiveDateTime;
+
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::calendar_feed_entity::CalendarFeedEntity;
+
+use super::calendar_payloads::CalendarFeedPayload;
+use super::calendar_presenters::CalendarFeedPresenter;
+
+pub struct CalendarFeedPresenterMapper {}
+
+impl ApiMapper<CalendarFeedEntity, CalendarFeedPresenter, CalendarFeedPayload> for CalendarFeedPresenterMapper {
+    // `feed_url` is relative to the server, the controller knows its public address
+    fn to_api(entity: CalendarFeedEntity) -> CalendarFeedPresenter {
+        let token = entity.token.unwrap_or_default();
+        CalendarFeedPresenter {
+            feed_url: format!("/api/v1/calendar/feed/{}.ics", token),
+            token,
+            created_at: naive_datetime_to_unixtimemillis(entity.created_at),
+        }
+    }
+
+    fn to_entity(_payload: CalendarFeedPayload) -> CalendarFeedEntity {
+        panic!("not implemented");
+    }
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_millis()
+}
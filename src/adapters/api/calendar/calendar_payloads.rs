@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::application::utils::validate_params;
+
+// The owner of a feed: the signed in user, or whoever holds the feed URL since calendar clients send no credentials
+#[derive(Debug, Default)]
+pub struct CalendarFeedOwnerPayload {
+    pub user_id: Option<String>,
+    pub token: Option<String>,
+}
+
+impl CalendarFeedOwnerPayload {
+    pub fn from_user_id(user_id: String) -> Self {
+        CalendarFeedOwnerPayload {
+            user_id: Some(user_id),
+            token: None,
+        }
+    }
+
+    pub fn from_token(token: String) -> Self {
+        CalendarFeedOwnerPayload { user_id: None, token: Some(token) }
+    }
+}
+
+#[derive(Serialize, Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Path)]
+pub struct CalendarFeedTokenPayload {
+    pub token: String,
+}
+
+// How tasks show up in the calendar, most calendar apps only display events
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CalendarEntryKind {
+    // VTODO, with the task status
+    #[default]
+    Todo,
+    // VEVENT at the due date, lasting the task duration
+    Event,
+}
+
+#[derive(Serialize, Deserialize, IntoParams, Validate, Default, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct CalendarFeedQueryPayload {
+    #[validate(custom(function = "validate_params::uuid"))]
+    pub project_id: Option<String>,
+    pub kind: Option<CalendarEntryKind>,
+}
+
+pub struct CalendarFeedPayload {}
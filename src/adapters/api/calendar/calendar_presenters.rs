@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// The URL holds the secret, it is only shown when generated
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct CalendarFeedPresenter {
+    #[schema(example = "https://tasks.example.com/api/v1/calendar/feed/Xq3...ics")]
+    pub feed_url: String,
+    pub token: String,
+    pub created_at: i64,
+}
@@ -0,0 +1,5 @@
+pub mod calendar_controllers;
+pub mod calendar_formats;
+pub mod calendar_mappers;
+pub mod calendar_payloads;
+pub mod calendar_presenters;
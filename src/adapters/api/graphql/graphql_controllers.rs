@@ -0,0 +1,126 @@
+use std::{future, pin::pin, str::FromStr, time::Duration};
+
+use actix_web::{
+    error::ErrorBadRequest,
+    get,
+    http::header::{self, HeaderValue},
+    post, rt, web, HttpRequest, HttpResponse,
+};
+use actix_ws::{CloseCode, CloseReason, Message};
+use async_graphql::{
+    futures_util::StreamExt,
+    http::{GraphiQLSource, WebSocket, WebSocketProtocols, WsMessage},
+};
+use chrono::Utc;
+
+use crate::{
+    adapters::api::shared::app_state::AppState,
+    application::utils::access_control::{caller::Caller, extractors::claims::Claims},
+};
+
+use super::graphql_schema::{build_schema, request_data, AppSchema};
+
+pub const GRAPHQL_PATH: &str = "/graphql";
+pub const GRAPHQL_WS_PATH: &str = "/graphql/ws";
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.app_data(web::Data::new(build_schema())).service(post_graphql).service(get_graphiql).service(get_graphql_ws);
+}
+
+#[post("/graphql")]
+async fn post_graphql(data: web::Data<AppState>, schema: web::Data<AppSchema>, claims: Claims, request: web::Json<async_graphql::Request>) -> HttpResponse {
+    let auth = Caller::resolve(&data, claims).await;
+    let mut request = request.into_inner();
+    request.data = request_data(data, auth);
+
+    HttpResponse::Ok().json(schema.execute(request).await)
+}
+
+// Query explorer, it sends the token the user pastes under Headers like any other client
+#[get("/graphql")]
+async fn get_graphiql() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(GraphiQLSource::build().endpoint(GRAPHQL_PATH).subscription_endpoint(GRAPHQL_WS_PATH).finish())
+}
+
+// Subscriptions over the `graphql-transport-ws` (or legacy `graphql-ws`) protocol. The upgrade request is authenticated
+// like any other, the bearer token or API key has to come with it rather than in the `connection_init` payload. The
+// socket is closed when that token expires.
+#[get("/graphql/ws")]
+async fn get_graphql_ws(req: HttpRequest, body: web::Payload, data: web::Data<AppState>, schema: web::Data<AppSchema>, claims: Claims) -> actix_web::Result<HttpResponse> {
+    let protocol = req
+        .headers()
+        .get(header::SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|protocols| protocols.split(',').find_map(|protocol| WebSocketProtocols::from_str(protocol.trim()).ok()))
+        .ok_or_else(|| ErrorBadRequest("Unsupported websocket subprotocol"))?;
+    let expires_in = Duration::from_secs(claims.exp.saturating_sub(Utc::now().timestamp() as usize) as u64);
+    let auth = Caller::resolve(&data, claims).await;
+
+    let (mut response, session, messages) = actix_ws::handle(&req, body)?;
+    response
+        .headers_mut()
+        .insert(header::SEC_WEBSOCKET_PROTOCOL, HeaderValue::from_static(protocol.sec_websocket_protocol()));
+
+    let schema = AppSchema::clone(&schema);
+    let pong_session = session.clone();
+    let incoming = messages
+        .take_while(|message| future::ready(!matches!(message, Ok(Message::Close(_)) | Err(_))))
+        .filter_map(move |message| {
+            let mut pong_session = pong_session.clone();
+            async move {
+                match message {
+                    Ok(Message::Text(text)) => Some(text.into_bytes()),
+                    Ok(Message::Binary(bytes)) => Some(bytes),
+                    Ok(Message::Ping(bytes)) => {
+                        let _ = pong_session.pong(&bytes).await;
+                        None
+                    }
+                    _ => None,
+                }
+            }
+        });
+
+    rt::spawn(async move {
+        let mut session = session;
+        let mut outgoing = pin!(WebSocket::new(schema, incoming, protocol).on_connection_init(move |_| async move { Ok(request_data(data, auth)) }));
+        let mut expiry = pin!(rt::time::sleep(expires_in));
+        loop {
+            let message = tokio::select! {
+                message = outgoing.next() => message,
+                _ = &mut expiry => {
+                    let _ = session
+                        .close(Some(CloseReason {
+                            code: CloseCode::Policy,
+                            description: Some(String::from("Token expired")),
+                        }))
+                        .await;
+                    return;
+                }
+            };
+            let Some(message) = message else {
+                break;
+            };
+            match message {
+                WsMessage::Text(text) => {
+                    if session.text(text).await.is_err() {
+                        return;
+                    }
+                }
+                WsMessage::Close(code, reason) => {
+                    let _ = session
+                        .close(Some(CloseReason {
+                            code: code.into(),
+                            description: Some(reason),
+                        }))
+                        .await;
+                    return;
+                }
+            }
+        }
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
+}
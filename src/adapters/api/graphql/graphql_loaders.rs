@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use actix_web::web;
+use async_graphql::{dataloader::Loader, Error};
+
+use crate::{
+    adapters::api::shared::app_state::AppState,
+    application::usecases::{interfaces::AbstractUseCase, project::get_projects_by_ids_usecase::GetProjectsByIdsUseCase, user::get_users_by_ids_usecase::GetUsersByIdsUseCase},
+    domain::{project_entity::ProjectEntity, user_entity::UserAllEntity},
+};
+
+use super::graphql_schema::run_use_case;
+
+// Assignees of every task in a response, fetched with one query instead of one per task
+pub struct UserLoader {
+    state: web::Data<AppState>,
+}
+
+impl UserLoader {
+    pub fn new(state: web::Data<AppState>) -> Self {
+        UserLoader { state }
+    }
+}
+
+impl Loader<String> for UserLoader {
+    type Value = UserAllEntity;
+    type Error = Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, UserAllEntity>, Error> {
+        let user_ids = keys.to_vec();
+        let users = run_use_case(
+            &self.state,
+            move |state| async move { GetUsersByIdsUseCase::new(&user_ids, &state.users_repository).execute().await },
+        )
+        .await?;
+
+        Ok(users.into_iter().map(|user| (user.id.clone(), user)).collect())
+    }
+}
+
+// Same for the projects of the tasks
+pub struct ProjectLoader {
+    state: web::Data<AppState>,
+}
+
+impl ProjectLoader {
+    pub fn new(state: web::Data<AppState>) -> Self {
+        ProjectLoader { state }
+    }
+}
+
+impl Loader<String> for ProjectLoader {
+    type Value = ProjectEntity;
+    type Error = Error;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, ProjectEntity>, Error> {
+        let project_ids = keys.to_vec();
+        let projects = run_use_case(&self.state, move |state| async move {
+            GetProjectsByIdsUseCase::new(&project_ids, &state.projects_repository).execute().await
+        })
+        .await?;
+
+        Ok(projects.into_iter().map(|project| (project.id.clone(), project)).collect())
+    }
+}
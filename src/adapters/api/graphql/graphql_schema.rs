@@ -0,0 +1,396 @@
+use std::future::Future;
+
+use actix_web::web;
+use async_graphql::{
+    connection::{query, Connection, CursorType, Edge},
+    dataloader::DataLoader,
+    futures_util::{stream, Stream, StreamExt},
+    Context, Data, Error, ErrorExtensions, Object, OutputType, Result, Schema, Subscription, Value, ID,
+};
+use chrono::Utc;
+use serde_json::json;
+use tokio::sync::broadcast::error::RecvError;
+use validator::Validate;
+
+use crate::{
+    adapters::api::{
+        shared::{app_state::AppState, blocking::run_blocking, validated_json::field_errors},
+        tasks::tasks_payloads::{TaskCreatePayload, TaskDataPayload, TaskListPagePayload, TaskUpdatePayload},
+        users::users_payloads::{UserIdPayload, UserPagePayload},
+    },
+    application::{
+        usecases::{
+            interfaces::AbstractUseCase,
+            task::{
+                delete_one_task_by_id_usecase::DeleteOneTaskByIdUseCase, get_one_task_by_id_usecase::GetOneTaskByIdUseCase, get_task_list_page_usecase::GetTaskListPageUseCase,
+                post_one_task_usecase::PostOneTaskUseCase, update_one_task_usecase::UpdateOneTaskUseCase,
+            },
+            user::{get_one_user_by_id_usecase::GetOneUserByIdUseCase, get_users_page_usecase::GetUsersPageUseCase},
+        },
+        utils::{
+            access_control::{
+                caller::Caller,
+                extractors::claims::{Claims, Permission},
+            },
+            error_handling_utils::ErrorHandlingUtils,
+        },
+    },
+    domain::{
+        error::{ApiError, DomainError, FieldError},
+        task_entity::TaskChangeKind,
+    },
+};
+
+use super::{
+    graphql_loaders::{ProjectLoader, UserLoader},
+    graphql_types::{CreateTaskInput, Task, TaskChangeEvent, TaskCursor, TaskSortValue, TaskSummary, UpdateTaskInput, User, UserCursor},
+};
+
+pub type AppSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+const MAX_QUERY_DEPTH: usize = 10;
+const MAX_QUERY_COMPLEXITY: usize = 1_000;
+// Connections return this many nodes when `first` is not given, and never more
+const MAX_PAGE_SIZE: usize = 100;
+
+pub fn build_schema() -> AppSchema {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+// Everything a query, mutation or subscription reads from its context. Loaders are per request so a batch never
+// mixes the callers of two requests.
+pub fn request_data(state: web::Data<AppState>, auth: Caller) -> Data {
+    let mut data = Data::default();
+    data.insert(DataLoader::new(UserLoader::new(state.clone()), tokio::spawn));
+    data.insert(DataLoader::new(ProjectLoader::new(state.clone()), tokio::spawn));
+    data.insert(state);
+    data.insert(auth);
+    data
+}
+
+// Resolvers have to be Send, use cases run on the blocking pool
+pub async fn run_use_case<T, F, Fut>(state: &web::Data<AppState>, use_case: F) -> Result<T>
+where
+    F: FnOnce(web::Data<AppState>) -> Fut + Send + 'static,
+    Fut: Future<Output = std::result::Result<T, ApiError>>,
+    T: Send + 'static,
+{
+    let state = state.clone();
+    run_blocking(move || async move { use_case(state).await.map_err(graphql_error) })
+        .await
+        .map_err(|e| Error::new(e.to_string()))?
+}
+
+// Same message as the REST error body, the status and `error_code` go to the extensions
+pub fn graphql_error(error: ApiError) -> Error {
+    let fields: Vec<_> = error
+        .fields
+        .iter()
+        .map(|field| json!({ "field": field.field, "code": field.code, "message": field.message }))
+        .collect();
+    Error::new(error.message).extend_with(|_, extensions| {
+        extensions.set("code", error.error_code);
+        extensions.set("status", error.code);
+        if !fields.is_empty() {
+            extensions.set("fields", Value::from_json(json!(fields)).unwrap_or_default());
+        }
+    })
+}
+
+// Inputs go through the `#[validate]` rules of the REST payloads, fields are reported under their GraphQL names
+fn validate<T: Validate>(payload: T) -> Result<T> {
+    match payload.validate() {
+        Ok(()) => Ok(payload),
+        Err(errors) => {
+            let fields = field_errors(&errors)
+                .into_iter()
+                .map(|field| FieldError {
+                    field: input_field_name(&field.field),
+                    ..field
+                })
+                .collect();
+            let error = DomainError::Validation {
+                message: String::from("Invalid input"),
+                fields,
+            };
+            Err(graphql_error(ErrorHandlingUtils::application_error("Invalid input", Some(error))))
+        }
+    }
+}
+
+fn input_field_name(field: &str) -> String {
+    match field {
+        "task_id" => String::from("id"),
+        "user_id" => String::from("assigneeId"),
+        other => {
+            let mut words = other.split('_');
+            let first = words.next().unwrap_or_default().to_string();
+            words.fold(first, |name, word| {
+                let mut chars = word.chars();
+                let capitalized = chars.next().map_or_else(String::new, |c| c.to_uppercase().chain(chars).collect());
+                name + &capitalized
+            })
+        }
+    }
+}
+
+fn context<'a>(ctx: &Context<'a>) -> (&'a web::Data<AppState>, &'a Caller) {
+    (ctx.data_unchecked::<web::Data<AppState>>(), ctx.data_unchecked::<Caller>())
+}
+
+// Connections page forward only, each page is one keyset query starting after the cursor. `load` gets that cursor and
+// how many nodes to read, one more than the page tells whether there is a next one.
+async fn paginate<C, T, F, Fut>(after: Option<String>, first: Option<i32>, cursor: fn(&T) -> C, load: F) -> Result<Connection<C, T>>
+where
+    C: CursorType + Send + Sync,
+    C::Error: Send + Sync + 'static,
+    T: OutputType,
+    F: FnOnce(Option<C>, i64) -> Fut,
+    Fut: Future<Output = Result<Vec<T>>>,
+{
+    query(
+        after,
+        None::<String>,
+        first,
+        None::<i32>,
+        |after: Option<C>, _before: Option<C>, first: Option<usize>, _last: Option<usize>| async move {
+            let limit = first.unwrap_or(MAX_PAGE_SIZE).min(MAX_PAGE_SIZE);
+            let has_previous_page = after.is_some();
+            let mut nodes = load(after, limit as i64 + 1).await?;
+            let has_next_page = nodes.len() > limit;
+            nodes.truncate(limit);
+
+            let mut connection = Connection::new(has_previous_page, has_next_page);
+            connection.edges.extend(nodes.into_iter().map(|node| Edge::new(cursor(&node), node)));
+            Ok::<_, Error>(connection)
+        },
+    )
+    .await
+}
+
+// A subscription outlives the authentication of its upgrade request, the token is checked again before each event
+async fn still_authorized(state: &web::Data<AppState>, claims: &Claims) -> bool {
+    if claims.exp <= Utc::now().timestamp() as usize {
+        return false;
+    }
+    if state.revocation_cache.is_stale() {
+        let refresh = run_use_case(state, |state| async move {
+            state.revocation_cache.refresh_if_stale(&state.tokens_repository).await;
+            Ok(())
+        });
+        if refresh.await.is_err() {
+            return false;
+        }
+    }
+    !state.revocation_cache.is_revoked(claims)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn me(&self, ctx: &Context<'_>) -> Result<User> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::AccountRead).map_err(graphql_error)?;
+        let user_payload = UserIdPayload::new(auth.claims.sub.clone());
+
+        let user = run_use_case(
+            state,
+            move |state| async move { GetOneUserByIdUseCase::new(&user_payload, &state.users_repository).execute().await },
+        )
+        .await?;
+        Ok(user.into())
+    }
+
+    async fn user(&self, ctx: &Context<'_>, id: ID) -> Result<User> {
+        let (state, auth) = context(ctx);
+        auth.require(if id.0 == auth.claims.sub { Permission::AccountRead } else { Permission::UsersRead }).map_err(graphql_error)?;
+        let user_payload = validate(UserIdPayload::new(id.0))?;
+
+        let user = run_use_case(
+            state,
+            move |state| async move { GetOneUserByIdUseCase::new(&user_payload, &state.users_repository).execute().await },
+        )
+        .await?;
+        Ok(user.into())
+    }
+
+    async fn users(&self, ctx: &Context<'_>, after: Option<String>, first: Option<i32>) -> Result<Connection<UserCursor, User>> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::UsersRead).map_err(graphql_error)?;
+
+        paginate(after, first, UserCursor::of, |after, limit| async move {
+            let page_payload = UserPagePayload { after: after.map(|cursor| cursor.0), limit };
+            let users = run_use_case(
+                state,
+                move |state| async move { GetUsersPageUseCase::new(&page_payload, &state.users_repository).execute().await },
+            )
+            .await?;
+            Ok(users.into_iter().map(User).collect())
+        })
+        .await
+    }
+
+    async fn task(&self, ctx: &Context<'_>, id: ID) -> Result<Task> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::TasksRead).map_err(graphql_error)?;
+        let task_payload = validate(TaskDataPayload::new(Some(id.0), None))?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(state, move |state| async move {
+            GetOneTaskByIdUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        Ok(Task(task))
+    }
+
+    // Callers without `tasks:read:any` only ever list their own tasks, whatever `assigneeId` says
+    async fn tasks(&self, ctx: &Context<'_>, assignee_id: Option<ID>, sort: Option<TaskSortValue>, after: Option<String>, first: Option<i32>) -> Result<Connection<TaskCursor, TaskSummary>> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::TasksRead).map_err(graphql_error)?;
+        let task_payload = validate(TaskDataPayload {
+            task_id: None,
+            user_id: assignee_id.map(|id| id.0),
+            sort: sort.map(Into::into),
+        })?;
+        let policy = auth.task_policy();
+
+        paginate(after, first, TaskCursor::of, |after, limit| async move {
+            let page_payload = TaskListPagePayload {
+                user_id: task_payload.user_id,
+                sort: task_payload.sort,
+                after: after.map(|cursor| cursor.0),
+                limit,
+            };
+            let tasks = run_use_case(state, move |state| async move {
+                GetTaskListPageUseCase::new(&page_payload, &policy, &state.tasks_repository).execute().await
+            })
+            .await?;
+            Ok(tasks.into_iter().map(TaskSummary).collect())
+        })
+        .await
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_task(&self, ctx: &Context<'_>, input: CreateTaskInput) -> Result<Task> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::TasksWrite).map_err(graphql_error)?;
+        let task_payload: TaskCreatePayload = validate(input.into_payload(&auth.claims.sub))?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(state, move |state| async move {
+            PostOneTaskUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        state.task_events.publish(TaskChangeKind::Created, &task);
+        Ok(Task(task))
+    }
+
+    async fn update_task(&self, ctx: &Context<'_>, input: UpdateTaskInput) -> Result<Task> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::TasksWrite).map_err(graphql_error)?;
+        let task_payload: TaskUpdatePayload = validate(input.into())?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(state, move |state| async move {
+            UpdateOneTaskUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        state.task_events.publish(TaskChangeKind::Updated, &task);
+        Ok(Task(task))
+    }
+
+    async fn delete_task(&self, ctx: &Context<'_>, id: ID) -> Result<Task> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::TasksDelete).map_err(graphql_error)?;
+        let task_payload = validate(TaskDataPayload::new(Some(id.0), None))?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(state, move |state| async move {
+            DeleteOneTaskByIdUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        state.task_events.publish(TaskChangeKind::Deleted, &task);
+        Ok(Task(task))
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    // Changes made through REST or GraphQL from now on, limited to the tasks the caller may read. The subscription ends
+    // once the token expires or is revoked.
+    async fn task_changed(&self, ctx: &Context<'_>) -> Result<impl Stream<Item = TaskChangeEvent>> {
+        let (state, auth) = context(ctx);
+        auth.require(Permission::TasksRead).map_err(graphql_error)?;
+        let subscriber = (state.task_events.subscribe(), state.clone(), auth.claims.clone(), auth.task_policy());
+
+        let changes = stream::unfold(subscriber, |(mut receiver, state, claims, policy)| async move {
+            loop {
+                let change = match receiver.recv().await {
+                    Ok(change) => change,
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                };
+                if !still_authorized(&state, &claims).await {
+                    return None;
+                }
+                if policy.can_read(&change.task) {
+                    return Some((change, (receiver, state, claims, policy)));
+                }
+            }
+        });
+        Ok(changes.map(TaskChangeEvent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::task_entity::{TaskAllEntity, TaskPriority, TaskStatus};
+    use chrono::DateTime;
+
+    #[test]
+    fn test_should_read_back_the_task_cursors_it_writes() {
+        // given the cursor of a task
+        let task = TaskSummary(TaskAllEntity::new(
+            String::from("5b3c4c3c-7d8b-4f0e-9a49-3f1d2f6c8e21"),
+            String::from("user1"),
+            String::from("project1"),
+            String::from("Title"),
+            TaskPriority::High,
+            TaskStatus::Completed,
+            String::new(),
+            DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap().naive_utc(),
+            DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap().naive_utc(),
+        ));
+        let cursor = TaskCursor::of(&task);
+
+        // when encoding and decoding it
+        let decoded = TaskCursor::decode_cursor(&cursor.encode_cursor()).unwrap();
+
+        // then expect the same sort key, malformed cursors refused
+        assert_eq!(decoded.0, cursor.0);
+        assert!(TaskCursor::decode_cursor("high:abc:5b3c4c3c-7d8b-4f0e-9a49-3f1d2f6c8e21").is_err());
+        assert!(TaskCursor::decode_cursor("urgent:0:5b3c4c3c-7d8b-4f0e-9a49-3f1d2f6c8e21").is_err());
+        assert!(TaskCursor::decode_cursor("high:0").is_err());
+        assert!(UserCursor::decode_cursor("1").is_err());
+    }
+
+    #[test]
+    fn test_should_name_payload_fields_as_in_the_inputs() {
+        assert_eq!(input_field_name("title"), "title");
+        assert_eq!(input_field_name("project_id"), "projectId");
+        assert_eq!(input_field_name("task_list"), "taskList");
+        assert_eq!(input_field_name("user_id"), "assigneeId");
+        assert_eq!(input_field_name("task_id"), "id");
+    }
+}
@@ -0,0 +1,401 @@
+use async_graphql::{connection::CursorType, dataloader::DataLoader, Context, Enum, InputObject, Object, Result, ID};
+use chrono::{DateTime, NaiveDateTime};
+use uuid::Uuid;
+
+use crate::{
+    adapters::api::tasks::tasks_payloads::{TaskCreatePayload, TaskListCursor, TaskUpdatePayload},
+    domain::{
+        project_entity::ProjectEntity,
+        task_entity::{TaskAllEntity, TaskChange, TaskEntity, TaskPriority, TaskStatusInProgress, TaskStatusToDo},
+        user_entity::{UserAllEntity, UserEntity},
+    },
+};
+
+use super::graphql_loaders::{ProjectLoader, UserLoader};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "TaskType", remote = "crate::domain::task_entity::TaskType")]
+pub enum TaskTypeValue {
+    None,
+    Personal,
+    Work,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "TaskPriority", remote = "crate::domain::task_entity::TaskPriority")]
+pub enum TaskPriorityValue {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+// GraphQL enums can't nest, the sub-status is flattened the same way as in the database
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "TaskStatus")]
+pub enum TaskStatusValue {
+    None,
+    ToDo,
+    ToDoNotStarted,
+    ToDoDocument,
+    ToDoBug,
+    InProgress,
+    InProgressDoing,
+    InProgressTesting,
+    Completed,
+}
+
+impl From<crate::domain::task_entity::TaskStatus> for TaskStatusValue {
+    fn from(status: crate::domain::task_entity::TaskStatus) -> Self {
+        use crate::domain::task_entity::TaskStatus;
+
+        match status {
+            TaskStatus::None => TaskStatusValue::None,
+            TaskStatus::ToDo(TaskStatusToDo::None) => TaskStatusValue::ToDo,
+            TaskStatus::ToDo(TaskStatusToDo::NotStarted) => TaskStatusValue::ToDoNotStarted,
+            TaskStatus::ToDo(TaskStatusToDo::Document) => TaskStatusValue::ToDoDocument,
+            TaskStatus::ToDo(TaskStatusToDo::Bug) => TaskStatusValue::ToDoBug,
+            TaskStatus::InProgress(TaskStatusInProgress::None) => TaskStatusValue::InProgress,
+            TaskStatus::InProgress(TaskStatusInProgress::Doing) => TaskStatusValue::InProgressDoing,
+            TaskStatus::InProgress(TaskStatusInProgress::Testing) => TaskStatusValue::InProgressTesting,
+            TaskStatus::Completed => TaskStatusValue::Completed,
+        }
+    }
+}
+
+impl From<TaskStatusValue> for crate::domain::task_entity::TaskStatus {
+    fn from(status: TaskStatusValue) -> Self {
+        use crate::domain::task_entity::TaskStatus;
+
+        match status {
+            TaskStatusValue::None => TaskStatus::None,
+            TaskStatusValue::ToDo => TaskStatus::ToDo(TaskStatusToDo::None),
+            TaskStatusValue::ToDoNotStarted => TaskStatus::ToDo(TaskStatusToDo::NotStarted),
+            TaskStatusValue::ToDoDocument => TaskStatus::ToDo(TaskStatusToDo::Document),
+            TaskStatusValue::ToDoBug => TaskStatus::ToDo(TaskStatusToDo::Bug),
+            TaskStatusValue::InProgress => TaskStatus::InProgress(TaskStatusInProgress::None),
+            TaskStatusValue::InProgressDoing => TaskStatus::InProgress(TaskStatusInProgress::Doing),
+            TaskStatusValue::InProgressTesting => TaskStatus::InProgress(TaskStatusInProgress::Testing),
+            TaskStatusValue::Completed => TaskStatus::Completed,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "TaskSort", remote = "crate::adapters::api::tasks::tasks_payloads::TaskSortPayload")]
+pub enum TaskSortValue {
+    PriorityAsc,
+    PriorityDesc,
+}
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+#[graphql(name = "TaskChangeKind", remote = "crate::domain::task_entity::TaskChangeKind")]
+pub enum TaskChangeKindValue {
+    Created,
+    Updated,
+    Deleted,
+}
+
+pub struct Task(pub TaskEntity);
+
+#[Object]
+impl Task {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn typ(&self) -> TaskTypeValue {
+        self.0.typ.into()
+    }
+
+    async fn priority(&self) -> TaskPriorityValue {
+        self.0.priority.into()
+    }
+
+    async fn status(&self) -> TaskStatusValue {
+        self.0.status.into()
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    async fn duration(&self) -> i32 {
+        self.0.duration
+    }
+
+    async fn due_date(&self) -> i64 {
+        self.0.due_date
+    }
+
+    async fn task_list(&self) -> &[String] {
+        &self.0.task_list
+    }
+
+    async fn updated_at(&self) -> NaiveDateTime {
+        self.0.updated_at
+    }
+
+    async fn created_at(&self) -> NaiveDateTime {
+        self.0.created_at
+    }
+
+    async fn assignee(&self, ctx: &Context<'_>) -> Result<Option<User>> {
+        load_user(ctx, &self.0.user_id).await
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<Project>> {
+        load_project(ctx, &self.0.project_id).await
+    }
+}
+
+// Lists only carry the fields of `TaskAllEntity`, the full task is one `task(id:)` away
+pub struct TaskSummary(pub TaskAllEntity);
+
+#[Object]
+impl TaskSummary {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn title(&self) -> &str {
+        &self.0.title
+    }
+
+    async fn priority(&self) -> TaskPriorityValue {
+        self.0.priority.into()
+    }
+
+    async fn status(&self) -> TaskStatusValue {
+        self.0.status.into()
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    async fn updated_at(&self) -> NaiveDateTime {
+        self.0.updated_at
+    }
+
+    async fn created_at(&self) -> NaiveDateTime {
+        self.0.created_at
+    }
+
+    async fn assignee(&self, ctx: &Context<'_>) -> Result<Option<User>> {
+        load_user(ctx, &self.0.user_id).await
+    }
+
+    async fn project(&self, ctx: &Context<'_>) -> Result<Option<Project>> {
+        load_project(ctx, &self.0.project_id).await
+    }
+}
+
+pub struct User(pub UserAllEntity);
+
+impl From<UserEntity> for User {
+    fn from(user: UserEntity) -> Self {
+        User(UserAllEntity {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            role: user.role,
+        })
+    }
+}
+
+#[Object]
+impl User {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn username(&self) -> &str {
+        &self.0.username
+    }
+
+    async fn email(&self) -> &str {
+        &self.0.email
+    }
+
+    async fn role(&self) -> &str {
+        &self.0.role
+    }
+}
+
+pub struct Project(pub ProjectEntity);
+
+#[Object]
+impl Project {
+    async fn id(&self) -> ID {
+        ID(self.0.id.clone())
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.0.description.as_deref()
+    }
+
+    async fn updated_at(&self) -> NaiveDateTime {
+        self.0.updated_at
+    }
+
+    async fn created_at(&self) -> NaiveDateTime {
+        self.0.created_at
+    }
+}
+
+pub struct TaskChangeEvent(pub TaskChange);
+
+#[Object(name = "TaskChange")]
+impl TaskChangeEvent {
+    async fn kind(&self) -> TaskChangeKindValue {
+        self.0.kind.into()
+    }
+
+    // As it was right after the change, or right before for a deletion
+    async fn task(&self) -> Task {
+        Task(self.0.task.clone())
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateTaskInput {
+    pub title: String,
+    // Defaults to the caller, someone else needs `tasks:write:any`
+    pub assignee_id: Option<ID>,
+    pub project_id: Option<ID>,
+    pub typ: Option<TaskTypeValue>,
+    pub priority: Option<TaskPriorityValue>,
+    pub status: Option<TaskStatusValue>,
+    pub description: Option<String>,
+    pub duration: Option<i32>,
+    pub due_date: Option<i64>,
+    pub task_list: Option<Vec<String>>,
+}
+
+impl CreateTaskInput {
+    pub fn into_payload(self, caller_id: &str) -> TaskCreatePayload {
+        TaskCreatePayload::new(
+            Some(self.assignee_id.map_or_else(|| caller_id.to_string(), |id| id.0)),
+            self.project_id.map(|id| id.0),
+            self.title,
+            self.typ.map(Into::into),
+            self.priority.map(Into::into),
+            self.status.map(Into::into),
+            self.description,
+            self.duration,
+            self.due_date,
+            self.task_list,
+        )
+    }
+}
+
+#[derive(InputObject)]
+pub struct UpdateTaskInput {
+    pub id: ID,
+    pub assignee_id: Option<ID>,
+    pub project_id: Option<ID>,
+    pub title: Option<String>,
+    pub typ: Option<TaskTypeValue>,
+    pub priority: Option<TaskPriorityValue>,
+    pub status: Option<TaskStatusValue>,
+    pub description: Option<String>,
+    pub duration: Option<i32>,
+    pub due_date: Option<i64>,
+    pub task_list: Option<Vec<String>>,
+}
+
+impl From<UpdateTaskInput> for TaskUpdatePayload {
+    fn from(input: UpdateTaskInput) -> Self {
+        TaskUpdatePayload::new(
+            input.id.0,
+            input.assignee_id.map(|id| id.0),
+            input.project_id.map(|id| id.0),
+            input.title,
+            input.typ.map(Into::into),
+            input.priority.map(Into::into),
+            input.status.map(Into::into),
+            input.description,
+            input.duration,
+            input.due_date,
+            input.task_list,
+        )
+    }
+}
+
+// Cursor of the `users` connection, the id of the last user of a page
+pub struct UserCursor(pub String);
+
+impl UserCursor {
+    pub fn of(user: &User) -> Self {
+        UserCursor(user.0.id.clone())
+    }
+}
+
+impl CursorType for UserCursor {
+    type Error = uuid::Error;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(s).map(|id| UserCursor(id.to_string()))
+    }
+
+    fn encode_cursor(&self) -> String {
+        self.0.clone()
+    }
+}
+
+// Cursor of the `tasks` connection, the sort key of the last task of a page as `priority:created_at:id`, the
+// timestamp in microseconds
+pub struct TaskCursor(pub TaskListCursor);
+
+impl TaskCursor {
+    pub fn of(task: &TaskSummary) -> Self {
+        TaskCursor(TaskListCursor {
+            priority: task.0.priority,
+            created_at: task.0.created_at,
+            id: task.0.id.clone(),
+        })
+    }
+}
+
+impl CursorType for TaskCursor {
+    type Error = String;
+
+    fn decode_cursor(s: &str) -> Result<Self, Self::Error> {
+        let mut parts = s.splitn(3, ':');
+        let (Some(priority), Some(created_at), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(String::from("Invalid cursor"));
+        };
+        let created_at = created_at
+            .parse()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or_else(|| String::from("Invalid cursor"))?;
+        Ok(TaskCursor(TaskListCursor {
+            priority: priority.parse::<TaskPriority>()?,
+            created_at: created_at.naive_utc(),
+            id: Uuid::parse_str(id).map_err(|e| e.to_string())?.to_string(),
+        }))
+    }
+
+    fn encode_cursor(&self) -> String {
+        format!("{}:{}:{}", self.0.priority, self.0.created_at.and_utc().timestamp_micros(), self.0.id)
+    }
+}
+
+// Tasks without a project point to the nil id, which the loader never finds
+async fn load_project(ctx: &Context<'_>, project_id: &str) -> Result<Option<Project>> {
+    Ok(ctx.data_unchecked::<DataLoader<ProjectLoader>>().load_one(project_id.to_string()).await?.map(Project))
+}
+
+async fn load_user(ctx: &Context<'_>, user_id: &str) -> Result<Option<User>> {
+    Ok(ctx.data_unchecked::<DataLoader<UserLoader>>().load_one(user_id.to_string()).await?.map(User))
+}
@@ -0,0 +1,4 @@
+pub mod graphql_controllers;
+pub mod graphql_loaders;
+pub mod graphql_schema;
+pub mod graphql_types;
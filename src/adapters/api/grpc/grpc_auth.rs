@@ -0,0 +1,26 @@
+use actix_web::web;
+use tonic::{metadata::MetadataMap, Status};
+
+use crate::{
+    adapters::api::shared::app_state::AppState,
+    application::utils::access_control::{auth_usecase::AuthUseCase, caller::Caller},
+};
+
+use super::grpc_services::run_use_case;
+
+// The caller of one call, authenticated from its metadata with the same rules as the `Claims` extractor:
+// `authorization: Bearer <token or API key>` or `x-api-key: <API key>`
+pub async fn authenticate(state: &web::Data<AppState>, metadata: &MetadataMap) -> Result<Caller, Status> {
+    let bearer_token = metadata
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(String::from);
+    let api_key = metadata.get("x-api-key").and_then(|value| value.to_str().ok()).map(String::from);
+
+    run_use_case(state, move |state| async move {
+        let claims = AuthUseCase::authenticate(Some(state.get_ref()), bearer_token, api_key).await?;
+        Ok::<_, Status>(Caller::resolve(&state, claims).await)
+    })
+    .await
+}
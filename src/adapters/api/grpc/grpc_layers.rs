@@ -0,0 +1,332 @@
+use std::{
+    future::Future,
+    net::IpAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use tonic::{
+    body::Body,
+    codegen::http::{header, HeaderName, HeaderValue, Request, Response},
+    transport::server::TcpConnectInfo,
+    Code, Status,
+};
+use tower::{Layer, Service};
+use tracing::{field, Instrument};
+use uuid::Uuid;
+
+use crate::{
+    adapters::{api::shared::blocking::run_blocking, spi::rate_limit::rate_limit_store::RateLimitStore},
+    application::utils::{
+        access_control::middlewares::{
+            rate_limit::{client_key, forwarded_client_ip, RateLimitConfig, TrustedProxies},
+            request_id::{valid_request_id, RequestId},
+        },
+        metrics::Metrics,
+    },
+    domain::error::{error_codes, ApiError},
+};
+
+use super::grpc_mappers::http_status;
+
+// The middlewares of the HTTP server, as tower layers for the gRPC server. Calls are seen as `POST /<service>/<method>`,
+// e.g. `POST /tasktracker.v1.TasksService/GetTask`, which is also how rate limit routes name them.
+
+const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+const TRACEPARENT_HEADER: &str = "traceparent";
+// Calls to unknown methods share one series, their paths are whatever clients send
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+type BoxFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send>>;
+
+// Keeps the x-request-id sent by the client or generates one, echoes it in the response metadata and runs the call in
+// a span holding it, as `request_id` does for HTTP requests
+#[derive(Clone)]
+pub struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestIdService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestIdService { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RequestIdService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let mut inner = ready_inner(&mut self.inner);
+        let request_id = header_value(&req, REQUEST_ID_HEADER.as_str())
+            .and_then(valid_request_id)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        let span = tracing::info_span!(
+            "grpc_request",
+            request_id = %request_id,
+            rpc = %req.uri().path(),
+            status = field::Empty,
+            traceparent = header_value(&req, TRACEPARENT_HEADER),
+        );
+        req.extensions_mut().insert(RequestId(request_id.clone()));
+        let request_id_value = HeaderValue::from_str(&request_id).ok();
+
+        Box::pin(
+            async move {
+                let mut response = inner.call(req).await?;
+                tracing::Span::current().record("status", grpc_code(&response) as i32);
+                if let Some(value) = request_id_value {
+                    response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+// One line per call once answered, as `logger` does for HTTP requests
+#[derive(Clone)]
+pub struct AccessLogLayer {
+    trusted_proxies: TrustedProxies,
+}
+
+impl AccessLogLayer {
+    pub fn new(trusted_proxies: TrustedProxies) -> Self {
+        AccessLogLayer { trusted_proxies }
+    }
+}
+
+impl<S> Layer<S> for AccessLogLayer {
+    type Service = AccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AccessLogService {
+            inner,
+            trusted_proxies: self.trusted_proxies.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessLogService<S> {
+    inner: S,
+    trusted_proxies: TrustedProxies,
+}
+
+impl<S> Service<Request<Body>> for AccessLogService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = ready_inner(&mut self.inner);
+        let rpc = req.uri().path().to_string();
+        let client_ip = client_ip(&req, &self.trusted_proxies).map_or_else(|| String::from("-"), |ip| ip.to_string());
+        let user_agent = header_value(&req, header::USER_AGENT.as_str()).unwrap_or("-").to_string();
+        let started_at = Instant::now();
+
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            let code = grpc_code(&response);
+            let latency_ms = started_at.elapsed().as_secs_f64() * 1000.0;
+            if http_status(code) >= 500 {
+                tracing::error!(%rpc, status = code as i32, latency_ms, %client_ip, %user_agent, "call failed");
+            } else {
+                tracing::info!(%rpc, status = code as i32, latency_ms, %client_ip, %user_agent, "call completed");
+            }
+            Ok(response)
+        })
+    }
+}
+
+// Counts every call with its latency next to the HTTP requests, the code mapped to the closest HTTP status
+#[derive(Clone)]
+pub struct MetricsLayer {
+    metrics: Arc<Metrics>,
+}
+
+impl MetricsLayer {
+    pub fn new(metrics: Arc<Metrics>) -> Self {
+        MetricsLayer { metrics }
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService {
+            inner,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    metrics: Arc<Metrics>,
+}
+
+impl<S> Service<Request<Body>> for MetricsService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = ready_inner(&mut self.inner);
+        let metrics = self.metrics.clone();
+        let method = req.method().to_string();
+        let rpc = req.uri().path().to_string();
+        let started_at = Instant::now();
+
+        Box::pin(async move {
+            let response = inner.call(req).await?;
+            let code = grpc_code(&response);
+            let route = if code == Code::Unimplemented { UNMATCHED_ROUTE } else { &rpc };
+            metrics.record_request(&method, route, http_status(code), started_at.elapsed());
+            Ok(response)
+        })
+    }
+}
+
+// Token bucket per client in the store of the HTTP server, so both servers share the same buckets
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    config: Arc<RateLimitConfig>,
+    store: Arc<dyn RateLimitStore + Send + Sync>,
+}
+
+impl RateLimitLayer {
+    pub fn new(config: RateLimitConfig, store: Arc<dyn RateLimitStore + Send + Sync>) -> Self {
+        RateLimitLayer { config: Arc::new(config), store }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            config: self.config.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    config: Arc<RateLimitConfig>,
+    store: Arc<dyn RateLimitStore + Send + Sync>,
+}
+
+impl<S> Service<Request<Body>> for RateLimitService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let mut inner = ready_inner(&mut self.inner);
+        let store = self.store.clone();
+        let (scope, rule) = self.config.rule_for(req.method().as_str(), req.uri().path());
+        let authorization = header_value(&req, header::AUTHORIZATION.as_str());
+        let key = format!("{}|{}", scope, client_key(authorization, client_ip(&req, &self.config.trusted_proxies)));
+
+        Box::pin(async move {
+            // the stores are `?Send` like the repositories
+            let decision = run_blocking(move || async move { store.take(&key, rule.limit, rule.window).await.map_err(|e| e.to_string()) })
+                .await
+                .map_err(|e| e.to_string())
+                .and_then(|result| result);
+            match decision {
+                Ok(decision) if !decision.allowed => {
+                    let status: Status = ApiError {
+                        code: 429,
+                        error_code: error_codes::TOO_MANY_REQUESTS,
+                        message: String::from("Too many requests, try again later"),
+                        fields: Vec::new(),
+                        error: None,
+                    }
+                    .into();
+                    let mut response = status.into_http::<Body>();
+                    response.headers_mut().insert(header::RETRY_AFTER, HeaderValue::from(decision.retry_after));
+                    return Ok(response);
+                }
+                Ok(_) => {}
+                // an unavailable store must not take the API down with it
+                Err(e) => log::error!("Rate limit store failed, letting the call through: {}", e),
+            }
+            inner.call(req).await
+        })
+    }
+}
+
+// The service polled ready is the one to call, a clone takes its place for the next call
+fn ready_inner<S: Clone>(inner: &mut S) -> S {
+    let clone = inner.clone();
+    std::mem::replace(inner, clone)
+}
+
+fn header_value<'a>(req: &'a Request<Body>, name: &str) -> Option<&'a str> {
+    req.headers().get(name).and_then(|value| value.to_str().ok())
+}
+
+fn client_ip(req: &Request<Body>, trusted_proxies: &TrustedProxies) -> Option<IpAddr> {
+    let peer = req.extensions().get::<TcpConnectInfo>()?.remote_addr()?.ip();
+    let forwarded_for = req.headers().get_all("x-forwarded-for").iter().filter_map(|value| value.to_str().ok());
+    Some(forwarded_client_ip(peer, forwarded_for, trusted_proxies))
+}
+
+// Handlers answer errors before any message, with the status in the headers. Otherwise it only comes in the trailers,
+// after the messages, and the call counts as OK.
+fn grpc_code(response: &Response<Body>) -> Code {
+    response
+        .headers()
+        .get("grpc-status")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<i32>().ok())
+        .map_or(Code::Ok, Code::from)
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+use validator::Validate;
+
+use crate::{
+    adapters::api::{
+        shared::validated_json::field_errors,
+        tasks::tasks_payloads::{TaskCreatePayload, TaskDataPayload, TaskSortPayload, TaskUpdatePayload},
+    },
+    application::utils::{access_control::extractors::claims::ClientError, error_handling_utils::ErrorHandlingUtils},
+    domain::{
+        error::{error_codes, ApiError, DomainError, FieldError},
+        project_entity::ProjectEntity,
+        task_entity::{TaskAllEntity, TaskEntity, TaskPriority, TaskStatus, TaskStatusInProgress, TaskStatusToDo, TaskType},
+        user_entity::{UserAllEntity, UserEntity},
+    },
+};
+
+use super::proto;
+
+// `domain` of the ErrorInfo detail, its `reason` is the `error_code` of the REST error body
+const ERROR_DOMAIN: &str = "tasktracker";
+
+// Same message as the REST error body, the HTTP status picks the closest gRPC code
+impl From<ApiError> for Status {
+    fn from(error: ApiError) -> Self {
+        let code = match error.code {
+            400 | 422 => Code::InvalidArgument,
+            401 => Code::Unauthenticated,
+            403 => Code::PermissionDenied,
+            404 => Code::NotFound,
+            409 => Code::AlreadyExists,
+            429 => Code::ResourceExhausted,
+            _ => Code::Internal,
+        };
+        let mut details = ErrorDetails::new();
+        details.set_error_info(error.error_code, ERROR_DOMAIN, HashMap::new());
+        for field in &error.fields {
+            details.add_bad_request_violation(field.field.clone(), field.message.clone());
+        }
+
+        Status::with_error_details(code, error.message, details)
+    }
+}
+
+// The other way round, gRPC calls are counted in the request metrics under the closest HTTP status
+pub fn http_status(code: Code) -> u16 {
+    match code {
+        Code::Ok => 200,
+        Code::InvalidArgument => 400,
+        Code::Unauthenticated => 401,
+        Code::PermissionDenied => 403,
+        Code::NotFound | Code::Unimplemented => 404,
+        Code::AlreadyExists => 409,
+        Code::ResourceExhausted => 429,
+        _ => 500,
+    }
+}
+
+// Same messages as the 401 answers of the REST extractor
+impl From<ClientError> for Status {
+    fn from(error: ClientError) -> Self {
+        let message = match error {
+            ClientError::Authentication(_) => String::from("Requires authentication"),
+            ClientError::NotFound(message) => message,
+            ClientError::Revoked => String::from("Token has been revoked"),
+            ClientError::Decode(_) | ClientError::UnsupportedAlgortithm(_) => String::from("Bad credentials"),
+        };
+        let mut details = ErrorDetails::new();
+        details.set_error_info(error_codes::UNAUTHORIZED, ERROR_DOMAIN, HashMap::new());
+
+        Status::with_error_details(Code::Unauthenticated, message, details)
+    }
+}
+
+// Requests go through the `#[validate]` rules of the REST payloads, fields are reported under their protobuf names
+pub fn validate<T: Validate>(payload: T) -> Result<T, Status> {
+    match payload.validate() {
+        Ok(()) => Ok(payload),
+        Err(errors) => Err(invalid_input(field_errors(&errors))),
+    }
+}
+
+fn invalid_input(fields: Vec<FieldError>) -> Status {
+    let fields = fields
+        .into_iter()
+        .map(|field| FieldError {
+            field: request_field_name(&field.field),
+            ..field
+        })
+        .collect();
+    let error = DomainError::Validation {
+        message: String::from("Invalid input"),
+        fields,
+    };
+    ErrorHandlingUtils::application_error("Invalid input", Some(error)).into()
+}
+
+fn request_field_name(field: &str) -> String {
+    match field {
+        "task_id" => String::from("id"),
+        "user_id" => String::from("assignee_id"),
+        "typ" => String::from("type"),
+        other => other.to_string(),
+    }
+}
+
+// prost keeps enums as plain integers, values this build doesn't know are refused like any other invalid field
+fn enum_value<T: TryFrom<i32>>(field: &str, value: i32) -> Result<T, Status> {
+    T::try_from(value).map_err(|_| invalid_input(vec![FieldError::new(field, "unknown_value", &format!("unknown value {}", value))]))
+}
+
+fn optional_enum_value<T: TryFrom<i32>>(field: &str, value: Option<i32>) -> Result<Option<T>, Status> {
+    value.map(|value| enum_value(field, value)).transpose()
+}
+
+impl From<TaskType> for proto::TaskType {
+    fn from(typ: TaskType) -> Self {
+        match typ {
+            TaskType::None => proto::TaskType::None,
+            TaskType::Personal => proto::TaskType::Personal,
+            TaskType::Work => proto::TaskType::Work,
+        }
+    }
+}
+
+impl From<proto::TaskType> for TaskType {
+    fn from(typ: proto::TaskType) -> Self {
+        match typ {
+            proto::TaskType::None => TaskType::None,
+            proto::TaskType::Personal => TaskType::Personal,
+            proto::TaskType::Work => TaskType::Work,
+        }
+    }
+}
+
+impl From<TaskPriority> for proto::TaskPriority {
+    fn from(priority: TaskPriority) -> Self {
+        match priority {
+            TaskPriority::None => proto::TaskPriority::None,
+            TaskPriority::Low => proto::TaskPriority::Low,
+            TaskPriority::Medium => proto::TaskPriority::Medium,
+            TaskPriority::High => proto::TaskPriority::High,
+        }
+    }
+}
+
+impl From<proto::TaskPriority> for TaskPriority {
+    fn from(priority: proto::TaskPriority) -> Self {
+        match priority {
+            proto::TaskPriority::None => TaskPriority::None,
+            proto::TaskPriority::Low => TaskPriority::Low,
+            proto::TaskPriority::Medium => TaskPriority::Medium,
+            proto::TaskPriority::High => TaskPriority::High,
+        }
+    }
+}
+
+impl From<TaskStatus> for proto::TaskStatus {
+    fn from(status: TaskStatus) -> Self {
+        match status {
+            TaskStatus::None => proto::TaskStatus::None,
+            TaskStatus::ToDo(TaskStatusToDo::None) => proto::TaskStatus::ToDo,
+            TaskStatus::ToDo(TaskStatusToDo::NotStarted) => proto::TaskStatus::ToDoNotStarted,
+            TaskStatus::ToDo(TaskStatusToDo::Document) => proto::TaskStatus::ToDoDocument,
+            TaskStatus::ToDo(TaskStatusToDo::Bug) => proto::TaskStatus::ToDoBug,
+            TaskStatus::InProgress(TaskStatusInProgress::None) => proto::TaskStatus::InProgress,
+            TaskStatus::InProgress(TaskStatusInProgress::Doing) => proto::TaskStatus::InProgressDoing,
+            TaskStatus::InProgress(TaskStatusInProgress::Testing) => proto::TaskStatus::InProgressTesting,
+            TaskStatus::Completed => proto::TaskStatus::Completed,
+        }
+    }
+}
+
+impl From<proto::TaskStatus> for TaskStatus {
+    fn from(status: proto::TaskStatus) -> Self {
+        match status {
+            proto::TaskStatus::None => TaskStatus::None,
+            proto::TaskStatus::ToDo => TaskStatus::ToDo(TaskStatusToDo::None),
+            proto::TaskStatus::ToDoNotStarted => TaskStatus::ToDo(TaskStatusToDo::NotStarted),
+            proto::TaskStatus::ToDoDocument => TaskStatus::ToDo(TaskStatusToDo::Document),
+            proto::TaskStatus::ToDoBug => TaskStatus::ToDo(TaskStatusToDo::Bug),
+            proto::TaskStatus::InProgress => TaskStatus::InProgress(TaskStatusInProgress::None),
+            proto::TaskStatus::InProgressDoing => TaskStatus::InProgress(TaskStatusInProgress::Doing),
+            proto::TaskStatus::InProgressTesting => TaskStatus::InProgress(TaskStatusInProgress::Testing),
+            proto::TaskStatus::Completed => TaskStatus::Completed,
+        }
+    }
+}
+
+impl From<TaskEntity> for proto::Task {
+    fn from(task: TaskEntity) -> Self {
+        proto::Task {
+            id: task.id,
+            assignee_id: task.user_id,
+            project_id: task.project_id,
+            title: task.title,
+            r#type: proto::TaskType::from(task.typ).into(),
+            priority: proto::TaskPriority::from(task.priority).into(),
+            status: proto::TaskStatus::from(task.status).into(),
+            description: task.description,
+            duration: task.duration,
+            due_date: task.due_date,
+            task_list: task.task_list,
+            updated_at: naive_datetime_to_unixtimemillis(task.updated_at),
+            created_at: naive_datetime_to_unixtimemillis(task.created_at),
+        }
+    }
+}
+
+impl From<TaskAllEntity> for proto::TaskSummary {
+    fn from(task: TaskAllEntity) -> Self {
+        proto::TaskSummary {
+            id: task.id,
+            assignee_id: task.user_id,
+            project_id: task.project_id,
+            title: task.title,
+            priority: proto::TaskPriority::from(task.priority).into(),
+            status: proto::TaskStatus::from(task.status).into(),
+            description: task.description,
+            updated_at: naive_datetime_to_unixtimemillis(task.updated_at),
+            created_at: naive_datetime_to_unixtimemillis(task.created_at),
+        }
+    }
+}
+
+impl From<ProjectEntity> for proto::Project {
+    fn from(project: ProjectEntity) -> Self {
+        proto::Project {
+            id: project.id,
+            name: project.name,
+            description: project.description,
+            updated_at: naive_datetime_to_unixtimemillis(project.updated_at),
+            created_at: naive_datetime_to_unixtimemillis(project.created_at),
+        }
+    }
+}
+
+impl From<UserAllEntity> for proto::User {
+    fn from(user: UserAllEntity) -> Self {
+        proto::User {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            role: user.role,
+        }
+    }
+}
+
+impl From<UserEntity> for proto::User {
+    fn from(user: UserEntity) -> Self {
+        proto::User {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            role: user.role,
+        }
+    }
+}
+
+pub fn create_task_payload(request: proto::CreateTaskRequest, caller_id: &str) -> Result<TaskCreatePayload, Status> {
+    Ok(TaskCreatePayload::new(
+        Some(request.assignee_id.unwrap_or_else(|| caller_id.to_string())),
+        request.project_id,
+        request.title,
+        optional_enum_value::<proto::TaskType>("type", request.r#type)?.map(Into::into),
+        optional_enum_value::<proto::TaskPriority>("priority", request.priority)?.map(Into::into),
+        optional_enum_value::<proto::TaskStatus>("status", request.status)?.map(Into::into),
+        request.description,
+        request.duration,
+        request.due_date,
+        Some(request.task_list).filter(|task_list| !task_list.is_empty()),
+    ))
+}
+
+pub fn update_task_payload(request: proto::UpdateTaskRequest) -> Result<TaskUpdatePayload, Status> {
+    Ok(TaskUpdatePayload::new(
+        request.id,
+        request.assignee_id,
+        request.project_id,
+        request.title,
+        optional_enum_value::<proto::TaskType>("type", request.r#type)?.map(Into::into),
+        optional_enum_value::<proto::TaskPriority>("priority", request.priority)?.map(Into::into),
+        optional_enum_value::<proto::TaskStatus>("status", request.status)?.map(Into::into),
+        request.description,
+        request.duration,
+        request.due_date,
+        request.task_list.map(|task_list| task_list.items),
+    ))
+}
+
+pub fn list_tasks_payload(request: proto::ListTasksRequest) -> Result<TaskDataPayload, Status> {
+    let sort = match enum_value("sort", request.sort)? {
+        proto::TaskSort::Unspecified => None,
+        proto::TaskSort::PriorityAsc => Some(TaskSortPayload::PriorityAsc),
+        proto::TaskSort::PriorityDesc => Some(TaskSortPayload::PriorityDesc),
+    };
+
+    Ok(TaskDataPayload {
+        task_id: None,
+        user_id: request.assignee_id,
+        sort,
+    })
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_report_unknown_enum_values_as_invalid_fields() {
+        let request = proto::CreateTaskRequest {
+            title: String::from("Title"),
+            priority: Some(42),
+            ..Default::default()
+        };
+
+        let status = create_task_payload(request, "caller").unwrap_err();
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+        let violations = status.get_details_bad_request().unwrap().field_violations;
+        assert_eq!(violations[0].field, "priority");
+    }
+
+    #[test]
+    fn test_should_map_http_statuses_to_grpc_codes() {
+        let status: Status = ErrorHandlingUtils::forbidden_error().into();
+
+        assert_eq!(status.code(), Code::PermissionDenied);
+        assert_eq!(status.get_details_error_info().unwrap().reason, "forbidden");
+    }
+
+    #[test]
+    fn test_should_count_grpc_codes_under_the_closest_http_status() {
+        assert_eq!(http_status(Code::Ok), 200);
+        assert_eq!(http_status(Code::PermissionDenied), 403);
+        assert_eq!(http_status(Code::Unimplemented), 404);
+        assert_eq!(http_status(Code::ResourceExhausted), 429);
+        assert_eq!(http_status(Code::Unavailable), 500);
+    }
+}
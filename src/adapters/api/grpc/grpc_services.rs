@@ -0,0 +1,197 @@
+use std::future::Future;
+
+use actix_web::web;
+use tonic::{service::Routes, Request, Response, Status};
+
+use crate::{
+    adapters::api::{
+        shared::{app_state::AppState, blocking::run_blocking},
+        tasks::tasks_payloads::TaskDataPayload,
+        users::users_payloads::UserIdPayload,
+    },
+    application::{
+        usecases::{
+            interfaces::AbstractUseCase,
+            project::get_projects_by_ids_usecase::GetProjectsByIdsUseCase,
+            task::{
+                delete_one_task_by_id_usecase::DeleteOneTaskByIdUseCase, get_all_tasks_usecase::GetAllTasksUseCase, get_one_task_by_id_usecase::GetOneTaskByIdUseCase,
+                post_one_task_usecase::PostOneTaskUseCase, update_one_task_usecase::UpdateOneTaskUseCase,
+            },
+            user::{get_all_users_usecase::GetAllUsersUseCase, get_one_user_by_id_usecase::GetOneUserByIdUseCase},
+        },
+        utils::access_control::extractors::claims::Permission,
+    },
+    domain::task_entity::TaskChangeKind,
+};
+
+use super::{
+    grpc_auth,
+    grpc_mappers::{create_task_payload, list_tasks_payload, update_task_payload, validate},
+    proto::{
+        self,
+        projects_service_server::{ProjectsService, ProjectsServiceServer},
+        tasks_service_server::{TasksService, TasksServiceServer},
+        users_service_server::{UsersService, UsersServiceServer},
+    },
+};
+
+// Every service, ready to be served next to the HTTP server
+pub fn routes(state: web::Data<AppState>) -> Routes {
+    Routes::new(TasksServiceServer::new(TasksGrpcService { state: state.clone() }))
+        .add_service(ProjectsServiceServer::new(ProjectsGrpcService { state: state.clone() }))
+        .add_service(UsersServiceServer::new(UsersGrpcService { state }))
+}
+
+// Tonic handlers have to be Send, use cases run on the blocking pool
+pub async fn run_use_case<T, E, F, Fut>(state: &web::Data<AppState>, use_case: F) -> Result<T, Status>
+where
+    F: FnOnce(web::Data<AppState>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<T, E>>,
+    E: Into<Status>,
+    T: Send + 'static,
+{
+    let state = state.clone();
+    run_blocking(move || async move { use_case(state).await.map_err(Into::into) })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+}
+
+pub struct TasksGrpcService {
+    state: web::Data<AppState>,
+}
+
+#[tonic::async_trait]
+impl TasksService for TasksGrpcService {
+    async fn create_task(&self, request: Request<proto::CreateTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::TasksWrite)?;
+        let task_payload = validate(create_task_payload(request.into_inner(), &auth.claims.sub)?)?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(&self.state, move |state| async move {
+            PostOneTaskUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        self.state.task_events.publish(TaskChangeKind::Created, &task);
+        Ok(Response::new(task.into()))
+    }
+
+    async fn get_task(&self, request: Request<proto::GetTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::TasksRead)?;
+        let task_payload = validate(TaskDataPayload::new(Some(request.into_inner().id), None))?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(&self.state, move |state| async move {
+            GetOneTaskByIdUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        Ok(Response::new(task.into()))
+    }
+
+    async fn list_tasks(&self, request: Request<proto::ListTasksRequest>) -> Result<Response<proto::ListTasksResponse>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::TasksRead)?;
+        let task_payload = validate(list_tasks_payload(request.into_inner())?)?;
+        let policy = auth.task_policy();
+
+        let tasks = run_use_case(&self.state, move |state| async move {
+            GetAllTasksUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        Ok(Response::new(proto::ListTasksResponse {
+            tasks: tasks.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn update_task(&self, request: Request<proto::UpdateTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::TasksWrite)?;
+        let task_payload = validate(update_task_payload(request.into_inner())?)?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(&self.state, move |state| async move {
+            UpdateOneTaskUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        self.state.task_events.publish(TaskChangeKind::Updated, &task);
+        Ok(Response::new(task.into()))
+    }
+
+    async fn delete_task(&self, request: Request<proto::DeleteTaskRequest>) -> Result<Response<proto::Task>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::TasksDelete)?;
+        let task_payload = validate(TaskDataPayload::new(Some(request.into_inner().id), None))?;
+        let policy = auth.task_policy();
+
+        let task = run_use_case(&self.state, move |state| async move {
+            DeleteOneTaskByIdUseCase::new(&task_payload, &policy, &state.tasks_repository).execute().await
+        })
+        .await?;
+        self.state.task_events.publish(TaskChangeKind::Deleted, &task);
+        Ok(Response::new(task.into()))
+    }
+}
+
+pub struct ProjectsGrpcService {
+    state: web::Data<AppState>,
+}
+
+#[tonic::async_trait]
+impl ProjectsService for ProjectsGrpcService {
+    async fn get_projects(&self, request: Request<proto::GetProjectsRequest>) -> Result<Response<proto::GetProjectsResponse>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::TasksRead)?;
+        let project_ids = request.into_inner().ids;
+
+        let projects = run_use_case(&self.state, move |state| async move {
+            GetProjectsByIdsUseCase::new(&project_ids, &state.projects_repository).execute().await
+        })
+        .await?;
+        Ok(Response::new(proto::GetProjectsResponse {
+            projects: projects.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+pub struct UsersGrpcService {
+    state: web::Data<AppState>,
+}
+
+#[tonic::async_trait]
+impl UsersService for UsersGrpcService {
+    async fn get_me(&self, request: Request<proto::GetMeRequest>) -> Result<Response<proto::User>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::AccountRead)?;
+        let user_payload = UserIdPayload::new(auth.claims.sub.clone());
+
+        let user = run_use_case(&self.state, move |state| async move {
+            GetOneUserByIdUseCase::new(&user_payload, &state.users_repository).execute().await
+        })
+        .await?;
+        Ok(Response::new(user.into()))
+    }
+
+    async fn get_user(&self, request: Request<proto::GetUserRequest>) -> Result<Response<proto::User>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        let user_id = request.into_inner().id;
+        auth.require(if user_id == auth.claims.sub { Permission::AccountRead } else { Permission::UsersRead })?;
+        let user_payload = validate(UserIdPayload::new(user_id))?;
+
+        let user = run_use_case(&self.state, move |state| async move {
+            GetOneUserByIdUseCase::new(&user_payload, &state.users_repository).execute().await
+        })
+        .await?;
+        Ok(Response::new(user.into()))
+    }
+
+    async fn list_users(&self, request: Request<proto::ListUsersRequest>) -> Result<Response<proto::ListUsersResponse>, Status> {
+        let auth = grpc_auth::authenticate(&self.state, request.metadata()).await?;
+        auth.require(Permission::UsersRead)?;
+
+        let users = run_use_case(&self.state, |state| async move { GetAllUsersUseCase::new(&state.users_repository).execute().await }).await?;
+        Ok(Response::new(proto::ListUsersResponse {
+            users: users.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
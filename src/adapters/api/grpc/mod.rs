@@ -0,0 +1,9 @@
+pub mod grpc_auth;
+pub mod grpc_layers;
+pub mod grpc_mappers;
+pub mod grpc_services;
+
+// Messages, servers and clients generated from proto/ by build.rs
+pub mod proto {
+    tonic::include_proto!("tasktracker.v1");
+}
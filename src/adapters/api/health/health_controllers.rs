@@ -0,0 +1,72 @@
+use actix_web::{
+    get,
+    http::{header, StatusCode},
+    web, HttpResponse, ResponseError,
+};
+use chrono::DateTime;
+
+use crate::{
+    adapters::api::{
+        health::{
+            health_mappers::ReadinessPresenterMapper,
+            health_presenters::{HealthPresenter, VersionPresenter},
+        },
+        shared::{app_state::AppState, error_presenter::ErrorResponse},
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{health::get_readiness_usecase::GetReadinessUseCase, interfaces::AbstractUseCase},
+    },
+};
+
+// Both set by build.rs
+const GIT_SHA: &str = env!("GIT_SHA");
+const BUILD_TIMESTAMP: &str = env!("BUILD_TIMESTAMP");
+
+// Probe paths, kept out of the request log
+pub const LIVENESS_PATH: &str = "/health/live";
+pub const READINESS_PATH: &str = "/health/ready";
+pub const VERSION_PATH: &str = "/version";
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_liveness).service(get_readiness).service(get_version);
+}
+
+// The process answers, nothing else is checked so a database outage doesn't get the service restarted
+#[get("/health/live")]
+async fn get_liveness() -> HttpResponse {
+    HttpResponse::Ok()
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(HealthPresenter { status: String::from("ok") })
+}
+
+// Whether the service should receive traffic, 503 until the database is reachable and migrated
+#[get("/health/ready")]
+async fn get_readiness(data: web::Data<AppState>) -> HttpResponse {
+    let get_readiness_usecase = GetReadinessUseCase::new(&data.health_repository);
+    let readiness = match get_readiness_usecase.execute().await {
+        Ok(readiness) => readiness,
+        Err(e) => return ErrorResponse::map_io_error(e).error_response(),
+    };
+
+    let status = if readiness.is_ready() { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    HttpResponse::build(status)
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .json(ReadinessPresenterMapper::to_api(readiness))
+}
+
+#[get("/version")]
+async fn get_version() -> HttpResponse {
+    let build_time = BUILD_TIMESTAMP
+        .parse::<i64>()
+        .ok()
+        .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+        .map(|datetime| datetime.to_rfc3339())
+        .unwrap_or_default();
+
+    HttpResponse::Ok().json(VersionPresenter {
+        version: String::from(env!("CARGO_PKG_VERSION")),
+        git_sha: String::from(GIT_SHA),
+        build_time,
+    })
+}
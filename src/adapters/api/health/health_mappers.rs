@@ -0,0 +1,27 @@
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::health_entity::{HealthCheckEntity, ReadinessEntity};
+
+use super::health_presenters::{HealthCheckPresenter, ReadinessPresenter};
+
+pub struct ReadinessPresenterMapper {}
+
+impl ApiMapper<ReadinessEntity, ReadinessPresenter, ()> for ReadinessPresenterMapper {
+    fn to_api(entity: ReadinessEntity) -> ReadinessPresenter {
+        ReadinessPresenter {
+            status: String::from(if entity.is_ready() { "ok" } else { "unavailable" }),
+            checks: entity.checks.into_iter().map(health_check_to_api).collect(),
+        }
+    }
+
+    fn to_entity(_payload: ()) -> ReadinessEntity {
+        panic!("not implemented");
+    }
+}
+
+fn health_check_to_api(entity: HealthCheckEntity) -> HealthCheckPresenter {
+    HealthCheckPresenter {
+        name: entity.name,
+        status: String::from(if entity.healthy { "up" } else { "down" }),
+        details: entity.details,
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HealthPresenter {
+    pub status: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HealthCheckPresenter {
+    pub name: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadinessPresenter {
+    pub status: String,
+    pub checks: Vec<HealthCheckPresenter>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VersionPresenter {
+    pub version: String,
+    pub git_sha: String,
+    // RFC 3339, UTC
+    pub build_time: String,
+}
@@ -0,0 +1,3 @@
+pub mod health_controllers;
+pub mod health_mappers;
+pub mod health_presenters;
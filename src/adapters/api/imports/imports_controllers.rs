@@ -0,0 +1,109 @@
+use crate::{
+    adapters::api::{
+        imports::{
+            imports_mappers::ImportJobPresenterMapper,
+            imports_parsers::parse,
+            imports_payloads::{ImportJobIdPayload, ImportQueryPayload},
+            imports_presenters::ImportJobPresenter,
+        },
+        shared::{
+            app_state::AppState,
+            blocking::run_blocking,
+            error_presenter::{ErrorPresenter, ErrorResponse},
+            success_presenter::SuccessResponse,
+            validated_json::ValidatedQuery,
+        },
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{
+            import::{get_import_job_usecase::GetImportJobUseCase, queue_import_usecase::QueueImportUseCase, run_import_usecase::RunImportUseCase},
+            interfaces::AbstractUseCase,
+        },
+        utils::access_control::extractors::authorized::{require, Authorized},
+    },
+    domain::task_entity::TaskChangeKind,
+};
+use actix_web::{get, post, rt, web, HttpResponse};
+use reqwest::StatusCode;
+use utoipa::OpenApi;
+
+// Body limit of an export file
+const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
+#[derive(OpenApi)]
+#[openapi(paths(start_import, get_import))]
+pub struct ImportsApi;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(start_import).service(get_import).app_data(web::PayloadConfig::new(MAX_IMPORT_BYTES));
+}
+
+#[utoipa::path(
+    tag = "imports",
+    summary = "Import a Trello, Todoist or Notion export",
+    description = "Reads the file right away, then creates a project and its tasks, owned by the caller, in the background. \
+        Poll `GET /{job_id}` for the progress and the items left out. Labels naming a type, priority or status set it, \
+        the others are listed in the task description. One import runs at a time per user.",
+    params(ImportQueryPayload),
+    request_body(description = "Trello board JSON, Todoist project CSV or Notion database CSV", content(
+        (String = "application/json"),
+        (String = "text/csv")
+    )),
+    responses(
+        (status = 200, description = "Success, `code` is 202 and the job is queued", body = SuccessResponse<ImportJobPresenter>),
+        (status = 400, description = "Not an export of `source`", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 409, description = "Another import of the caller is running", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("")]
+async fn start_import(data: web::Data<AppState>, auth: Authorized<require::TasksWrite>, path: ValidatedQuery<ImportQueryPayload>, body: web::Bytes) -> Result<HttpResponse, ErrorResponse> {
+    let query = path.into_inner();
+    let import_payload = parse(query.source, &body, query.project_name.as_deref()).map_err(ErrorResponse::map_io_error)?;
+    let queue_import_usecase = QueueImportUseCase::new(&auth.sub, query.source, &import_payload.skipped, &data.import_jobs_repository);
+    let job = queue_import_usecase.execute().await.map_err(ErrorResponse::map_io_error)?;
+
+    let (state, job_id, user_id) = (data.clone(), job.id.clone(), auth.sub.clone());
+    // the repositories block on the database, the import runs on the blocking pool and keeps the worker free
+    rt::spawn(async move {
+        let imported = run_blocking(move || async move {
+            let run_import_usecase = RunImportUseCase::new(&job_id, &user_id, &import_payload, &state.import_jobs_repository, &state.projects_repository, &state.tasks_repository);
+            // a failure is kept on the job
+            if let Ok(tasks) = run_import_usecase.execute().await {
+                for task in &tasks {
+                    state.task_events.publish(TaskChangeKind::Created, task);
+                }
+            }
+        })
+        .await;
+        if let Err(e) = imported {
+            log::error!("Import stopped before the end: {}", e);
+        }
+    });
+
+    Ok(SuccessResponse::new(StatusCode::ACCEPTED, "Import started", ImportJobPresenterMapper::to_api(job)).to_http_response())
+}
+
+#[utoipa::path(
+    tag = "imports",
+    summary = "Progress and report of an import of the caller",
+    params(ImportJobIdPayload),
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<ImportJobPresenter>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Unknown job, another user's or finished over an hour ago", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/{job_id}")]
+async fn get_import(data: web::Data<AppState>, auth: Authorized<require::TasksRead>, path: web::Path<ImportJobIdPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let get_import_job_usecase = GetImportJobUseCase::new(&path.job_id, &auth.sub, &data.import_jobs_repository);
+    let job = get_import_job_usecase.execute().await.map_err(ErrorResponse::map_io_error)?;
+
+    Ok(SuccessResponse::new(StatusCode::OK, "Import retrieved successfully", ImportJobPresenterMapper::to_api(job)).to_http_response())
+}
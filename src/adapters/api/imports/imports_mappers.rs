@@ -0,0 +1,42 @@
+use chrono::NaiveDateTime;
+
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::import_entity::ImportJobEntity;
+
+use super::imports_payloads::ImportJobPayload;
+use super::imports_presenters::{ImportJobPresenter, ImportSkippedPresenter};
+
+pub struct ImportJobPresenterMapper {}
+
+impl ApiMapper<ImportJobEntity, ImportJobPresenter, ImportJobPayload> for ImportJobPresenterMapper {
+    fn to_api(entity: ImportJobEntity) -> ImportJobPresenter {
+        ImportJobPresenter {
+            job_id: entity.id,
+            source: entity.source,
+            state: entity.state,
+            total: entity.total,
+            processed: entity.processed,
+            project_ids: entity.project_ids,
+            task_ids: entity.task_ids,
+            skipped: entity
+                .skipped
+                .into_iter()
+                .map(|skipped| ImportSkippedPresenter {
+                    item: skipped.item,
+                    reason: skipped.reason,
+                })
+                .collect(),
+            error: entity.error,
+            updated_at: naive_datetime_to_unixtimemillis(entity.updated_at),
+            created_at: naive_datetime_to_unixtimemillis(entity.created_at),
+        }
+    }
+
+    fn to_entity(_payload: ImportJobPayload) -> ImportJobEntity {
+        panic!("not implemented");
+    }
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_millis()
+}
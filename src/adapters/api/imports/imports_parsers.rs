@@ -0,0 +1,505 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    adapters::api::{
+        imports::imports_payloads::{ImportPayload, ImportProjectPayload},
+        shared::validated_json::field_errors,
+        tasks::tasks_payloads::TaskCreatePayload,
+    },
+    application::utils::error_handling_utils::ErrorHandlingUtils,
+    domain::{
+        error::ApiError,
+        import_entity::{ImportSkippedItem, ImportSource},
+        task_entity::{TaskPriority, TaskStatus, TaskStatusInProgress, TaskStatusToDo, TaskType},
+    },
+};
+
+// Reads an export file. A file that isn't an export of `source` fails as a whole, what can't become a task is
+// listed in `skipped`. `project_name` replaces the name of the created project.
+pub fn parse(source: ImportSource, body: &[u8], project_name: Option<&str>) -> Result<ImportPayload, ApiError> {
+    match source {
+        ImportSource::Trello => trello(body, project_name),
+        ImportSource::Todoist => todoist(body, project_name.unwrap_or("Todoist import")),
+        ImportSource::Notion => notion(body, project_name.unwrap_or("Notion import")),
+    }
+}
+
+fn unreadable_file(message: &str) -> ApiError {
+    ErrorHandlingUtils::application_error(message, None)
+}
+
+// A task as read from an export, before it is checked
+#[derive(Default)]
+struct ImportedTask {
+    title: String,
+    description: Vec<String>,
+    typ: Option<TaskType>,
+    priority: Option<TaskPriority>,
+    status: Option<TaskStatus>,
+    due_date: Option<i64>,
+    task_list: Vec<String>,
+    labels: Vec<String>,
+}
+
+impl ImportedTask {
+    fn new(title: &str) -> Self {
+        ImportedTask {
+            title: title.trim().to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn describe(&mut self, text: &str) {
+        if !text.trim().is_empty() {
+            self.description.push(text.trim().to_string());
+        }
+    }
+
+    // Tasks have no labels: one naming a type, priority or status sets it ("Work", "High", "Bug"), the others
+    // are listed at the end of the description
+    fn label(&mut self, label: &str) {
+        let key = normalize(label);
+        if let Some(typ) = type_from_name(&key) {
+            self.typ = Some(typ);
+        } else if let Some(priority) = priority_from_name(&key) {
+            self.priority = Some(priority);
+        } else if let Some(status) = status_from_name(&key) {
+            self.status = Some(status);
+        } else if !key.is_empty() && !self.labels.contains(&label.trim().to_string()) {
+            self.labels.push(label.trim().to_string());
+        }
+    }
+
+    fn into_payload(mut self) -> TaskCreatePayload {
+        if !self.labels.is_empty() {
+            self.description.push(format!("Labels: {}", self.labels.join(", ")));
+        }
+        TaskCreatePayload::new(
+            None,
+            None,
+            self.title,
+            self.typ,
+            self.priority,
+            self.status,
+            Some(self.description.join("\n\n")).filter(|description| !description.is_empty()),
+            None,
+            self.due_date,
+            Some(self.task_list).filter(|task_list| !task_list.is_empty()),
+        )
+    }
+}
+
+// Tasks breaking the rules of `POST /tasks/one` (a blank title, too many checklist items...) are skipped
+fn push_task(project: &mut ImportProjectPayload, skipped: &mut Vec<ImportSkippedItem>, item: &str, task: ImportedTask) {
+    let task_payload = task.into_payload();
+    match task_payload.validate() {
+        Ok(()) => project.tasks.push(task_payload),
+        Err(errors) => {
+            let reasons: Vec<String> = field_errors(&errors).into_iter().map(|error| format!("{} {}", error.field, error.message)).collect();
+            skipped.push(ImportSkippedItem::new(item, &reasons.join(", ")));
+        }
+    }
+}
+
+// "In Progress", "in-progress" and "IN_PROGRESS" all read as `in_progress`
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase().replace([' ', '-'], "_")
+}
+
+fn type_from_name(key: &str) -> Option<TaskType> {
+    TaskType::from_str(key).ok().filter(|typ| *typ != TaskType::None)
+}
+
+fn priority_from_name(key: &str) -> Option<TaskPriority> {
+    match key {
+        "urgent" | "p1" => Some(TaskPriority::High),
+        "p2" => Some(TaskPriority::Medium),
+        "p3" => Some(TaskPriority::Low),
+        other => TaskPriority::from_str(other).ok().filter(|priority| *priority != TaskPriority::None),
+    }
+}
+
+// List, section and status names of the usual boards
+fn status_from_name(key: &str) -> Option<TaskStatus> {
+    match key {
+        "done" | "complete" | "finished" => Some(TaskStatus::Completed),
+        "todo" | "backlog" => Some(TaskStatus::ToDo(TaskStatusToDo::None)),
+        "not_started" => Some(TaskStatus::ToDo(TaskStatusToDo::NotStarted)),
+        "bug" => Some(TaskStatus::ToDo(TaskStatusToDo::Bug)),
+        "docs" | "documentation" | "document" => Some(TaskStatus::ToDo(TaskStatusToDo::Document)),
+        "doing" => Some(TaskStatus::InProgress(TaskStatusInProgress::Doing)),
+        "testing" | "review" | "qa" => Some(TaskStatus::InProgress(TaskStatusInProgress::Testing)),
+        other => TaskStatus::from_str(other).ok().filter(|status| *status != TaskStatus::None),
+    }
+}
+
+// Due dates in unix millis. Notion writes ranges as "May 1, 2024 → May 3, 2024", only the start is kept.
+fn due_date(value: &str) -> Option<i64> {
+    let value = value.split('→').next().unwrap_or_default().trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.timestamp_millis());
+    }
+    let datetime = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M", "%B %d, %Y %I:%M %p", "%b %d, %Y %I:%M %p"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            ["%Y-%m-%d", "%B %d, %Y", "%b %d, %Y", "%d %b %Y", "%m/%d/%Y"]
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+    Some(datetime.and_utc().timestamp_millis())
+}
+
+fn set_due_date(task: &mut ImportedTask, skipped: &mut Vec<ImportSkippedItem>, item: &str, value: &str) {
+    if value.trim().is_empty() {
+        return;
+    }
+    match due_date(value) {
+        Some(due_date) => task.due_date = Some(due_date),
+        None => skipped.push(ImportSkippedItem::new(
+            &format!("due date of {}", item),
+            &format!("\"{}\" is not a date, the task has none", value.trim()),
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+struct TrelloBoard {
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(default)]
+    lists: Vec<TrelloList>,
+    #[serde(default)]
+    cards: Vec<TrelloCard>,
+    #[serde(default)]
+    checklists: Vec<TrelloChecklist>,
+}
+
+#[derive(Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+}
+
+#[derive(Deserialize)]
+struct TrelloCard {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(rename = "idList")]
+    id_list: String,
+    due: Option<String>,
+    #[serde(default, rename = "dueComplete")]
+    due_complete: bool,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+}
+
+#[derive(Deserialize)]
+struct TrelloLabel {
+    #[serde(default)]
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TrelloChecklist {
+    #[serde(rename = "idCard")]
+    id_card: String,
+    #[serde(default)]
+    pos: f64,
+    #[serde(default, rename = "checkItems")]
+    check_items: Vec<TrelloCheckItem>,
+}
+
+#[derive(Deserialize)]
+struct TrelloCheckItem {
+    name: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    pos: f64,
+}
+
+// Board JSON export (Menu > Print, export and share > Export as JSON). Lists become statuses, checklists the
+// checklist of their card with done items marked "[x] ", archived cards and lists are skipped.
+fn trello(body: &[u8], project_name: Option<&str>) -> Result<ImportPayload, ApiError> {
+    let board: TrelloBoard = serde_json::from_slice(body).map_err(|e| unreadable_file(&format!("Not a Trello board export: {}", e)))?;
+    let lists: HashMap<&str, &TrelloList> = board.lists.iter().map(|list| (list.id.as_str(), list)).collect();
+    let mut checklists: HashMap<&str, Vec<&TrelloChecklist>> = HashMap::new();
+    for checklist in &board.checklists {
+        checklists.entry(checklist.id_card.as_str()).or_default().push(checklist);
+    }
+
+    let mut project = ImportProjectPayload {
+        name: project_name.unwrap_or(&board.name).to_string(),
+        description: Some(board.desc.clone()).filter(|desc| !desc.trim().is_empty()),
+        tasks: Vec::new(),
+    };
+    let mut skipped = Vec::new();
+    for card in &board.cards {
+        let item = format!("card \"{}\"", card.name);
+        let list = lists.get(card.id_list.as_str());
+        if card.closed {
+            skipped.push(ImportSkippedItem::new(&item, "archived"));
+            continue;
+        }
+        if list.is_some_and(|list| list.closed) {
+            skipped.push(ImportSkippedItem::new(&item, "in an archived list"));
+            continue;
+        }
+
+        let mut task = ImportedTask::new(&card.name);
+        task.describe(&card.desc);
+        task.status = list.and_then(|list| status_from_name(&normalize(&list.name)));
+        for label in &card.labels {
+            match (label.name.trim().is_empty(), &label.color) {
+                (false, _) => task.label(&label.name),
+                (true, Some(color)) => task.label(color),
+                (true, None) => {}
+            }
+        }
+        set_due_date(&mut task, &mut skipped, &item, card.due.as_deref().unwrap_or_default());
+        if card.due_complete {
+            task.status = Some(TaskStatus::Completed);
+        }
+        let mut card_checklists = checklists.remove(card.id.as_str()).unwrap_or_default();
+        card_checklists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+        for checklist in card_checklists {
+            let mut check_items: Vec<&TrelloCheckItem> = checklist.check_items.iter().collect();
+            check_items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+            task.task_list.extend(check_items.into_iter().map(|check_item| match check_item.state.as_str() {
+                "complete" => format!("[x] {}", check_item.name.trim()),
+                _ => check_item.name.trim().to_string(),
+            }));
+        }
+        push_task(&mut project, &mut skipped, &item, task);
+    }
+
+    Ok(ImportPayload { projects: vec![project], skipped })
+}
+
+// Project CSV export (Project > Export as a template). Sections become statuses, sub-tasks the checklist of their
+// parent, comments its description and "@labels" are taken out of the title. PRIORITY 1 is the highest.
+fn todoist(body: &[u8], project_name: &str) -> Result<ImportPayload, ApiError> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).trim(csv::Trim::All).from_reader(body);
+    let headers = reader.headers().map_err(|e| unreadable_file(&format!("Not a Todoist export: {}", e)))?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.eq_ignore_ascii_case(name));
+    let (Some(type_column), Some(content_column)) = (column("TYPE"), column("CONTENT")) else {
+        return Err(unreadable_file("Not a Todoist export: missing TYPE or CONTENT column"));
+    };
+    let (description_column, priority_column, indent_column, date_column) = (column("DESCRIPTION"), column("PRIORITY"), column("INDENT"), column("DATE"));
+
+    let mut project = ImportProjectPayload {
+        name: project_name.to_string(),
+        description: None,
+        tasks: Vec::new(),
+    };
+    let mut skipped = Vec::new();
+    let mut section_status = None;
+    let mut current: Option<(String, ImportedTask)> = None;
+    for record in reader.records() {
+        let record = record.map_err(|e| unreadable_file(&format!("Not a Todoist export: {}", e)))?;
+        let field = |column: Option<usize>| column.and_then(|column| record.get(column)).unwrap_or_default();
+        let content = field(Some(content_column));
+
+        match field(Some(type_column)).to_lowercase().as_str() {
+            "task" if field(indent_column).parse::<u32>().unwrap_or(1) > 1 => match current.as_mut() {
+                Some((_, task)) => task.task_list.push(content.to_string()),
+                None => skipped.push(ImportSkippedItem::new(&format!("sub-task \"{}\"", content), "has no parent task")),
+            },
+            "task" => {
+                if let Some((item, task)) = current.take() {
+                    push_task(&mut project, &mut skipped, &item, task);
+                }
+                let (labels, words): (Vec<&str>, Vec<&str>) = content.split_whitespace().partition(|word| word.len() > 1 && word.starts_with('@'));
+                let item = format!("task \"{}\"", content);
+                let mut task = ImportedTask::new(&words.join(" "));
+                task.status = section_status;
+                task.describe(field(description_column));
+                task.priority = match field(priority_column) {
+                    "1" => Some(TaskPriority::High),
+                    "2" => Some(TaskPriority::Medium),
+                    "3" => Some(TaskPriority::Low),
+                    _ => None,
+                };
+                for label in labels {
+                    task.label(&label[1..]);
+                }
+                set_due_date(&mut task, &mut skipped, &item, field(date_column));
+                current = Some((item, task));
+            }
+            "note" => match current.as_mut() {
+                Some((_, task)) => task.describe(content),
+                None => skipped.push(ImportSkippedItem::new(&format!("comment \"{}\"", content), "belongs to the project, not to a task")),
+            },
+            "section" => {
+                section_status = status_from_name(&normalize(content));
+                if section_status.is_none() {
+                    skipped.push(ImportSkippedItem::new(
+                        &format!("section \"{}\"", content),
+                        "has no matching status, its tasks are imported without one",
+                    ));
+                }
+            }
+            // blank separator rows and view settings
+            "" | "meta" => {}
+            other => skipped.push(ImportSkippedItem::new(&format!("{} \"{}\"", other, content), "not a task")),
+        }
+    }
+    if let Some((item, task)) = current.take() {
+        push_task(&mut project, &mut skipped, &item, task);
+    }
+
+    Ok(ImportPayload { projects: vec![project], skipped })
+}
+
+enum NotionColumn {
+    Labels,
+    Due,
+    Description,
+    Done,
+    Other,
+}
+
+// Database CSV export (... > Export > Markdown & CSV). The first column is the page title, "Status",
+// "Priority" and "Tags" values are read as labels, other properties have no task equivalent.
+fn notion(body: &[u8], project_name: &str) -> Result<ImportPayload, ApiError> {
+    let mut reader = csv::ReaderBuilder::new().flexible(true).from_reader(body);
+    let headers = reader.headers().map_err(|e| unreadable_file(&format!("Not a Notion export: {}", e)))?.clone();
+    if headers.is_empty() {
+        return Err(unreadable_file("Not a Notion export: no columns"));
+    }
+    let columns: Vec<NotionColumn> = headers
+        .iter()
+        .skip(1)
+        .map(|header| match normalize(header).as_str() {
+            "status" | "state" | "stage" | "priority" | "tags" | "labels" | "label" | "type" | "category" => NotionColumn::Labels,
+            "due" | "due_date" | "date" | "deadline" => NotionColumn::Due,
+            "description" | "notes" | "summary" => NotionColumn::Description,
+            "done" | "completed" | "complete" | "checkbox" => NotionColumn::Done,
+            _ => NotionColumn::Other,
+        })
+        .collect();
+
+    let mut project = ImportProjectPayload {
+        name: project_name.to_string(),
+        description: None,
+        tasks: Vec::new(),
+    };
+    let mut skipped: Vec<ImportSkippedItem> = headers
+        .iter()
+        .skip(1)
+        .zip(&columns)
+        .filter(|(_, column)| matches!(column, NotionColumn::Other))
+        .map(|(header, _)| ImportSkippedItem::new(&format!("column \"{}\"", header), "has no task equivalent"))
+        .collect();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| unreadable_file(&format!("Not a Notion export: {}", e)))?;
+        let title = record.get(0).unwrap_or_default();
+        let item = format!("row {} \"{}\"", index + 1, title);
+        let mut task = ImportedTask::new(title);
+        for (value, column) in record.iter().skip(1).zip(&columns) {
+            match column {
+                NotionColumn::Labels => value.split(',').for_each(|label| task.label(label)),
+                NotionColumn::Due => set_due_date(&mut task, &mut skipped, &item, value),
+                NotionColumn::Description => task.describe(value),
+                NotionColumn::Done if matches!(normalize(value).as_str(), "yes" | "true" | "checked") => task.status = Some(TaskStatus::Completed),
+                NotionColumn::Done | NotionColumn::Other => {}
+            }
+        }
+        push_task(&mut project, &mut skipped, &item, task);
+    }
+
+    Ok(ImportPayload { projects: vec![project], skipped })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_read_trello_boards() {
+        let body = br#"{
+            "name": "Launch", "desc": "Q3 launch",
+            "lists": [{"id": "l1", "name": "Doing"}, {"id": "l2", "name": "Old", "closed": true}],
+            "cards": [
+                {"id": "c1", "name": "Write docs", "idList": "l1", "due": "2024-05-01T12:00:00.000Z",
+                 "labels": [{"name": "High"}, {"name": "Marketing"}, {"name": "", "color": "green"}]},
+                {"id": "c2", "name": "Gone", "idList": "l2"},
+                {"id": "c3", "name": "Archived", "idList": "l1", "closed": true}
+            ],
+            "checklists": [{"idCard": "c1", "checkItems": [{"name": "review", "state": "incomplete", "pos": 2}, {"name": "outline", "state": "complete", "pos": 1}]}]
+        }"#;
+
+        let import_payload = parse(ImportSource::Trello, body, None).unwrap();
+
+        let project = &import_payload.projects[0];
+        assert_eq!((project.name.as_str(), project.description.as_deref()), ("Launch", Some("Q3 launch")));
+        let task = &project.tasks[0];
+        assert_eq!(task.status, Some(TaskStatus::InProgress(TaskStatusInProgress::Doing)));
+        assert_eq!(task.priority, Some(TaskPriority::High));
+        assert_eq!(task.due_date, Some(1714564800000));
+        assert_eq!(task.task_list, Some(vec![String::from("[x] outline"), String::from("review")]));
+        assert_eq!(task.description.as_deref(), Some("Labels: Marketing, green"));
+        let reasons: Vec<&str> = import_payload.skipped.iter().map(|skipped| skipped.reason.as_str()).collect();
+        assert_eq!(reasons, vec!["in an archived list", "archived"]);
+    }
+
+    #[test]
+    fn test_should_read_todoist_projects() {
+        let body = b"TYPE,CONTENT,DESCRIPTION,PRIORITY,INDENT,AUTHOR,RESPONSIBLE,DATE,DATE_LANG,TIMEZONE\n\
+            section,In Progress,,,,,,,,\n\
+            task,Fix login @bug @backend,Users stuck,1,1,,,2024-05-01,en,\n\
+            task,Reproduce,,4,2,,,,,\n\
+            note,Seen on staging,,,,,,,,\n\
+            task,Water plants,,4,1,,,every day,en,\n";
+
+        let import_payload = parse(ImportSource::Todoist, body, Some("Work")).unwrap();
+
+        let project = &import_payload.projects[0];
+        assert_eq!(project.name, "Work");
+        let task = &project.tasks[0];
+        assert_eq!(task.title, "Fix login");
+        assert_eq!(task.priority, Some(TaskPriority::High));
+        assert_eq!(task.status, Some(TaskStatus::ToDo(TaskStatusToDo::Bug)));
+        assert_eq!(task.task_list, Some(vec![String::from("Reproduce")]));
+        assert_eq!(task.description.as_deref(), Some("Users stuck\n\nSeen on staging\n\nLabels: backend"));
+        assert_eq!(project.tasks[1].due_date, None);
+        assert_eq!(import_payload.skipped[0].item, "due date of task \"Water plants\"");
+    }
+
+    #[test]
+    fn test_should_read_notion_databases() {
+        let body = "Name,Status,Priority,Due,Assignee\nShip it,Done,Medium,\"May 1, 2024 → May 3, 2024\",Ana\n,Not started,,,\n".as_bytes();
+
+        let import_payload = parse(ImportSource::Notion, body, None).unwrap();
+
+        let project = &import_payload.projects[0];
+        assert_eq!(project.name, "Notion import");
+        assert_eq!(project.tasks.len(), 1);
+        assert_eq!(project.tasks[0].status, Some(TaskStatus::Completed));
+        assert_eq!(project.tasks[0].priority, Some(TaskPriority::Medium));
+        assert_eq!(project.tasks[0].due_date, Some(1714521600000));
+        let items: Vec<&str> = import_payload.skipped.iter().map(|skipped| skipped.item.as_str()).collect();
+        assert_eq!(items, vec!["column \"Assignee\"", "row 2 \"\""]);
+    }
+
+    #[test]
+    fn test_should_refuse_files_of_another_tool() {
+        let error = parse(ImportSource::Trello, b"Name,Status\n", None).unwrap_err();
+
+        assert_eq!(error.code, 400);
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
+
+use crate::{
+    adapters::api::tasks::tasks_payloads::TaskCreatePayload,
+    application::utils::validate_params,
+    domain::import_entity::{ImportSkippedItem, ImportSource},
+};
+
+// Query of an import, the body is the export file itself
+#[derive(Serialize, Deserialize, IntoParams, Validate, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQueryPayload {
+    pub source: ImportSource,
+    // Name of the project created for a Todoist or Notion file, Trello boards carry their own
+    #[validate(custom(function = "validate_params::not_blank"), length(max = 255, code = "too_long", message = "must be at most 255 characters"))]
+    pub project_name: Option<String>,
+}
+
+// What an export file holds once read, every task already follows the rules of `TaskCreatePayload`
+#[derive(Debug, Default)]
+pub struct ImportPayload {
+    pub projects: Vec<ImportProjectPayload>,
+    pub skipped: Vec<ImportSkippedItem>,
+}
+
+impl ImportPayload {
+    pub fn task_count(&self) -> usize {
+        self.projects.iter().map(|project| project.tasks.len()).sum()
+    }
+}
+
+// A board, project or database, its tasks get the id of the project created for it and the caller as owner
+#[derive(Debug)]
+pub struct ImportProjectPayload {
+    pub name: String,
+    pub description: Option<String>,
+    pub tasks: Vec<TaskCreatePayload>,
+}
+
+#[derive(Serialize, Deserialize, IntoParams, Debug)]
+#[into_params(parameter_in = Path)]
+pub struct ImportJobIdPayload {
+    pub job_id: String,
+}
+
+pub struct ImportJobPayload {}
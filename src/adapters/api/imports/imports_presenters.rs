@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain::import_entity::{ImportJobState, ImportSource};
+
+// `processed` moves towards `total` while the job runs, `skipped` is known as soon as the file is read
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct ImportJobPresenter {
+    pub job_id: String,
+    pub source: ImportSource,
+    pub state: ImportJobState,
+    pub total: usize,
+    pub processed: usize,
+    pub project_ids: Vec<String>,
+    pub task_ids: Vec<String>,
+    pub skipped: Vec<ImportSkippedPresenter>,
+    pub error: Option<String>,
+    pub updated_at: i64,
+    pub created_at: i64,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct ImportSkippedPresenter {
+    #[schema(example = "card \"Old idea\"")]
+    pub item: String,
+    #[schema(example = "archived")]
+    pub reason: String,
+}
@@ -0,0 +1,5 @@
+pub mod imports_controllers;
+pub mod imports_mappers;
+pub mod imports_parsers;
+pub mod imports_payloads;
+pub mod imports_presenters;
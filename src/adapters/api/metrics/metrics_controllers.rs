@@ -0,0 +1,51 @@
+use actix_web::{
+    get,
+    http::{header, StatusCode},
+    web, HttpRequest, HttpResponse,
+};
+
+use crate::{
+    adapters::api::shared::{app_state::AppState, error_presenter::ErrorPresenter},
+    application::{
+        usecases::{interfaces::AbstractUseCase, metrics::get_metrics_snapshot_usecase::GetMetricsSnapshotUseCase},
+        utils::token_hash::hash_token,
+    },
+    domain::error::error_codes,
+};
+
+pub const METRICS_PATH: &str = "/metrics";
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_metrics);
+}
+
+// Prometheus scrape target. Open unless a scrape token is configured, in which case it must come as a bearer token.
+#[get("/metrics")]
+async fn get_metrics(data: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    if let Some(token) = &data.settings.metrics.token {
+        let bearer = req.headers().get(header::AUTHORIZATION).and_then(|h| h.to_str().ok()).and_then(|h| h.strip_prefix("Bearer "));
+        // digests are compared so the check doesn't reveal how much of the token matched
+        if bearer.map(hash_token) != Some(hash_token(token)) {
+            return HttpResponse::Unauthorized().json(ErrorPresenter {
+                code: StatusCode::UNAUTHORIZED.as_u16(),
+                error_code: error_codes::UNAUTHORIZED.to_string(),
+                message: String::from("Requires authentication"),
+                data: None,
+            });
+        }
+    }
+
+    let get_metrics_snapshot_usecase = GetMetricsSnapshotUseCase::new(&data.metrics_repository);
+    // the request metrics are still worth serving when the database can't be read
+    let snapshot = match get_metrics_snapshot_usecase.execute().await {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            log::error!("Metrics snapshot failed: {}", e.get_error_message());
+            None
+        }
+    };
+
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4; charset=utf-8")
+        .body(data.metrics.render(snapshot.as_ref()))
+}
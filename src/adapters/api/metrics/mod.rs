@@ -0,0 +1 @@
+pub mod metrics_controllers;
@@ -0,0 +1,104 @@
+use crate::{
+    adapters::api::{
+        mfa::{
+            mfa_mappers::{MfaEnrollmentPresenterMapper, MfaRecoveryCodesPresenterMapper, MfaRoleRequirementPresenterMapper},
+            mfa_payloads::{MfaCodePayload, MfaLoginPayload, MfaRoleRequirementPayload},
+        },
+        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+        users::{
+            users_mappers::UserPresenterMapper,
+            users_payloads::{UserIdPayload, UserRolePayload},
+        },
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{
+            interfaces::AbstractUseCase,
+            mfa::{
+                confirm_mfa_usecase::ConfirmMfaUseCase, disable_mfa_usecase::DisableMfaUseCase, enroll_mfa_usecase::EnrollMfaUseCase, login_mfa_usecase::LoginMfaUseCase,
+                update_mfa_role_requirement_usecase::UpdateMfaRoleRequirementUseCase,
+            },
+        },
+        utils::access_control::{
+            extractors::{authorized::{require, Authorized}, claims::Role, signed_in::SignedIn},
+        },
+    },
+};
+use actix_web::{patch, post, web, HttpResponse};
+use reqwest::StatusCode;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(login_mfa)
+        .service(enroll_mfa_own)
+        .service(confirm_mfa_own)
+        .service(disable_mfa_own)
+        .service(update_mfa_role_requirement);
+}
+
+#[post("/login")]
+async fn login_mfa(data: web::Data<AppState>, path: web::Json<MfaLoginPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mfa_payload = path.into_inner();
+    let login_mfa_usecase = LoginMfaUseCase::new(&mfa_payload, &data.mfa_repository);
+
+    match login_mfa_usecase.execute().await {
+        Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "User signin successfully", UserPresenterMapper::to_api(user)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+// Enrollment only needs a valid token, not an API key: users whose role requires 2FA get permission-less tokens until they enroll
+#[post("/enroll_own")]
+async fn enroll_mfa_own(data: web::Data<AppState>, claims: SignedIn) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = UserIdPayload::new(claims.sub.clone());
+    let enroll_mfa_usecase = EnrollMfaUseCase::new(&user_payload, &data.mfa_repository);
+
+    match enroll_mfa_usecase.execute().await {
+        Ok(enrollment) => Ok(SuccessResponse::new(StatusCode::OK, "Two-factor enrollment started", MfaEnrollmentPresenterMapper::to_api(enrollment)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[post("/confirm_own")]
+async fn confirm_mfa_own(data: web::Data<AppState>, claims: SignedIn, path: web::Json<MfaCodePayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut mfa_payload = path.into_inner();
+    mfa_payload.user_id = Some(claims.sub.clone());
+    let confirm_mfa_usecase = ConfirmMfaUseCase::new(&mfa_payload, &data.mfa_repository);
+
+    match confirm_mfa_usecase.execute().await {
+        Ok(recovery_codes) => Ok(SuccessResponse::new(
+            StatusCode::OK,
+            "Two-factor authentication enabled, store the recovery codes and sign in again",
+            MfaRecoveryCodesPresenterMapper::to_api(recovery_codes),
+        )
+        .to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[post("/disable_own")]
+async fn disable_mfa_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountWrite>>, path: web::Json<MfaCodePayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut mfa_payload = path.into_inner();
+    mfa_payload.user_id = Some(auth.sub.clone());
+    let disable_mfa_usecase = DisableMfaUseCase::new(&mfa_payload, &data.mfa_repository);
+
+    match disable_mfa_usecase.execute().await {
+        Ok(_) => Ok(SuccessResponse::new(StatusCode::OK, "Two-factor authentication disabled", ()).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[patch("/role_requirement")]
+async fn update_mfa_role_requirement(data: web::Data<AppState>, auth: Authorized<require::MfaManage>, path: web::Json<MfaRoleRequirementPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut mfa_payload = path.into_inner();
+    mfa_payload.updated_by = Some(auth.sub.clone());
+    // the super admin requirement stays with super admins, whatever the catalogue grants
+    if matches!(mfa_payload.role, UserRolePayload::SuperAdmin) && auth.role != Role::SuperAdmin {
+        return Err(ErrorResponse::auth_default());
+    }
+    let update_mfa_role_requirement_usecase = UpdateMfaRoleRequirementUseCase::new(&mfa_payload, &data.mfa_repository);
+
+    match update_mfa_role_requirement_usecase.execute().await {
+        Ok(requirement) => Ok(SuccessResponse::new(StatusCode::OK, "Two-factor requirement updated successfully", MfaRoleRequirementPresenterMapper::to_api(requirement)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
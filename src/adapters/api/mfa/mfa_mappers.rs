@@ -0,0 +1,58 @@
+use chrono::NaiveDateTime;
+
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::mfa_entity::{MfaEnrollmentEntity, MfaRecoveryCodesEntity, MfaRoleRequirementEntity};
+
+use super::mfa_payloads::MfaPayload;
+use super::mfa_presenters::{MfaEnrollmentPresenter, MfaRecoveryCodesPresenter, MfaRoleRequirementPresenter};
+
+pub struct MfaEnrollmentPresenterMapper {}
+
+pub struct MfaRecoveryCodesPresenterMapper {}
+
+pub struct MfaRoleRequirementPresenterMapper {}
+
+impl ApiMapper<MfaEnrollmentEntity, MfaEnrollmentPresenter, MfaPayload> for MfaEnrollmentPresenterMapper {
+    fn to_api(entity: MfaEnrollmentEntity) -> MfaEnrollmentPresenter {
+        MfaEnrollmentPresenter {
+            secret: entity.secret,
+            otpauth_uri: entity.otpauth_uri,
+        }
+    }
+
+    fn to_entity(_payload: MfaPayload) -> MfaEnrollmentEntity {
+        panic!("not implemented");
+    }
+}
+
+impl ApiMapper<MfaRecoveryCodesEntity, MfaRecoveryCodesPresenter, MfaPayload> for MfaRecoveryCodesPresenterMapper {
+    fn to_api(entity: MfaRecoveryCodesEntity) -> MfaRecoveryCodesPresenter {
+        MfaRecoveryCodesPresenter {
+            recovery_codes: entity.recovery_codes,
+        }
+    }
+
+    fn to_entity(_payload: MfaPayload) -> MfaRecoveryCodesEntity {
+        panic!("not implemented");
+    }
+}
+
+impl ApiMapper<MfaRoleRequirementEntity, MfaRoleRequirementPresenter, MfaPayload> for MfaRoleRequirementPresenterMapper {
+    fn to_api(entity: MfaRoleRequirementEntity) -> MfaRoleRequirementPresenter {
+        MfaRoleRequirementPresenter {
+            role: entity.role,
+            required: entity.required,
+            updated_by: entity.updated_by,
+            updated_at: naive_datetime_to_unixtimemillis(entity.updated_at),
+        }
+    }
+
+    fn to_entity(_payload: MfaPayload) -> MfaRoleRequirementEntity {
+        panic!("not implemented");
+    }
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    // Get the Unix timestamp in seconds and convert to milliseconds
+    datetime.and_utc().timestamp_millis()
+}
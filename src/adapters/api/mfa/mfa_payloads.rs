@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::api::users::users_payloads::UserRolePayload;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MfaCodePayload {
+    #[serde(skip_deserializing)]
+    pub user_id: Option<String>,
+    pub code: String,
+}
+
+impl MfaCodePayload {
+    pub fn new(user_id: Option<String>, code: String) -> Self {
+        MfaCodePayload { user_id, code }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MfaLoginPayload {
+    pub mfa_token: String,
+    pub code: String,
+}
+
+impl MfaLoginPayload {
+    pub fn new(mfa_token: String, code: String) -> Self {
+        MfaLoginPayload { mfa_token, code }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MfaRoleRequirementPayload {
+    pub role: UserRolePayload,
+    pub required: bool,
+    #[serde(skip_deserializing)]
+    pub updated_by: Option<String>,
+}
+
+impl MfaRoleRequirementPayload {
+    pub fn new(role: UserRolePayload, required: bool, updated_by: Option<String>) -> Self {
+        MfaRoleRequirementPayload { role, required, updated_by }
+    }
+}
+
+pub struct MfaPayload {}
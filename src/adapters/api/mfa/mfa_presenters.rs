@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MfaEnrollmentPresenter {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MfaRecoveryCodesPresenter {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MfaRoleRequirementPresenter {
+    pub role: String,
+    pub required: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: i64,
+}
@@ -0,0 +1,4 @@
+pub mod mfa_controllers;
+pub mod mfa_mappers;
+pub mod mfa_payloads;
+pub mod mfa_presenters;
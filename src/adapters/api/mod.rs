@@ -1,3 +1,16 @@
 pub mod users;
 pub mod tasks;
+pub mod imports;
+pub mod calendar;
+pub mod sessions;
+pub mod mfa;
+pub mod api_keys;
+pub mod permissions;
+pub mod oidc;
+pub mod well_known;
+pub mod health;
+pub mod metrics;
+pub mod openapi;
+pub mod graphql;
+pub mod grpc;
 pub mod shared;
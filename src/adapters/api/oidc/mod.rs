@@ -0,0 +1,2 @@
+pub mod oidc_controllers;
+pub mod oidc_payloads;
@@ -0,0 +1,53 @@
+use crate::{
+    adapters::api::{
+        oidc::oidc_payloads::{OidcCallbackPayload, OidcProviderPayload},
+        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+        users::users_mappers::UserPresenterMapper,
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        utils::access_control::middlewares::rate_limit::client_ip,
+        usecases::{
+            interfaces::AbstractUseCase,
+            oidc::{complete_oidc_login_usecase::CompleteOidcLoginUseCase, start_oidc_login_usecase::StartOidcLoginUseCase},
+        },
+    },
+};
+use actix_web::{get, http::header, web, HttpRequest, HttpResponse};
+use reqwest::StatusCode;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_all_providers).service(authorize_provider).service(callback_provider);
+}
+
+#[get("/providers")]
+async fn get_all_providers(data: web::Data<AppState>) -> Result<HttpResponse, ErrorResponse> {
+    Ok(SuccessResponse::new(StatusCode::OK, "Identity providers retrieved successfully", data.oidc_client.provider_names()).to_http_response())
+}
+
+// Sends the browser to the identity provider, which comes back to the callback with a code
+#[get("/{provider}/authorize")]
+async fn authorize_provider(data: web::Data<AppState>, path: web::Path<OidcProviderPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let oidc_payload = path.into_inner();
+    let start_oidc_login_usecase = StartOidcLoginUseCase::new(&oidc_payload, &data.oidc_repository, data.oidc_client.as_ref());
+
+    match start_oidc_login_usecase.execute().await {
+        Ok(authorization) => Ok(HttpResponse::Found().insert_header((header::LOCATION, authorization.authorization_url)).finish()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[get("/{provider}/callback")]
+async fn callback_provider(data: web::Data<AppState>, req: HttpRequest, path: web::Path<OidcProviderPayload>, query: web::Query<OidcCallbackPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut oidc_payload = query.into_inner();
+    oidc_payload.provider = path.into_inner().provider;
+    oidc_payload.ip_address = client_ip(&req, &data.trusted_proxies).map(|ip| ip.to_string());
+    oidc_payload.user_agent = req.headers().get(header::USER_AGENT).and_then(|h| h.to_str().ok()).map(String::from);
+    let complete_oidc_login_usecase = CompleteOidcLoginUseCase::new(&oidc_payload, &data.oidc_repository, data.oidc_client.as_ref());
+
+    match complete_oidc_login_usecase.execute().await {
+        Ok(user) if user.mfa_token.is_some() => Ok(SuccessResponse::new(StatusCode::OK, "Two-factor code required", UserPresenterMapper::to_api(user)).to_http_response()),
+        Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "User signin successfully", UserPresenterMapper::to_api(user)).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
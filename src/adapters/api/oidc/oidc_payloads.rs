@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OidcProviderPayload {
+    pub provider: String,
+}
+
+impl OidcProviderPayload {
+    pub fn new(provider: String) -> Self {
+        OidcProviderPayload { provider }
+    }
+}
+
+// Query string the identity provider redirects back with
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct OidcCallbackPayload {
+    #[serde(skip_deserializing)]
+    pub provider: String,
+    pub code: Option<String>,
+    pub state: Option<String>,
+    // Set instead of the code when the user declined or the request was rejected
+    pub error: Option<String>,
+    #[serde(skip_deserializing)]
+    pub ip_address: Option<String>,
+    #[serde(skip_deserializing)]
+    pub user_agent: Option<String>,
+}
+
+impl OidcCallbackPayload {
+    pub fn new(provider: String, code: Option<String>, state: Option<String>) -> Self {
+        OidcCallbackPayload {
+            provider,
+            code,
+            state,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OidcLoginStatePayload {
+    pub provider: String,
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+impl OidcLoginStatePayload {
+    pub fn new(provider: String, state: String, nonce: String, code_verifier: String) -> Self {
+        OidcLoginStatePayload {
+            provider,
+            state,
+            nonce,
+            code_verifier,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OidcIdentityPayload {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    // Set for providers allowed to attach the identity to an existing account by email
+    pub trust_email: bool,
+    pub name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
@@ -0,0 +1,36 @@
+use actix_web::web;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi,
+};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::adapters::api::{calendar::calendar_controllers::CalendarApi, imports::imports_controllers::ImportsApi, tasks::tasks_controllers::TasksApi, users::users_controllers::UsersApi};
+
+pub const OPENAPI_PATH: &str = "/openapi.json";
+
+// The document is built from the `#[utoipa::path]` annotations on the handlers, so it moves with the routes
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Task Tracker API", description = "Tasks and user accounts. Every answer is wrapped in a `{code, message, data}` envelope, errors add an `error_code`."),
+    nest((path = "/api/v1/tasks", api = TasksApi), (path = "/api/v1/imports", api = ImportsApi), (path = "/api/v1/calendar", api = CalendarApi), (path = "/api/v1/users", api = UsersApi)),
+    modifiers(&BearerAuth),
+    tags((name = "tasks", description = "Task management"), (name = "imports", description = "Trello, Todoist and Notion imports"), (name = "calendar", description = "iCalendar feeds of due tasks"), (name = "users", description = "Accounts, sign in and tokens"))
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(SwaggerUi::new("/swagger-ui/{_:.*}").url(OPENAPI_PATH, ApiDoc::openapi()));
+}
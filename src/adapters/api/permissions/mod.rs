@@ -0,0 +1,4 @@
+pub mod permissions_controllers;
+pub mod permissions_mappers;
+pub mod permissions_payloads;
+pub mod permissions_presenters;
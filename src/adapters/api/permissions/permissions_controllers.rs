@@ -0,0 +1,66 @@
+use crate::{
+    adapters::api::{
+        permissions::{permissions_mappers::RolePermissionsPresenterMapper, permissions_payloads::RolePermissionsPayload, permissions_presenters::RolePermissionsPresenter},
+        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{
+            interfaces::AbstractUseCase,
+            permission::{get_all_role_permissions_usecase::GetAllRolePermissionsUseCase, update_role_permissions_usecase::UpdateRolePermissionsUseCase},
+        },
+        utils::access_control::extractors::{
+            authorized::{require, Authorized},
+            claims::Permission,
+        },
+    },
+    domain::{error::ApiError, permission_entity::RolePermissionsEntity},
+};
+use actix_web::{get, patch, web, HttpResponse};
+use reqwest::StatusCode;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_permission_catalogue).service(get_all_role_permissions).service(update_one_role_permissions);
+}
+
+#[get("/catalogue")]
+async fn get_permission_catalogue(_auth: Authorized<require::PermissionsManage>) -> Result<HttpResponse, ErrorResponse> {
+    let catalogue = Permission::ALL.iter().map(|permission| permission.to_string()).collect::<Vec<String>>();
+
+    Ok(SuccessResponse::new(StatusCode::OK, "Permission catalogue retrieved successfully", catalogue).to_http_response())
+}
+
+#[get("/all")]
+async fn get_all_role_permissions(data: web::Data<AppState>, _auth: Authorized<require::PermissionsManage>) -> Result<HttpResponse, ErrorResponse> {
+    let get_all_role_permissions_usecase = GetAllRolePermissionsUseCase::new(&data.permissions_repository);
+    let role_permissions: Result<Vec<RolePermissionsEntity>, ApiError> = get_all_role_permissions_usecase.execute().await;
+
+    match role_permissions {
+        Ok(datas) => Ok(SuccessResponse::new(
+            StatusCode::OK,
+            "Role permissions retrieved successfully",
+            datas.into_iter().map(RolePermissionsPresenterMapper::to_api).collect::<Vec<RolePermissionsPresenter>>(),
+        )
+        .to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[patch("/one_role")]
+async fn update_one_role_permissions(
+    data: web::Data<AppState>,
+    _auth: Authorized<require::PermissionsManage>,
+    path: web::Json<RolePermissionsPayload>,
+) -> Result<HttpResponse, ErrorResponse> {
+    let permission_payload = path.into_inner();
+    let update_role_permissions_usecase = UpdateRolePermissionsUseCase::new(&permission_payload, &data.permissions_repository);
+
+    match update_role_permissions_usecase.execute().await {
+        Ok(role_permissions) => {
+            // removed grants take effect on the next request, added ones with the next token
+            data.permission_cache.mark_stale();
+            Ok(SuccessResponse::new(StatusCode::OK, "Role permissions updated successfully", RolePermissionsPresenterMapper::to_api(role_permissions)).to_http_response())
+        }
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
@@ -0,0 +1,20 @@
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::permission_entity::RolePermissionsEntity;
+
+use super::permissions_payloads::PermissionPayload;
+use super::permissions_presenters::RolePermissionsPresenter;
+
+pub struct RolePermissionsPresenterMapper {}
+
+impl ApiMapper<RolePermissionsEntity, RolePermissionsPresenter, PermissionPayload> for RolePermissionsPresenterMapper {
+    fn to_api(entity: RolePermissionsEntity) -> RolePermissionsPresenter {
+        RolePermissionsPresenter {
+            role: entity.role,
+            permissions: entity.permissions,
+        }
+    }
+
+    fn to_entity(_payload: PermissionPayload) -> RolePermissionsEntity {
+        panic!("not implemented");
+    }
+}
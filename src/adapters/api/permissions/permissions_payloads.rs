@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::adapters::api::users::users_payloads::UserRolePayload;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RolePermissionsPayload {
+    pub role: UserRolePayload,
+    // Replaces every permission currently granted to the role
+    pub permissions: Vec<String>,
+}
+
+impl RolePermissionsPayload {
+    pub fn new(role: UserRolePayload, permissions: Vec<String>) -> Self {
+        RolePermissionsPayload { role, permissions }
+    }
+}
+
+pub struct PermissionPayload {}
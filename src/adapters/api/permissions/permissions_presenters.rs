@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RolePermissionsPresenter {
+    pub role: String,
+    pub permissions: Vec<String>,
+}
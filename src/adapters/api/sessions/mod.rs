@@ -0,0 +1,4 @@
+pub mod sessions_controllers;
+pub mod sessions_mappers;
+pub mod sessions_payloads;
+pub mod sessions_presenters;
@@ -0,0 +1,58 @@
+use crate::{
+    adapters::api::{
+        sessions::{sessions_mappers::SessionPresenterMapper, sessions_payloads::SessionDataPayload, sessions_presenters::SessionPresenter},
+        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+    },
+    application::{
+        mappers::api_mapper::ApiMapper,
+        usecases::{
+            interfaces::AbstractUseCase,
+            session::{get_all_sessions_usecase::GetAllSessionsUseCase, revoke_one_session_usecase::RevokeOneSessionUseCase},
+        },
+        utils::access_control::extractors::{authorized::{require, Authorized}, signed_in::SignedIn},
+    },
+    domain::{error::ApiError, session_entity::SessionEntity},
+};
+use actix_web::{delete, get, web, HttpResponse};
+use chrono::Utc;
+use reqwest::StatusCode;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_all_sessions_own).service(revoke_one_session_own);
+}
+
+#[get("/all_own")]
+async fn get_all_sessions_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountRead>>) -> Result<HttpResponse, ErrorResponse> {
+    let session_payload = SessionDataPayload::new(None, Some(auth.sub.clone()));
+    let current_session_id = auth.sid.clone();
+    let get_all_sessions_usecase = GetAllSessionsUseCase::new(&session_payload, &data.sessions_repository);
+    let sessions: Result<Vec<SessionEntity>, ApiError> = get_all_sessions_usecase.execute().await;
+
+    match sessions {
+        Ok(datas) => Ok(SuccessResponse::new(
+            StatusCode::OK,
+            "Sessions retrieved successfully",
+            datas
+                .into_iter()
+                .map(|session| SessionPresenterMapper::to_api_with_current(session, current_session_id.as_deref()))
+                .collect::<Vec<SessionPresenter>>(),
+        )
+        .to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[delete("/one_own")]
+async fn revoke_one_session_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountWrite>>, path: web::Json<SessionDataPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut session_payload = path.into_inner();
+    session_payload.user_id = Some(auth.sub.clone());
+    let revoke_one_session_usecase = RevokeOneSessionUseCase::new(&session_payload, &data.sessions_repository);
+
+    match revoke_one_session_usecase.execute().await {
+        Ok(session) => {
+            data.revocation_cache.revoke_session(&session.id, Utc::now().timestamp());
+            Ok(SuccessResponse::new(StatusCode::OK, "Session revoked successfully", SessionPresenterMapper::to_api(session)).to_http_response())
+        }
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
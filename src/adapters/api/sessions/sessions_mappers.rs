@@ -0,0 +1,43 @@
+use chrono::NaiveDateTime;
+
+use crate::application::mappers::api_mapper::ApiMapper;
+use crate::domain::session_entity::SessionEntity;
+
+use super::sessions_payloads::SessionPayload;
+use super::sessions_presenters::SessionPresenter;
+
+pub struct SessionPresenterMapper {}
+
+impl SessionPresenterMapper {
+    // Flag the session the request was made from
+    pub fn to_api_with_current(entity: SessionEntity, current_session_id: Option<&str>) -> SessionPresenter {
+        let current = current_session_id == Some(entity.id.as_str());
+        SessionPresenter {
+            current,
+            ..SessionPresenterMapper::to_api(entity)
+        }
+    }
+}
+
+impl ApiMapper<SessionEntity, SessionPresenter, SessionPayload> for SessionPresenterMapper {
+    fn to_api(entity: SessionEntity) -> SessionPresenter {
+        SessionPresenter {
+            session_id: entity.id,
+            device_name: entity.device_name,
+            ip_address: entity.ip_address,
+            user_agent: entity.user_agent,
+            current: false,
+            last_used_at: naive_datetime_to_unixtimemillis(entity.last_used_at),
+            created_at: naive_datetime_to_unixtimemillis(entity.created_at),
+        }
+    }
+
+    fn to_entity(_payload: SessionPayload) -> SessionEntity {
+        panic!("not implemented");
+    }
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    // Get the Unix timestamp in seconds and convert to milliseconds
+    datetime.and_utc().timestamp_millis()
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct SessionDataPayload {
+    pub session_id: Option<String>,
+    pub user_id: Option<String>,
+}
+
+impl SessionDataPayload {
+    pub fn new(session_id: Option<String>, user_id: Option<String>) -> Self {
+        SessionDataPayload { session_id, user_id }
+    }
+}
+
+pub struct SessionPayload {}
@@ -1,7 +1,39 @@
-use crate::adapters::spi::db::{db_tasks_repository::TasksRepository, db_users_repository::UsersRepository};
+use crate::adapters::spi::db::{
+    db_account_tokens_repository::AccountTokensRepository, db_api_keys_repository::ApiKeysRepository, db_calendar_feeds_repository::CalendarFeedsRepository, db_health_repository::HealthRepository, db_import_jobs_repository::ImportJobsRepository, db_metrics_repository::MetricsRepository, db_login_attempts_repository::LoginAttemptsRepository, db_mfa_repository::MfaRepository, db_oidc_repository::OidcRepository, db_permissions_repository::PermissionsRepository, db_projects_repository::ProjectsRepository, db_sessions_repository::SessionsRepository, db_tasks_repository::TasksRepository, db_tokens_repository::TokensRepository, db_users_repository::UsersRepository,
+};
+use std::sync::Arc;
+
+use crate::adapters::spi::mail::mailer::Mailer;
+use crate::adapters::spi::oidc::oidc_client::OidcClient;
+use crate::application::utils::access_control::{middlewares::rate_limit::TrustedProxies, permission_cache::PermissionCache, revocation_cache::RevocationCache};
+use crate::application::utils::metrics::Metrics;
+use crate::application::utils::task_events::TaskEvents;
+use crate::infrastructure::settings::Settings;
 
 pub struct AppState {
     pub app_name: String,
     pub users_repository: UsersRepository,
     pub tasks_repository: TasksRepository,
+    pub projects_repository: ProjectsRepository,
+    pub tokens_repository: TokensRepository,
+    pub sessions_repository: SessionsRepository,
+    pub account_tokens_repository: AccountTokensRepository,
+    pub mfa_repository: MfaRepository,
+    pub api_keys_repository: ApiKeysRepository,
+    pub permissions_repository: PermissionsRepository,
+    pub oidc_repository: OidcRepository,
+    pub login_attempts_repository: LoginAttemptsRepository,
+    pub health_repository: HealthRepository,
+    pub metrics_repository: MetricsRepository,
+    pub calendar_feeds_repository: CalendarFeedsRepository,
+    pub import_jobs_repository: ImportJobsRepository,
+    pub mailer: Box<dyn Mailer + Send + Sync>,
+    pub oidc_client: Box<dyn OidcClient + Send + Sync>,
+    pub revocation_cache: RevocationCache,
+    pub permission_cache: PermissionCache,
+    pub metrics: Arc<Metrics>,
+    pub task_events: TaskEvents,
+    // peers whose X-Forwarded-For is believed when naming the client of a request
+    pub trusted_proxies: TrustedProxies,
+    pub settings: Settings,
 }
@@ -0,0 +1,18 @@
+use std::future::Future;
+
+use actix_web::{error::BlockingError, web};
+use tokio::runtime::Handle;
+
+// Use cases, repositories and stores are `?Send`, written for actix's single threaded workers, while GraphQL
+// resolvers, tonic handlers and background tasks need Send futures. They run to completion on the blocking pool
+// instead, next to their synchronous diesel calls, so a slow database never stalls a worker either. Errors have to be
+// made Send, e.g. turned into the transport's own error, before they leave `task`.
+pub async fn run_blocking<R, F, Fut>(task: F) -> Result<R, BlockingError>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = R>,
+    R: Send + 'static,
+{
+    let handle = Handle::current();
+    web::block(move || handle.block_on(task())).await
+}
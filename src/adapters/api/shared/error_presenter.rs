@@ -1,14 +1,21 @@
 use crate::application::utils::access_control::extractors::claims::ClientError;
-use crate::domain::error::ApiError;
+use crate::domain::error::{error_codes, ApiError};
 use actix_web::{error::ResponseError, http::StatusCode, HttpResponse};
 use derive_more::Display;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct ErrorPresenter {
     pub code: u16,
+    // stable identifier of the failure, see domain::error::error_codes
+    #[serde(default)]
+    #[schema(example = "validation_failed")]
+    pub error_code: String,
     pub message: String,
+    // the invalid fields when `error_code` is "validation_failed"
+    #[schema(value_type = Option<Vec<FieldErrorPresenter>>)]
     pub data: Option<serde_json::Value>,
 }
 
@@ -17,14 +24,27 @@ pub struct ErrorMessage {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code: Option<u16>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub message: Option<String>,
     pub data: Option<serde_json::Value>,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct FieldErrorPresenter<'a> {
+    #[schema(example = "title")]
+    field: &'a str,
+    #[schema(example = "required")]
+    code: &'a str,
+    #[schema(example = "must not be empty")]
+    message: &'a str,
+}
+
 #[derive(Error, Debug, Display)]
 #[display(fmt = "{:?}", message)]
 pub struct ErrorResponse {
     code: StatusCode,
+    error_code: &'static str,
     message: String,
     data: Option<serde_json::Value>,
 }
@@ -38,8 +58,9 @@ impl ResponseError for ErrorResponse {
         let code = self.status_code();
         let error_response = ErrorPresenter {
             code: code.as_u16(),
+            error_code: self.error_code.to_string(),
             message: self.message.clone(),
-            data: None,
+            data: self.data.clone(),
         };
         HttpResponse::build(code).json(error_response)
     }
@@ -47,46 +68,57 @@ impl ResponseError for ErrorResponse {
 
 impl ErrorResponse {
     pub fn map_io_error(e: ApiError) -> ErrorResponse {
-        match e.get_error_code() {
-            400 => ErrorResponse {
-                code: StatusCode::BAD_REQUEST,
-                message: e.get_error_message(),
-                data: None,
-            },
-            401 => ErrorResponse {
-                code: StatusCode::UNAUTHORIZED,
-                message: e.get_error_message(),
-                data: None,
-            },
-            403 => ErrorResponse {
-                code: StatusCode::FORBIDDEN,
-                message: e.get_error_message(),
-                data: None,
-            },
-            404 => ErrorResponse {
-                code: StatusCode::NOT_FOUND,
-                message: e.get_error_message(),
-                data: None,
-            },
-            _ => ErrorResponse {
-                code: StatusCode::INTERNAL_SERVER_ERROR,
-                message: String::from("Error: an unknown error occured"),
-                data: None,
-            },
+        let code = match StatusCode::from_u16(e.get_error_code()) {
+            Ok(code) if code.is_client_error() || code.is_server_error() => code,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        // invalid fields are listed for the client to point at them
+        let data = match e.fields.is_empty() {
+            true => None,
+            false => {
+                let fields: Vec<FieldErrorPresenter> = e
+                    .fields
+                    .iter()
+                    .map(|field| FieldErrorPresenter {
+                        field: &field.field,
+                        code: &field.code,
+                        message: &field.message,
+                    })
+                    .collect();
+                serde_json::to_value(fields).ok()
+            }
+        };
+
+        ErrorResponse {
+            code,
+            error_code: e.error_code,
+            message: e.get_error_message(),
+            data,
         }
     }
 
     pub fn map_io_error_default(e: String) -> ErrorResponse {
         ErrorResponse {
             code: StatusCode::BAD_REQUEST,
+            error_code: error_codes::BAD_REQUEST,
             message: e,
             data: None,
         }
     }
 
+    pub fn forbidden(message: &str) -> ErrorResponse {
+        ErrorResponse {
+            code: StatusCode::FORBIDDEN,
+            error_code: error_codes::FORBIDDEN,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+
     pub fn auth_default() -> ErrorResponse {
         ErrorResponse {
             code: StatusCode::UNAUTHORIZED,
+            error_code: error_codes::UNAUTHORIZED,
             message: "Permission denied".to_string(),
             data: None,
         }
@@ -98,24 +130,34 @@ impl ResponseError for ClientError {
         match self {
             Self::Authentication(_) => HttpResponse::Unauthorized().json(ErrorMessage {
                 code: Some(StatusCode::UNAUTHORIZED.as_u16()),
+                error_code: Some(error_codes::UNAUTHORIZED.to_string()),
                 message: Some("Requires authentication".to_string()),
                 data: None,
             }),
             Self::Decode(_) => HttpResponse::Unauthorized().json(ErrorMessage {
                 code: Some(StatusCode::UNAUTHORIZED.as_u16()),
+                error_code: Some(error_codes::UNAUTHORIZED.to_string()),
                 message: Some("Bad credentials".to_string()),
                 data: None,
             }),
             Self::NotFound(msg) => HttpResponse::Unauthorized().json(ErrorMessage {
                 code: Some(StatusCode::UNAUTHORIZED.as_u16()),
+                error_code: Some(error_codes::UNAUTHORIZED.to_string()),
                 message: Some(msg.to_string()),
                 data: None,
             }),
             Self::UnsupportedAlgortithm(_) => HttpResponse::Unauthorized().json(ErrorMessage {
                 code: Some(StatusCode::UNAUTHORIZED.as_u16()),
+                error_code: Some(error_codes::UNAUTHORIZED.to_string()),
                 message: Some("Bad credentials".to_string()),
                 data: None,
             }),
+            Self::Revoked => HttpResponse::Unauthorized().json(ErrorMessage {
+                code: Some(StatusCode::UNAUTHORIZED.as_u16()),
+                error_code: Some(error_codes::UNAUTHORIZED.to_string()),
+                message: Some("Token has been revoked".to_string()),
+                data: None,
+            }),
         }
     }
 
@@ -2,3 +2,5 @@ pub mod app_state;
 pub mod success_presenter;
 pub mod error_presenter;
 pub mod routes;
+pub mod validated_json;
+pub mod blocking;
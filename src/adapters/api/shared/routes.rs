@@ -1,9 +1,24 @@
 use actix_web::web;
 
-use crate::adapters::api::{tasks::tasks_controllers, users::users_controllers};
+use crate::adapters::api::{
+    api_keys::api_keys_controllers, calendar::calendar_controllers, graphql::graphql_controllers, health::health_controllers, imports::imports_controllers, metrics::metrics_controllers, mfa::mfa_controllers, oidc::oidc_controllers, openapi::openapi_controllers, permissions::permissions_controllers, sessions::sessions_controllers, tasks::tasks_controllers, users::users_controllers,
+    well_known::well_known_controllers,
+};
 
 pub fn routes(config: &mut web::ServiceConfig) {
     config
         .service(web::scope("/api/v1/users").configure(users_controllers::routes))
-        .service(web::scope("/api/v1/tasks").configure(tasks_controllers::routes));
+        .service(web::scope("/api/v1/tasks").configure(tasks_controllers::routes))
+        .service(web::scope("/api/v1/imports").configure(imports_controllers::routes))
+        .service(web::scope("/api/v1/calendar").configure(calendar_controllers::routes))
+        .service(web::scope("/api/v1/sessions").configure(sessions_controllers::routes))
+        .service(web::scope("/api/v1/mfa").configure(mfa_controllers::routes))
+        .service(web::scope("/api/v1/api_keys").configure(api_keys_controllers::routes))
+        .service(web::scope("/api/v1/permissions").configure(permissions_controllers::routes))
+        .service(web::scope("/api/v1/oidc").configure(oidc_controllers::routes))
+        .service(web::scope("/.well-known").configure(well_known_controllers::routes))
+        .configure(health_controllers::routes)
+        .configure(metrics_controllers::routes)
+        .configure(openapi_controllers::routes)
+        .configure(graphql_controllers::routes);
 }
@@ -1,14 +1,24 @@
 use actix_web::HttpResponse;
 use reqwest::StatusCode;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct SuccessResponse<T> {
     pub code: u16,
     pub message: String,
     pub data: T,
 }
 
+// What `SuccessResponse<()>` looks like to clients, for the API documentation
+#[derive(Serialize, ToSchema)]
+pub struct EmptySuccessResponse {
+    pub code: u16,
+    pub message: String,
+    #[schema(value_type = Option<Object>, example = json!(null))]
+    pub data: (),
+}
+
 impl<T: Serialize> SuccessResponse<T> {
     pub fn new(code: StatusCode, message: &str, data: T) -> Self {
         SuccessResponse {
@@ -0,0 +1,127 @@
+use std::{future::Future, ops::Deref, pin::Pin};
+
+use actix_web::{dev::Payload, error::JsonPayloadError, web, FromRequest, HttpRequest};
+use serde::de::DeserializeOwned;
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    adapters::api::shared::error_presenter::ErrorResponse,
+    application::utils::error_handling_utils::ErrorHandlingUtils,
+    domain::error::{DomainError, FieldError},
+};
+
+// A JSON body checked against the `#[validate]` rules of its payload before the handler runs,
+// a body breaking them is answered 422 with one `{field, code, message}` per failure
+pub struct ValidatedJson<T>(pub T);
+
+// Same for routes where the body may be left out, only an empty body gives `None`, an unreadable one is answered 400
+pub struct OptionalValidatedJson<T>(pub Option<T>);
+
+// Same for a query string, an unreadable one is answered 400
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T> ValidatedJson<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for ValidatedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> ValidatedQuery<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Default> OptionalValidatedJson<T> {
+    pub fn into_inner_or_default(self) -> T {
+        self.0.unwrap_or_default()
+    }
+}
+
+impl<T> FromRequest for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = ErrorResponse;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let json = web::Json::<T>::from_request(req, payload);
+        Box::pin(async move {
+            let json = json.await.map_err(|e| ErrorResponse::map_io_error_default(e.to_string()))?;
+            validate(json.into_inner()).map(ValidatedJson)
+        })
+    }
+}
+
+impl<T> FromRequest for OptionalValidatedJson<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = ErrorResponse;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let body = web::Bytes::from_request(req, payload);
+        Box::pin(async move {
+            let body = body.await.map_err(|e| ErrorResponse::map_io_error_default(e.to_string()))?;
+            if body.is_empty() {
+                return Ok(OptionalValidatedJson(None));
+            }
+            let value = serde_json::from_slice::<T>(&body).map_err(|e| ErrorResponse::map_io_error_default(JsonPayloadError::Deserialize(e).to_string()))?;
+            validate(value).map(|value| OptionalValidatedJson(Some(value)))
+        })
+    }
+}
+
+impl<T> FromRequest for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate + 'static,
+{
+    type Error = ErrorResponse;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let query = web::Query::<T>::from_query(req.query_string());
+        Box::pin(async move {
+            let query = query.map_err(|e| ErrorResponse::map_io_error_default(e.to_string()))?;
+            validate(query.into_inner()).map(ValidatedQuery)
+        })
+    }
+}
+
+fn validate<T: Validate>(value: T) -> Result<T, ErrorResponse> {
+    match value.validate() {
+        Ok(()) => Ok(value),
+        Err(errors) => {
+            let error = DomainError::Validation {
+                message: String::from("Invalid request payload"),
+                fields: field_errors(&errors),
+            };
+            Err(ErrorResponse::map_io_error(ErrorHandlingUtils::application_error("Invalid request payload", Some(error))))
+        }
+    }
+}
+
+// Sorted by field so the report is stable, the rules of one field keep their declaration order
+pub fn field_errors(errors: &ValidationErrors) -> Vec<FieldError> {
+    let mut fields: Vec<_> = errors.field_errors().into_iter().collect();
+    fields.sort_by(|(a, _), (b, _)| a.cmp(b));
+    fields
+        .into_iter()
+        .flat_map(|(field, errors)| {
+            errors.iter().map(move |error| {
+                let message = error.message.as_deref().unwrap_or(&error.code);
+                FieldError::new(&field, &error.code, message)
+            })
+        })
+        .collect()
+}
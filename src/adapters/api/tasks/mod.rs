@@ -1,4 +1,5 @@
 pub mod tasks_controllers;
+pub mod tasks_formats;
 pub mod tasks_mappers;
 pub mod tasks_payloads;
 pub mod tasks_presenters;
@@ -1,10 +1,16 @@
 use crate::{
     adapters::api::{
-        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+        shared::{
+            app_state::AppState,
+            error_presenter::{ErrorPresenter, ErrorResponse},
+            success_presenter::SuccessResponse,
+            validated_json::{OptionalValidatedJson, ValidatedJson, ValidatedQuery},
+        },
         tasks::{
+            tasks_formats::{import_format, import_mapping, import_rows, TaskExportWriter},
             tasks_mappers::*,
-            tasks_payloads::{TaskCreatePayload, TaskDataPayload, TaskUpdatePayload},
-            tasks_presenters::TaskAllPresenter,
+            tasks_payloads::{TaskCreatePayload, TaskDataPayload, TaskExportPayload, TaskImportPayload, TaskImportQueryPayload, TaskUpdatePayload},
+            tasks_presenters::{TaskAllPresenter, TaskImportPresenter, TaskPresenter},
         },
     },
     application::{
@@ -12,16 +18,48 @@ use crate::{
         usecases::{
             interfaces::AbstractUseCase,
             task::{
-                delete_one_task_by_id_usecase::DeleteOneTaskByIdUseCase, get_all_tasks_usecase::GetAllTasksUseCase, get_one_task_by_id_usecase::GetOneTaskByIdUseCase,
-                post_one_task_usecase::PostOneTaskUseCase, update_one_task_usecase::UpdateOneTaskUseCase,
+                delete_one_task_by_id_usecase::DeleteOneTaskByIdUseCase,
+                export_tasks_page_usecase::{ExportTasksPageUseCase, EXPORT_PAGE_SIZE},
+                get_all_tasks_usecase::GetAllTasksUseCase,
+                get_one_task_by_id_usecase::GetOneTaskByIdUseCase,
+                import_tasks_usecase::ImportTasksUseCase,
+                post_one_task_usecase::PostOneTaskUseCase,
+                update_one_task_usecase::UpdateOneTaskUseCase,
             },
         },
-        utils::access_control::{auth_usecase::AuthCheckUseCase, extractors::claims::Claims},
+        utils::access_control::{
+            extractors::authorized::{require, Authorized},
+            task_policy::TaskPolicy,
+        },
     },
     domain::{error::ApiError, task_entity::*},
 };
-use actix_web::{delete, get, patch, post, web, HttpResponse};
+use actix_web::{
+    delete, get,
+    http::header::{ContentDisposition, DispositionParam, DispositionType, CONTENT_TYPE},
+    patch, post, web, HttpRequest, HttpResponse,
+};
+use futures_util::stream;
 use reqwest::StatusCode;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    post_one_task,
+    post_one_task_own,
+    get_all_tasks,
+    get_all_tasks_by_user_id,
+    get_all_tasks_by_user_id_own,
+    update_one_task,
+    update_one_task_own,
+    get_one_task_by_id,
+    get_one_task_by_id_own,
+    delete_one_task_by_id,
+    delete_one_task_by_id_own,
+    export_tasks,
+    import_tasks
+))]
+pub struct TasksApi;
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
     cfg.service(post_one_task)
@@ -34,39 +72,89 @@ pub fn routes(cfg: &mut web::ServiceConfig) {
         .service(get_one_task_by_id)
         .service(get_one_task_by_id_own)
         .service(delete_one_task_by_id)
-        .service(delete_one_task_by_id_own);
+        .service(delete_one_task_by_id_own)
+        .service(export_tasks)
+        .service(import_tasks)
+        .app_data(web::PayloadConfig::new(MAX_IMPORT_BYTES));
 }
 
+// Body limit of the routes reading raw bytes, i.e. imports
+const MAX_IMPORT_BYTES: usize = 5 * 1024 * 1024;
+
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Create a task for any user",
+    request_body = TaskCreatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[post("/one")]
-async fn post_one_task(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskCreatePayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+async fn post_one_task(data: web::Data<AppState>, auth: Authorized<require::TasksWrite>, path: ValidatedJson<TaskCreatePayload>) -> Result<HttpResponse, ErrorResponse> {
     let task_payload = path.into_inner();
-    let post_one_task_usecase = PostOneTaskUseCase::new(&task_payload, &data.tasks_repository);
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let post_one_task_usecase = PostOneTaskUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match post_one_task_usecase.execute().await {
-        Ok(task) => Ok(SuccessResponse::new(StatusCode::CREATED, "Task created successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
+        Ok(task) => {
+            data.task_events.publish(TaskChangeKind::Created, &task);
+            Ok(SuccessResponse::new(StatusCode::CREATED, "Task created successfully", TaskPresenterMapper::to_api(task)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Create a task owned by the caller",
+    request_body = TaskCreatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[post("/one_own")]
-async fn post_one_task_own(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskCreatePayload>) -> Result<HttpResponse, ErrorResponse> {
+async fn post_one_task_own(data: web::Data<AppState>, auth: Authorized<require::TasksWrite>, path: ValidatedJson<TaskCreatePayload>) -> Result<HttpResponse, ErrorResponse> {
     let mut task_payload = path.into_inner();
-    task_payload.user_id = Some(claims.sub.clone());
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
-    let post_one_task_usecase = PostOneTaskUseCase::new(&task_payload, &data.tasks_repository);
+    task_payload.user_id = Some(auth.sub.clone());
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let post_one_task_usecase = PostOneTaskUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match post_one_task_usecase.execute().await {
-        Ok(task) => Ok(SuccessResponse::new(StatusCode::CREATED, "Task created successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
+        Ok(task) => {
+            data.task_events.publish(TaskChangeKind::Created, &task);
+            Ok(SuccessResponse::new(StatusCode::CREATED, "Task created successfully", TaskPresenterMapper::to_api(task)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "List every task",
+    request_body = Option<TaskDataPayload>,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<Vec<TaskAllPresenter>>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/all")]
-async fn get_all_tasks(data: web::Data<AppState>, claims: Claims, path: Option<web::Json<TaskDataPayload>>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
-    let task_payload = path.unwrap_or_else(|| web::Json(TaskDataPayload::default())).into_inner();
-    let get_all_tasks_usecase = GetAllTasksUseCase::new(&task_payload, &data.tasks_repository);
+async fn get_all_tasks(data: web::Data<AppState>, auth: Authorized<require::TasksReadAny>, path: OptionalValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let task_payload = path.into_inner_or_default();
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let get_all_tasks_usecase = GetAllTasksUseCase::new(&task_payload, &policy, &data.tasks_repository);
     let tasks: Result<Vec<TaskAllEntity>, ApiError> = get_all_tasks_usecase.execute().await;
 
     match tasks {
@@ -80,11 +168,24 @@ async fn get_all_tasks(data: web::Data<AppState>, claims: Claims, path: Option<w
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "List the tasks of a user",
+    request_body = TaskDataPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<Vec<TaskAllPresenter>>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/all_by_user_id")]
-async fn get_all_tasks_by_user_id(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+async fn get_all_tasks_by_user_id(data: web::Data<AppState>, auth: Authorized<require::TasksReadAny>, path: ValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
     let task_payload = path.into_inner();
-    let get_all_tasks_usecase = GetAllTasksUseCase::new(&task_payload, &data.tasks_repository);
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let get_all_tasks_usecase = GetAllTasksUseCase::new(&task_payload, &policy, &data.tasks_repository);
     let tasks: Result<Vec<TaskAllEntity>, ApiError> = get_all_tasks_usecase.execute().await;
 
     match tasks {
@@ -98,12 +199,24 @@ async fn get_all_tasks_by_user_id(data: web::Data<AppState>, claims: Claims, pat
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "List the caller's tasks",
+    request_body = Option<TaskDataPayload>,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<Vec<TaskAllPresenter>>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/all_by_user_id_own")]
-async fn get_all_tasks_by_user_id_own(data: web::Data<AppState>, claims: Claims, path: Option<web::Json<TaskDataPayload>>) -> Result<HttpResponse, ErrorResponse> {
-    let mut task_payload = path.unwrap_or_else(|| web::Json(TaskDataPayload::default())).into_inner();
-    task_payload.user_id = Some(claims.sub.clone());
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
-    let get_all_tasks_usecase = GetAllTasksUseCase::new(&task_payload, &data.tasks_repository);
+async fn get_all_tasks_by_user_id_own(data: web::Data<AppState>, auth: Authorized<require::TasksRead>, path: OptionalValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut task_payload = path.into_inner_or_default();
+    task_payload.user_id = Some(auth.sub.clone());
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let get_all_tasks_usecase = GetAllTasksUseCase::new(&task_payload, &policy, &data.tasks_repository);
     let tasks: Result<Vec<TaskAllEntity>, ApiError> = get_all_tasks_usecase.execute().await;
 
     match tasks {
@@ -117,36 +230,84 @@ async fn get_all_tasks_by_user_id_own(data: web::Data<AppState>, claims: Claims,
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Update any task",
+    request_body = TaskUpdatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[patch("/one")]
-async fn update_one_task(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+async fn update_one_task(data: web::Data<AppState>, auth: Authorized<require::TasksWrite>, path: ValidatedJson<TaskUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
     let task_payload = path.into_inner();
-    let update_one_task_usecase = UpdateOneTaskUseCase::new(&task_payload, &data.tasks_repository);
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let update_one_task_usecase = UpdateOneTaskUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match update_one_task_usecase.execute().await {
-        Ok(task) => Ok(SuccessResponse::new(StatusCode::OK, "Task updated successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
+        Ok(task) => {
+            data.task_events.publish(TaskChangeKind::Updated, &task);
+            Ok(SuccessResponse::new(StatusCode::OK, "Task updated successfully", TaskPresenterMapper::to_api(task)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Update a task of the caller",
+    request_body = TaskUpdatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[patch("/one_own")]
-async fn update_one_task_own(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
+async fn update_one_task_own(data: web::Data<AppState>, auth: Authorized<require::TasksWrite>, path: ValidatedJson<TaskUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
     let mut task_payload = path.into_inner();
-    task_payload.user_id = Some(claims.sub.clone());
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
-    let update_one_task_usecase = UpdateOneTaskUseCase::new(&task_payload, &data.tasks_repository);
+    task_payload.user_id = Some(auth.sub.clone());
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let update_one_task_usecase = UpdateOneTaskUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match update_one_task_usecase.execute().await {
-        Ok(task) => Ok(SuccessResponse::new(StatusCode::OK, "Task updated successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
+        Ok(task) => {
+            data.task_events.publish(TaskChangeKind::Updated, &task);
+            Ok(SuccessResponse::new(StatusCode::OK, "Task updated successfully", TaskPresenterMapper::to_api(task)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Get a task",
+    request_body = TaskDataPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/one")]
-async fn get_one_task_by_id(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
+async fn get_one_task_by_id(data: web::Data<AppState>, auth: Authorized<require::TasksRead>, path: ValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
     let task_payload = path.into_inner();
-    let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&task_payload, &data.tasks_repository);
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match get_one_task_by_id_usecase.execute().await {
         Ok(task) => Ok(SuccessResponse::new(StatusCode::OK, "Task retrieved successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
@@ -154,12 +315,26 @@ async fn get_one_task_by_id(data: web::Data<AppState>, claims: Claims, path: web
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Get a task of the caller",
+    request_body = TaskDataPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/one_own")]
-async fn get_one_task_by_id_own(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
+async fn get_one_task_by_id_own(data: web::Data<AppState>, auth: Authorized<require::TasksRead>, path: ValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
     let mut task_payload = path.into_inner();
-    task_payload.user_id = Some(claims.sub.clone());
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
-    let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&task_payload, &data.tasks_repository);
+    task_payload.user_id = Some(auth.sub.clone());
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match get_one_task_by_id_usecase.execute().await {
         Ok(task) => Ok(SuccessResponse::new(StatusCode::OK, "Task retrieved successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
@@ -167,27 +342,204 @@ async fn get_one_task_by_id_own(data: web::Data<AppState>, claims: Claims, path:
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Delete any task",
+    request_body = TaskDataPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[delete("/one")]
-async fn delete_one_task_by_id(data: web::Data<AppState>, claims: Claims, path: web::Json<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
+async fn delete_one_task_by_id(data: web::Data<AppState>, auth: Authorized<require::TasksDelete>, path: ValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
     let task_payload = path.into_inner();
-    let delete_one_task_usecase = DeleteOneTaskByIdUseCase::new(&task_payload, &data.tasks_repository);
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let delete_one_task_usecase = DeleteOneTaskByIdUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match delete_one_task_usecase.execute().await {
-        Ok(task) => Ok(SuccessResponse::new(StatusCode::OK, "Task deleted successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
+        Ok(task) => {
+            data.task_events.publish(TaskChangeKind::Deleted, &task);
+            Ok(SuccessResponse::new(StatusCode::OK, "Task deleted successfully", TaskPresenterMapper::to_api(task)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Delete a task of the caller",
+    request_body = Option<TaskDataPayload>,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<TaskPresenter>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[delete("/one_own")]
-async fn delete_one_task_by_id_own(data: web::Data<AppState>, claims: Claims, path: Option<web::Json<TaskDataPayload>>) -> Result<HttpResponse, ErrorResponse> {
-    let mut task_payload = path.unwrap_or_else(|| web::Json(TaskDataPayload::default())).into_inner();
-    task_payload.user_id = Some(claims.sub.clone());
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
-    let delete_one_task_usecase = DeleteOneTaskByIdUseCase::new(&task_payload, &data.tasks_repository);
+async fn delete_one_task_by_id_own(data: web::Data<AppState>, auth: Authorized<require::TasksDelete>, path: OptionalValidatedJson<TaskDataPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut task_payload = path.into_inner_or_default();
+    task_payload.user_id = Some(auth.sub.clone());
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let delete_one_task_usecase = DeleteOneTaskByIdUseCase::new(&task_payload, &policy, &data.tasks_repository);
 
     match delete_one_task_usecase.execute().await {
-        Ok(task) => Ok(SuccessResponse::new(StatusCode::OK, "Task deleted successfully", TaskPresenterMapper::to_api(task)).to_http_response()),
+        Ok(task) => {
+            data.task_events.publish(TaskChangeKind::Deleted, &task);
+            Ok(SuccessResponse::new(StatusCode::OK, "Task deleted successfully", TaskPresenterMapper::to_api(task)).to_http_response())
+        }
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+// What an export stream needs between two pages
+struct TaskExportState {
+    data: web::Data<AppState>,
+    task_payload: TaskExportPayload,
+    policy: TaskPolicy,
+    writer: TaskExportWriter,
+    last: Option<TaskEntity>,
+    finished: bool,
+}
+
+impl TaskExportState {
+    // The bytes of one page, the footer once a page comes back short
+    fn write(&mut self, tasks: Vec<TaskEntity>) -> web::Bytes {
+        let mut bytes = self.writer.page(&tasks);
+        if tasks.len() < EXPORT_PAGE_SIZE {
+            self.finished = true;
+            bytes.extend(self.writer.footer());
+        }
+        self.last = tasks.into_iter().last();
+        web::Bytes::from(bytes)
+    }
+}
+
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Export tasks as a CSV, JSON or NDJSON file",
+    description = "Streams every field of the caller's tasks, or of `user_id`'s or `project_id`'s tasks with `tasks:read:any`. \
+        Enums are written as their labels (`to_do_bug`), timestamps as unix milliseconds, checklist items one per line in CSV.",
+    params(TaskExportPayload),
+    responses(
+        (status = 200, description = "The file, in creation order", content(
+            (String = "text/csv"),
+            (String = "application/json"),
+            (String = "application/x-ndjson")
+        )),
+        (status = 400, description = "Malformed query", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[get("/export")]
+async fn export_tasks(data: web::Data<AppState>, auth: Authorized<require::TasksRead>, path: ValidatedQuery<TaskExportPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut task_payload = path.into_inner();
+    if task_payload.user_id.is_none() && task_payload.project_id.is_none() {
+        task_payload.user_id = Some(auth.sub.clone());
+    }
+    let format = task_payload.format.unwrap_or_default();
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+
+    // the first page is read before answering so that a failure still gets an error status
+    let first_page = ExportTasksPageUseCase::new(&task_payload, None, &policy, &data.tasks_repository)
+        .execute()
+        .await
+        .map_err(ErrorResponse::map_io_error)?;
+    let mut state = TaskExportState {
+        data: data.clone(),
+        task_payload,
+        policy,
+        writer: TaskExportWriter::new(format),
+        last: None,
+        finished: false,
+    };
+    let mut first_chunk = state.writer.header();
+    first_chunk.extend(state.write(first_page));
+
+    let next_pages = stream::unfold(state, |mut state| async move {
+        if state.finished {
+            return None;
+        }
+        let page = ExportTasksPageUseCase::new(&state.task_payload, state.last.as_ref(), &state.policy, &state.data.tasks_repository)
+            .execute()
+            .await;
+        match page {
+            Ok(tasks) => Some((Ok(state.write(tasks)), state)),
+            // the status is already sent, the client sees a truncated file
+            Err(e) => {
+                state.finished = true;
+                Some((Err(actix_web::Error::from(ErrorResponse::map_io_error(e))), state))
+            }
+        }
+    });
+    let body = futures_util::StreamExt::chain(stream::once(async move { Ok::<_, actix_web::Error>(web::Bytes::from(first_chunk)) }), next_pages);
+
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!("tasks.{}", format.extension()))],
+        })
+        .streaming(body))
+}
+
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Create tasks from a CSV, JSON or NDJSON file",
+    description = "Rows follow the rules of `POST /one`, tasks without `user_id` go to the caller. Either every row is valid and all tasks \
+        are created at once, or nothing is and the invalid rows are listed in `data`. `dry_run=true` only reports. \
+        The files written by `GET /export` can be imported back.",
+    params(TaskImportQueryPayload),
+    request_body(description = "The file, at most 5000 rows", content(
+        (String = "text/csv"),
+        (String = "application/json"),
+        (String = "application/x-ndjson")
+    )),
+    responses(
+        (status = 200, description = "Success, `code` is 201 once imported", body = SuccessResponse<TaskImportPresenter>),
+        (status = 400, description = "Unreadable file or unknown format", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid rows, listed in `data` as `rows[<row>].<field>`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/import")]
+async fn import_tasks(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    auth: Authorized<require::TasksWrite>,
+    path: ValidatedQuery<TaskImportQueryPayload>,
+    body: web::Bytes,
+) -> Result<HttpResponse, ErrorResponse> {
+    let query = path.into_inner();
+    let content_type = req.headers().get(CONTENT_TYPE).and_then(|value| value.to_str().ok());
+    let format = import_format(query.format, content_type).map_err(ErrorResponse::map_io_error)?;
+    let mapping = import_mapping(query.mapping.as_deref()).map_err(ErrorResponse::map_io_error)?;
+    let rows = import_rows(&body, format, &mapping, &auth.sub).map_err(ErrorResponse::map_io_error)?;
+    let import_payload = TaskImportPayload { rows, dry_run: query.dry_run };
+    let policy = TaskPolicy::from_claims(&auth, &auth.role_permissions);
+    let import_tasks_usecase = ImportTasksUseCase::new(&import_payload, &policy, &data.tasks_repository);
+
+    match import_tasks_usecase.execute().await {
+        Ok(report) if report.dry_run => Ok(SuccessResponse::new(StatusCode::OK, "Import checked, nothing was created", TaskImportPresenterMapper::to_api(report)).to_http_response()),
+        Ok(report) => {
+            for task in &report.created {
+                data.task_events.publish(TaskChangeKind::Created, task);
+            }
+            Ok(SuccessResponse::new(StatusCode::CREATED, "Tasks imported successfully", TaskImportPresenterMapper::to_api(report)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
@@ -0,0 +1,447 @@
+use std::{collections::HashMap, str::FromStr};
+
+use chrono::NaiveDateTime;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    adapters::api::{
+        shared::validated_json::field_errors,
+        tasks::tasks_payloads::{TaskCreatePayload, TaskFileFormat, TaskImportRowPayload},
+    },
+    application::utils::error_handling_utils::ErrorHandlingUtils,
+    domain::{
+        error::{ApiError, DomainError, FieldError},
+        task_entity::TaskEntity,
+    },
+};
+
+// Larger files are refused as a whole, they are parsed and checked in memory
+pub const MAX_IMPORT_ROWS: usize = 5000;
+
+// Every field of a task, in this order in CSV files
+const EXPORT_COLUMNS: [&str; 13] = [
+    "task_id",
+    "user_id",
+    "project_id",
+    "title",
+    "typ",
+    "priority",
+    "status",
+    "description",
+    "duration",
+    "due_date",
+    "task_list",
+    "updated_at",
+    "created_at",
+];
+
+// Spreadsheets run cells starting with these as formulas
+const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+// Fields an import may set, the other export columns are ignored so that an export can be imported back
+const IMPORT_FIELDS: [&str; 10] = ["user_id", "project_id", "title", "typ", "priority", "status", "description", "duration", "due_date", "task_list"];
+
+impl TaskFileFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            TaskFileFormat::Csv => "text/csv; charset=utf-8",
+            TaskFileFormat::Json => "application/json",
+            TaskFileFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TaskFileFormat::Csv => "csv",
+            TaskFileFormat::Json => "json",
+            TaskFileFormat::Ndjson => "ndjson",
+        }
+    }
+
+    fn from_content_type(content_type: &str) -> Option<Self> {
+        let mime = content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match mime.as_str() {
+            "text/csv" => Some(TaskFileFormat::Csv),
+            "application/json" => Some(TaskFileFormat::Json),
+            "application/x-ndjson" | "application/ndjson" => Some(TaskFileFormat::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+// The format asked in the query wins over the Content-Type of the body
+pub fn import_format(format: Option<TaskFileFormat>, content_type: Option<&str>) -> Result<TaskFileFormat, ApiError> {
+    format
+        .or_else(|| content_type.and_then(TaskFileFormat::from_content_type))
+        .ok_or_else(|| ErrorHandlingUtils::application_error("Unknown import format, use ?format=csv|json|ndjson or a matching Content-Type", None))
+}
+
+// Exported tasks as the client reads them: enums as their labels, timestamps in unix millis, no project as empty
+#[derive(Serialize)]
+struct TaskExportRecord<'a> {
+    task_id: &'a str,
+    user_id: &'a str,
+    project_id: Option<&'a str>,
+    title: &'a str,
+    typ: &'static str,
+    priority: &'static str,
+    status: &'static str,
+    description: &'a str,
+    duration: i32,
+    due_date: i64,
+    task_list: &'a [String],
+    updated_at: i64,
+    created_at: i64,
+}
+
+impl<'a> From<&'a TaskEntity> for TaskExportRecord<'a> {
+    fn from(task: &'a TaskEntity) -> Self {
+        TaskExportRecord {
+            task_id: &task.id,
+            user_id: &task.user_id,
+            project_id: Some(task.project_id.as_str()).filter(|project_id| *project_id != Uuid::nil().to_string()),
+            title: &task.title,
+            typ: task.typ.as_str(),
+            priority: task.priority.as_str(),
+            status: task.status.as_str(),
+            description: &task.description,
+            duration: task.duration,
+            due_date: task.due_date,
+            task_list: &task.task_list,
+            updated_at: naive_datetime_to_unixtimemillis(task.updated_at),
+            created_at: naive_datetime_to_unixtimemillis(task.created_at),
+        }
+    }
+}
+
+impl TaskExportRecord<'_> {
+    // CSV has no lists, checklist items go one per line in a single cell. Free text is escaped, it may hold formulas.
+    fn csv_fields(&self) -> [String; 13] {
+        [
+            self.task_id.to_string(),
+            self.user_id.to_string(),
+            self.project_id.unwrap_or_default().to_string(),
+            escape_formula(self.title),
+            self.typ.to_string(),
+            self.priority.to_string(),
+            self.status.to_string(),
+            escape_formula(self.description),
+            self.duration.to_string(),
+            self.due_date.to_string(),
+            escape_formula(&self.task_list.join("\n")),
+            self.updated_at.to_string(),
+            self.created_at.to_string(),
+        ]
+    }
+}
+
+// Writes an export one page at a time: `header`, then `page` for every page, then `footer`
+pub struct TaskExportWriter {
+    format: TaskFileFormat,
+    written: usize,
+}
+
+impl TaskExportWriter {
+    pub fn new(format: TaskFileFormat) -> Self {
+        TaskExportWriter { format, written: 0 }
+    }
+
+    pub fn header(&self) -> Vec<u8> {
+        match self.format {
+            TaskFileFormat::Csv => csv_line(EXPORT_COLUMNS),
+            TaskFileFormat::Json => b"[".to_vec(),
+            TaskFileFormat::Ndjson => Vec::new(),
+        }
+    }
+
+    pub fn page(&mut self, tasks: &[TaskEntity]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for task in tasks {
+            let record = TaskExportRecord::from(task);
+            match self.format {
+                TaskFileFormat::Csv => bytes.extend(csv_line(record.csv_fields())),
+                TaskFileFormat::Json => {
+                    if self.written > 0 {
+                        bytes.push(b',');
+                    }
+                    bytes.extend(serde_json::to_vec(&record).unwrap_or_default());
+                }
+                TaskFileFormat::Ndjson => {
+                    bytes.extend(serde_json::to_vec(&record).unwrap_or_default());
+                    bytes.push(b'\n');
+                }
+            }
+            self.written += 1;
+        }
+        bytes
+    }
+
+    pub fn footer(&self) -> Vec<u8> {
+        match self.format {
+            TaskFileFormat::Json => b"]".to_vec(),
+            TaskFileFormat::Csv | TaskFileFormat::Ndjson => Vec::new(),
+        }
+    }
+}
+
+// A leading `'` makes spreadsheets show the cell as text, imports drop it again
+fn escape_formula(text: &str) -> String {
+    match text.starts_with(FORMULA_PREFIXES) {
+        true => format!("'{}", text),
+        false => text.to_string(),
+    }
+}
+
+fn unescape_formula(text: &str) -> &str {
+    match text.strip_prefix('\'') {
+        Some(formula) if formula.starts_with(FORMULA_PREFIXES) => formula,
+        _ => text,
+    }
+}
+
+fn csv_line<I, T>(fields: I) -> Vec<u8>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // writing to memory can't fail
+    let _ = writer.write_record(fields);
+    writer.into_inner().unwrap_or_default()
+}
+
+// `mapping` is a JSON object of source column to task field, every target must be an importable field
+pub fn import_mapping(mapping: Option<&str>) -> Result<HashMap<String, String>, ApiError> {
+    let Some(mapping) = mapping else {
+        return Ok(HashMap::new());
+    };
+    let mapping: HashMap<String, String> = serde_json::from_str(mapping).map_err(|_| invalid_mapping("must be a JSON object of column to field names"))?;
+    match mapping.values().find(|field| !IMPORT_FIELDS.contains(&field.as_str())) {
+        Some(field) => Err(invalid_mapping(&format!("unknown field {}, expected one of {}", field, IMPORT_FIELDS.join(", ")))),
+        None => Ok(mapping),
+    }
+}
+
+fn invalid_mapping(message: &str) -> ApiError {
+    let error = DomainError::Validation {
+        message: String::from("Invalid input"),
+        fields: vec![FieldError::new("mapping", "invalid", message)],
+    };
+    ErrorHandlingUtils::application_error("Invalid input", Some(error))
+}
+
+// Reads the rows of an import file. An unreadable file fails as a whole, a row that can't become a valid
+// `TaskCreatePayload` is kept with its errors. Tasks nobody is assigned to go to the caller.
+pub fn import_rows(body: &[u8], format: TaskFileFormat, mapping: &HashMap<String, String>, caller_id: &str) -> Result<Vec<TaskImportRowPayload>, ApiError> {
+    let records = match format {
+        TaskFileFormat::Csv => csv_records(body)?,
+        TaskFileFormat::Json => serde_json::from_slice::<Vec<Map<String, Value>>>(body).map_err(|e| unreadable_file(&format!("Invalid JSON file: {}", e)))?,
+        TaskFileFormat::Ndjson => ndjson_records(body)?,
+    };
+    if records.len() > MAX_IMPORT_ROWS {
+        let error = DomainError::Validation {
+            message: String::from("Invalid input"),
+            fields: vec![FieldError::new("rows", "too_many", &format!("must have at most {} rows", MAX_IMPORT_ROWS))],
+        };
+        return Err(ErrorHandlingUtils::application_error("Invalid input", Some(error)));
+    }
+
+    Ok(records
+        .into_iter()
+        .enumerate()
+        .map(|(index, record)| TaskImportRowPayload {
+            row: index + 1,
+            task: import_task(record, mapping, caller_id),
+        })
+        .collect())
+}
+
+fn unreadable_file(message: &str) -> ApiError {
+    ErrorHandlingUtils::application_error(message, None)
+}
+
+fn csv_records(body: &[u8]) -> Result<Vec<Map<String, Value>>, ApiError> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::Headers).from_reader(body);
+    let headers = reader.headers().map_err(|e| unreadable_file(&format!("Invalid CSV file: {}", e)))?.clone();
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| unreadable_file(&format!("Invalid CSV file: {}", e)))?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(header, value)| (header.to_string(), Value::String(unescape_formula(value).to_string())))
+                .collect())
+        })
+        .collect()
+}
+
+fn ndjson_records(body: &[u8]) -> Result<Vec<Map<String, Value>>, ApiError> {
+    let body = std::str::from_utf8(body).map_err(|_| unreadable_file("Invalid NDJSON file: not UTF-8"))?;
+    body.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| serde_json::from_str(line).map_err(|e| unreadable_file(&format!("Invalid NDJSON file at line {}: {}", index + 1, e))))
+        .collect()
+}
+
+fn import_task(record: Map<String, Value>, mapping: &HashMap<String, String>, caller_id: &str) -> Result<TaskCreatePayload, Vec<FieldError>> {
+    let mut fields: HashMap<&str, Value> = HashMap::new();
+    for (column, value) in record {
+        let field = mapping.get(&column).unwrap_or(&column);
+        if let Some(field) = IMPORT_FIELDS.iter().find(|import_field| *import_field == field) {
+            fields.insert(field, value);
+        }
+    }
+
+    let mut errors = Vec::new();
+    let task_payload = TaskCreatePayload::new(
+        Some(text(fields.remove("user_id")).unwrap_or_else(|| caller_id.to_string())),
+        text(fields.remove("project_id")),
+        text(fields.remove("title")).unwrap_or_default(),
+        label("typ", fields.remove("typ"), &mut errors),
+        label("priority", fields.remove("priority"), &mut errors),
+        label("status", fields.remove("status"), &mut errors),
+        text(fields.remove("description")),
+        number("duration", fields.remove("duration"), &mut errors),
+        number("due_date", fields.remove("due_date"), &mut errors),
+        items(fields.remove("task_list")),
+    );
+    if let Err(validation_errors) = task_payload.validate() {
+        errors.extend(field_errors(&validation_errors));
+    }
+
+    match errors.is_empty() {
+        true => Ok(task_payload),
+        false => Err(errors),
+    }
+}
+
+// Empty cells count as missing, CSV can't tell them apart
+fn text(value: Option<Value>) -> Option<String> {
+    match value? {
+        Value::Null => None,
+        Value::String(text) => Some(text).filter(|text| !text.is_empty()),
+        other => Some(other.to_string()),
+    }
+}
+
+// Labels are matched case-insensitively ("High", "to_do_bug"), JSON files may also use the REST representation
+fn label<T: FromStr + DeserializeOwned>(field: &str, value: Option<Value>, errors: &mut Vec<FieldError>) -> Option<T> {
+    let parsed = match value {
+        None | Some(Value::Null) => return None,
+        Some(Value::String(text)) if text.trim().is_empty() => return None,
+        Some(Value::String(text)) => text.trim().to_ascii_lowercase().parse().ok().or_else(|| serde_json::from_value(Value::String(text.clone())).ok()),
+        Some(other) => serde_json::from_value(other).ok(),
+    };
+    if parsed.is_none() {
+        errors.push(FieldError::new(field, "unknown_value", "is not a known value"));
+    }
+    parsed
+}
+
+fn number<T: TryFrom<i64>>(field: &str, value: Option<Value>, errors: &mut Vec<FieldError>) -> Option<T> {
+    let parsed = match value {
+        None | Some(Value::Null) => return None,
+        Some(Value::String(text)) if text.trim().is_empty() => return None,
+        Some(Value::String(text)) => text.trim().parse::<i64>().ok(),
+        Some(other) => other.as_i64(),
+    };
+    match parsed.and_then(|parsed| T::try_from(parsed).ok()) {
+        Some(parsed) => Some(parsed),
+        None => {
+            errors.push(FieldError::new(field, "invalid_number", "must be a whole number"));
+            None
+        }
+    }
+}
+
+// A JSON list, or one item per line as written by CSV exports
+fn items(value: Option<Value>) -> Option<Vec<String>> {
+    let items: Vec<String> = match value? {
+        Value::Array(values) => values.into_iter().filter_map(|value| text(Some(value))).collect(),
+        Value::String(text) => text.lines().map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect(),
+        _ => return None,
+    };
+    Some(items).filter(|items| !items.is_empty())
+}
+
+fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::task_entity::{TaskPriority, TaskStatus, TaskStatusToDo},
+        test_support::TaskEntityBuilder,
+    };
+
+    const CALLER_ID: &str = "0b3e9c5e-6f1a-4d3b-9a8e-2f4c1d7b6a50";
+
+    #[test]
+    fn test_should_map_columns_and_read_lenient_values() {
+        let body = b"Name,Priority,Checklist,Ignored\nWrite docs,High,\"one\ntwo\",x\n,urgent,,\n";
+        let mapping = import_mapping(Some(r#"{"Name":"title","Priority":"priority","Checklist":"task_list"}"#)).unwrap();
+
+        let rows = import_rows(body, TaskFileFormat::Csv, &mapping, CALLER_ID).unwrap();
+
+        let task = rows[0].task.as_ref().unwrap();
+        assert_eq!(task.title, "Write docs");
+        assert_eq!(task.priority, Some(TaskPriority::High));
+        assert_eq!(task.task_list, Some(vec![String::from("one"), String::from("two")]));
+        assert_eq!(task.user_id.as_deref(), Some(CALLER_ID));
+        let fields: Vec<&str> = rows[1].task.as_ref().unwrap_err().iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["priority", "title"]);
+    }
+
+    #[test]
+    fn test_should_read_rest_enums_from_json_files() {
+        let body = br#"[{"title":"Bug","status":{"ToDo":"Bug"},"duration":"15"}]"#;
+
+        let rows = import_rows(body, TaskFileFormat::Json, &HashMap::new(), CALLER_ID).unwrap();
+
+        let task = rows[0].task.as_ref().unwrap();
+        assert_eq!(task.status, Some(TaskStatus::ToDo(TaskStatusToDo::Bug)));
+        assert_eq!(task.duration, Some(15));
+    }
+
+    #[test]
+    fn test_should_refuse_mappings_to_unknown_fields() {
+        let error = import_mapping(Some(r#"{"Name":"name"}"#)).unwrap_err();
+
+        assert_eq!(error.code, 422);
+        assert_eq!(error.fields[0].field, "mapping");
+    }
+
+    #[test]
+    fn test_should_escape_formulas_in_csv_exports() {
+        // given
+        let task = TaskEntityBuilder::new("id1")
+            .user_id(CALLER_ID)
+            .title("=HYPERLINK(\"http://evil.example\",\"open\")")
+            .description("@SUM(A1:A2)")
+            .task_list(&["+1", "two"])
+            .build();
+
+        // when
+        let mut writer = TaskExportWriter::new(TaskFileFormat::Csv);
+        let mut body = writer.header();
+        body.extend(writer.page(std::slice::from_ref(&task)));
+        let rows = import_rows(&body, TaskFileFormat::Csv, &HashMap::new(), CALLER_ID).unwrap();
+
+        // then
+        let csv = String::from_utf8(body.clone()).unwrap();
+        assert!(csv.contains("\"'=HYPERLINK(\"\"http://evil.example\"\",\"\"open\"\")\""));
+        assert!(csv.contains(",'@SUM(A1:A2),"));
+        assert!(csv.contains("\"'+1\ntwo\""));
+        let imported = rows[0].task.as_ref().unwrap();
+        assert_eq!(imported.title, task.title);
+        assert_eq!(imported.description.as_deref(), Some("@SUM(A1:A2)"));
+        assert_eq!(imported.task_list, Some(vec![String::from("+1"), String::from("two")]));
+    }
+}
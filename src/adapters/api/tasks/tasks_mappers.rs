@@ -4,8 +4,8 @@ use crate::adapters::{api::tasks::tasks_payloads::TaskCreatePayload, api::tasks:
 use crate::application::mappers::api_mapper::ApiMapper;
 use crate::domain::task_entity::*;
 
-use super::tasks_payloads::{TaskDataPayload, TaskPayload, TaskUpdatePayload};
-use super::tasks_presenters::TaskAllPresenter;
+use super::tasks_payloads::{TaskDataPayload, TaskImportPayload, TaskPayload, TaskUpdatePayload};
+use super::tasks_presenters::{TaskAllPresenter, TaskImportErrorPresenter, TaskImportPresenter};
 
 pub struct TaskCreatePresenterMapper {}
 
@@ -15,6 +15,8 @@ pub struct TaskAllPresenterMapper {}
 
 pub struct TaskPresenterMapper {}
 
+pub struct TaskImportPresenterMapper {}
+
 impl ApiMapper<TaskEntity, TaskPresenter, TaskDataPayload> for TaskPresenterMapper {
     fn to_api(entity: TaskEntity) -> TaskPresenter {
         TaskPresenter {
@@ -94,6 +96,8 @@ impl ApiMapper<TaskAllEntity, TaskAllPresenter, TaskPayload> for TaskAllPresente
             user_id: entity.user_id,
             project_id: entity.project_id,
             title: entity.title,
+            priority: entity.priority,
+            status: entity.status,
             description: entity.description,
             updated_at: naive_datetime_to_unixtimemillis(entity.updated_at),
             created_at: naive_datetime_to_unixtimemillis(entity.created_at),
@@ -105,6 +109,30 @@ impl ApiMapper<TaskAllEntity, TaskAllPresenter, TaskPayload> for TaskAllPresente
     }
 }
 
+impl ApiMapper<TaskImportReport, TaskImportPresenter, TaskImportPayload> for TaskImportPresenterMapper {
+    fn to_api(entity: TaskImportReport) -> TaskImportPresenter {
+        TaskImportPresenter {
+            dry_run: entity.dry_run,
+            rows: entity.rows,
+            valid_rows: entity.valid_rows,
+            task_ids: entity.created.into_iter().map(|task| task.id).collect(),
+            errors: entity
+                .errors
+                .into_iter()
+                .map(|error| TaskImportErrorPresenter {
+                    field: error.field,
+                    code: error.code,
+                    message: error.message,
+                })
+                .collect(),
+        }
+    }
+
+    fn to_entity(_payload: TaskImportPayload) -> TaskImportReport {
+        panic!("not implemented");
+    }
+}
+
 fn naive_datetime_to_unixtimemillis(datetime: NaiveDateTime) -> i64 {
     // Get the Unix timestamp in seconds and convert to milliseconds
     let millis = datetime.and_utc().timestamp_millis();
@@ -1,131 +1,32 @@
-use std::fmt;
-
 use actix_web::web;
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum TaskTypePayload {
-    Personal,
-    Work,
-    None,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum TaskPriorityPayload {
-    Low,
-    Medium,
-    High,
-    None,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum TaskStatusToDoPayload {
-    NotStarted,
-    Document,
-    Bug,
-    None,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum TaskStatusInProgressPayload {
-    Doing,
-    Testing,
-    None,
-}
-
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub enum TaskStatusPayload {
-    ToDo(TaskStatusToDoPayload),
-    InProgress(TaskStatusInProgressPayload),
-    Completed,
-    None,
-}
-
-impl Default for TaskTypePayload {
-    fn default() -> Self {
-        TaskTypePayload::None
-    }
-}
-
-impl Default for TaskPriorityPayload {
-    fn default() -> Self {
-        TaskPriorityPayload::None
-    }
-}
-
-impl Default for TaskStatusPayload {
-    fn default() -> Self {
-        TaskStatusPayload::None
-    }
-}
-
-impl Default for TaskStatusToDoPayload {
-    fn default() -> Self {
-        TaskStatusToDoPayload::None
-    }
-}
-
-impl Default for TaskStatusInProgressPayload {
-    fn default() -> Self {
-        TaskStatusInProgressPayload::None
-    }
-}
-
-impl fmt::Display for TaskTypePayload {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TaskTypePayload::Personal => write!(f, "Personal"),
-            TaskTypePayload::Work => write!(f, "Work"),
-            TaskTypePayload::None => write!(f, "None"),
-        }
-    }
-}
-
-impl fmt::Display for TaskPriorityPayload {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TaskPriorityPayload::Low => write!(f, "Low"),
-            TaskPriorityPayload::Medium => write!(f, "Medium"),
-            TaskPriorityPayload::High => write!(f, "High"),
-            TaskPriorityPayload::None => write!(f, "None"),
-        }
-    }
-}
-
-impl fmt::Display for TaskStatusPayload {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TaskStatusPayload::ToDo(status) => write!(f, "ToDo: ({})", status),
-            TaskStatusPayload::InProgress(status) => write!(f, "In Progress: ({})", status),
-            TaskStatusPayload::Completed => write!(f, "Completed"),
-            TaskStatusPayload::None => write!(f, "None"),
-        }
-    }
-}
-
-impl fmt::Display for TaskStatusToDoPayload {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TaskStatusToDoPayload::NotStarted => write!(f, "Not Started"),
-            TaskStatusToDoPayload::Document => write!(f, "Document"),
-            TaskStatusToDoPayload::Bug => write!(f, "Bug"),
-            TaskStatusToDoPayload::None => write!(f, "None"),
-        }
-    }
-}
-
-impl fmt::Display for TaskStatusInProgressPayload {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            TaskStatusInProgressPayload::Doing => write!(f, "Doing"),
-            TaskStatusInProgressPayload::Testing => write!(f, "Testing"),
-            TaskStatusInProgressPayload::None => write!(f, "None"),
-        }
-    }
-}
-
-#[derive(Serialize, Deserialize, Debug)]
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::{
+    application::utils::validate_params,
+    domain::{
+        error::FieldError,
+        task_entity::{TaskPriority, TaskStatus, TaskType},
+    },
+};
+
+const MAX_TITLE_LENGTH: u64 = 255;
+const MAX_DESCRIPTION_LENGTH: u64 = 10_000;
+const MAX_TASK_LIST_ITEMS: u64 = 100;
+
+// How a list of tasks is ordered, priorities compare by rank (none < low < medium < high), not by name
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskSortPayload {
+    PriorityAsc,
+    PriorityDesc,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
 pub struct TaskIdPayload {
+    #[validate(custom(function = "validate_params::uuid"))]
     pub task_id: String,
 }
 
@@ -135,15 +36,18 @@ impl TaskIdPayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Default, Debug)]
 pub struct TaskDataPayload {
+    #[validate(custom(function = "validate_params::uuid"))]
     pub task_id: Option<String>,
+    #[validate(custom(function = "validate_params::uuid"))]
     pub user_id: Option<String>,
+    pub sort: Option<TaskSortPayload>,
 }
 
 impl TaskDataPayload {
     pub fn new(task_id: Option<String>, user_id: Option<String>) -> Self {
-        TaskDataPayload { task_id, user_id }
+        TaskDataPayload { task_id, user_id, sort: None }
     }
 
     pub fn from_option(option: Option<web::Json<TaskDataPayload>>) -> Self {
@@ -151,17 +55,30 @@ impl TaskDataPayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug, Clone)]
 pub struct TaskCreatePayload {
+    #[validate(custom(function = "validate_params::uuid"))]
     pub user_id: Option<String>,
+    #[validate(custom(function = "validate_params::uuid"))]
     pub project_id: Option<String>,
+    #[validate(
+        custom(function = "validate_params::not_blank"),
+        length(max = MAX_TITLE_LENGTH, code = "too_long", message = "must be at most 255 characters")
+    )]
     pub title: String,
-    pub typ: Option<TaskTypePayload>,
-    pub priority: Option<TaskPriorityPayload>,
-    pub status: Option<TaskStatusPayload>,
+    pub typ: Option<TaskType>,
+    pub priority: Option<TaskPriority>,
+    pub status: Option<TaskStatus>,
+    #[validate(length(max = MAX_DESCRIPTION_LENGTH, code = "too_long", message = "must be at most 10000 characters"))]
     pub description: Option<String>,
+    #[validate(range(min = 0, code = "out_of_range", message = "must not be negative"))]
     pub duration: Option<i32>,
+    #[validate(range(min = 0, code = "out_of_range", message = "must not be negative"))]
     pub due_date: Option<i64>,
+    #[validate(
+        custom(function = "validate_params::not_blank_items"),
+        length(max = MAX_TASK_LIST_ITEMS, code = "too_many", message = "must have at most 100 items")
+    )]
     pub task_list: Option<Vec<String>>,
 }
 
@@ -170,9 +87,9 @@ impl TaskCreatePayload {
         user_id: Option<String>,
         project_id: Option<String>,
         title: String,
-        typ: Option<TaskTypePayload>,
-        priority: Option<TaskPriorityPayload>,
-        status: Option<TaskStatusPayload>,
+        typ: Option<TaskType>,
+        priority: Option<TaskPriority>,
+        status: Option<TaskStatus>,
         description: Option<String>,
         duration: Option<i32>,
         due_date: Option<i64>,
@@ -193,18 +110,32 @@ impl TaskCreatePayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
 pub struct TaskUpdatePayload {
+    #[validate(custom(function = "validate_params::uuid"))]
     pub task_id: String,
+    #[validate(custom(function = "validate_params::uuid"))]
     pub user_id: Option<String>,
+    #[validate(custom(function = "validate_params::uuid"))]
     pub project_id: Option<String>,
+    #[validate(
+        custom(function = "validate_params::not_blank"),
+        length(max = MAX_TITLE_LENGTH, code = "too_long", message = "must be at most 255 characters")
+    )]
     pub title: Option<String>,
-    pub typ: Option<TaskTypePayload>,
-    pub priority: Option<TaskPriorityPayload>,
-    pub status: Option<TaskStatusPayload>,
+    pub typ: Option<TaskType>,
+    pub priority: Option<TaskPriority>,
+    pub status: Option<TaskStatus>,
+    #[validate(length(max = MAX_DESCRIPTION_LENGTH, code = "too_long", message = "must be at most 10000 characters"))]
     pub description: Option<String>,
+    #[validate(range(min = 0, code = "out_of_range", message = "must not be negative"))]
     pub duration: Option<i32>,
+    #[validate(range(min = 0, code = "out_of_range", message = "must not be negative"))]
     pub due_date: Option<i64>,
+    #[validate(
+        custom(function = "validate_params::not_blank_items"),
+        length(max = MAX_TASK_LIST_ITEMS, code = "too_many", message = "must have at most 100 items")
+    )]
     pub task_list: Option<Vec<String>>,
 }
 
@@ -214,9 +145,9 @@ impl TaskUpdatePayload {
         user_id: Option<String>,
         project_id: Option<String>,
         title: Option<String>,
-        typ: Option<TaskTypePayload>,
-        priority: Option<TaskPriorityPayload>,
-        status: Option<TaskStatusPayload>,
+        typ: Option<TaskType>,
+        priority: Option<TaskPriority>,
+        status: Option<TaskStatus>,
         description: Option<String>,
         duration: Option<i32>,
         due_date: Option<i64>,
@@ -239,3 +170,83 @@ impl TaskUpdatePayload {
 }
 
 pub struct TaskPayload {}
+
+// File format of an export or import
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskFileFormat {
+    #[default]
+    Csv,
+    Json,
+    // one JSON object per line
+    Ndjson,
+}
+
+// Query of an export, the caller's own tasks when neither filter is given
+#[derive(Serialize, Deserialize, IntoParams, Validate, Default, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct TaskExportPayload {
+    pub format: Option<TaskFileFormat>,
+    #[validate(custom(function = "validate_params::uuid"))]
+    pub user_id: Option<String>,
+    #[validate(custom(function = "validate_params::uuid"))]
+    pub project_id: Option<String>,
+}
+
+// Query of an import, the body is the file itself
+#[derive(Serialize, Deserialize, IntoParams, Validate, Default, Debug)]
+#[into_params(parameter_in = Query)]
+pub struct TaskImportQueryPayload {
+    // Taken from the Content-Type when missing
+    pub format: Option<TaskFileFormat>,
+    // Reports what would be imported without creating anything
+    #[serde(default)]
+    pub dry_run: bool,
+    // JSON object of source column (or key) to task field, e.g. {"Name":"title","Due":"due_date"}.
+    // Columns left out keep their name, those matching no field are ignored.
+    pub mapping: Option<String>,
+}
+
+// The rows of an import file, rows are counted from 1 without the CSV header
+#[derive(Debug)]
+pub struct TaskImportPayload {
+    pub rows: Vec<TaskImportRowPayload>,
+    pub dry_run: bool,
+}
+
+#[derive(Debug)]
+pub struct TaskImportRowPayload {
+    pub row: usize,
+    // The task to create, or what is wrong with the row
+    pub task: Result<TaskCreatePayload, Vec<FieldError>>,
+}
+
+// One page of an export, in creation order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskPagePayload {
+    pub user_id: Option<String>,
+    pub project_id: Option<String>,
+    // created_at and id of the last task of the previous page
+    pub after: Option<(NaiveDateTime, String)>,
+    pub limit: i64,
+    // only tasks with a due date
+    pub due_only: bool,
+}
+
+// One page of a task list in its display order, see `TaskSortPayload`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskListPagePayload {
+    pub user_id: Option<String>,
+    pub sort: Option<TaskSortPayload>,
+    // sort key of the last task of the previous page
+    pub after: Option<TaskListCursor>,
+    pub limit: i64,
+}
+
+// Where a task sits in any of the list orders, the priority is only read when sorting by it
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskListCursor {
+    pub priority: TaskPriority,
+    pub created_at: NaiveDateTime,
+    pub id: String,
+}
@@ -1,14 +1,17 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+use crate::domain::task_entity::{TaskPriority, TaskStatus, TaskType};
+
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct TaskPresenter {
     pub task_id: String,
     pub user_id: String,
     pub project_id: String,
     pub title: String,
-    pub typ: String,
-    pub priority: String,
-    pub status: String,
+    pub typ: TaskType,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: String,
     pub duration: i32,
     pub due_date: i64,
@@ -17,13 +20,34 @@ pub struct TaskPresenter {
     pub created_at: i64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct TaskAllPresenter {
     pub task_id: String,
     pub user_id: String,
     pub project_id: String,
     pub title: String,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: String,
     pub updated_at: i64,
     pub created_at: i64,
 }
+
+// Outcome of an import, `task_ids` stays empty on a dry run
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct TaskImportPresenter {
+    pub dry_run: bool,
+    pub rows: usize,
+    pub valid_rows: usize,
+    pub task_ids: Vec<String>,
+    pub errors: Vec<TaskImportErrorPresenter>,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
+pub struct TaskImportErrorPresenter {
+    #[schema(example = "rows[3].title")]
+    pub field: String,
+    #[schema(example = "required")]
+    pub code: String,
+    pub message: String,
+}
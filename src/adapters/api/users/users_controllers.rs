@@ -2,34 +2,89 @@ use std::str::FromStr;
 
 use crate::{
     adapters::api::{
-        shared::{app_state::AppState, error_presenter::ErrorResponse, success_presenter::SuccessResponse},
+        shared::{
+            app_state::AppState,
+            error_presenter::{ErrorPresenter, ErrorResponse},
+            success_presenter::{EmptySuccessResponse, SuccessResponse},
+            validated_json::{OptionalValidatedJson, ValidatedJson},
+        },
         users::{
             users_mappers::{UserAccessTokenPresenterMapper, UserAllPresenterMapper, UserPresenterMapper},
-            users_payloads::{UserIdPayload, UserLoginPayload, UserRefreshTokenPayload, UserRegisterPayload, UserRolePayload, UserUpdatePayload},
-            users_presenters::UserAllPresenter,
+            users_payloads::{
+                UserForgotPasswordPayload, UserIdPayload, UserLoginPayload, UserLogoutPayload, UserRefreshTokenPayload, UserRegisterPayload, UserResetPasswordPayload, UserRolePayload,
+                UserUnlockAccountPayload, UserUnlockPayload, UserUpdatePayload, UserVerifyEmailPayload,
+            },
+            users_presenters::{UserAccessTokenPresenter, UserAllPresenter, UserPresenter},
         },
     },
     application::{
         mappers::api_mapper::ApiMapper,
+        repositories::users_repository_abstract::REFRESH_TOKEN_REUSED,
         usecases::{
             interfaces::AbstractUseCase,
             user::{
-                delete_one_user_by_id_usecase::DeleteOneUserByIdUseCase, get_all_users_usecase::GetAllUsersUseCase, get_one_user_by_id_usecase::GetOneUserByIdUseCase,
-                login_user_usecase::LoginUserUseCase, refresh_token_user_usecase::RefreshTokenUserUseCase, register_user_usecase::RegisterUserUseCase,
-                update_one_user_usecase::UpdateOneUserUseCase,
+                get_all_users_usecase::GetAllUsersUseCase, get_one_user_by_id_usecase::GetOneUserByIdUseCase,
+                login_user_usecase::LoginUserUseCase, logout_all_user_usecase::LogoutAllUserUseCase, logout_user_usecase::LogoutUserUseCase,
+                delete_one_user_by_id_usecase::DeleteOneUserByIdUseCase, forgot_password_usecase::ForgotPasswordUseCase,
+                refresh_token_user_usecase::RefreshTokenUserUseCase, register_user_usecase::RegisterUserUseCase, reset_password_usecase::ResetPasswordUseCase,
+                send_verification_email_usecase::SendVerificationEmailUseCase, unlock_account_usecase::UnlockAccountUseCase, unlock_user_usecase::UnlockUserUseCase, update_one_user_usecase::UpdateOneUserUseCase,
+                verify_email_usecase::VerifyEmailUseCase,
+            },
+        },
+        utils::{
+            access_control::{
+                extractors::{authorized::{require, Authorized}, signed_in::SignedIn},
+                middlewares::rate_limit::client_ip,
             },
+            metrics::LoginOutcome,
         },
-        utils::access_control::{auth_usecase::AuthCheckUseCase, extractors::claims::Claims},
     },
-    domain::{error::ApiError, user_entity::UserAllEntity},
+    domain::{
+        error::{ApiError, DomainError},
+        user_entity::UserAllEntity,
+    },
 };
-use actix_web::{delete, get, patch, post, web, HttpResponse};
+use actix_web::{delete, get, http::header, patch, post, web, HttpRequest, HttpResponse};
+use chrono::Utc;
 use reqwest::StatusCode;
+use utoipa::OpenApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    register_user,
+    login_user,
+    logout_user,
+    logout_all_user,
+    get_refresh_token,
+    verify_email,
+    resend_verification_email,
+    forgot_password,
+    reset_password,
+    unlock_account,
+    unlock_user,
+    update_one_user,
+    update_one_user_own,
+    update_one_user_role,
+    get_all_users,
+    get_one_user_by_id,
+    get_one_user_by_id_own,
+    delete_one_user_by_id,
+    delete_one_user_by_id_own
+))]
+pub struct UsersApi;
 
 pub fn routes(cfg: &mut web::ServiceConfig) {
     cfg.service(register_user)
         .service(login_user)
+        .service(logout_user)
+        .service(logout_all_user)
         .service(get_refresh_token)
+        .service(verify_email)
+        .service(resend_verification_email)
+        .service(forgot_password)
+        .service(reset_password)
+        .service(unlock_account)
+        .service(unlock_user)
         .service(update_one_user)
         .service(update_one_user_own)
         .service(update_one_user_role)
@@ -40,86 +95,408 @@ pub fn routes(cfg: &mut web::ServiceConfig) {
         .service(delete_one_user_by_id_own);
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Register an account",
+    request_body = UserRegisterPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 409, description = "Conflicting state", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+)]
 #[post("/register")]
-async fn register_user(data: web::Data<AppState>, path: web::Json<UserRegisterPayload>) -> Result<HttpResponse, ErrorResponse> {
+async fn register_user(data: web::Data<AppState>, path: ValidatedJson<UserRegisterPayload>) -> Result<HttpResponse, ErrorResponse> {
     let user_payload = path.into_inner();
     let register_user_usecase = RegisterUserUseCase::new(&user_payload, &data.users_repository);
 
     match register_user_usecase.execute().await {
-        Ok(user) => Ok(SuccessResponse::new(StatusCode::CREATED, "User created successfully", UserPresenterMapper::to_api(user)).to_http_response()),
+        Ok(user) => {
+            // the account is usable right away, a failed verification mail can be resent later
+            let verification_payload = UserIdPayload::new(user.id.clone());
+            let base_url = data.settings.app.base_url.clone();
+            let send_verification_email_usecase = SendVerificationEmailUseCase::new(&verification_payload, &base_url, &data.account_tokens_repository, data.mailer.as_ref());
+            if let Err(e) = send_verification_email_usecase.execute().await {
+                log::warn!("{}", e.message);
+            }
+            Ok(SuccessResponse::new(StatusCode::CREATED, "User created successfully", UserPresenterMapper::to_api(user)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Sign in, the user carries a two-factor challenge when one is required",
+    request_body = UserLoginPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+        (status = 429, description = "Too many attempts, the account or client is locked for a while", body = ErrorPresenter),
+    ),
+)]
 #[post("/login")]
-async fn login_user(data: web::Data<AppState>, path: web::Json<UserLoginPayload>) -> Result<HttpResponse, ErrorResponse> {
-    let user_payload = path.into_inner();
-    let login_user_usecase = LoginUserUseCase::new(&user_payload, &data.users_repository);
+async fn login_user(data: web::Data<AppState>, req: HttpRequest, path: ValidatedJson<UserLoginPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut user_payload = path.into_inner();
+    user_payload.ip_address = client_ip(&req, &data.trusted_proxies).map(|ip| ip.to_string());
+    user_payload.user_agent = req.headers().get(header::USER_AGENT).and_then(|h| h.to_str().ok()).map(String::from);
+    let base_url = data.settings.app.base_url.clone();
+    let login_user_usecase = LoginUserUseCase::new(&user_payload, &base_url, &data.users_repository, &data.login_attempts_repository, data.mailer.as_ref());
 
-    match login_user_usecase.execute().await {
+    let result = login_user_usecase.execute().await;
+    data.metrics.record_login(match &result {
+        Ok(_) => LoginOutcome::Success,
+        Err(e) if e.get_error_code() == StatusCode::TOO_MANY_REQUESTS.as_u16() => LoginOutcome::Locked,
+        Err(_) => LoginOutcome::Failure,
+    });
+
+    match result {
+        Ok(user) if user.mfa_token.is_some() => Ok(SuccessResponse::new(StatusCode::OK, "Two-factor code required", UserPresenterMapper::to_api(user)).to_http_response()),
         Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "User signin successfully", UserPresenterMapper::to_api(user)).to_http_response()),
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Revoke the caller's token and session",
+    request_body = Option<UserLogoutPayload>,
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Not allowed with an API key", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/logout")]
+async fn logout_user(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountRead>>, path: OptionalValidatedJson<UserLogoutPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut user_payload = path.into_inner_or_default();
+    user_payload.user_id = Some(auth.sub.clone());
+    user_payload.jti = Some(auth.jti.clone());
+    user_payload.expires_at = Some(auth.exp as i64);
+    user_payload.session_id = auth.sid.clone();
+    let logout_user_usecase = LogoutUserUseCase::new(&user_payload, &data.tokens_repository);
+
+    match logout_user_usecase.execute().await {
+        Ok(_) => {
+            data.revocation_cache
+                .revoke_token(&user_payload.jti.unwrap_or_default(), user_payload.expires_at.unwrap_or_default());
+            if let Some(session_id) = user_payload.session_id {
+                data.revocation_cache.revoke_session(&session_id, Utc::now().timestamp());
+            }
+            Ok(SuccessResponse::new(StatusCode::OK, "User logout successfully", ()).to_http_response())
+        }
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Revoke every token of the caller",
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Not allowed with an API key", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/logout_all")]
+async fn logout_all_user(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountRead>>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = UserIdPayload { user_id: auth.sub.clone() };
+    let logout_all_user_usecase = LogoutAllUserUseCase::new(&user_payload, &data.tokens_repository);
+
+    match logout_all_user_usecase.execute().await {
+        Ok(revoked_at) => {
+            data.revocation_cache.revoke_user(&user_payload.user_id, revoked_at);
+            Ok(SuccessResponse::new(StatusCode::OK, "User logout from all devices successfully", ()).to_http_response())
+        }
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Exchange a refresh token for a new token pair",
+    request_body = UserRefreshTokenPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserAccessTokenPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+)]
 #[post("/refresh")]
-async fn get_refresh_token(data: web::Data<AppState>, path: web::Json<UserRefreshTokenPayload>) -> Result<HttpResponse, ErrorResponse> {
-    let user_payload = path.into_inner();
+async fn get_refresh_token(data: web::Data<AppState>, req: HttpRequest, path: ValidatedJson<UserRefreshTokenPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut user_payload = path.into_inner();
+    user_payload.ip_address = client_ip(&req, &data.trusted_proxies).map(|ip| ip.to_string());
+    user_payload.user_agent = req.headers().get(header::USER_AGENT).and_then(|h| h.to_str().ok()).map(String::from);
     let login_user_usecase = RefreshTokenUserUseCase::new(&user_payload, &data.users_repository);
 
     match login_user_usecase.execute().await {
         Ok(token) => Ok(SuccessResponse::new(StatusCode::OK, "Token generate successfully", UserAccessTokenPresenterMapper::to_api(token)).to_http_response()),
+        Err(e) => {
+            // the replay revoked the session in the database, its access tokens must stop working now
+            if matches!(&e.error, Some(DomainError::Unauthorized(message)) if message == REFRESH_TOKEN_REUSED) {
+                data.revocation_cache.mark_stale();
+            }
+            Err(ErrorResponse::map_io_error(e))
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Confirm an email address from the emailed token",
+    request_body = UserVerifyEmailPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 409, description = "Conflicting state", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+)]
+#[post("/verify_email")]
+async fn verify_email(data: web::Data<AppState>, path: ValidatedJson<UserVerifyEmailPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = path.into_inner();
+    let verify_email_usecase = VerifyEmailUseCase::new(&user_payload, &data.account_tokens_repository);
+
+    match verify_email_usecase.execute().await {
+        Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "Email verified successfully", UserPresenterMapper::to_api(user)).to_http_response()),
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
-#[patch("/one")]
-async fn update_one_user(data: web::Data<AppState>, claims: Claims, path: web::Json<UserUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+#[utoipa::path(
+    tag = "users",
+    summary = "Send the verification email again",
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 409, description = "Conflicting state", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/verify_email/resend")]
+async fn resend_verification_email(data: web::Data<AppState>, auth: Authorized<require::AccountRead>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = UserIdPayload::new(auth.sub.clone());
+    let base_url = data.settings.app.base_url.clone();
+    let send_verification_email_usecase = SendVerificationEmailUseCase::new(&user_payload, &base_url, &data.account_tokens_repository, data.mailer.as_ref());
+
+    match send_verification_email_usecase.execute().await {
+        Ok(_) => Ok(SuccessResponse::new(StatusCode::OK, "Verification email sent successfully", ()).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Email a password reset link, answers the same whether or not the account exists",
+    request_body = UserForgotPasswordPayload,
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+)]
+#[post("/forgot_password")]
+async fn forgot_password(data: web::Data<AppState>, path: ValidatedJson<UserForgotPasswordPayload>) -> Result<HttpResponse, ErrorResponse> {
     let user_payload = path.into_inner();
+    let base_url = data.settings.app.base_url.clone();
+    let forgot_password_usecase = ForgotPasswordUseCase::new(&user_payload, &base_url, &data.account_tokens_repository, data.mailer.as_ref());
+
+    match forgot_password_usecase.execute().await {
+        Ok(_) => Ok(SuccessResponse::new(StatusCode::OK, "If the email is registered, a reset link has been sent", ()).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Set a new password from the emailed token",
+    request_body = UserResetPasswordPayload,
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+)]
+#[post("/reset_password")]
+async fn reset_password(data: web::Data<AppState>, path: ValidatedJson<UserResetPasswordPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = path.into_inner();
+    let reset_password_usecase = ResetPasswordUseCase::new(&user_payload, &data.account_tokens_repository);
+
+    match reset_password_usecase.execute().await {
+        Ok(_) => {
+            // the reset revoked every token of the user in the database
+            data.revocation_cache.mark_stale();
+            Ok(SuccessResponse::new(StatusCode::OK, "Password reset successfully", ()).to_http_response())
+        }
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Unlock an account from the emailed token",
+    request_body = UserUnlockAccountPayload,
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+)]
+#[post("/unlock_account")]
+async fn unlock_account(data: web::Data<AppState>, path: ValidatedJson<UserUnlockAccountPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = path.into_inner();
+    let unlock_account_usecase = UnlockAccountUseCase::new(&user_payload, &data.login_attempts_repository);
+
+    match unlock_account_usecase.execute().await {
+        Ok(_) => Ok(SuccessResponse::new(StatusCode::OK, "Account unlocked successfully", ()).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Unlock a locked account",
+    request_body = UserUnlockPayload,
+    responses(
+        (status = 200, description = "Success", body = EmptySuccessResponse),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[post("/unlock")]
+async fn unlock_user(data: web::Data<AppState>, auth: Authorized<require::UsersWrite>, path: ValidatedJson<UserUnlockPayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut user_payload = path.into_inner();
+    user_payload.unlocked_by = Some(auth.sub.clone());
+    let unlock_user_usecase = UnlockUserUseCase::new(&user_payload, &data.login_attempts_repository);
+
+    match unlock_user_usecase.execute().await {
+        Ok(_) => Ok(SuccessResponse::new(StatusCode::OK, "User unlocked successfully", ()).to_http_response()),
+        Err(e) => Err(ErrorResponse::map_io_error(e)),
+    }
+}
+
+#[utoipa::path(
+    tag = "users",
+    summary = "Update any user",
+    request_body = UserUpdatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
+#[patch("/one")]
+async fn update_one_user(data: web::Data<AppState>, _auth: Authorized<require::UsersWrite>, path: ValidatedJson<UserUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
+    let mut user_payload = path.into_inner();
+    // roles change through `/one_role` only, the stored role is kept
+    user_payload.role = None;
+    user_payload.role_promote = None;
     let update_one_user_usecase = UpdateOneUserUseCase::new(&user_payload, &data.users_repository);
 
     match update_one_user_usecase.execute().await {
-        Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "User updated successfully", UserPresenterMapper::to_api(user)).to_http_response()),
+        Ok(user) => {
+            // password or role changes revoke the user's tokens in the database
+            data.revocation_cache.mark_stale();
+            Ok(SuccessResponse::new(StatusCode::OK, "User updated successfully", UserPresenterMapper::to_api(user)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Update the caller's account",
+    request_body = UserUpdatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted, or not allowed with an API key", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[patch("/one_own")]
-async fn update_one_user_own(data: web::Data<AppState>, claims: Claims, path: web::Json<UserUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
+async fn update_one_user_own(data: web::Data<AppState>, auth: SignedIn<Authorized<require::AccountWrite>>, path: ValidatedJson<UserUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
     let mut user_payload = path.into_inner();
-    user_payload.user_id = Some(claims.sub.clone());
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
+    user_payload.user_id = Some(auth.sub.clone());
+    user_payload.role = None;
+    user_payload.role_promote = None;
     let update_one_user_usecase = UpdateOneUserUseCase::new(&user_payload, &data.users_repository);
 
     match update_one_user_usecase.execute().await {
-        Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "User updated successfully", UserPresenterMapper::to_api(user)).to_http_response()),
+        Ok(user) => {
+            // password or role changes revoke the user's tokens in the database
+            data.revocation_cache.mark_stale();
+            Ok(SuccessResponse::new(StatusCode::OK, "User updated successfully", UserPresenterMapper::to_api(user)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Promote or demote a user",
+    request_body = UserUpdatePayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[patch("/one_role")]
-async fn update_one_user_role(data: web::Data<AppState>, claims: Claims, path: web::Json<UserUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
+async fn update_one_user_role(data: web::Data<AppState>, auth: Authorized<require::UsersRole>, path: ValidatedJson<UserUpdatePayload>) -> Result<HttpResponse, ErrorResponse> {
     let mut user_payload = path.into_inner();
-    let data_role = UserRolePayload::from_str(&claims.role.clone().to_string().as_str());
+    let data_role = UserRolePayload::from_str(&auth.role.clone().to_string().as_str());
     user_payload.role = Some(data_role.clone().unwrap_or_default());
 
-    if user_payload.user_id == Some(claims.sub.clone()) {
+    if user_payload.user_id == Some(auth.sub.clone()) {
         return Err(ErrorResponse::map_io_error_default("Can't self promote!!!".to_string()));
     }
 
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
     let update_one_user_usecase = UpdateOneUserUseCase::new(&user_payload, &data.users_repository);
 
     match update_one_user_usecase.execute().await {
-        Ok(user) => Ok(SuccessResponse::new(StatusCode::OK, "User updated successfully", UserPresenterMapper::to_api(user)).to_http_response()),
+        Ok(user) => {
+            // password or role changes revoke the user's tokens in the database
+            data.revocation_cache.mark_stale();
+            Ok(SuccessResponse::new(StatusCode::OK, "User updated successfully", UserPresenterMapper::to_api(user)).to_http_response())
+        }
         Err(e) => Err(ErrorResponse::map_io_error(e)),
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "List every user",
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<Vec<UserAllPresenter>>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/all")]
-async fn get_all_users(data: web::Data<AppState>, claims: Claims) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+async fn get_all_users(data: web::Data<AppState>, _auth: Authorized<require::UsersRead>) -> Result<HttpResponse, ErrorResponse> {
     let get_all_users_usecase = GetAllUsersUseCase::new(&data.users_repository);
     let users: Result<Vec<UserAllEntity>, ApiError> = get_all_users_usecase.execute().await;
 
@@ -134,9 +511,22 @@ async fn get_all_users(data: web::Data<AppState>, claims: Claims) -> Result<Http
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Get a user",
+    request_body = UserIdPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/one")]
-async fn get_one_user_by_id(data: web::Data<AppState>, claims: Claims, path: web::Json<UserIdPayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+async fn get_one_user_by_id(data: web::Data<AppState>, _auth: Authorized<require::UsersRead>, path: ValidatedJson<UserIdPayload>) -> Result<HttpResponse, ErrorResponse> {
     let user_payload = path.into_inner();
     let get_one_user_by_id_usecase = GetOneUserByIdUseCase::new(&user_payload, &data.users_repository);
 
@@ -146,10 +536,19 @@ async fn get_one_user_by_id(data: web::Data<AppState>, claims: Claims, path: web
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Get the caller's account",
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[get("/one_own")]
-async fn get_one_user_by_id_own(data: web::Data<AppState>, claims: Claims) -> Result<HttpResponse, ErrorResponse> {
-    let user_payload = UserIdPayload { user_id: claims.sub.clone() };
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
+async fn get_one_user_by_id_own(data: web::Data<AppState>, auth: Authorized<require::AccountRead>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = UserIdPayload { user_id: auth.sub.clone() };
     let get_one_user_by_id_usecase = GetOneUserByIdUseCase::new(&user_payload, &data.users_repository);
 
     match get_one_user_by_id_usecase.execute().await {
@@ -158,9 +557,22 @@ async fn get_one_user_by_id_own(data: web::Data<AppState>, claims: Claims) -> Re
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Delete a user",
+    request_body = UserIdPayload,
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 400, description = "Malformed request body", body = ErrorPresenter),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+        (status = 404, description = "Resource not found", body = ErrorPresenter),
+        (status = 422, description = "Invalid fields, listed in `data`", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[delete("/one")]
-async fn delete_one_user_by_id(data: web::Data<AppState>, claims: Claims, path: web::Json<UserIdPayload>) -> Result<HttpResponse, ErrorResponse> {
-    AuthCheckUseCase::check_permission_up_to_admin(claims)?;
+async fn delete_one_user_by_id(data: web::Data<AppState>, _auth: Authorized<require::UsersDelete>, path: ValidatedJson<UserIdPayload>) -> Result<HttpResponse, ErrorResponse> {
     let user_payload = path.into_inner();
     let delete_one_user_usecase = DeleteOneUserByIdUseCase::new(&user_payload, &data.users_repository);
 
@@ -170,10 +582,19 @@ async fn delete_one_user_by_id(data: web::Data<AppState>, claims: Claims, path:
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Delete the caller's account",
+    responses(
+        (status = 200, description = "Success", body = SuccessResponse<UserPresenter>),
+        (status = 401, description = "Missing, invalid or revoked credentials", body = ErrorPresenter),
+        (status = 403, description = "Permission not granted", body = ErrorPresenter),
+    ),
+    security(("bearer_auth" = []))
+)]
 #[delete("/one_own")]
-async fn delete_one_user_by_id_own(data: web::Data<AppState>, claims: Claims) -> Result<HttpResponse, ErrorResponse> {
-    let user_payload = UserIdPayload { user_id: claims.sub.clone() };
-    AuthCheckUseCase::check_permission_up_to_customer(claims)?;
+async fn delete_one_user_by_id_own(data: web::Data<AppState>, auth: Authorized<require::AccountDelete>) -> Result<HttpResponse, ErrorResponse> {
+    let user_payload = UserIdPayload { user_id: auth.sub.clone() };
     let delete_one_user_usecase = DeleteOneUserByIdUseCase::new(&user_payload, &data.users_repository);
 
     match delete_one_user_usecase.execute().await {
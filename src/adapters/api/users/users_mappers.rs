@@ -23,6 +23,9 @@ impl ApiMapper<UserEntity, UserPresenter, UserRegisterPayload> for UserPresenter
             refresh_token: entity.refresh_token,
             access_token: entity.access_token,
             fcm_token: entity.fcm_token,
+            email_verified_at: entity.email_verified_at.map(naive_datetime_to_unixtimemillis),
+            mfa_token: entity.mfa_token,
+            mfa_enrollment_required: entity.mfa_enrollment_required,
             last_login: naive_datetime_to_unixtimemillis(entity.last_login),
             updated_at: naive_datetime_to_unixtimemillis(entity.updated_at),
             created_at: naive_datetime_to_unixtimemillis(entity.created_at),
@@ -53,6 +56,7 @@ impl ApiMapper<UserAccessTokenEntity, UserAccessTokenPresenter, UserPayload> for
     fn to_api(entity: UserAccessTokenEntity) -> UserAccessTokenPresenter {
         UserAccessTokenPresenter {
             access_token: entity.access_token,
+            refresh_token: entity.refresh_token,
         }
     }
 
@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
 
 use std::{fmt, str::FromStr};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+use crate::application::utils::validate_params;
+
+const MAX_DEVICE_NAME_LENGTH: u64 = 100;
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum UserRolePayload {
     SuperAdmin,
@@ -80,7 +86,7 @@ impl FromStr for UserRolePayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum UserRolePromotePayload {
     Promote,
@@ -130,8 +136,9 @@ impl UserRolePromotePayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
 pub struct UserIdPayload {
+    #[validate(custom(function = "validate_params::uuid"))]
     pub user_id: String,
 }
 
@@ -141,10 +148,21 @@ impl UserIdPayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+// One page of the user list, in id order
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserPagePayload {
+    // id of the last user of the previous page
+    pub after: Option<String>,
+    pub limit: i64,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
 pub struct UserRegisterPayload {
+    #[validate(custom(function = "validate_params::username"))]
     pub username: String,
+    #[validate(custom(function = "validate_params::email"))]
     pub email: String,
+    #[validate(custom(function = "validate_params::password"))]
     pub password: String,
     pub role: Option<UserRolePayload>,
 }
@@ -155,23 +173,43 @@ impl UserRegisterPayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
 pub struct UserLoginPayload {
+    #[validate(custom(function = "validate_params::email"))]
     pub email: String,
+    #[validate(custom(function = "validate_params::not_blank"))]
     pub password: String,
+    #[validate(length(max = MAX_DEVICE_NAME_LENGTH, code = "too_long", message = "must be at most 100 characters"))]
+    pub device_name: Option<String>,
+    #[serde(skip_deserializing)]
+    #[schema(ignore)]
+    pub ip_address: Option<String>,
+    #[serde(skip_deserializing)]
+    #[schema(ignore)]
+    pub user_agent: Option<String>,
 }
 
 impl UserLoginPayload {
     pub fn new(email: String, password: String) -> Self {
-        UserLoginPayload { email, password }
+        UserLoginPayload {
+            email,
+            password,
+            device_name: None,
+            ip_address: None,
+            user_agent: None,
+        }
     }
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Default, Debug)]
 pub struct UserUpdatePayload {
+    #[validate(custom(function = "validate_params::uuid"))]
     pub user_id: Option<String>,
+    #[validate(custom(function = "validate_params::username"))]
     pub username: Option<String>,
+    #[validate(custom(function = "validate_params::email"))]
     pub email: Option<String>,
+    #[validate(custom(function = "validate_params::password"))]
     pub password: Option<String>,
     pub role: Option<UserRolePayload>,
     pub role_promote: Option<UserRolePromotePayload>,
@@ -197,14 +235,121 @@ impl UserUpdatePayload {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
 pub struct UserRefreshTokenPayload {
+    #[validate(custom(function = "validate_params::not_blank"))]
     pub refresh_token: String,
+    #[serde(skip_deserializing)]
+    #[schema(ignore)]
+    pub ip_address: Option<String>,
+    #[serde(skip_deserializing)]
+    #[schema(ignore)]
+    pub user_agent: Option<String>,
 }
 
 impl UserRefreshTokenPayload {
     pub fn new(refresh_token: String) -> Self {
-        UserRefreshTokenPayload { refresh_token }
+        UserRefreshTokenPayload {
+            refresh_token,
+            ip_address: None,
+            user_agent: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Default, Debug)]
+pub struct UserLogoutPayload {
+    pub user_id: Option<String>,
+    pub jti: Option<String>,
+    pub expires_at: Option<i64>,
+    pub session_id: Option<String>,
+}
+
+impl UserLogoutPayload {
+    pub fn new(user_id: Option<String>, jti: Option<String>, expires_at: Option<i64>, session_id: Option<String>) -> Self {
+        UserLogoutPayload {
+            user_id,
+            jti,
+            expires_at,
+            session_id,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
+pub struct UserVerifyEmailPayload {
+    #[validate(custom(function = "validate_params::not_blank"))]
+    pub token: String,
+}
+
+impl UserVerifyEmailPayload {
+    pub fn new(token: String) -> Self {
+        UserVerifyEmailPayload { token }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
+pub struct UserForgotPasswordPayload {
+    #[validate(custom(function = "validate_params::email"))]
+    pub email: String,
+}
+
+impl UserForgotPasswordPayload {
+    pub fn new(email: String) -> Self {
+        UserForgotPasswordPayload { email }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
+pub struct UserResetPasswordPayload {
+    #[validate(custom(function = "validate_params::not_blank"))]
+    pub token: String,
+    #[validate(custom(function = "validate_params::password"))]
+    pub password: String,
+}
+
+impl UserResetPasswordPayload {
+    pub fn new(token: String, password: String) -> Self {
+        UserResetPasswordPayload { token, password }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UserLoginAttemptPayload {
+    pub email: String,
+    pub ip_address: Option<String>,
+}
+
+impl UserLoginAttemptPayload {
+    pub fn new(email: String, ip_address: Option<String>) -> Self {
+        UserLoginAttemptPayload { email, ip_address }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
+pub struct UserUnlockAccountPayload {
+    #[validate(custom(function = "validate_params::not_blank"))]
+    pub token: String,
+}
+
+impl UserUnlockAccountPayload {
+    pub fn new(token: String) -> Self {
+        UserUnlockAccountPayload { token }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Validate, Debug)]
+pub struct UserUnlockPayload {
+    #[validate(custom(function = "validate_params::uuid"))]
+    pub user_id: String,
+    #[serde(skip_deserializing)]
+    #[schema(ignore)]
+    pub unlocked_by: Option<String>,
+}
+
+impl UserUnlockPayload {
+    pub fn new(user_id: String, unlocked_by: Option<String>) -> Self {
+        UserUnlockPayload { user_id, unlocked_by }
     }
 }
 
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct UserPresenter {
     pub user_id: String,
     pub username: String,
@@ -13,12 +14,18 @@ pub struct UserPresenter {
     pub access_token: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fcm_token: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_verified_at: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa_token: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+    pub mfa_enrollment_required: bool,
     pub last_login: i64,
     pub updated_at: i64,
     pub created_at: i64,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct UserAllPresenter {
     pub user_id: String,
     pub username: String,
@@ -26,7 +33,8 @@ pub struct UserAllPresenter {
     pub role: String,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, ToSchema, Debug)]
 pub struct UserAccessTokenPresenter {
     pub access_token: String,
+    pub refresh_token: String,
 }
@@ -0,0 +1 @@
+pub mod well_known_controllers;
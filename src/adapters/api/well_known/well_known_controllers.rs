@@ -0,0 +1,14 @@
+use actix_web::{get, http::header, web, HttpResponse};
+
+use crate::application::utils::access_control::key_ring::KeyRing;
+
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_jwks);
+}
+
+// Public keys verifying our tokens, served as a bare JWK set since that's what JWT libraries fetch.
+// Retired keys stay listed until they are removed from the key ring.
+#[get("/jwks.json")]
+async fn get_jwks() -> HttpResponse {
+    HttpResponse::Ok().insert_header((header::CACHE_CONTROL, "public, max-age=300")).json(KeyRing::global().jwks())
+}
@@ -0,0 +1,30 @@
+use crate::adapters::spi::db::schema::*;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+pub const EMAIL_VERIFICATION_PURPOSE: &str = "email_verification";
+pub const PASSWORD_RESET_PURPOSE: &str = "password_reset";
+pub const ACCOUNT_UNLOCK_PURPOSE: &str = "account_unlock";
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = account_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub purpose: String,
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = account_tokens)]
+pub struct AccountTokenNew<'a> {
+    pub user_id: &'a Uuid,
+    pub purpose: &'a str,
+    pub token_hash: &'a str,
+    pub expires_at: NaiveDateTime,
+}
@@ -0,0 +1,31 @@
+use crate::adapters::spi::db::schema::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ApiKey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub permissions: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = api_keys)]
+pub struct ApiKeyNew<'a> {
+    pub user_id: &'a Uuid,
+    pub name: &'a str,
+    pub prefix: &'a str,
+    pub key_hash: &'a str,
+    pub permissions: &'a [String],
+    pub expires_at: Option<NaiveDateTime>,
+}
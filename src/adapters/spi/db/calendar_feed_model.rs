@@ -0,0 +1,20 @@
+use crate::adapters::spi::db::schema::*;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = calendar_feeds)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CalendarFeed {
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = calendar_feeds)]
+pub struct CalendarFeedNew<'a> {
+    pub user_id: &'a Uuid,
+    pub token_hash: &'a str,
+}
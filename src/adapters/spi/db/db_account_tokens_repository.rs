@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use bcrypt::{hash, DEFAULT_COST};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::users::users_payloads::{UserForgotPasswordPayload, UserIdPayload, UserResetPasswordPayload, UserVerifyEmailPayload};
+use crate::application::mappers::db_mapper::DbMapper;
+use crate::application::utils::token_hash::{generate_secret, hash_token};
+use crate::application::utils::validate_params;
+use crate::{
+    application::repositories::account_tokens_repository_abstract::AccountTokensRepositoryAbstract,
+    domain::{account_token_entity::AccountTokenEntity, user_entity::UserEntity},
+};
+
+use super::account_token_model::*;
+use super::db_sessions_repository::revoke_user_sessions;
+use super::db_users_mappers::UserDbMapper;
+use super::schema::{account_tokens, users};
+use super::user_model::User;
+use crate::adapters::spi::db::db_connection::DbConnection;
+use crate::infrastructure::settings::TokenSettings;
+
+pub struct AccountTokensRepository {
+    pub db_connection: Arc<DbConnection>,
+    pub token_settings: TokenSettings,
+}
+
+#[async_trait(?Send)]
+impl AccountTokensRepositoryAbstract for AccountTokensRepository {
+    #[instrument(name = "AccountTokensRepository::create_email_verification", skip_all)]
+    async fn create_email_verification(&self, user_payload: &UserIdPayload) -> Result<AccountTokenEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&user_payload.user_id)?;
+
+        let user = match users::table.filter(users::id.eq(data_user_id)).select(User::as_select()).first::<User>(&mut conn) {
+            Ok(user) => user,
+            Err(_) => return Err(DomainError::NotFound(String::from("User not found"))),
+        };
+
+        if user.email_verified_at.is_some() {
+            return Err(DomainError::Conflict(String::from("Email already verified")));
+        }
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| issue_account_token(conn, &user, EMAIL_VERIFICATION_PURPOSE, self.token_settings.email_verification_ttl as i64));
+
+        match result {
+            Ok(entity) => Ok(entity),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "AccountTokensRepository::verify_email", skip_all)]
+    async fn verify_email(&self, user_payload: &UserVerifyEmailPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let token = match consume_account_token(conn, &user_payload.token, EMAIL_VERIFICATION_PURPOSE)? {
+                Some(token) => token,
+                None => return Ok(None),
+            };
+
+            let model = diesel::update(users::table.filter(users::id.eq(token.user_id)))
+                .set(users::email_verified_at.eq(Utc::now().naive_utc()))
+                .returning(User::as_returning())
+                .get_result(conn)?;
+
+            Ok(Some(model))
+        });
+
+        match result {
+            Ok(Some(model)) => Ok(UserDbMapper::to_entity(model)),
+            Ok(None) => Err(DomainError::validation("Invalid or expired token")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "AccountTokensRepository::create_password_reset", skip_all)]
+    async fn create_password_reset(&self, user_payload: &UserForgotPasswordPayload) -> Result<Option<AccountTokenEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        // unknown emails are not an error, callers must not be able to tell them apart
+        let user = match users::table
+            .filter(users::email.eq(&user_payload.email))
+            .select(User::as_select())
+            .first::<User>(&mut conn)
+            .optional()?
+        {
+            Some(user) => user,
+            None => return Ok(None),
+        };
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| issue_account_token(conn, &user, PASSWORD_RESET_PURPOSE, self.token_settings.password_reset_ttl as i64));
+
+        match result {
+            Ok(entity) => Ok(Some(entity)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "AccountTokensRepository::reset_password", skip_all)]
+    async fn reset_password(&self, user_payload: &UserResetPasswordPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        if !validate_params::is_password(&user_payload.password) {
+            return Err(DomainError::validation("Invalid Password!"));
+        }
+
+        let hashed_password = hash(user_payload.password.as_str(), DEFAULT_COST)?;
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let token = match consume_account_token(conn, &user_payload.token, PASSWORD_RESET_PURPOSE)? {
+                Some(token) => token,
+                None => return Ok(None),
+            };
+
+            // a new password logs the user out everywhere
+            revoke_user_sessions(conn, &token.user_id)?;
+            let now = Utc::now().naive_utc();
+            let model = diesel::update(users::table.filter(users::id.eq(token.user_id)))
+                .set((users::password_hash.eq(&hashed_password), users::tokens_revoked_at.eq(now), users::updated_at.eq(now)))
+                .returning(User::as_returning())
+                .get_result(conn)?;
+
+            Ok(Some(model))
+        });
+
+        match result {
+            Ok(Some(model)) => Ok(UserDbMapper::to_entity(model)),
+            Ok(None) => Err(DomainError::validation("Invalid or expired token")),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+pub fn issue_account_token(conn: &mut PgConnection, user: &User, purpose: &str, expiration: i64) -> QueryResult<AccountTokenEntity> {
+    let now = Utc::now().naive_utc();
+
+    // only the latest token of a kind stays usable
+    diesel::update(
+        account_tokens::table
+            .filter(account_tokens::user_id.eq(user.id))
+            .filter(account_tokens::purpose.eq(purpose))
+            .filter(account_tokens::used_at.is_null()),
+    )
+    .set(account_tokens::used_at.eq(now))
+    .execute(conn)?;
+
+    let data_token = generate_secret(48);
+    let data_token_hash = hash_token(&data_token);
+    let data_expires_at = now + Duration::seconds(expiration);
+    let new_token = AccountTokenNew {
+        user_id: &user.id,
+        purpose,
+        token_hash: &data_token_hash,
+        expires_at: data_expires_at,
+    };
+    diesel::insert_into(account_tokens::table).values(&new_token).execute(conn)?;
+
+    Ok(AccountTokenEntity::new(
+        user.id.to_string(),
+        user.email.clone(),
+        user.username.clone(),
+        data_token,
+        data_expires_at,
+    ))
+}
+
+pub fn consume_account_token(conn: &mut PgConnection, token: &str, purpose: &str) -> QueryResult<Option<AccountToken>> {
+    let now = Utc::now().naive_utc();
+    let found = account_tokens::table
+        .filter(account_tokens::token_hash.eq(hash_token(token)))
+        .filter(account_tokens::purpose.eq(purpose))
+        .filter(account_tokens::used_at.is_null())
+        .filter(account_tokens::expires_at.gt(now))
+        .select(AccountToken::as_select())
+        .for_update()
+        .first::<AccountToken>(conn)
+        .optional()?;
+
+    if let Some(data) = &found {
+        diesel::update(account_tokens::table.filter(account_tokens::id.eq(data.id)))
+            .set(account_tokens::used_at.eq(now))
+            .execute(conn)?;
+    }
+
+    Ok(found)
+}
@@ -0,0 +1,24 @@
+use crate::domain::api_key_entity::ApiKeyEntity;
+
+use super::api_key_model::ApiKey;
+
+pub struct ApiKeyDbMapper {}
+
+impl ApiKeyDbMapper {
+    // The owner's role is not stored on the key, it is read at use time
+    pub fn to_entity(model: ApiKey, user_role: String) -> ApiKeyEntity {
+        ApiKeyEntity {
+            id: model.id.to_string(),
+            user_id: model.user_id.to_string(),
+            user_role,
+            name: model.name,
+            prefix: model.prefix,
+            key: None,
+            permissions: model.permissions,
+            expires_at: model.expires_at,
+            last_used_at: model.last_used_at,
+            revoked_at: model.revoked_at,
+            created_at: model.created_at,
+        }
+    }
+}
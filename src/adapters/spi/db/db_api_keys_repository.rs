@@ -0,0 +1,165 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::api_keys::api_keys_payloads::{ApiKeyCreatePayload, ApiKeyDataPayload};
+use crate::application::utils::access_control::extractors::claims::Permission;
+use crate::application::utils::token_hash::{generate_secret, hash_token};
+use crate::{application::repositories::api_keys_repository_abstract::ApiKeysRepositoryAbstract, domain::api_key_entity::ApiKeyEntity};
+
+use super::api_key_model::*;
+use super::db_api_keys_mappers::ApiKeyDbMapper;
+use super::db_permissions_repository::role_permissions;
+use super::schema::{api_keys, users};
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+pub const API_KEY_PREFIX: &str = "tt_";
+const API_KEY_LAST_USED_PRECISION: i64 = 60;
+
+pub struct ApiKeysRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl ApiKeysRepositoryAbstract for ApiKeysRepository {
+    #[instrument(name = "ApiKeysRepository::create_api_key", skip_all)]
+    async fn create_api_key(&self, api_key_payload: &ApiKeyCreatePayload) -> Result<ApiKeyEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&api_key_payload.user_id.clone().unwrap_or_default())?;
+        let data_name = api_key_payload.name.trim();
+
+        if data_name.is_empty() {
+            return Err(DomainError::validation("Invalid API key name"));
+        }
+
+        let data_role = users::table.filter(users::id.eq(data_user_id)).select(users::role).first::<String>(&mut conn)?;
+
+        // a key can only narrow down what its owner is allowed to do
+        let role_permissions = role_permissions(&mut conn, &data_role)?;
+        let data_permissions = match &api_key_payload.permissions {
+            Some(permissions) => {
+                let mut scoped = Vec::new();
+                for permission in permissions {
+                    let permission = Permission::from_str(permission).map_err(|e| DomainError::validation(&e))?;
+                    if !role_permissions.contains(&permission) {
+                        return Err(DomainError::Forbidden(format!("Permission not granted to your role: {}", permission)));
+                    }
+                    scoped.push(permission.to_string());
+                }
+                scoped
+            }
+            None => role_permissions.iter().map(|permission| permission.to_string()).collect(),
+        };
+
+        let data_expires_at = match api_key_payload.expires_at {
+            Some(millis) => match DateTime::from_timestamp_millis(millis) {
+                Some(datetime) if datetime > Utc::now() => Some(datetime.naive_utc()),
+                _ => return Err(DomainError::validation("Invalid API key expiry")),
+            },
+            None => None,
+        };
+
+        let data_key = format!("{}{}", API_KEY_PREFIX, generate_secret(40));
+        let data_prefix = data_key[..API_KEY_PREFIX.len() + 6].to_string();
+        let data_key_hash = hash_token(&data_key);
+        let new_api_key = ApiKeyNew {
+            user_id: &data_user_id,
+            name: data_name,
+            prefix: &data_prefix,
+            key_hash: &data_key_hash,
+            permissions: &data_permissions,
+            expires_at: data_expires_at,
+        };
+
+        let result = diesel::insert_into(api_keys::table).values(&new_api_key).returning(ApiKey::as_returning()).get_result(&mut conn);
+
+        match result {
+            Ok(model) => {
+                let mut entity = ApiKeyDbMapper::to_entity(model, data_role);
+                entity.key = Some(data_key);
+                Ok(entity)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "ApiKeysRepository::get_all_api_keys", skip_all)]
+    async fn get_all_api_keys(&self, api_key_payload: &ApiKeyDataPayload) -> Result<Vec<ApiKeyEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&api_key_payload.user_id.clone().unwrap_or_default())?;
+
+        let results = api_keys::table
+            .inner_join(users::table)
+            .filter(api_keys::user_id.eq(data_user_id))
+            .filter(api_keys::revoked_at.is_null())
+            .order(api_keys::created_at.desc())
+            .select((ApiKey::as_select(), users::role))
+            .load::<(ApiKey, String)>(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models
+                .into_iter()
+                .map(|(model, data_role)| ApiKeyDbMapper::to_entity(model, data_role))
+                .collect::<Vec<ApiKeyEntity>>()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "ApiKeysRepository::revoke_api_key", skip_all)]
+    async fn revoke_api_key(&self, api_key_payload: &ApiKeyDataPayload) -> Result<ApiKeyEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_api_key_id = Uuid::parse_str(&api_key_payload.api_key_id.clone().unwrap_or_default())?;
+        let data_user_id = Uuid::parse_str(&api_key_payload.user_id.clone().unwrap_or_default())?;
+
+        let target = api_keys::table
+            .filter(api_keys::id.eq(data_api_key_id))
+            .filter(api_keys::user_id.eq(data_user_id))
+            .filter(api_keys::revoked_at.is_null());
+        let result = diesel::update(target)
+            .set(api_keys::revoked_at.eq(Utc::now().naive_utc()))
+            .returning(ApiKey::as_returning())
+            .get_result(&mut conn)
+            .optional();
+
+        match result {
+            Ok(Some(model)) => Ok(ApiKeyDbMapper::to_entity(model, String::new())),
+            Ok(None) => Err(DomainError::NotFound(String::from("API key not found"))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "ApiKeysRepository::authenticate_api_key", skip_all)]
+    async fn authenticate_api_key(&self, key: &str) -> Result<ApiKeyEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let now = Utc::now().naive_utc();
+
+        let found = api_keys::table
+            .inner_join(users::table)
+            .filter(api_keys::key_hash.eq(hash_token(key)))
+            .filter(api_keys::revoked_at.is_null())
+            .filter(api_keys::expires_at.is_null().or(api_keys::expires_at.gt(now)))
+            .select((ApiKey::as_select(), users::role))
+            .first::<(ApiKey, String)>(&mut conn)
+            .optional()?;
+
+        let (model, data_role) = match found {
+            Some(data) => data,
+            None => return Err(DomainError::Unauthorized(String::from("Invalid or expired API key"))),
+        };
+
+        // last use is tracked at minute precision to spare a write on every request
+        if model.last_used_at.is_none_or(|last_used_at| last_used_at < now - Duration::seconds(API_KEY_LAST_USED_PRECISION)) {
+            diesel::update(api_keys::table.filter(api_keys::id.eq(model.id)))
+                .set(api_keys::last_used_at.eq(now))
+                .execute(&mut conn)?;
+        }
+
+        // the permission guard intersects the key's scope with the owner's current role on every request
+        Ok(ApiKeyDbMapper::to_entity(model, data_role))
+    }
+}
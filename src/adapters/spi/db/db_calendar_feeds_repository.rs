@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use diesel::{pg::upsert::excluded, prelude::*};
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::application::utils::token_hash::{generate_secret, hash_token};
+use crate::domain::error::DomainError;
+use crate::{application::repositories::calendar_feeds_repository_abstract::CalendarFeedsRepositoryAbstract, domain::calendar_feed_entity::CalendarFeedEntity};
+
+use super::calendar_feed_model::{CalendarFeed, CalendarFeedNew};
+use super::schema::calendar_feeds;
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+// Long enough to be unguessable, short enough for a URL
+const CALENDAR_FEED_TOKEN_LENGTH: usize = 40;
+
+pub struct CalendarFeedsRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl CalendarFeedsRepositoryAbstract for CalendarFeedsRepository {
+    #[instrument(name = "CalendarFeedsRepository::regenerate_feed", skip_all)]
+    async fn regenerate_feed(&self, user_id: &str) -> Result<CalendarFeedEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(user_id)?;
+        let data_token = generate_secret(CALENDAR_FEED_TOKEN_LENGTH);
+        let data_token_hash = hash_token(&data_token);
+        let new_feed = CalendarFeedNew {
+            user_id: &data_user_id,
+            token_hash: &data_token_hash,
+        };
+
+        let result = diesel::insert_into(calendar_feeds::table)
+            .values(&new_feed)
+            .on_conflict(calendar_feeds::user_id)
+            .do_update()
+            .set((calendar_feeds::token_hash.eq(excluded(calendar_feeds::token_hash)), calendar_feeds::created_at.eq(diesel::dsl::now)))
+            .returning(CalendarFeed::as_returning())
+            .get_result(&mut conn);
+
+        match result {
+            Ok(model) => Ok(CalendarFeedEntity::new(model.user_id.to_string(), Some(data_token), model.created_at)),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "CalendarFeedsRepository::get_feed_by_token", skip_all)]
+    async fn get_feed_by_token(&self, token: &str) -> Result<CalendarFeedEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let result = calendar_feeds::table
+            .filter(calendar_feeds::token_hash.eq(hash_token(token)))
+            .select(CalendarFeed::as_select())
+            .first(&mut conn)
+            .optional();
+
+        match result {
+            Ok(Some(model)) => Ok(CalendarFeedEntity::new(model.user_id.to_string(), None, model.created_at)),
+            Ok(None) => Err(DomainError::NotFound(String::from("Calendar feed not found"))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "CalendarFeedsRepository::revoke_feed", skip_all)]
+    async fn revoke_feed(&self, user_id: &str) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(user_id)?;
+
+        let result = diesel::delete(calendar_feeds::table.filter(calendar_feeds::user_id.eq(data_user_id))).execute(&mut conn);
+
+        match result {
+            Ok(0) => Err(DomainError::NotFound(String::from("Calendar feed not found"))),
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
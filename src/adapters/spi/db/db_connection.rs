@@ -1,19 +1,73 @@
 use diesel::pg::PgConnection;
-use diesel::r2d2::{self, ConnectionManager};
+use diesel::r2d2::{
+    self,
+    event::{CheckoutEvent, TimeoutEvent},
+    ConnectionManager, HandleEvent,
+};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations};
+use std::sync::{Arc, OnceLock};
+
+use crate::application::utils::metrics::Metrics;
+
+// Compiled into the binary so the readiness check can tell whether the database is behind
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 
 type DbPool = r2d2::Pool<ConnectionManager<PgConnection>>;
 
 pub struct DbConnection {
     pub db_name: String,
+    database_url: String,
+    pool_max_size: u32,
+    // opened on first use and shared by every repository afterwards
+    pool: OnceLock<DbPool>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+// Feeds checkout wait times and timeouts to the metrics
+#[derive(Debug)]
+struct PoolMetricsHandler {
+    metrics: Arc<Metrics>,
+}
+
+impl HandleEvent for PoolMetricsHandler {
+    fn handle_checkout(&self, event: CheckoutEvent) {
+        self.metrics.record_db_pool_wait(event.duration());
+    }
+
+    fn handle_timeout(&self, _event: TimeoutEvent) {
+        self.metrics.record_db_pool_timeout();
+    }
 }
 
 impl DbConnection {
-    pub fn get_pool(&self) -> DbPool {
-        let database_url = dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set");
-        let database = format!("{}/{}", database_url, &self.db_name);
+    pub fn new(database_url: &str, db_name: &str, pool_max_size: u32) -> Self {
+        DbConnection {
+            db_name: db_name.to_string(),
+            database_url: database_url.to_string(),
+            pool_max_size,
+            pool: OnceLock::new(),
+            metrics: None,
+        }
+    }
 
-        let manager = ConnectionManager::<PgConnection>::new(&database);
+    pub fn with_metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    pub fn get_pool(&self) -> DbPool {
+        self.pool
+            .get_or_init(|| {
+                let database = format!("{}/{}", self.database_url, &self.db_name);
+                let manager = ConnectionManager::<PgConnection>::new(&database);
 
-        r2d2::Pool::new(manager).unwrap()
+                // connections are opened on demand, an unreachable database fails the checkout rather than the whole pool
+                let mut builder = r2d2::Pool::builder().max_size(self.pool_max_size);
+                if let Some(metrics) = &self.metrics {
+                    builder = builder.event_handler(Box::new(PoolMetricsHandler { metrics: metrics.clone() }));
+                }
+                builder.build_unchecked(manager)
+            })
+            .clone()
     }
 }
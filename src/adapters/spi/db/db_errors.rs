@@ -0,0 +1,49 @@
+use std::error::Error;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+
+use crate::domain::error::DomainError;
+
+// Missing rows and broken constraints are the caller's problem, anything else is the database's
+impl From<DieselError> for DomainError {
+    fn from(error: DieselError) -> Self {
+        match error {
+            DieselError::NotFound => DomainError::NotFound(String::from("Resource not found")),
+            DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => DomainError::Conflict(String::from("Resource already exists")),
+            DieselError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _) => DomainError::Conflict(String::from("Resource is referenced by another one")),
+            error => DomainError::Infrastructure(Box::new(error)),
+        }
+    }
+}
+
+// Ids come from the request path or body, one that doesn't parse can't match a row
+impl From<uuid::Error> for DomainError {
+    fn from(_: uuid::Error) -> Self {
+        DomainError::NotFound(String::from("Resource not found"))
+    }
+}
+
+impl From<r2d2::Error> for DomainError {
+    fn from(error: r2d2::Error) -> Self {
+        DomainError::Infrastructure(Box::new(error))
+    }
+}
+
+// The migration harness reports its failures boxed
+impl From<Box<dyn Error + Send + Sync>> for DomainError {
+    fn from(error: Box<dyn Error + Send + Sync>) -> Self {
+        DomainError::Infrastructure(error)
+    }
+}
+
+impl From<bcrypt::BcryptError> for DomainError {
+    fn from(error: bcrypt::BcryptError) -> Self {
+        DomainError::Infrastructure(Box::new(error))
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for DomainError {
+    fn from(error: jsonwebtoken::errors::Error) -> Self {
+        DomainError::Infrastructure(Box::new(error))
+    }
+}
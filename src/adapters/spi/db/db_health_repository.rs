@@ -0,0 +1,36 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use diesel_migrations::MigrationHarness;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::instrument;
+
+use crate::domain::error::DomainError;
+use crate::application::repositories::health_repository_abstract::HealthRepositoryAbstract;
+
+use super::db_connection::MIGRATIONS;
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+// Probes are retried by the orchestrator, waiting for the pool's full timeout would only delay the answer
+const CHECKOUT_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct HealthRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl HealthRepositoryAbstract for HealthRepository {
+    #[instrument(name = "HealthRepository::ping_database", skip_all)]
+    async fn ping_database(&self) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get_timeout(CHECKOUT_TIMEOUT)?;
+        diesel::sql_query("SELECT 1").execute(&mut conn)?;
+        Ok(())
+    }
+
+    #[instrument(name = "HealthRepository::get_pending_migrations", skip_all)]
+    async fn get_pending_migrations(&self) -> Result<Vec<String>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get_timeout(CHECKOUT_TIMEOUT)?;
+        let pending = conn.pending_migrations(MIGRATIONS)?;
+        Ok(pending.iter().map(|migration| migration.name().version().to_string()).collect())
+    }
+}
@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    prelude::*,
+    result::{DatabaseErrorKind, Error as DieselError},
+};
+use std::{sync::Arc, time::Duration};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::{
+    application::repositories::import_jobs_repository_abstract::ImportJobsRepositoryAbstract,
+    domain::import_entity::{ImportJobEntity, ImportJobState, ImportSkippedItem, ImportSource},
+};
+
+use super::import_job_model::{ImportJob, ImportJobNew, ImportSkippedItemModel};
+use super::schema::{import_jobs, import_skipped_items};
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+// A running import writes its progress every chunk of tasks. One silent for that long was cut short by a stopped
+// instance, it no longer holds the user's slot.
+const INTERRUPTED_AFTER: Duration = Duration::from_secs(15 * 60);
+
+pub struct ImportJobsRepository {
+    pub db_connection: Arc<DbConnection>,
+    // finished jobs are kept that long for their report
+    pub retention: Duration,
+}
+
+#[async_trait(?Send)]
+impl ImportJobsRepositoryAbstract for ImportJobsRepository {
+    #[instrument(name = "ImportJobsRepository::queue_import_job", skip_all)]
+    async fn queue_import_job(&self, user_id: &str, source: ImportSource, skipped: &[ImportSkippedItem]) -> Result<ImportJobEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(user_id)?;
+        let now = Utc::now().naive_utc();
+        let unfinished: Vec<&str> = ImportJobState::UNFINISHED.iter().map(ImportJobState::as_str).collect();
+
+        let result = conn.transaction::<_, DieselError, _>(|conn| {
+            diesel::update(
+                import_jobs::table
+                    .filter(import_jobs::user_id.eq(data_user_id))
+                    .filter(import_jobs::state.eq_any(&unfinished))
+                    .filter(import_jobs::updated_at.lt(before(now, INTERRUPTED_AFTER))),
+            )
+            .set((
+                import_jobs::state.eq(ImportJobState::Failed.as_str()),
+                import_jobs::error.eq("Interrupted before the end"),
+                import_jobs::updated_at.eq(now),
+            ))
+            .execute(conn)?;
+            diesel::delete(
+                import_jobs::table
+                    .filter(import_jobs::state.ne_all(&unfinished))
+                    .filter(import_jobs::updated_at.lt(before(now, self.retention))),
+            )
+            .execute(conn)?;
+
+            let new_job = ImportJobNew {
+                user_id: &data_user_id,
+                source: source.as_str(),
+            };
+            let model = diesel::insert_into(import_jobs::table).values(&new_job).returning(ImportJob::as_returning()).get_result(conn)?;
+            let skipped_models: Vec<ImportSkippedItemModel> = skipped
+                .iter()
+                .enumerate()
+                .map(|(position, skipped)| ImportSkippedItemModel {
+                    job_id: model.id,
+                    position: position as i32,
+                    item: skipped.item.clone(),
+                    reason: skipped.reason.clone(),
+                })
+                .collect();
+            if !skipped_models.is_empty() {
+                diesel::insert_into(import_skipped_items::table).values(&skipped_models).execute(conn)?;
+            }
+            Ok((model, skipped_models))
+        });
+
+        match result {
+            Ok((model, skipped_models)) => to_entity(model, skipped_models),
+            Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => Err(DomainError::Conflict(String::from("An import is already running, wait for it to finish"))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "ImportJobsRepository::get_import_job", skip_all)]
+    async fn get_import_job(&self, job_id: &str, user_id: &str) -> Result<ImportJobEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let not_found = || DomainError::NotFound(String::from("Import not found"));
+        let data_job_id = Uuid::parse_str(job_id).map_err(|_| not_found())?;
+        let data_user_id = Uuid::parse_str(user_id)?;
+        let unfinished: Vec<&str> = ImportJobState::UNFINISHED.iter().map(ImportJobState::as_str).collect();
+
+        let model = import_jobs::table
+            .filter(import_jobs::id.eq(data_job_id))
+            .filter(import_jobs::user_id.eq(data_user_id))
+            .filter(import_jobs::state.eq_any(&unfinished).or(import_jobs::updated_at.ge(before(Utc::now().naive_utc(), self.retention))))
+            .select(ImportJob::as_select())
+            .first(&mut conn)
+            .optional()?
+            .ok_or_else(not_found)?;
+        let skipped_models = import_skipped_items::table
+            .filter(import_skipped_items::job_id.eq(data_job_id))
+            .order(import_skipped_items::position.asc())
+            .select(ImportSkippedItemModel::as_select())
+            .load(&mut conn)?;
+
+        to_entity(model, skipped_models)
+    }
+
+    #[instrument(name = "ImportJobsRepository::start_import_job", skip_all)]
+    async fn start_import_job(&self, job_id: &str, total: usize) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_job_id = Uuid::parse_str(job_id)?;
+
+        diesel::update(import_jobs::table.find(data_job_id))
+            .set((
+                import_jobs::state.eq(ImportJobState::Running.as_str()),
+                import_jobs::total.eq(total as i32),
+                import_jobs::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    #[instrument(name = "ImportJobsRepository::add_import_project", skip_all)]
+    async fn add_import_project(&self, job_id: &str, project_id: &str) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_job_id = Uuid::parse_str(job_id)?;
+        let data_project_id = Uuid::parse_str(project_id)?;
+
+        diesel::update(import_jobs::table.find(data_job_id))
+            .set((
+                import_jobs::project_ids.eq(import_jobs::project_ids.concat(vec![data_project_id])),
+                import_jobs::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    #[instrument(name = "ImportJobsRepository::add_import_tasks", skip_all)]
+    async fn add_import_tasks(&self, job_id: &str, task_ids: &[String]) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_job_id = Uuid::parse_str(job_id)?;
+        let data_task_ids = task_ids.iter().map(|task_id| Uuid::parse_str(task_id)).collect::<Result<Vec<Uuid>, _>>()?;
+
+        diesel::update(import_jobs::table.find(data_job_id))
+            .set((
+                import_jobs::processed.eq(import_jobs::processed + data_task_ids.len() as i32),
+                import_jobs::task_ids.eq(import_jobs::task_ids.concat(data_task_ids)),
+                import_jobs::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    #[instrument(name = "ImportJobsRepository::complete_import_job", skip_all)]
+    async fn complete_import_job(&self, job_id: &str) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_job_id = Uuid::parse_str(job_id)?;
+
+        diesel::update(import_jobs::table.find(data_job_id))
+            .set((import_jobs::state.eq(ImportJobState::Completed.as_str()), import_jobs::updated_at.eq(diesel::dsl::now)))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    #[instrument(name = "ImportJobsRepository::fail_import_job", skip_all)]
+    async fn fail_import_job(&self, job_id: &str, message: &str) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_job_id = Uuid::parse_str(job_id)?;
+
+        diesel::update(import_jobs::table.find(data_job_id))
+            .set((
+                import_jobs::state.eq(ImportJobState::Failed.as_str()),
+                import_jobs::error.eq(message),
+                import_jobs::updated_at.eq(diesel::dsl::now),
+            ))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+}
+
+fn before(now: NaiveDateTime, duration: Duration) -> NaiveDateTime {
+    now - chrono::Duration::from_std(duration).unwrap_or_default()
+}
+
+// The columns only hold known labels, checked by the table
+fn to_entity(model: ImportJob, skipped_models: Vec<ImportSkippedItemModel>) -> Result<ImportJobEntity, DomainError> {
+    let source = model.source.parse::<ImportSource>().map_err(|e| DomainError::Infrastructure(e.into()))?;
+    let state = model.state.parse::<ImportJobState>().map_err(|e| DomainError::Infrastructure(e.into()))?;
+
+    Ok(ImportJobEntity {
+        id: model.id.to_string(),
+        user_id: model.user_id.to_string(),
+        source,
+        state,
+        total: model.total as usize,
+        processed: model.processed as usize,
+        project_ids: model.project_ids.iter().map(Uuid::to_string).collect(),
+        task_ids: model.task_ids.iter().map(Uuid::to_string).collect(),
+        skipped: skipped_models.into_iter().map(|skipped| ImportSkippedItem::new(&skipped.item, &skipped.reason)).collect(),
+        error: model.error,
+        updated_at: model.updated_at,
+        created_at: model.created_at,
+    })
+}
@@ -0,0 +1,275 @@
+use async_trait::async_trait;
+use chrono::{Duration, NaiveDateTime, Utc};
+use diesel::{
+    dsl::{case_when, Eq, Filter},
+    prelude::*,
+    sql_types::Integer,
+};
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::users::users_payloads::{UserLoginAttemptPayload, UserUnlockAccountPayload, UserUnlockPayload};
+use crate::{
+    application::repositories::login_attempts_repository_abstract::LoginAttemptsRepositoryAbstract,
+    domain::{account_token_entity::AccountTokenEntity, login_throttle_entity::LoginThrottleEntity},
+};
+
+use super::account_token_model::ACCOUNT_UNLOCK_PURPOSE;
+use super::db_account_tokens_repository::{consume_account_token, issue_account_token};
+use super::login_throttle_model::*;
+use super::schema::{audit_events, login_throttles, users};
+use super::user_model::User;
+use crate::adapters::spi::db::db_connection::DbConnection;
+use crate::infrastructure::settings::{LoginThrottleSettings, TokenSettings};
+
+const ACCOUNT_SCOPE: &str = "account";
+const IP_SCOPE: &str = "ip";
+
+struct ThrottlePolicy {
+    // failures before every attempt waits, the wait doubles with each further failure
+    delay_after: i32,
+    lock_after: i32,
+}
+
+const ACCOUNT_POLICY: ThrottlePolicy = ThrottlePolicy { delay_after: 3, lock_after: 10 };
+// an IP may legitimately host many users, it gets more room than a single account
+const IP_POLICY: ThrottlePolicy = ThrottlePolicy { delay_after: 10, lock_after: 50 };
+
+pub const ACCOUNT_LOCKED_EVENT: &str = "account_locked";
+pub const ACCOUNT_UNLOCKED_EVENT: &str = "account_unlocked";
+pub const IP_BLOCKED_EVENT: &str = "ip_blocked";
+
+pub struct LoginAttemptsRepository {
+    pub db_connection: Arc<DbConnection>,
+    pub token_settings: TokenSettings,
+    pub throttle_settings: LoginThrottleSettings,
+}
+
+#[async_trait(?Send)]
+impl LoginAttemptsRepositoryAbstract for LoginAttemptsRepository {
+    #[instrument(name = "LoginAttemptsRepository::count_login_attempt", skip_all)]
+    async fn count_login_attempt(&self, attempt_payload: &UserLoginAttemptPayload) -> Result<LoginThrottleEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let now = Utc::now().naive_utc();
+        let mut entity = LoginThrottleEntity::default();
+        let subjects = throttle_subjects(attempt_payload);
+
+        // attempts refused by a lock are not counted, a locked IP must not run the counter of the account up
+        for (scope, subject, _) in &subjects {
+            let locked_until = throttle_of(scope, subject).select(login_throttles::locked_until).first::<Option<NaiveDateTime>>(&mut conn).optional()?.flatten();
+            if locked_until.is_some_and(|locked_until| locked_until > now) {
+                entity.locked_until = entity.locked_until.max(locked_until);
+            }
+        }
+        if entity.locked_until.is_some() {
+            return Ok(entity);
+        }
+
+        for (scope, subject, policy) in subjects {
+            match count_attempt(&mut conn, scope, &subject, &self.throttle_settings, now)? {
+                Some(throttle) => {
+                    // the failures before this attempt set its delay
+                    entity.delay_seconds = entity.delay_seconds.max(delay_seconds(throttle.failed_count - 1, &policy, &self.throttle_settings));
+                    entity.exhausted |= throttle.failed_count > policy.lock_after;
+                }
+                None => {
+                    let locked_until = throttle_of(scope, &subject).select(login_throttles::locked_until).first::<Option<NaiveDateTime>>(&mut conn)?;
+                    entity.locked_until = entity.locked_until.max(locked_until);
+                }
+            }
+        }
+
+        Ok(entity)
+    }
+
+    #[instrument(name = "LoginAttemptsRepository::record_failed_login", skip_all)]
+    async fn record_failed_login(&self, attempt_payload: &UserLoginAttemptPayload) -> Result<Option<AccountTokenEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut unlock_token = None;
+
+            for (scope, subject, policy) in throttle_subjects(attempt_payload) {
+                if !lock_if_exhausted(conn, scope, &subject, &policy, &self.throttle_settings)? {
+                    continue;
+                }
+
+                if scope == IP_SCOPE {
+                    record_audit_event(conn, IP_BLOCKED_EVENT, None, None, attempt_payload.ip_address.as_deref(), None)?;
+                    continue;
+                }
+
+                let user = users::table
+                    .filter(users::email.eq(&attempt_payload.email))
+                    .select(User::as_select())
+                    .first::<User>(conn)
+                    .optional()?;
+                let details = format!("email: {}", subject);
+                record_audit_event(
+                    conn,
+                    ACCOUNT_LOCKED_EVENT,
+                    user.as_ref().map(|user| &user.id),
+                    None,
+                    attempt_payload.ip_address.as_deref(),
+                    Some(&details),
+                )?;
+                // only a registered owner can be told, unknown addresses are locked silently all the same
+                if let Some(user) = user {
+                    unlock_token = Some(issue_account_token(conn, &user, ACCOUNT_UNLOCK_PURPOSE, self.token_settings.account_unlock_ttl as i64)?);
+                }
+            }
+
+            Ok(unlock_token)
+        });
+
+        match result {
+            Ok(unlock_token) => Ok(unlock_token),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "LoginAttemptsRepository::record_successful_login", skip_all)]
+    async fn record_successful_login(&self, attempt_payload: &UserLoginAttemptPayload) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        // the IP counter is kept, one good password must not clear failures against other accounts. Only the attempt
+        // counted in advance is given back.
+        clear_account_throttle(&mut conn, &attempt_payload.email)?;
+        if let Some(ip_address) = &attempt_payload.ip_address {
+            diesel::update(throttle_of(IP_SCOPE, ip_address).filter(login_throttles::failed_count.gt(0)))
+                .set(login_throttles::failed_count.eq(login_throttles::failed_count - 1))
+                .execute(&mut conn)?;
+        }
+        Ok(())
+    }
+
+    #[instrument(name = "LoginAttemptsRepository::unlock_account", skip_all)]
+    async fn unlock_account(&self, user_payload: &UserUnlockAccountPayload) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let token = match consume_account_token(conn, &user_payload.token, ACCOUNT_UNLOCK_PURPOSE)? {
+                Some(token) => token,
+                None => return Ok(false),
+            };
+
+            let user = users::table.filter(users::id.eq(token.user_id)).select(User::as_select()).first::<User>(conn)?;
+            clear_account_throttle(conn, &user.email)?;
+            record_audit_event(conn, ACCOUNT_UNLOCKED_EVENT, Some(&user.id), None, None, Some("unlock email"))?;
+            Ok(true)
+        });
+
+        match result {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DomainError::validation("Invalid or expired token")),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "LoginAttemptsRepository::unlock_user", skip_all)]
+    async fn unlock_user(&self, user_payload: &UserUnlockPayload) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&user_payload.user_id)?;
+        let data_unlocked_by = user_payload.unlocked_by.as_deref().map(Uuid::parse_str).transpose()?;
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let user = match users::table.filter(users::id.eq(data_user_id)).select(User::as_select()).first::<User>(conn).optional()? {
+                Some(user) => user,
+                None => return Ok(false),
+            };
+
+            clear_account_throttle(conn, &user.email)?;
+            record_audit_event(conn, ACCOUNT_UNLOCKED_EVENT, Some(&user.id), data_unlocked_by.as_ref(), None, Some("administrator"))?;
+            Ok(true)
+        });
+
+        match result {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(DomainError::NotFound(String::from("User not found"))),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+pub fn record_audit_event(conn: &mut PgConnection, event: &str, user_id: Option<&Uuid>, actor_id: Option<&Uuid>, ip_address: Option<&str>, details: Option<&str>) -> QueryResult<()> {
+    log::warn!(target: "audit", "{} user: {:?}, actor: {:?}, ip: {:?}, {}", event, user_id, actor_id, ip_address, details.unwrap_or_default());
+
+    let new_event = AuditEventNew {
+        event,
+        user_id,
+        actor_id,
+        ip_address,
+        details,
+    };
+    diesel::insert_into(audit_events::table).values(&new_event).execute(conn)?;
+    Ok(())
+}
+
+// Emails are compared case-insensitively so spelling variants share one counter
+fn throttle_subjects(attempt_payload: &UserLoginAttemptPayload) -> Vec<(&'static str, String, ThrottlePolicy)> {
+    let mut subjects = vec![(ACCOUNT_SCOPE, attempt_payload.email.trim().to_lowercase(), ACCOUNT_POLICY)];
+    if let Some(ip_address) = &attempt_payload.ip_address {
+        subjects.push((IP_SCOPE, ip_address.clone(), IP_POLICY));
+    }
+    subjects
+}
+
+type ThrottleOf<'a> = Filter<Filter<login_throttles::table, Eq<login_throttles::scope, &'a str>>, Eq<login_throttles::subject, &'a str>>;
+
+fn throttle_of<'a>(scope: &'a str, subject: &'a str) -> ThrottleOf<'a> {
+    login_throttles::table.filter(login_throttles::scope.eq(scope)).filter(login_throttles::subject.eq(subject))
+}
+
+fn delay_seconds(failed_count: i32, policy: &ThrottlePolicy, throttle_settings: &LoginThrottleSettings) -> u64 {
+    if failed_count < policy.delay_after {
+        return 0;
+    }
+    let exponent = (failed_count - policy.delay_after).min(16) as u32;
+    2u64.pow(exponent).min(throttle_settings.max_delay)
+}
+
+// Counts the attempt in one statement and returns the counter, `None` while the subject is locked. Failures older than
+// the window are forgotten.
+fn count_attempt(conn: &mut PgConnection, scope: &str, subject: &str, throttle_settings: &LoginThrottleSettings, now: NaiveDateTime) -> QueryResult<Option<LoginThrottle>> {
+    let new_throttle = LoginThrottleNew {
+        scope,
+        subject,
+        failed_count: 0,
+        last_failed_at: now,
+    };
+    diesel::insert_into(login_throttles::table).values(&new_throttle).on_conflict_do_nothing().execute(conn)?;
+
+    let window_start = now - Duration::seconds(throttle_settings.failure_window as i64);
+    diesel::update(throttle_of(scope, subject).filter(login_throttles::locked_until.is_null().or(login_throttles::locked_until.le(now))))
+        .set((
+            login_throttles::failed_count.eq(case_when::<_, _, Integer>(login_throttles::last_failed_at.lt(window_start), 1).otherwise(login_throttles::failed_count + 1)),
+            login_throttles::last_failed_at.eq(now),
+        ))
+        .returning(LoginThrottle::as_returning())
+        .get_result(conn)
+        .optional()
+}
+
+// Locks a counter the failed attempt brought to the limit, returns whether it did. The counter is set back under the
+// limit: once the lock expires the subject gets one more attempt, a failure locks it again.
+fn lock_if_exhausted(conn: &mut PgConnection, scope: &str, subject: &str, policy: &ThrottlePolicy, throttle_settings: &LoginThrottleSettings) -> QueryResult<bool> {
+    let now = Utc::now().naive_utc();
+    let locked = diesel::update(
+        throttle_of(scope, subject)
+            .filter(login_throttles::failed_count.ge(policy.lock_after))
+            .filter(login_throttles::locked_until.is_null().or(login_throttles::locked_until.le(now))),
+    )
+    .set((
+        login_throttles::failed_count.eq(policy.lock_after - 1),
+        login_throttles::locked_until.eq(now + Duration::seconds(throttle_settings.lock_duration as i64)),
+    ))
+    .execute(conn)?;
+
+    Ok(locked > 0)
+}
+
+fn clear_account_throttle(conn: &mut PgConnection, email: &str) -> QueryResult<usize> {
+    diesel::delete(throttle_of(ACCOUNT_SCOPE, &email.trim().to_lowercase())).execute(conn)
+}
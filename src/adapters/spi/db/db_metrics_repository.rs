@@ -0,0 +1,48 @@
+use async_trait::async_trait;
+use diesel::dsl::count_star;
+use diesel::prelude::*;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::domain::error::DomainError;
+use crate::application::repositories::metrics_repository_abstract::MetricsRepositoryAbstract;
+use crate::domain::metrics_entity::{DbPoolStatsEntity, TaskStatusCountEntity};
+use crate::domain::task_entity::TaskStatus;
+
+use super::schema::tasks;
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+pub struct MetricsRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl MetricsRepositoryAbstract for MetricsRepository {
+    #[instrument(name = "MetricsRepository::get_db_pool_stats", skip_all)]
+    async fn get_db_pool_stats(&self) -> Result<DbPoolStatsEntity, DomainError> {
+        let pool = self.db_connection.get_pool();
+        let state = pool.state();
+
+        Ok(DbPoolStatsEntity {
+            connections: state.connections,
+            idle_connections: state.idle_connections,
+            max_size: pool.max_size(),
+        })
+    }
+
+    #[instrument(name = "MetricsRepository::count_open_tasks_by_status", skip_all)]
+    async fn count_open_tasks_by_status(&self) -> Result<Vec<TaskStatusCountEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let counts = tasks::table
+            .filter(tasks::status.ne(TaskStatus::Completed))
+            .group_by(tasks::status)
+            .select((tasks::status, count_star()))
+            .load::<(TaskStatus, i64)>(&mut conn)?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(status, count)| TaskStatusCountEntity::new(status.to_string(), count))
+            .collect())
+    }
+}
@@ -0,0 +1,341 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::mfa::mfa_payloads::{MfaCodePayload, MfaLoginPayload, MfaRoleRequirementPayload};
+use crate::adapters::api::users::users_payloads::UserIdPayload;
+use crate::application::utils::access_control::auth_usecase::AuthUseCase;
+use crate::application::utils::access_control::extractors::claims::Permission;
+use crate::application::utils::token_hash::{generate_secret, hash_token};
+use crate::application::utils::totp::{generate_totp_secret, otpauth_uri, verify_totp_code};
+use crate::{
+    application::repositories::mfa_repository_abstract::MfaRepositoryAbstract,
+    domain::{
+        mfa_entity::{MfaEnrollmentEntity, MfaRecoveryCodesEntity, MfaRoleRequirementEntity},
+        user_entity::UserEntity,
+    },
+};
+
+use super::db_permissions_repository::role_permissions;
+use super::db_users_repository::issue_login;
+use super::mfa_model::*;
+use super::schema::{mfa_challenges, mfa_recovery_codes, mfa_role_requirements, user_mfa, users};
+use super::session_model::UserSessionNew;
+use super::user_model::User;
+use crate::adapters::spi::db::db_connection::DbConnection;
+use crate::infrastructure::settings::TokenSettings;
+
+const MFA_CHALLENGE_EXPIRATION: i64 = 300;
+const MFA_CHALLENGE_MAX_ATTEMPTS: i32 = 5;
+const MFA_RECOVERY_CODES_COUNT: usize = 10;
+
+pub struct MfaRepository {
+    pub db_connection: Arc<DbConnection>,
+    pub token_settings: TokenSettings,
+}
+
+enum MfaLoginOutcome {
+    LoggedIn(Box<UserEntity>),
+    InvalidCode,
+    InvalidChallenge,
+}
+
+#[async_trait(?Send)]
+impl MfaRepositoryAbstract for MfaRepository {
+    #[instrument(name = "MfaRepository::enroll", skip_all)]
+    async fn enroll(&self, user_payload: &UserIdPayload) -> Result<MfaEnrollmentEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&user_payload.user_id)?;
+
+        let user = users::table.filter(users::id.eq(data_user_id)).select(User::as_select()).first::<User>(&mut conn)?;
+
+        if has_confirmed_mfa(&mut conn, &user.id)? {
+            return Err(DomainError::Conflict(String::from("Two-factor authentication already enabled")));
+        }
+
+        // restarting an unconfirmed enrollment replaces its secret
+        let data_secret = generate_totp_secret();
+        let data_otpauth_uri = otpauth_uri(&data_secret, &user.email).map_err(totp_error)?;
+        let new_mfa = UserMfaNew {
+            user_id: &user.id,
+            secret: &data_secret,
+        };
+        diesel::insert_into(user_mfa::table)
+            .values(&new_mfa)
+            .on_conflict(user_mfa::user_id)
+            .do_update()
+            .set((
+                user_mfa::secret.eq(&data_secret),
+                user_mfa::last_used_step.eq(None::<i64>),
+                user_mfa::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .execute(&mut conn)?;
+
+        Ok(MfaEnrollmentEntity::new(data_secret, data_otpauth_uri))
+    }
+
+    #[instrument(name = "MfaRepository::confirm", skip_all)]
+    async fn confirm(&self, mfa_payload: &MfaCodePayload) -> Result<MfaRecoveryCodesEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&mfa_payload.user_id.clone().unwrap_or_default())?;
+
+        let result = conn.transaction::<_, DomainError, _>(|conn| {
+            let mfa = match user_mfa::table
+                .filter(user_mfa::user_id.eq(data_user_id))
+                .filter(user_mfa::confirmed_at.is_null())
+                .select(UserMfa::as_select())
+                .for_update()
+                .first::<UserMfa>(conn)
+                .optional()?
+            {
+                Some(mfa) => mfa,
+                None => return Ok(None),
+            };
+
+            // only an authenticator code proves the secret was stored, recovery codes do not exist yet
+            let step = match verify_totp_code(&mfa.secret, &mfa_payload.code, mfa.last_used_step).map_err(totp_error)? {
+                Some(step) => step,
+                None => return Ok(None),
+            };
+
+            let now = Utc::now().naive_utc();
+            diesel::update(user_mfa::table.filter(user_mfa::user_id.eq(data_user_id)))
+                .set((user_mfa::confirmed_at.eq(now), user_mfa::last_used_step.eq(step), user_mfa::updated_at.eq(now)))
+                .execute(conn)?;
+
+            Ok(Some(replace_recovery_codes(conn, &data_user_id)?))
+        })?;
+
+        match result {
+            Some(recovery_codes) => Ok(MfaRecoveryCodesEntity::new(recovery_codes)),
+            None => Err(DomainError::validation("Invalid two-factor code")),
+        }
+    }
+
+    #[instrument(name = "MfaRepository::disable", skip_all)]
+    async fn disable(&self, mfa_payload: &MfaCodePayload) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&mfa_payload.user_id.clone().unwrap_or_default())?;
+
+        let user = users::table.filter(users::id.eq(data_user_id)).select(User::as_select()).first::<User>(&mut conn)?;
+
+        if is_mfa_required_for_role(&mut conn, &user.role)? {
+            return Err(DomainError::Forbidden(String::from("Two-factor authentication is required for your role")));
+        }
+
+        let disabled = conn.transaction::<_, DomainError, _>(|conn| {
+            let mfa = match find_confirmed_mfa(conn, &data_user_id)? {
+                Some(mfa) => mfa,
+                None => return Ok(false),
+            };
+
+            if !check_second_factor(conn, &mfa, &mfa_payload.code)? {
+                return Ok(false);
+            }
+
+            diesel::delete(mfa_recovery_codes::table.filter(mfa_recovery_codes::user_id.eq(data_user_id))).execute(conn)?;
+            diesel::delete(user_mfa::table.filter(user_mfa::user_id.eq(data_user_id))).execute(conn)?;
+            Ok(true)
+        })?;
+
+        match disabled {
+            true => Ok(()),
+            false => Err(DomainError::validation("Invalid two-factor code")),
+        }
+    }
+
+    #[instrument(name = "MfaRepository::login", skip_all)]
+    async fn login(&self, mfa_payload: &MfaLoginPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_token_hash = hash_token(&mfa_payload.mfa_token);
+        let now = Utc::now().naive_utc();
+
+        let outcome = conn.transaction::<_, DomainError, _>(|conn| {
+            let challenge = match mfa_challenges::table
+                .filter(mfa_challenges::token_hash.eq(&data_token_hash))
+                .filter(mfa_challenges::used_at.is_null())
+                .filter(mfa_challenges::expires_at.gt(now))
+                .filter(mfa_challenges::attempts.lt(MFA_CHALLENGE_MAX_ATTEMPTS))
+                .select(MfaChallenge::as_select())
+                .for_update()
+                .first::<MfaChallenge>(conn)
+                .optional()?
+            {
+                Some(challenge) => challenge,
+                None => return Ok(MfaLoginOutcome::InvalidChallenge),
+            };
+
+            let mfa = match find_confirmed_mfa(conn, &challenge.user_id)? {
+                Some(mfa) => mfa,
+                None => return Ok(MfaLoginOutcome::InvalidChallenge),
+            };
+
+            // failed attempts are kept, the challenge burns out after a few wrong codes
+            if !check_second_factor(conn, &mfa, &mfa_payload.code)? {
+                diesel::update(mfa_challenges::table.filter(mfa_challenges::id.eq(challenge.id)))
+                    .set(mfa_challenges::attempts.eq(challenge.attempts + 1))
+                    .execute(conn)?;
+                return Ok(MfaLoginOutcome::InvalidCode);
+            }
+
+            diesel::update(mfa_challenges::table.filter(mfa_challenges::id.eq(challenge.id)))
+                .set(mfa_challenges::used_at.eq(now))
+                .execute(conn)?;
+
+            let user = users::table.filter(users::id.eq(challenge.user_id)).select(User::as_select()).first::<User>(conn)?;
+            let new_session = UserSessionNew {
+                user_id: &user.id,
+                device_name: challenge.device_name.as_deref(),
+                ip_address: challenge.ip_address.as_deref(),
+                user_agent: challenge.user_agent.as_deref(),
+            };
+            let permissions = role_permissions(conn, &user.role)?;
+
+            Ok(MfaLoginOutcome::LoggedIn(Box::new(issue_login(conn, &user, &new_session, permissions, &self.token_settings)?)))
+        })?;
+
+        match outcome {
+            MfaLoginOutcome::LoggedIn(entity) => Ok(*entity),
+            MfaLoginOutcome::InvalidCode => Err(DomainError::Unauthorized(String::from("Invalid two-factor code"))),
+            MfaLoginOutcome::InvalidChallenge => Err(DomainError::Unauthorized(String::from("Invalid or expired two-factor challenge"))),
+        }
+    }
+
+    #[instrument(name = "MfaRepository::update_role_requirement", skip_all)]
+    async fn update_role_requirement(&self, mfa_payload: &MfaRoleRequirementPayload) -> Result<MfaRoleRequirementEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_updated_by = match &mfa_payload.updated_by {
+            Some(data) => Some(Uuid::parse_str(data)?),
+            None => None,
+        };
+
+        let requirement = MfaRoleRequirement {
+            role: mfa_payload.role.to_string(),
+            required: mfa_payload.required,
+            updated_by: data_updated_by,
+            updated_at: Utc::now().naive_utc(),
+        };
+
+        let result = diesel::insert_into(mfa_role_requirements::table)
+            .values(&requirement)
+            .on_conflict(mfa_role_requirements::role)
+            .do_update()
+            .set(&requirement)
+            .returning(MfaRoleRequirement::as_returning())
+            .get_result(&mut conn);
+
+        match result {
+            Ok(model) => Ok(MfaRoleRequirementEntity::new(model.role, model.required, model.updated_by.map(|data| data.to_string()), model.updated_at)),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+pub fn has_confirmed_mfa(conn: &mut PgConnection, data_user_id: &Uuid) -> QueryResult<bool> {
+    Ok(find_confirmed_mfa(conn, data_user_id)?.is_some())
+}
+
+pub fn is_mfa_required_for_role(conn: &mut PgConnection, data_role: &str) -> QueryResult<bool> {
+    let required = mfa_role_requirements::table
+        .filter(mfa_role_requirements::role.eq(data_role))
+        .select(mfa_role_requirements::required)
+        .first::<bool>(conn)
+        .optional()?;
+
+    Ok(required.unwrap_or(false))
+}
+
+// Permissions granted on login, withheld until enrollment when the role requires 2FA
+pub fn login_permissions(conn: &mut PgConnection, user: &User) -> QueryResult<(HashSet<Permission>, bool)> {
+    if is_mfa_required_for_role(conn, &user.role)? && !has_confirmed_mfa(conn, &user.id)? {
+        return Ok((AuthUseCase::no(), true));
+    }
+
+    Ok((role_permissions(conn, &user.role)?, false))
+}
+
+pub fn create_mfa_challenge(conn: &mut PgConnection, new_session: &UserSessionNew) -> QueryResult<String> {
+    let data_token = generate_secret(48);
+    let data_token_hash = hash_token(&data_token);
+    let new_challenge = MfaChallengeNew {
+        user_id: new_session.user_id,
+        token_hash: &data_token_hash,
+        device_name: new_session.device_name,
+        ip_address: new_session.ip_address,
+        user_agent: new_session.user_agent,
+        expires_at: Utc::now().naive_utc() + Duration::seconds(MFA_CHALLENGE_EXPIRATION),
+    };
+    diesel::insert_into(mfa_challenges::table).values(&new_challenge).execute(conn)?;
+
+    Ok(data_token)
+}
+
+fn find_confirmed_mfa(conn: &mut PgConnection, data_user_id: &Uuid) -> QueryResult<Option<UserMfa>> {
+    user_mfa::table
+        .filter(user_mfa::user_id.eq(data_user_id))
+        .filter(user_mfa::confirmed_at.is_not_null())
+        .select(UserMfa::as_select())
+        .first::<UserMfa>(conn)
+        .optional()
+}
+
+// A stored secret that can't be read is our fault, not the code's
+fn totp_error(error: String) -> DomainError {
+    DomainError::Infrastructure(error.into())
+}
+
+fn check_second_factor(conn: &mut PgConnection, mfa: &UserMfa, code: &str) -> Result<bool, DomainError> {
+    /*
+     *  second factor:
+     *    - a TOTP code newer than the last accepted one, so a code cannot be replayed
+     *    - or an unused recovery code, consumed on success
+     */
+
+    if let Some(step) = verify_totp_code(&mfa.secret, code, mfa.last_used_step).map_err(totp_error)? {
+        diesel::update(user_mfa::table.filter(user_mfa::user_id.eq(mfa.user_id)))
+            .set(user_mfa::last_used_step.eq(step))
+            .execute(conn)?;
+        return Ok(true);
+    }
+
+    let used = diesel::update(
+        mfa_recovery_codes::table
+            .filter(mfa_recovery_codes::user_id.eq(mfa.user_id))
+            .filter(mfa_recovery_codes::code_hash.eq(hash_token(&normalize_recovery_code(code))))
+            .filter(mfa_recovery_codes::used_at.is_null()),
+    )
+    .set(mfa_recovery_codes::used_at.eq(Utc::now().naive_utc()))
+    .execute(conn)?;
+
+    Ok(used > 0)
+}
+
+fn replace_recovery_codes(conn: &mut PgConnection, data_user_id: &Uuid) -> QueryResult<Vec<String>> {
+    diesel::delete(mfa_recovery_codes::table.filter(mfa_recovery_codes::user_id.eq(data_user_id))).execute(conn)?;
+
+    let codes: Vec<String> = (0..MFA_RECOVERY_CODES_COUNT)
+        .map(|_| {
+            let code = generate_secret(10).to_lowercase();
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect();
+    let new_codes: Vec<MfaRecoveryCodeNew> = codes
+        .iter()
+        .map(|code| MfaRecoveryCodeNew {
+            user_id: data_user_id,
+            code_hash: hash_token(&normalize_recovery_code(code)),
+        })
+        .collect();
+    diesel::insert_into(mfa_recovery_codes::table).values(&new_codes).execute(conn)?;
+
+    Ok(codes)
+}
+
+fn normalize_recovery_code(code: &str) -> String {
+    code.trim().to_lowercase().replace('-', "")
+}
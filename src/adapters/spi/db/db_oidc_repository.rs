@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use bcrypt::{hash, DEFAULT_COST};
+use chrono::{Duration, Utc};
+use diesel::prelude::*;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::oidc::oidc_payloads::{OidcCallbackPayload, OidcIdentityPayload, OidcLoginStatePayload};
+use crate::adapters::api::users::users_payloads::UserRolePayload;
+use crate::application::utils::token_hash::{generate_secret, hash_token};
+use crate::{
+    application::repositories::oidc_repository_abstract::OidcRepositoryAbstract,
+    domain::{oidc_entity::OidcLoginStateEntity, user_entity::UserEntity},
+};
+
+use super::db_users_repository::complete_login;
+use super::oidc_model::*;
+use super::schema::{oidc_login_states, user_identities, users};
+use super::session_model::UserSessionNew;
+use super::user_model::{User, UserProvision};
+use crate::adapters::spi::db::db_connection::DbConnection;
+use crate::infrastructure::settings::TokenSettings;
+
+// Time the user has to sign in at the identity provider
+const LOGIN_STATE_EXPIRATION: i64 = 600;
+
+pub struct OidcRepository {
+    pub db_connection: Arc<DbConnection>,
+    pub token_settings: TokenSettings,
+}
+
+#[async_trait(?Send)]
+impl OidcRepositoryAbstract for OidcRepository {
+    #[instrument(name = "OidcRepository::create_login_state", skip_all)]
+    async fn create_login_state(&self, oidc_payload: &OidcLoginStatePayload) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let now = Utc::now().naive_utc();
+
+        // abandoned sign-ins are cleaned up as new ones start
+        diesel::delete(oidc_login_states::table.filter(oidc_login_states::expires_at.lt(now))).execute(&mut conn)?;
+
+        let data_state_hash = hash_token(&oidc_payload.state);
+        let new_login_state = OidcLoginStateNew {
+            provider: &oidc_payload.provider,
+            state_hash: &data_state_hash,
+            nonce: &oidc_payload.nonce,
+            code_verifier: &oidc_payload.code_verifier,
+            expires_at: now + Duration::seconds(LOGIN_STATE_EXPIRATION),
+        };
+        diesel::insert_into(oidc_login_states::table).values(&new_login_state).execute(&mut conn)?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "OidcRepository::consume_login_state", skip_all)]
+    async fn consume_login_state(&self, oidc_payload: &OidcCallbackPayload) -> Result<Option<OidcLoginStateEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_state = match &oidc_payload.state {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        let now = Utc::now().naive_utc();
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let found = oidc_login_states::table
+                .filter(oidc_login_states::state_hash.eq(hash_token(data_state)))
+                .filter(oidc_login_states::provider.eq(&oidc_payload.provider))
+                .filter(oidc_login_states::used_at.is_null())
+                .filter(oidc_login_states::expires_at.gt(now))
+                .select(OidcLoginState::as_select())
+                .for_update()
+                .first::<OidcLoginState>(conn)
+                .optional()?;
+
+            if let Some(data) = &found {
+                diesel::update(oidc_login_states::table.filter(oidc_login_states::id.eq(data.id)))
+                    .set(oidc_login_states::used_at.eq(now))
+                    .execute(conn)?;
+            }
+
+            Ok(found)
+        });
+
+        match result {
+            Ok(found) => Ok(found.map(|data| OidcLoginStateEntity::new(data.provider, data.nonce, data.code_verifier))),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "OidcRepository::login_identity", skip_all)]
+    async fn login_identity(&self, oidc_payload: &OidcIdentityPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let now = Utc::now().naive_utc();
+
+        let user = conn.transaction::<_, DomainError, _>(|conn| {
+            let target_identity = user_identities::table
+                .filter(user_identities::provider.eq(&oidc_payload.provider))
+                .filter(user_identities::subject.eq(&oidc_payload.subject));
+
+            let linked = target_identity.inner_join(users::table).select(User::as_select()).first::<User>(conn).optional()?;
+            let user = match linked {
+                Some(user) => user,
+                None => {
+                    let user = link_or_provision_user(conn, oidc_payload)?;
+                    let new_identity = UserIdentityNew {
+                        user_id: &user.id,
+                        provider: &oidc_payload.provider,
+                        subject: &oidc_payload.subject,
+                        email: oidc_payload.email.as_deref(),
+                    };
+                    diesel::insert_into(user_identities::table).values(&new_identity).execute(conn)?;
+                    user
+                }
+            };
+
+            diesel::update(target_identity)
+                .set((user_identities::email.eq(&oidc_payload.email), user_identities::last_login_at.eq(now)))
+                .execute(conn)?;
+
+            Ok(user)
+        })?;
+
+        let data_user_id = user.id;
+        let new_session = UserSessionNew {
+            user_id: &data_user_id,
+            device_name: None,
+            ip_address: oidc_payload.ip_address.as_deref(),
+            user_agent: oidc_payload.user_agent.as_deref(),
+        };
+        complete_login(&mut conn, user, &new_session, &self.token_settings)
+    }
+}
+
+// First sign-in with an identity: create the account owning the email, or attach the identity to it when the provider is trusted
+fn link_or_provision_user(conn: &mut PgConnection, oidc_payload: &OidcIdentityPayload) -> Result<User, DomainError> {
+    // accounts are only matched on an address the identity provider vouches for
+    let data_email = match (&oidc_payload.email, oidc_payload.email_verified) {
+        (Some(data), true) => data,
+        _ => return Err(DomainError::Forbidden(String::from("Identity provider did not share a verified email"))),
+    };
+    let now = Utc::now().naive_utc();
+
+    let existing = users::table.filter(users::email.eq(data_email)).select(User::as_select()).first::<User>(conn).optional()?;
+    if let Some(user) = existing {
+        // anyone able to register that address at the provider would otherwise take the account over
+        if !oidc_payload.trust_email {
+            return Err(DomainError::Conflict(String::from("An account already uses this email")));
+        }
+        let model = diesel::update(users::table.filter(users::id.eq(user.id)))
+            .set(users::email_verified_at.eq(user.email_verified_at.unwrap_or(now)))
+            .returning(User::as_returning())
+            .get_result(conn)?;
+        return Ok(model);
+    }
+
+    // provisioned accounts get a random password, the owner can still set one through a password reset
+    let data_password_hash = hash(generate_secret(32), DEFAULT_COST)?;
+    let data_username = oidc_payload.name.clone().unwrap_or_else(|| data_email.split('@').next().unwrap_or_default().to_string());
+    let data_role = UserRolePayload::default().to_string();
+    let new_user = UserProvision {
+        username: &data_username,
+        email: data_email,
+        password_hash: &data_password_hash,
+        role: &data_role,
+        email_verified_at: now,
+    };
+
+    Ok(diesel::insert_into(users::table).values(&new_user).returning(User::as_returning()).get_result(conn)?)
+}
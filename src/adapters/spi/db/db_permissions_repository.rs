@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::collections::{BTreeMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::instrument;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::permissions::permissions_payloads::RolePermissionsPayload;
+use crate::adapters::api::users::users_payloads::UserRolePayload;
+use crate::application::utils::access_control::extractors::claims::Permission;
+use crate::{application::repositories::permissions_repository_abstract::PermissionsRepositoryAbstract, domain::permission_entity::RolePermissionsEntity};
+
+use super::permission_model::*;
+use super::schema::role_permissions;
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+pub struct PermissionsRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl PermissionsRepositoryAbstract for PermissionsRepository {
+    #[instrument(name = "PermissionsRepository::get_all_role_permissions", skip_all)]
+    async fn get_all_role_permissions(&self) -> Result<Vec<RolePermissionsEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let results = role_permissions::table
+            .order((role_permissions::role.asc(), role_permissions::permission.asc()))
+            .select(RolePermission::as_select())
+            .load::<RolePermission>(&mut conn);
+
+        match results {
+            Ok(models) => {
+                let mut grouped: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                for model in models {
+                    grouped.entry(model.role).or_default().push(model.permission);
+                }
+                Ok(grouped.into_iter().map(|(role, permissions)| RolePermissionsEntity::new(role, permissions)).collect())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "PermissionsRepository::update_role_permissions", skip_all)]
+    async fn update_role_permissions(&self, permission_payload: &RolePermissionsPayload) -> Result<RolePermissionsEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_role = permission_payload.role.to_string();
+
+        let mut data_permissions = Vec::new();
+        for permission in &permission_payload.permissions {
+            let permission = Permission::from_str(permission).map_err(|e| DomainError::validation(&e))?;
+            if !data_permissions.contains(&permission) {
+                data_permissions.push(permission);
+            }
+        }
+
+        // super admins must keep the ability to fix the mapping
+        if matches!(permission_payload.role, UserRolePayload::SuperAdmin) && !data_permissions.contains(&Permission::PermissionsManage) {
+            return Err(DomainError::Forbidden(format!("The super_admin role cannot lose {}", Permission::PermissionsManage)));
+        }
+
+        let new_permissions: Vec<RolePermissionNew> = data_permissions
+            .iter()
+            .map(|permission| RolePermissionNew {
+                role: &data_role,
+                permission: permission.as_str(),
+            })
+            .collect();
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::delete(role_permissions::table.filter(role_permissions::role.eq(&data_role))).execute(conn)?;
+            diesel::insert_into(role_permissions::table).values(&new_permissions).execute(conn)
+        });
+
+        match result {
+            Ok(_) => Ok(RolePermissionsEntity::new(data_role, data_permissions.iter().map(|permission| permission.to_string()).collect())),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// Permissions currently granted to a role, unknown strings left over in the table are ignored
+pub fn role_permissions(conn: &mut PgConnection, data_role: &str) -> QueryResult<HashSet<Permission>> {
+    let results = role_permissions::table
+        .filter(role_permissions::role.eq(data_role))
+        .select(role_permissions::permission)
+        .load::<String>(conn)?;
+
+    Ok(results.iter().filter_map(|permission| Permission::from_str(permission).ok()).collect())
+}
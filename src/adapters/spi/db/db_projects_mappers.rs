@@ -0,0 +1,11 @@
+use crate::domain::project_entity::ProjectEntity;
+
+use super::project_model::Project;
+
+pub struct ProjectDbMapper {}
+
+impl ProjectDbMapper {
+    pub fn to_entity(model: Project) -> ProjectEntity {
+        ProjectEntity::new(model.id.to_string(), model.name, model.description, model.updated_at, model.created_at)
+    }
+}
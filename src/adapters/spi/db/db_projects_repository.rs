@@ -0,0 +1,47 @@
+use async_trait::async_trait;
+use diesel::prelude::*;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::{application::repositories::projects_repository_abstract::ProjectsRepositoryAbstract, domain::project_entity::ProjectEntity};
+
+use super::db_projects_mappers::ProjectDbMapper;
+use super::project_model::{Project, ProjectNew};
+use super::schema::projects;
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+pub struct ProjectsRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl ProjectsRepositoryAbstract for ProjectsRepository {
+    #[instrument(name = "ProjectsRepository::get_projects_by_ids", skip_all)]
+    async fn get_projects_by_ids(&self, project_ids: &[String]) -> Result<Vec<ProjectEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        // tasks without a project point to the nil id, which never matches a row
+        let data_project_ids: Vec<Uuid> = project_ids.iter().filter_map(|project_id| Uuid::parse_str(project_id).ok()).collect();
+
+        let results = projects::table.filter(projects::id.eq_any(data_project_ids)).select(Project::as_select()).load(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models.into_iter().map(ProjectDbMapper::to_entity).collect()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "ProjectsRepository::post_project", skip_all)]
+    async fn post_project<'a>(&self, name: &str, description: Option<&'a str>) -> Result<ProjectEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let new_project = ProjectNew { name, description };
+
+        let result = diesel::insert_into(projects::table).values(&new_project).returning(Project::as_returning()).get_result(&mut conn);
+
+        match result {
+            Ok(model) => Ok(ProjectDbMapper::to_entity(model)),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use uuid::Uuid;
+
+use crate::application::mappers::db_mapper::DbMapper;
+use crate::domain::session_entity::SessionEntity;
+
+use super::session_model::UserSession;
+
+pub struct SessionDbMapper {}
+
+impl DbMapper<SessionEntity, UserSession> for SessionDbMapper {
+    fn to_db(entity: SessionEntity) -> UserSession {
+        UserSession {
+            id: Uuid::parse_str(&entity.id).unwrap_or_default(),
+            user_id: Uuid::parse_str(&entity.user_id).unwrap_or_default(),
+            device_name: entity.device_name,
+            ip_address: entity.ip_address,
+            user_agent: entity.user_agent,
+            revoked_at: entity.revoked_at,
+            last_used_at: entity.last_used_at,
+            created_at: entity.created_at,
+        }
+    }
+
+    fn to_entity(model: UserSession) -> SessionEntity {
+        SessionEntity {
+            id: model.id.to_string(),
+            user_id: model.user_id.to_string(),
+            device_name: model.device_name,
+            ip_address: model.ip_address,
+            user_agent: model.user_agent,
+            revoked_at: model.revoked_at,
+            last_used_at: model.last_used_at,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use diesel::dsl::exists;
+use diesel::prelude::*;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::sessions::sessions_payloads::SessionDataPayload;
+use crate::application::mappers::db_mapper::DbMapper;
+use crate::application::utils::token_hash::hash_token;
+use crate::{application::repositories::sessions_repository_abstract::SessionsRepositoryAbstract, domain::session_entity::SessionEntity};
+
+use super::db_sessions_mappers::SessionDbMapper;
+use super::schema::{session_refresh_tokens, user_sessions};
+use super::session_model::*;
+use crate::adapters::spi::db::db_connection::DbConnection;
+
+pub struct SessionsRepository {
+    pub db_connection: Arc<DbConnection>,
+}
+
+#[async_trait(?Send)]
+impl SessionsRepositoryAbstract for SessionsRepository {
+    #[instrument(name = "SessionsRepository::get_all_sessions", skip_all)]
+    async fn get_all_sessions(&self, session_payload: &SessionDataPayload) -> Result<Vec<SessionEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&session_payload.user_id.clone().unwrap_or_default())?;
+        let now = Utc::now().naive_utc();
+
+        // a session is alive while the latest token of its rotation family can still be used
+        let active_token = session_refresh_tokens::table
+            .filter(session_refresh_tokens::session_id.eq(user_sessions::id))
+            .filter(session_refresh_tokens::rotated_at.is_null())
+            .filter(session_refresh_tokens::expires_at.gt(now));
+
+        let results = user_sessions::table
+            .filter(user_sessions::user_id.eq(data_user_id))
+            .filter(user_sessions::revoked_at.is_null())
+            .filter(exists(active_token))
+            .order(user_sessions::last_used_at.desc())
+            .select(UserSession::as_select())
+            .load::<UserSession>(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models.into_iter().map(SessionDbMapper::to_entity).collect::<Vec<SessionEntity>>()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "SessionsRepository::revoke_session", skip_all)]
+    async fn revoke_session(&self, session_payload: &SessionDataPayload) -> Result<SessionEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_session_id = Uuid::parse_str(&session_payload.session_id.clone().unwrap_or_default())?;
+        let data_user_id = Uuid::parse_str(&session_payload.user_id.clone().unwrap_or_default())?;
+
+        let target = user_sessions::table
+            .filter(user_sessions::id.eq(data_session_id))
+            .filter(user_sessions::user_id.eq(data_user_id))
+            .filter(user_sessions::revoked_at.is_null());
+        let result = diesel::update(target)
+            .set(user_sessions::revoked_at.eq(Utc::now().naive_utc()))
+            .returning(UserSession::as_returning())
+            .get_result(&mut conn)
+            .optional();
+
+        match result {
+            Ok(Some(model)) => Ok(SessionDbMapper::to_entity(model)),
+            Ok(None) => Err(DomainError::NotFound(String::from("Session not found"))),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+pub fn create_session(conn: &mut PgConnection, new_session: &UserSessionNew) -> QueryResult<UserSession> {
+    diesel::insert_into(user_sessions::table)
+        .values(new_session)
+        .returning(UserSession::as_returning())
+        .get_result(conn)
+}
+
+pub fn store_refresh_token(conn: &mut PgConnection, data_session_id: &Uuid, refresh_token: &str, expiration: usize) -> QueryResult<usize> {
+    let data_token_hash = hash_token(refresh_token);
+    let new_token = SessionRefreshTokenNew {
+        session_id: data_session_id,
+        token_hash: &data_token_hash,
+        expires_at: Utc::now().naive_utc() + Duration::seconds(expiration as i64),
+    };
+
+    diesel::insert_into(session_refresh_tokens::table).values(&new_token).execute(conn)
+}
+
+pub fn revoke_session_by_id(conn: &mut PgConnection, data_session_id: &Uuid) -> QueryResult<usize> {
+    diesel::update(user_sessions::table.filter(user_sessions::id.eq(data_session_id)).filter(user_sessions::revoked_at.is_null()))
+        .set(user_sessions::revoked_at.eq(Utc::now().naive_utc()))
+        .execute(conn)
+}
+
+pub fn revoke_user_sessions(conn: &mut PgConnection, data_user_id: &Uuid) -> QueryResult<usize> {
+    diesel::update(user_sessions::table.filter(user_sessions::user_id.eq(data_user_id)).filter(user_sessions::revoked_at.is_null()))
+        .set(user_sessions::revoked_at.eq(Utc::now().naive_utc()))
+        .execute(conn)
+}
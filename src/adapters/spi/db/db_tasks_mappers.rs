@@ -16,9 +16,9 @@ impl DbMapper<TaskEntity, Task> for TaskDbMapper {
             user_id: Uuid::parse_str(&entity.user_id).unwrap_or_default(),
             project_id: Uuid::parse_str(&entity.project_id).unwrap_or_default(),
             title: entity.title,
-            typ: Some(entity.typ),
-            status: Some(entity.status),
-            priority: Some(entity.priority),
+            typ: entity.typ,
+            status: entity.status,
+            priority: entity.priority,
             description: entity.description,
             duration: Some(entity.duration),
             due_date: Some(entity.due_date),
@@ -34,9 +34,9 @@ impl DbMapper<TaskEntity, Task> for TaskDbMapper {
             user_id: model.user_id.to_string(),
             project_id: model.project_id.to_string(),
             title: model.title,
-            typ: model.typ.unwrap_or_default(),
-            priority: model.priority.unwrap_or_default(),
-            status: model.status.unwrap_or_default(),
+            typ: model.typ,
+            priority: model.priority,
+            status: model.status,
             description: model.description,
             duration: model.duration.unwrap_or_default(),
             due_date: model.due_date.unwrap_or_default(),
@@ -56,8 +56,8 @@ impl DbMapper<TaskAllEntity, Task> for TaskAllDbMapper {
             title: entity.title,
             description: entity.description,
             typ: todo!(),
-            priority: todo!(),
-            status: todo!(),
+            priority: entity.priority,
+            status: entity.status,
             duration: todo!(),
             due_date: todo!(),
             task_list: todo!(),
@@ -72,6 +72,8 @@ impl DbMapper<TaskAllEntity, Task> for TaskAllDbMapper {
             user_id: model.user_id.to_string(),
             project_id: model.project_id.to_string(),
             title: model.title,
+            priority: model.priority,
+            status: model.status,
             description: model.description,
             updated_at: model.updated_at,
             created_at: model.created_at,
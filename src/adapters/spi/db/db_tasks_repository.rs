@@ -1,10 +1,11 @@
 use async_trait::async_trait;
 use diesel::prelude::*;
-use std::error::Error;
 use std::sync::Arc;
+use tracing::instrument;
 use uuid::Uuid;
 
 use crate::application::mappers::db_mapper::DbMapper;
+use crate::domain::error::DomainError;
 use crate::domain::task_entity::*;
 use crate::{adapters::api::tasks::tasks_payloads::*, application::repositories::tasks_repository_abstract::TasksRepositoryAbstract};
 
@@ -13,63 +14,45 @@ use super::schema::tasks::{self, *};
 use super::task_model::*;
 use crate::adapters::spi::db::{db_connection::DbConnection, schema::tasks::dsl::*};
 
+// Rows per INSERT of an import, well under the bind parameter limit of Postgres
+const INSERT_CHUNK_SIZE: usize = 500;
+
 pub struct TasksRepository {
     pub db_connection: Arc<DbConnection>,
 }
 
 #[async_trait(?Send)]
 impl TasksRepositoryAbstract for TasksRepository {
-    async fn post_one_task(&self, task_payload: &TaskCreatePayload) -> Result<TaskEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
-
-        let data_user_id_uuid = Uuid::parse_str(&task_payload.user_id.clone().unwrap_or_default()).unwrap_or_default();
-        let data_project_id_uuid = Uuid::parse_str(&task_payload.project_id.clone().unwrap_or_default()).unwrap_or_default();
-        let data_title = task_payload.title.clone();
-        let data_typ = task_payload.typ.clone().unwrap_or_default().to_string();
-        let data_priority = task_payload.priority.clone().unwrap_or_default().to_string();
-        let data_status = task_payload.status.clone().unwrap_or_default().to_string();
-        let data_description = task_payload.description.clone().unwrap_or_default();
-        let data_duration = task_payload.duration.unwrap_or_default();
-        let data_due_date = task_payload.due_date.unwrap_or_default();
-        let data_task_list: Option<Vec<&str>> = task_payload.task_list.as_ref().map(|vec| vec.iter().map(|s| s.as_str()).collect());
-
-        let new_task = TaskNew {
-            user_id: &data_user_id_uuid,
-            project_id: &data_project_id_uuid,
-            title: &data_title,
-            typ: &data_typ,
-            priority: &data_priority,
-            status: &data_status,
-            description: &data_description,
-            duration: data_duration,
-            due_date: data_due_date,
-            task_list: data_task_list.unwrap_or_default(),
-        };
+    #[instrument(name = "TasksRepository::post_one_task", skip_all)]
+    async fn post_one_task(&self, task_payload: &TaskCreatePayload) -> Result<TaskEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let (data_user_id_uuid, data_project_id_uuid) = payload_ids(task_payload)?;
+        let new_task = new_task(task_payload, &data_user_id_uuid, &data_project_id_uuid);
 
         let result = diesel::insert_into(tasks::table).values(&new_task).returning(Task::as_returning()).get_result(&mut conn);
 
         match result {
             Ok(model) => Ok(TaskDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn update_one_task(&self, task_payload: &TaskUpdatePayload) -> Result<TaskEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
-        let task_id_uuid = Uuid::parse_str(&task_payload.task_id).unwrap();
-        let user_id_uuid = Uuid::parse_str(&task_payload.user_id.clone().unwrap_or_default()).ok();
-        let target = tasks::table
-            .filter(id.eq(task_id_uuid))
-            .filter(user_id.eq(user_id_uuid.unwrap()));
+    #[instrument(name = "TasksRepository::update_one_task", skip_all)]
+    async fn update_one_task(&self, task_payload: &TaskUpdatePayload) -> Result<TaskEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let task_id_uuid = Uuid::parse_str(&task_payload.task_id)?;
+        let user_id_uuid = task_payload.user_id.as_deref().map(Uuid::parse_str).transpose()?;
+        // ownership is checked by the task policy before, `user_id` here is the (possibly new) owner
+        let target = tasks::table.filter(id.eq(task_id_uuid));
         let data_task_list: Option<Vec<&str>> = task_payload.task_list.as_ref().map(|vec| vec.iter().map(|s| s.as_str()).collect());
 
         let result = diesel::update(target)
             .set((
                 user_id_uuid.clone().map(|data| user_id.eq(data)),
                 task_payload.title.clone().map(|data| title.eq(data)),
-                task_payload.typ.clone().map(|data| typ.eq(data.to_string())),
-                task_payload.priority.clone().map(|data| priority.eq(data.to_string())),
-                task_payload.status.clone().map(|data| priority.eq(data.to_string())),
+                task_payload.typ.map(|data| typ.eq(data)),
+                task_payload.priority.map(|data| priority.eq(data)),
+                task_payload.status.map(|data| status.eq(data)),
                 task_payload.description.clone().map(|data| description.eq(data)),
                 task_payload.duration.map(|data| duration.eq(data)),
                 task_payload.due_date.map(|data| due_date.eq(data)),
@@ -80,52 +63,175 @@ impl TasksRepositoryAbstract for TasksRepository {
 
         match result {
             Ok(model) => Ok(TaskDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn get_all_tasks(&self, task_payload: &TaskDataPayload) -> Result<Vec<TaskAllEntity>, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "TasksRepository::get_all_tasks", skip_all)]
+    async fn get_all_tasks(&self, task_payload: &TaskDataPayload) -> Result<Vec<TaskAllEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
         let user_id_uuid = task_payload.user_id.as_ref().and_then(|data: &String| Uuid::parse_str(data).ok());
         let mut query = tasks.into_boxed();
         if let Some(data) = user_id_uuid {
             query = query.filter(user_id.eq(data));
         }
+        // the enum columns sort by declaration order, so by rank; ties keep the newest first
+        query = match task_payload.sort {
+            Some(TaskSortPayload::PriorityAsc) => query.order((priority.asc(), created_at.desc())),
+            Some(TaskSortPayload::PriorityDesc) => query.order((priority.desc(), created_at.desc())),
+            None => query,
+        };
         let results = query.load::<Task>(&mut conn);
         match results {
             Ok(models) => Ok(models.into_iter().map(TaskAllDbMapper::to_entity).collect::<Vec<TaskAllEntity>>()),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn get_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<TaskEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "TasksRepository::get_task_by_id", skip_all)]
+    async fn get_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<Option<TaskEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
         let task_id_uuid = task_payload.task_id.as_ref().and_then(|data: &String| Uuid::parse_str(data).ok());
         let user_id_uuid = task_payload.user_id.as_ref().and_then(|data: &String| Uuid::parse_str(data).ok());
 
-        let mut query = tasks.into_boxed();
-        if let Some(data) = task_id_uuid {
-            query = query.filter(id.eq(data));
+        // a missing or malformed id matches no task
+        let Some(task_id_uuid) = task_id_uuid else {
+            return Ok(None);
+        };
+        let mut query = tasks.filter(id.eq(task_id_uuid)).into_boxed();
+        if let Some(data) = user_id_uuid {
+            query = query.filter(user_id.eq(data));
+        }
+        let result = query.get_result::<Task>(&mut conn).optional();
+
+        match result {
+            Ok(model) => Ok(model.map(TaskDbMapper::to_entity)),
+            Err(e) => Err(e.into()),
         }
+    }
+
+    #[instrument(name = "TasksRepository::delete_task_by_id", skip_all)]
+    async fn delete_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<TaskEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let task_id_uuid = Uuid::parse_str(&task_payload.task_id.clone().unwrap_or_default())?;
+        let user_id_uuid = task_payload.user_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let mut query = diesel::delete(tasks).filter(id.eq(task_id_uuid)).into_boxed();
         if let Some(data) = user_id_uuid {
             query = query.filter(user_id.eq(data));
         }
-        let result = query.get_result::<Task>(&mut conn);
+        let result = query.returning(Task::as_returning()).get_result(&mut conn).optional();
 
         match result {
-            Ok(model) => Ok(TaskDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Ok(Some(model)) => Ok(TaskDbMapper::to_entity(model)),
+            Ok(None) => Err(DomainError::NotFound(String::from("Task not found"))),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn delete_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<TaskEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
-        let task_id_uuid = Uuid::parse_str(&task_payload.task_id.clone().unwrap()).unwrap();
-        let result = tasks.filter(id.eq(task_id_uuid)).get_result::<Task>(&mut conn);
+    #[instrument(name = "TasksRepository::get_tasks_page", skip_all)]
+    async fn get_tasks_page(&self, page_payload: &TaskPagePayload) -> Result<Vec<TaskEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let user_id_uuid = page_payload.user_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let project_id_uuid = page_payload.project_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let mut query = tasks.into_boxed();
+        if let Some(data) = user_id_uuid {
+            query = query.filter(user_id.eq(data));
+        }
+        if let Some(data) = project_id_uuid {
+            query = query.filter(project_id.eq(data));
+        }
+        if page_payload.due_only {
+            query = query.filter(due_date.gt(0));
+        }
+        // keyset pagination, pages stay cheap however deep the export goes
+        if let Some((after_created_at, after_id)) = &page_payload.after {
+            let after_id_uuid = Uuid::parse_str(after_id)?;
+            query = query.filter(created_at.gt(after_created_at).or(created_at.eq(after_created_at).and(id.gt(after_id_uuid))));
+        }
+        let results = query.order((created_at.asc(), id.asc())).limit(page_payload.limit).load::<Task>(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models.into_iter().map(TaskDbMapper::to_entity).collect::<Vec<TaskEntity>>()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "TasksRepository::get_task_list_page", skip_all)]
+    async fn get_task_list_page(&self, page_payload: &TaskListPagePayload) -> Result<Vec<TaskAllEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let user_id_uuid = page_payload.user_id.as_deref().map(Uuid::parse_str).transpose()?;
+        let mut query = tasks.into_boxed();
+        if let Some(data) = user_id_uuid {
+            query = query.filter(user_id.eq(data));
+        }
+        // keyset pagination over the same order as `get_all_tasks`, the id breaks the remaining ties
+        if let Some(after) = &page_payload.after {
+            let after_id_uuid = Uuid::parse_str(&after.id)?;
+            let same_priority_after = priority
+                .eq(after.priority)
+                .and(created_at.lt(after.created_at).or(created_at.eq(after.created_at).and(id.lt(after_id_uuid))));
+            query = match page_payload.sort {
+                Some(TaskSortPayload::PriorityAsc) => query.filter(priority.gt(after.priority).or(same_priority_after)),
+                Some(TaskSortPayload::PriorityDesc) => query.filter(priority.lt(after.priority).or(same_priority_after)),
+                None => query.filter(created_at.gt(after.created_at).or(created_at.eq(after.created_at).and(id.gt(after_id_uuid)))),
+            };
+        }
+        query = match page_payload.sort {
+            Some(TaskSortPayload::PriorityAsc) => query.order((priority.asc(), created_at.desc(), id.desc())),
+            Some(TaskSortPayload::PriorityDesc) => query.order((priority.desc(), created_at.desc(), id.desc())),
+            None => query.order((created_at.asc(), id.asc())),
+        };
+        let results = query.limit(page_payload.limit).load::<Task>(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models.into_iter().map(TaskAllDbMapper::to_entity).collect::<Vec<TaskAllEntity>>()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "TasksRepository::post_tasks", skip_all, fields(count = task_payloads.len()))]
+    async fn post_tasks(&self, task_payloads: &[TaskCreatePayload]) -> Result<Vec<TaskEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let ids = task_payloads.iter().map(payload_ids).collect::<Result<Vec<(Uuid, Uuid)>, uuid::Error>>()?;
+        let new_tasks: Vec<TaskNew> = task_payloads
+            .iter()
+            .zip(&ids)
+            .map(|(task_payload, (data_user_id_uuid, data_project_id_uuid))| new_task(task_payload, data_user_id_uuid, data_project_id_uuid))
+            .collect();
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut models = Vec::with_capacity(new_tasks.len());
+            for chunk in new_tasks.chunks(INSERT_CHUNK_SIZE) {
+                models.extend(diesel::insert_into(tasks::table).values(chunk).returning(Task::as_returning()).get_results(conn)?);
+            }
+            Ok(models)
+        });
 
         match result {
-            Ok(model) => Ok(TaskDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Ok(models) => Ok(models.into_iter().map(TaskDbMapper::to_entity).collect::<Vec<TaskEntity>>()),
+            Err(e) => Err(e.into()),
         }
     }
 }
+
+// ids are checked by the payload validation, only missing ones fall back to the nil id
+fn payload_ids(task_payload: &TaskCreatePayload) -> Result<(Uuid, Uuid), uuid::Error> {
+    let data_user_id_uuid = task_payload.user_id.as_deref().map(Uuid::parse_str).transpose()?.unwrap_or_default();
+    let data_project_id_uuid = task_payload.project_id.as_deref().map(Uuid::parse_str).transpose()?.unwrap_or_default();
+    Ok((data_user_id_uuid, data_project_id_uuid))
+}
+
+fn new_task<'a>(task_payload: &'a TaskCreatePayload, data_user_id_uuid: &'a Uuid, data_project_id_uuid: &'a Uuid) -> TaskNew<'a> {
+    TaskNew {
+        user_id: data_user_id_uuid,
+        project_id: data_project_id_uuid,
+        title: &task_payload.title,
+        typ: task_payload.typ.unwrap_or_default(),
+        priority: task_payload.priority.unwrap_or_default(),
+        status: task_payload.status.unwrap_or_default(),
+        description: task_payload.description.as_deref().unwrap_or_default(),
+        duration: task_payload.duration.unwrap_or_default(),
+        due_date: task_payload.due_date.unwrap_or_default(),
+        task_list: task_payload.task_list.iter().flatten().map(String::as_str).collect(),
+    }
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use diesel::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::domain::error::DomainError;
+use crate::adapters::api::users::users_payloads::{UserIdPayload, UserLogoutPayload};
+use crate::{application::repositories::tokens_repository_abstract::TokensRepositoryAbstract, domain::token_entity::RevocationListEntity};
+
+use super::db_sessions_repository::{revoke_session_by_id, revoke_user_sessions};
+use super::schema::{revoked_tokens, user_sessions, users};
+use super::token_model::*;
+use crate::adapters::spi::db::db_connection::DbConnection;
+use crate::infrastructure::settings::TokenSettings;
+
+pub struct TokensRepository {
+    pub db_connection: Arc<DbConnection>,
+    pub token_settings: TokenSettings,
+}
+
+#[async_trait(?Send)]
+impl TokensRepositoryAbstract for TokensRepository {
+    #[instrument(name = "TokensRepository::revoke_token", skip_all)]
+    async fn revoke_token(&self, user_payload: &UserLogoutPayload) -> Result<(), DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+
+        let data_user_id = Uuid::parse_str(&user_payload.user_id.clone().unwrap_or_default())?;
+        let data_jti = Uuid::parse_str(&user_payload.jti.clone().unwrap_or_default())?;
+        let data_expires_at = timestamp_to_naive(user_payload.expires_at.unwrap_or_default());
+
+        let new_revoked_token = RevokedTokenNew {
+            jti: &data_jti,
+            user_id: &data_user_id,
+            expires_at: data_expires_at,
+        };
+        let data_session_id = user_payload.session_id.as_ref().and_then(|data| Uuid::parse_str(data).ok());
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::insert_into(revoked_tokens::table).values(&new_revoked_token).on_conflict_do_nothing().execute(conn)?;
+
+            // ending the session kills its refresh token family as well
+            if let Some(data) = data_session_id {
+                revoke_session_by_id(conn, &data)?;
+            }
+
+            Ok(())
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "TokensRepository::revoke_all_user_tokens", skip_all)]
+    async fn revoke_all_user_tokens(&self, user_payload: &UserIdPayload) -> Result<i64, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_id = Uuid::parse_str(&user_payload.user_id)?;
+        let now = Utc::now().naive_utc();
+
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let updated = diesel::update(users::table.filter(users::id.eq(data_user_id)))
+                .set(users::tokens_revoked_at.eq(now))
+                .execute(conn)?;
+            revoke_user_sessions(conn, &data_user_id)?;
+            Ok(updated)
+        });
+
+        match result {
+            Ok(0) => Err(DomainError::NotFound(String::from("User not found"))),
+            Ok(_) => Ok(now.and_utc().timestamp_millis()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "TokensRepository::get_revocation_list", skip_all)]
+    async fn get_revocation_list(&self) -> Result<RevocationListEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let now = Utc::now().naive_utc();
+
+        let tokens = revoked_tokens::table.filter(revoked_tokens::expires_at.gt(now)).select(RevokedToken::as_select()).load::<RevokedToken>(&mut conn)?;
+        let revoked_users = users::table
+            .filter(users::tokens_revoked_at.is_not_null())
+            .select((users::id, users::tokens_revoked_at))
+            .load::<(Uuid, Option<chrono::NaiveDateTime>)>(&mut conn)?;
+
+        // tokens of sessions revoked longer ago than the refresh token lifetime are expired already
+        let revoked_sessions = user_sessions::table
+            .filter(user_sessions::revoked_at.gt(now - Duration::seconds(self.token_settings.refresh_token_ttl as i64)))
+            .select((user_sessions::id, user_sessions::revoked_at))
+            .load::<(Uuid, Option<chrono::NaiveDateTime>)>(&mut conn)?;
+
+        Ok(RevocationListEntity::new(
+            tokens
+                .into_iter()
+                .map(|token| (token.jti.to_string(), token.expires_at.and_utc().timestamp()))
+                .collect::<HashMap<String, i64>>(),
+            revoked_users
+                .into_iter()
+                .filter_map(|(data_id, data_revoked_at)| data_revoked_at.map(|at| (data_id.to_string(), at.and_utc().timestamp_millis())))
+                .collect::<HashMap<String, i64>>(),
+            revoked_sessions
+                .into_iter()
+                .filter_map(|(data_id, data_revoked_at)| data_revoked_at.map(|at| (data_id.to_string(), at.and_utc().timestamp())))
+                .collect::<HashMap<String, i64>>(),
+        ))
+    }
+
+    #[instrument(name = "TokensRepository::purge_expired_tokens", skip_all)]
+    async fn purge_expired_tokens(&self) -> Result<usize, DomainError> {
+        // runs in the background, an unreachable database is reported rather than taking the server down
+        let mut conn = self.db_connection.get_pool().get()?;
+        let now = Utc::now().naive_utc();
+
+        // expired tokens fail validation anyway, no need to keep them around
+        let result = diesel::delete(revoked_tokens::table.filter(revoked_tokens::expires_at.le(now))).execute(&mut conn);
+
+        match result {
+            Ok(count) => Ok(count),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+fn timestamp_to_naive(timestamp: i64) -> chrono::NaiveDateTime {
+    DateTime::from_timestamp(timestamp, 0).unwrap_or_default().naive_utc()
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveDateTime;
 use uuid::Uuid;
 
 use crate::application::mappers::db_mapper::DbMapper;
@@ -9,8 +10,6 @@ pub struct UserDbMapper {}
 
 pub struct UserAllDbMapper {}
 
-pub struct UserAccessTokenDbMapper {}
-
 // impl DbMapper<UserEntity, User> for UserDbMapper {
 //     fn to_db(entity: UserEntity) -> User {
 //         User {
@@ -92,8 +91,9 @@ impl DbMapper<UserEntity, User> for UserDbMapper {
             email: entity.email,
             password_hash: entity.password,
             role: entity.role,
-            refresh_token: entity.refresh_token.unwrap_or_default(),
             fcm_token: entity.fcm_token.unwrap_or_default(),
+            tokens_revoked_at: None,
+            email_verified_at: entity.email_verified_at,
             last_login: entity.last_login,
             updated_at: entity.updated_at,
             created_at: entity.created_at,
@@ -107,9 +107,12 @@ impl DbMapper<UserEntity, User> for UserDbMapper {
             email: model.email,
             password: model.password_hash,
             role: model.role,
-            refresh_token: Some(model.refresh_token),
+            refresh_token: None,
             fcm_token: Some(model.fcm_token),
             access_token: None,
+            email_verified_at: model.email_verified_at,
+            mfa_token: None,
+            mfa_enrollment_required: false,
             last_login: model.last_login,
             updated_at: model.updated_at,
             created_at: model.created_at,
@@ -118,18 +121,20 @@ impl DbMapper<UserEntity, User> for UserDbMapper {
 }
 
 impl DbMapper<UserAllEntity, User> for UserAllDbMapper {
+    // A listed user carries neither credentials nor timestamps, those are left empty
     fn to_db(entity: UserAllEntity) -> User {
         User {
             id: Uuid::parse_str(&entity.id).unwrap_or_default(),
             username: entity.username,
             email: entity.email,
             role: entity.role,
-            password_hash: todo!(),
-            refresh_token: todo!(),
-            fcm_token: todo!(),
-            last_login: todo!(),
-            updated_at: todo!(),
-            created_at: todo!(),
+            password_hash: String::new(),
+            fcm_token: String::new(),
+            tokens_revoked_at: None,
+            email_verified_at: None,
+            last_login: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            created_at: NaiveDateTime::default(),
         }
     }
 
@@ -142,15 +147,3 @@ impl DbMapper<UserAllEntity, User> for UserAllDbMapper {
         }
     }
 }
-
-impl DbMapper<UserAccessTokenEntity, String> for UserAccessTokenDbMapper {
-    fn to_db(entity: UserAccessTokenEntity) -> String {
-        entity.access_token
-    }
-
-    fn to_entity(access_token: String) -> UserAccessTokenEntity {
-        UserAccessTokenEntity {
-            access_token: access_token.to_string(),
-        }
-    }
-}
@@ -2,34 +2,57 @@ use async_trait::async_trait;
 use bcrypt::{hash, DEFAULT_COST};
 use chrono::Utc;
 use diesel::prelude::*;
-use std::error::Error;
+use std::collections::HashSet;
 use std::str::FromStr;
 use std::sync::Arc;
+use tracing::instrument;
 use uuid::Uuid;
 
-use crate::adapters::api::shared::error_presenter::ErrorResponse;
+use crate::domain::error::DomainError;
 use crate::adapters::api::users::users_payloads::*;
 use crate::application::mappers::db_mapper::DbMapper;
 use crate::application::utils::access_control::auth_usecase::AuthUseCase;
+use crate::application::utils::access_control::extractors::claims::{Permission, TokenType};
+use crate::application::utils::token_hash::hash_token;
 use crate::application::utils::validate_params;
 use crate::{
-    application::repositories::users_repository_abstract::UsersRepositoryAbstract,
-    domain::user_entity::{UserAccessTokenEntity, UserAllEntity, UserEntity},
+    application::repositories::users_repository_abstract::{UsersRepositoryAbstract, REFRESH_TOKEN_REUSED},
+    domain::{
+        user_entity::{UserAccessTokenEntity, UserAllEntity, UserEntity},
+    },
 };
 
-use super::db_users_mappers::{UserAccessTokenDbMapper, UserAllDbMapper, UserDbMapper};
+use super::db_mfa_repository::{create_mfa_challenge, has_confirmed_mfa, login_permissions};
+use super::db_sessions_repository::{create_session, revoke_session_by_id, revoke_user_sessions, store_refresh_token};
+use super::db_users_mappers::{UserAllDbMapper, UserDbMapper};
 use super::schema::users::{self, *};
+use super::schema::{session_refresh_tokens, user_sessions};
+use super::session_model::*;
 use super::user_model::*;
 use crate::adapters::spi::db::{db_connection::DbConnection, schema::users::dsl::*};
+use crate::infrastructure::settings::TokenSettings;
 
 pub struct UsersRepository {
     pub db_connection: Arc<DbConnection>,
+    pub token_settings: TokenSettings,
+}
+
+enum RefreshOutcome {
+    Rotated(UserAccessTokenEntity),
+    Reused,
+    Invalid,
+}
+
+// Unknown email and wrong password get the same answer, the login use case counts it as a failed attempt
+fn invalid_credentials() -> DomainError {
+    DomainError::Unauthorized(String::from("Invalid email or password!"))
 }
 
 #[async_trait(?Send)]
 impl UsersRepositoryAbstract for UsersRepository {
-    async fn register_user(&self, user_payload: &UserRegisterPayload) -> Result<UserEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::register_user", skip_all)]
+    async fn register_user(&self, user_payload: &UserRegisterPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
 
         let data_username = user_payload.username.clone();
         let data_email = user_payload.email.clone();
@@ -40,13 +63,13 @@ impl UsersRepositoryAbstract for UsersRepository {
         let valid_password = validate_params::is_password(&data_password);
 
         if !valid_email || !valid_password {
-            return Err(String::from("Invalid Email or Password!").into());
+            return Err(DomainError::validation("Invalid Email or Password!"));
         }
 
         let email_already_exists = users::table.filter(users::email.eq(&data_email)).select(users::id).first::<Uuid>(&mut conn).is_ok();
 
         if email_already_exists {
-            return Err(String::from("Invalid email or password!").into());
+            return Err(DomainError::validation("Invalid email or password!"));
         }
 
         let hashed_password = hash(data_password.as_str(), DEFAULT_COST).unwrap();
@@ -62,111 +85,163 @@ impl UsersRepositoryAbstract for UsersRepository {
 
         match result {
             Ok(model) => Ok(UserDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn login_user(&self, user_payload: &UserLoginPayload) -> Result<UserEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::login_user", skip_all)]
+    async fn login_user(&self, user_payload: &UserLoginPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
 
         let data_email = user_payload.email.clone();
         let data_password = user_payload.password.clone();
 
         let user = match users::table.filter(users::email.eq(&data_email)).select(User::as_select()).first::<User>(&mut conn) {
             Ok(user) => user,
-            Err(_) => return Err(String::from("Invalid email or password!").into()),
+            Err(_) => return Err(invalid_credentials()),
         };
 
         if user.email != data_email {
-            return Err(String::from("Invalid email or password!").into());
+            return Err(invalid_credentials());
         };
 
         if !bcrypt::verify(data_password, &user.password_hash).unwrap() {
-            return Err(String::from("Invalid email or password!").into());
+            return Err(invalid_credentials());
         }
-        let permissions = AuthUseCase::check_role(&user.role);
-        let data_refresh_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, 3600 * 24, Some(permissions.clone())).unwrap_or_default();
-        let data_access_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, 3600, Some(permissions.clone())).unwrap_or_default();
-
-        let target = users.filter(id.eq(user.id));
-        let result = diesel::update(target)
-            .set((last_login.eq(Utc::now().naive_utc().clone()), refresh_token.eq(data_refresh_token.clone())))
-            .returning(User::as_returning())
-            .get_result(&mut conn);
+        let data_user_id = user.id;
+        let new_session = UserSessionNew {
+            user_id: &data_user_id,
+            device_name: user_payload.device_name.as_deref(),
+            ip_address: user_payload.ip_address.as_deref(),
+            user_agent: user_payload.user_agent.as_deref(),
+        };
 
-        match result {
-            Ok(model) => {
-                let mut entity = UserDbMapper::to_entity(model);
-                entity.access_token = Some(data_access_token);
-                Ok(entity)
-            }
-            Err(e) => Err(Box::new(e)),
-        }
+        complete_login(&mut conn, user, &new_session, &self.token_settings)
     }
 
-    async fn get_refresh(&self, user_payload: &UserRefreshTokenPayload) -> Result<UserAccessTokenEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::get_refresh", skip_all)]
+    async fn get_refresh(&self, user_payload: &UserRefreshTokenPayload) -> Result<UserAccessTokenEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
 
         let claims = match AuthUseCase::validate_token(&user_payload.refresh_token) {
-            Ok(data) => data,
-            Err(_) => return Err(Box::new(ErrorResponse::auth_default())), // Box the ClientError
-        };
-
-        let user_id = Uuid::parse_str(&claims.sub)?;
-        let target_user = users::table.filter(id.eq(user_id)).filter(refresh_token.eq(&user_payload.refresh_token));
-
-        let user = match target_user.select(User::as_select()).first::<User>(&mut conn) {
-            Ok(user) => user,
-            Err(_) => return Err(String::from("Failed refresh token").into()),
+            Ok(data) if data.typ == TokenType::Refresh => data,
+            _ => return Err(DomainError::Unauthorized(String::from("Permission denied"))),
         };
 
-        let permissions = AuthUseCase::check_role(&user.role);
-        let new_refresh_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, 3600 * 24, Some(permissions.clone())).unwrap_or_default();
-
-        let result = diesel::update(target_user)
-            .set(refresh_token.eq(new_refresh_token))
-            .returning(refresh_token)
-            .get_result(&mut conn);
+        let data_token_hash = hash_token(&user_payload.refresh_token);
+        let now = Utc::now().naive_utc();
+
+        let outcome = conn.transaction::<_, DomainError, _>(|conn| {
+            let found = session_refresh_tokens::table
+                .inner_join(user_sessions::table)
+                .filter(session_refresh_tokens::token_hash.eq(&data_token_hash))
+                .select((SessionRefreshToken::as_select(), UserSession::as_select()))
+                .for_update()
+                .first::<(SessionRefreshToken, UserSession)>(conn)
+                .optional()?;
+
+            let (token, session) = match found {
+                Some(data) if data.1.revoked_at.is_none() && data.0.expires_at > now && data.1.user_id.to_string() == claims.sub => data,
+                _ => return Ok(RefreshOutcome::Invalid),
+            };
+
+            // an already rotated token is being replayed, the whole family is compromised
+            if token.rotated_at.is_some() {
+                revoke_session_by_id(conn, &session.id)?;
+                return Ok(RefreshOutcome::Reused);
+            }
 
-        match result {
-            Ok(model) => Ok(UserAccessTokenDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            let user = users.filter(id.eq(session.user_id)).select(User::as_select()).first::<User>(conn)?;
+            let data_session_id = session.id.to_string();
+            let (permissions, _) = login_permissions(conn, &user)?;
+            let new_refresh_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, self.token_settings.refresh_token_ttl as usize, Some(permissions.clone()), Some(&data_session_id), TokenType::Refresh)?;
+            let new_access_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, self.token_settings.access_token_ttl as usize, Some(permissions.clone()), Some(&data_session_id), TokenType::Access)?;
+
+            diesel::update(session_refresh_tokens::table.filter(session_refresh_tokens::id.eq(token.id)))
+                .set(session_refresh_tokens::rotated_at.eq(now))
+                .execute(conn)?;
+            store_refresh_token(conn, &session.id, &new_refresh_token, self.token_settings.refresh_token_ttl as usize)?;
+            diesel::update(user_sessions::table.filter(user_sessions::id.eq(session.id)))
+                .set((
+                    user_sessions::last_used_at.eq(now),
+                    user_payload.ip_address.clone().map(|data| user_sessions::ip_address.eq(data)),
+                    user_payload.user_agent.clone().map(|data| user_sessions::user_agent.eq(data)),
+                ))
+                .execute(conn)?;
+
+            Ok(RefreshOutcome::Rotated(UserAccessTokenEntity::new(new_access_token, new_refresh_token)))
+        })?;
+
+        match outcome {
+            RefreshOutcome::Rotated(entity) => Ok(entity),
+            RefreshOutcome::Reused => Err(DomainError::Unauthorized(String::from(REFRESH_TOKEN_REUSED))),
+            RefreshOutcome::Invalid => Err(DomainError::Unauthorized(String::from("Failed refresh token"))),
         }
     }
 
-    async fn update_one_user(&self, user_payload: &UserUpdatePayload) -> Result<UserEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::update_one_user", skip_all)]
+    async fn update_one_user(&self, user_payload: &UserUpdatePayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
         let user_id = Uuid::parse_str(&user_payload.user_id.clone().unwrap_or_default()).unwrap_or_default();
         let target = users::table.filter(id.eq(user_id));
 
         let user = match target.select(User::as_select()).first::<User>(&mut conn) {
             Ok(user) => user,
-            Err(_) => return Err(String::from("Failed refresh token").into()),
+            Err(_) => return Err(DomainError::Unauthorized(String::from("Failed refresh token"))),
         };
 
         let data_my_role = user_payload.role.clone().unwrap_or_default();
         let data_user_role = UserRolePayload::from_str(&user.role).clone().unwrap_or_default();
         let promote_role = &user_payload.role_promote.clone().unwrap_or_default();
+        let data_new_role = promote_role.apply_role_change(data_my_role, data_user_role).to_string();
+        let data_password_hash = match &user_payload.password {
+            Some(data) => Some(hash(data.as_str(), DEFAULT_COST)?),
+            None => None,
+        };
+
+        // a password or role change invalidates every token issued so far
+        let now = Utc::now().naive_utc();
+        let revoke_tokens = data_password_hash.is_some() || data_new_role != user.role;
 
-        let result = diesel::update(target)
-            .set((
-                role.eq(promote_role.apply_role_change(data_my_role, data_user_role).to_string()),
-                user_payload.username.clone().map(|data| username.eq(data.to_string())),
-                user_payload.email.clone().map(|data| email.eq(data.to_string())),
-                user_payload.password.clone().map(|data| password_hash.eq(data.to_string())),
-                updated_at.eq(Utc::now().naive_utc().clone()),
-            ))
-            .returning(User::as_returning())
-            .get_result(&mut conn);
+        let result = conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            if revoke_tokens {
+                revoke_user_sessions(conn, &user.id)?;
+            }
+
+            diesel::update(target)
+                .set((
+                    role.eq(data_new_role),
+                    user_payload.username.clone().map(|data| username.eq(data.to_string())),
+                    user_payload.email.clone().map(|data| email.eq(data.to_string())),
+                    data_password_hash.map(|data| password_hash.eq(data)),
+                    revoke_tokens.then_some(tokens_revoked_at.eq(now)),
+                    updated_at.eq(now),
+                ))
+                .returning(User::as_returning())
+                .get_result(conn)
+        });
 
         match result {
             Ok(model) => Ok(UserDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn get_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::get_users_by_ids", skip_all)]
+    async fn get_users_by_ids(&self, user_ids: &[String]) -> Result<Vec<UserAllEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let data_user_ids: Vec<Uuid> = user_ids.iter().filter_map(|user_id| Uuid::parse_str(user_id).ok()).collect();
+        let results = users.filter(id.eq_any(data_user_ids)).load::<User>(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models.into_iter().map(UserAllDbMapper::to_entity).collect::<Vec<UserAllEntity>>()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "UsersRepository::get_user_by_id", skip_all)]
+    async fn get_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
         let user_id = Uuid::parse_str(&user_payload.user_id)?;
         let result = users.filter(id.eq(user_id)).get_result::<User>(&mut conn);
 
@@ -179,29 +254,88 @@ impl UsersRepositoryAbstract for UsersRepository {
                 entity.fcm_token = None;
                 Ok(entity)
             }
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn delete_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::delete_user_by_id", skip_all)]
+    async fn delete_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
         let user_id = Uuid::parse_str(&user_payload.user_id)?;
         let target_user = users::table.filter(users::id.eq(user_id));
         let result = diesel::delete(target_user).get_result::<User>(&mut conn);
 
         match result {
             Ok(model) => Ok(UserDbMapper::to_entity(model)),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn get_all_users(&self) -> Result<Vec<UserAllEntity>, Box<dyn Error>> {
-        let mut conn = self.db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    #[instrument(name = "UsersRepository::get_all_users", skip_all)]
+    async fn get_all_users(&self) -> Result<Vec<UserAllEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
         let results = users.load::<User>(&mut conn);
 
         match results {
             Ok(models) => Ok(models.into_iter().map(UserAllDbMapper::to_entity).collect::<Vec<UserAllEntity>>()),
-            Err(e) => Err(Box::new(e)),
+            Err(e) => Err(e.into()),
         }
     }
+
+    #[instrument(name = "UsersRepository::get_users_page", skip_all)]
+    async fn get_users_page(&self, page_payload: &UserPagePayload) -> Result<Vec<UserAllEntity>, DomainError> {
+        let mut conn = self.db_connection.get_pool().get()?;
+        let mut query = users.into_boxed();
+        // keyset pagination, pages stay cheap however many users there are
+        if let Some(after) = &page_payload.after {
+            query = query.filter(id.gt(Uuid::parse_str(after)?));
+        }
+        let results = query.order(id.asc()).limit(page_payload.limit).load::<User>(&mut conn);
+
+        match results {
+            Ok(models) => Ok(models.into_iter().map(UserAllDbMapper::to_entity).collect::<Vec<UserAllEntity>>()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// Second step of every sign-in once the user is identified, by password or by an identity provider
+pub fn complete_login(conn: &mut PgConnection, user: User, new_session: &UserSessionNew, token_settings: &TokenSettings) -> Result<UserEntity, DomainError> {
+    // with 2FA on, the first factor only buys a short-lived challenge for the second step
+    if has_confirmed_mfa(conn, &user.id)? {
+        let data_mfa_token = create_mfa_challenge(conn, new_session)?;
+        let mut entity = UserDbMapper::to_entity(user);
+        entity.mfa_token = Some(data_mfa_token);
+        return Ok(entity);
+    }
+
+    let (permissions, enrollment_required) = login_permissions(conn, &user)?;
+    let mut entity = conn.transaction::<_, DomainError, _>(|conn| issue_login(conn, &user, new_session, permissions, token_settings))?;
+    entity.mfa_enrollment_required = enrollment_required;
+    Ok(entity)
+}
+
+// Opens a session for an authenticated user and issues its first token pair
+pub fn issue_login(
+    conn: &mut PgConnection,
+    user: &User,
+    new_session: &UserSessionNew,
+    permissions: HashSet<Permission>,
+    token_settings: &TokenSettings,
+) -> Result<UserEntity, DomainError> {
+    let session = create_session(conn, new_session)?;
+    let data_session_id = session.id.to_string();
+    let data_refresh_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, token_settings.refresh_token_ttl as usize, Some(permissions.clone()), Some(&data_session_id), TokenType::Refresh)?;
+    let data_access_token = AuthUseCase::generate_token(&user.id.to_string(), &user.role, token_settings.access_token_ttl as usize, Some(permissions.clone()), Some(&data_session_id), TokenType::Access)?;
+    store_refresh_token(conn, &session.id, &data_refresh_token, token_settings.refresh_token_ttl as usize)?;
+
+    let model = diesel::update(users.filter(id.eq(user.id)))
+        .set(last_login.eq(Utc::now().naive_utc()))
+        .returning(User::as_returning())
+        .get_result(conn)?;
+
+    let mut entity = UserDbMapper::to_entity(model);
+    entity.refresh_token = Some(data_refresh_token);
+    entity.access_token = Some(data_access_token);
+    Ok(entity)
 }
@@ -0,0 +1,38 @@
+use crate::adapters::spi::db::schema::*;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = import_jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ImportJob {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub source: String,
+    pub state: String,
+    pub total: i32,
+    pub processed: i32,
+    pub project_ids: Vec<Uuid>,
+    pub task_ids: Vec<Uuid>,
+    pub error: Option<String>,
+    pub updated_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = import_jobs)]
+pub struct ImportJobNew<'a> {
+    pub user_id: &'a Uuid,
+    pub source: &'a str,
+}
+
+#[derive(Queryable, Selectable, Insertable)]
+#[diesel(table_name = import_skipped_items)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ImportSkippedItemModel {
+    pub job_id: Uuid,
+    pub position: i32,
+    pub item: String,
+    pub reason: String,
+}
@@ -0,0 +1,34 @@
+use crate::adapters::spi::db::schema::*;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = login_throttles)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LoginThrottle {
+    pub scope: String,
+    pub subject: String,
+    pub failed_count: i32,
+    pub last_failed_at: NaiveDateTime,
+    pub locked_until: Option<NaiveDateTime>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = login_throttles)]
+pub struct LoginThrottleNew<'a> {
+    pub scope: &'a str,
+    pub subject: &'a str,
+    pub failed_count: i32,
+    pub last_failed_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = audit_events)]
+pub struct AuditEventNew<'a> {
+    pub event: &'a str,
+    pub user_id: Option<&'a Uuid>,
+    pub actor_id: Option<&'a Uuid>,
+    pub ip_address: Option<&'a str>,
+    pub details: Option<&'a str>,
+}
@@ -0,0 +1,78 @@
+use crate::adapters::spi::db::schema::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = user_mfa)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserMfa {
+    pub user_id: Uuid,
+    pub secret: String,
+    pub confirmed_at: Option<NaiveDateTime>,
+    pub last_used_step: Option<i64>,
+    pub updated_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = user_mfa)]
+pub struct UserMfaNew<'a> {
+    pub user_id: &'a Uuid,
+    pub secret: &'a str,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = mfa_recovery_codes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MfaRecoveryCode {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub code_hash: String,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = mfa_recovery_codes)]
+pub struct MfaRecoveryCodeNew<'a> {
+    pub user_id: &'a Uuid,
+    pub code_hash: String,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = mfa_challenges)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MfaChallenge {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub device_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub attempts: i32,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = mfa_challenges)]
+pub struct MfaChallengeNew<'a> {
+    pub user_id: &'a Uuid,
+    pub token_hash: &'a str,
+    pub device_name: Option<&'a str>,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = mfa_role_requirements)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MfaRoleRequirement {
+    pub role: String,
+    pub required: bool,
+    pub updated_by: Option<Uuid>,
+    pub updated_at: NaiveDateTime,
+}
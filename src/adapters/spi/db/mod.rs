@@ -1,8 +1,36 @@
 pub mod db_connection;
 pub mod db_users_repository;
 pub mod db_tasks_repository;
+pub mod db_projects_repository;
+pub mod db_tokens_repository;
+pub mod db_sessions_repository;
+pub mod db_account_tokens_repository;
+pub mod db_mfa_repository;
+pub mod db_api_keys_repository;
+pub mod db_permissions_repository;
+pub mod db_oidc_repository;
+pub mod db_login_attempts_repository;
+pub mod db_health_repository;
+pub mod db_metrics_repository;
+pub mod db_calendar_feeds_repository;
+pub mod db_import_jobs_repository;
 pub mod db_users_mappers;
 pub mod db_tasks_mappers;
+pub mod db_projects_mappers;
+pub mod db_sessions_mappers;
+pub mod db_api_keys_mappers;
 pub mod user_model;
 pub mod task_model;
+pub mod project_model;
+pub mod token_model;
+pub mod session_model;
+pub mod account_token_model;
+pub mod mfa_model;
+pub mod api_key_model;
+pub mod permission_model;
+pub mod oidc_model;
+pub mod login_throttle_model;
+pub mod calendar_feed_model;
+pub mod import_job_model;
 pub mod schema;
+pub mod db_errors;
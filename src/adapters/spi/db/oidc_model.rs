@@ -0,0 +1,50 @@
+use crate::adapters::spi::db::schema::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = oidc_login_states)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct OidcLoginState {
+    pub id: Uuid,
+    pub provider: String,
+    pub state_hash: String,
+    pub nonce: String,
+    pub code_verifier: String,
+    pub expires_at: NaiveDateTime,
+    pub used_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = oidc_login_states)]
+pub struct OidcLoginStateNew<'a> {
+    pub provider: &'a str,
+    pub state_hash: &'a str,
+    pub nonce: &'a str,
+    pub code_verifier: &'a str,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = user_identities)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserIdentity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub last_login_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = user_identities)]
+pub struct UserIdentityNew<'a> {
+    pub user_id: &'a Uuid,
+    pub provider: &'a str,
+    pub subject: &'a str,
+    pub email: Option<&'a str>,
+}
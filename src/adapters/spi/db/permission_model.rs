@@ -0,0 +1,19 @@
+use crate::adapters::spi::db::schema::*;
+use diesel::prelude::*;
+use chrono::NaiveDateTime;
+
+#[derive(Queryable, Selectable)]
+#[diesel(table_name = role_permissions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RolePermission {
+    pub role: String,
+    pub permission: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = role_permissions)]
+pub struct RolePermissionNew<'a> {
+    pub role: &'a str,
+    pub permission: &'a str,
+}
@@ -1,3 +1,17 @@
+pub mod sql_types {
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "task_type"))]
+    pub struct TaskType;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "task_priority"))]
+    pub struct TaskPriority;
+
+    #[derive(diesel::query_builder::QueryId, Clone, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "task_status"))]
+    pub struct TaskStatus;
+}
+
 diesel::table! {
     users (id) {
         id -> Uuid,
@@ -5,8 +19,9 @@ diesel::table! {
         email -> Text,
         password_hash -> Text,
         role -> Text,
-        refresh_token -> VarChar,
         fcm_token -> VarChar,
+        tokens_revoked_at -> Nullable<Timestamp>,
+        email_verified_at -> Nullable<Timestamp>,
         last_login -> Timestamp,
         updated_at -> Timestamp,
         created_at -> Timestamp,
@@ -24,14 +39,17 @@ diesel::table! {
 }
 
 diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::{TaskPriority, TaskStatus, TaskType};
+
     tasks (id) {
         id -> Uuid,
         user_id -> Uuid,
         project_id -> Uuid,
         title -> Text,
-        typ -> Nullable<Text>,
-        priority -> Nullable<Text>,
-        status -> Nullable<Text>,
+        typ -> TaskType,
+        priority -> TaskPriority,
+        status -> TaskStatus,
         description -> Text,
         duration -> Nullable<Int4>,
         due_date -> Nullable<BigInt>,
@@ -41,9 +59,231 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    revoked_tokens (jti) {
+        jti -> Uuid,
+        user_id -> Uuid,
+        expires_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_sessions (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        device_name -> Nullable<Text>,
+        ip_address -> Nullable<Text>,
+        user_agent -> Nullable<Text>,
+        revoked_at -> Nullable<Timestamp>,
+        last_used_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    session_refresh_tokens (id) {
+        id -> Uuid,
+        session_id -> Uuid,
+        token_hash -> Text,
+        expires_at -> Timestamp,
+        rotated_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    account_tokens (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        purpose -> Text,
+        token_hash -> Text,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_mfa (user_id) {
+        user_id -> Uuid,
+        secret -> Text,
+        confirmed_at -> Nullable<Timestamp>,
+        last_used_step -> Nullable<Int8>,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mfa_recovery_codes (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        code_hash -> Text,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mfa_challenges (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        token_hash -> Text,
+        device_name -> Nullable<Text>,
+        ip_address -> Nullable<Text>,
+        user_agent -> Nullable<Text>,
+        attempts -> Int4,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    mfa_role_requirements (role) {
+        role -> Text,
+        required -> Bool,
+        updated_by -> Nullable<Uuid>,
+        updated_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    api_keys (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        name -> Text,
+        prefix -> Text,
+        key_hash -> Text,
+        permissions -> Array<Text>,
+        expires_at -> Nullable<Timestamp>,
+        last_used_at -> Nullable<Timestamp>,
+        revoked_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    role_permissions (role, permission) {
+        role -> Text,
+        permission -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    oidc_login_states (id) {
+        id -> Uuid,
+        provider -> Text,
+        state_hash -> Text,
+        nonce -> Text,
+        code_verifier -> Text,
+        expires_at -> Timestamp,
+        used_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    user_identities (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        provider -> Text,
+        subject -> Text,
+        email -> Nullable<Text>,
+        last_login_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    login_throttles (scope, subject) {
+        scope -> Text,
+        subject -> Text,
+        failed_count -> Int4,
+        last_failed_at -> Timestamp,
+        locked_until -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    audit_events (id) {
+        id -> Uuid,
+        event -> Text,
+        user_id -> Nullable<Uuid>,
+        actor_id -> Nullable<Uuid>,
+        ip_address -> Nullable<Text>,
+        details -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    calendar_feeds (user_id) {
+        user_id -> Uuid,
+        token_hash -> Text,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    import_jobs (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        source -> Text,
+        state -> Text,
+        total -> Int4,
+        processed -> Int4,
+        project_ids -> Array<Uuid>,
+        task_ids -> Array<Uuid>,
+        error -> Nullable<Text>,
+        updated_at -> Timestamp,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    import_skipped_items (job_id, position) {
+        job_id -> Uuid,
+        position -> Int4,
+        item -> Text,
+        reason -> Text,
+    }
+}
+
+joinable!(session_refresh_tokens -> user_sessions (session_id));
+joinable!(account_tokens -> users (user_id));
+joinable!(user_mfa -> users (user_id));
+joinable!(mfa_recovery_codes -> users (user_id));
+joinable!(mfa_challenges -> users (user_id));
+joinable!(api_keys -> users (user_id));
+joinable!(user_identities -> users (user_id));
+joinable!(calendar_feeds -> users (user_id));
+joinable!(import_jobs -> users (user_id));
+joinable!(import_skipped_items -> import_jobs (job_id));
+
 // joinable!(tasks -> projects (project_id));
 
 allow_tables_to_appear_in_same_query!(
     tasks,
     projects,
+    users,
+    revoked_tokens,
+    user_sessions,
+    session_refresh_tokens,
+    account_tokens,
+    user_mfa,
+    mfa_recovery_codes,
+    mfa_challenges,
+    mfa_role_requirements,
+    api_keys,
+    role_permissions,
+    oidc_login_states,
+    user_identities,
+    login_throttles,
+    audit_events,
+    calendar_feeds,
+    import_jobs,
+    import_skipped_items,
 );
@@ -0,0 +1,48 @@
+use crate::adapters::spi::db::schema::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Queryable, Selectable, Identifiable)]
+#[diesel(table_name = user_sessions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct UserSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub device_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub last_used_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = user_sessions)]
+pub struct UserSessionNew<'a> {
+    pub user_id: &'a Uuid,
+    pub device_name: Option<&'a str>,
+    pub ip_address: Option<&'a str>,
+    pub user_agent: Option<&'a str>,
+}
+
+#[derive(Queryable, Selectable, Identifiable, Associations)]
+#[diesel(belongs_to(UserSession, foreign_key = session_id))]
+#[diesel(table_name = session_refresh_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SessionRefreshToken {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: NaiveDateTime,
+    pub rotated_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = session_refresh_tokens)]
+pub struct SessionRefreshTokenNew<'a> {
+    pub session_id: &'a Uuid,
+    pub token_hash: &'a str,
+    pub expires_at: NaiveDateTime,
+}
@@ -1,7 +1,19 @@
+use std::io::Write;
+
 use crate::adapters::spi::db::schema::*;
-use diesel::prelude::*;
-use uuid::Uuid;
+use crate::domain::task_entity::{TaskPriority, TaskStatus, TaskType};
 use chrono::NaiveDateTime;
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql, Queryable},
+    expression::AsExpression,
+    internal::derives::as_expression::Bound,
+    pg::{Pg, PgValue},
+    prelude::*,
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types::{Nullable, SingleValue},
+};
+use uuid::Uuid;
 
 #[derive(Queryable, Insertable, Selectable, AsChangeset, QueryableByName)]
 #[diesel(table_name = tasks)]
@@ -11,9 +23,9 @@ pub struct Task {
     pub user_id: Uuid,
     pub project_id: Uuid,
     pub title: String,
-    pub typ: Option<String>,
-    pub priority: Option<String>,
-    pub status: Option<String>,
+    pub typ: TaskType,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: String,
     pub duration: Option<i32>,
     pub due_date: Option<i64>,
@@ -28,9 +40,9 @@ pub struct TaskNew<'a> {
     pub user_id: &'a Uuid,
     pub project_id: &'a Uuid,
     pub title: &'a str,
-    pub typ: &'a str,
-    pub priority: &'a str,
-    pub status: &'a str,
+    pub typ: TaskType,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: &'a str,
     pub duration: i32,
     pub due_date: i64,
@@ -43,12 +55,72 @@ pub struct TaskUpdate<'a> {
     pub user_id: Option<&'a Uuid>,
     pub project_id: Option<&'a Uuid>,
     pub title: &'a str,
-    pub typ: Option<&'a str>,
-    pub priority: Option<&'a str>,
-    pub status: Option<&'a str>,
+    pub typ: Option<TaskType>,
+    pub priority: Option<TaskPriority>,
+    pub status: Option<TaskStatus>,
     pub description: &'a str,
     pub duration: Option<&'a i32>,
     pub due_date: Option<&'a i64>,
     pub task_list: Option<Vec<&'a str>>,
     pub updated_at: NaiveDateTime,
 }
+
+// The domain enums are stored as Postgres enums through their labels, a label the code doesn't know fails the read
+// rather than turning into a default
+macro_rules! impl_task_enum_sql {
+    ($($name:ident),*) => {
+        $(
+            impl ToSql<sql_types::$name, Pg> for $name {
+                fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+                    out.write_all(self.as_str().as_bytes())?;
+                    Ok(IsNull::No)
+                }
+            }
+
+            impl FromSql<sql_types::$name, Pg> for $name {
+                fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+                    Ok(std::str::from_utf8(bytes.as_bytes())?.parse()?)
+                }
+            }
+
+            impl<DB, ST> Queryable<ST, DB> for $name
+            where
+                DB: Backend,
+                ST: SingleValue,
+                Self: FromSql<ST, DB>,
+            {
+                type Row = Self;
+
+                fn build(row: Self) -> deserialize::Result<Self> {
+                    Ok(row)
+                }
+            }
+
+            impl AsExpression<sql_types::$name> for $name {
+                type Expression = Bound<sql_types::$name, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl AsExpression<Nullable<sql_types::$name>> for $name {
+                type Expression = Bound<Nullable<sql_types::$name>, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+
+            impl<'a> AsExpression<sql_types::$name> for &'a $name {
+                type Expression = Bound<sql_types::$name, Self>;
+
+                fn as_expression(self) -> Self::Expression {
+                    Bound::new(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_task_enum_sql!(TaskType, TaskPriority, TaskStatus);
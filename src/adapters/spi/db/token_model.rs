@@ -0,0 +1,22 @@
+use crate::adapters::spi::db::schema::*;
+use diesel::prelude::*;
+use uuid::Uuid;
+use chrono::NaiveDateTime;
+
+#[derive(Queryable, Insertable, Selectable)]
+#[diesel(table_name = revoked_tokens)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RevokedToken {
+    pub jti: Uuid,
+    pub user_id: Uuid,
+    pub expires_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = revoked_tokens)]
+pub struct RevokedTokenNew<'a> {
+    pub jti: &'a Uuid,
+    pub user_id: &'a Uuid,
+    pub expires_at: NaiveDateTime,
+}
@@ -12,8 +12,9 @@ pub struct User {
     pub email: String,
     pub password_hash: String,
     pub role: String,
-    pub refresh_token: String,
     pub fcm_token: String,
+    pub tokens_revoked_at: Option<NaiveDateTime>,
+    pub email_verified_at: Option<NaiveDateTime>,
     pub last_login: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
@@ -28,3 +29,13 @@ pub struct UserRegister<'a> {
     pub role: &'a str,
 }
 
+// Account created on a first single sign-on, its email was verified by the identity provider
+#[derive(Insertable)]
+#[diesel(table_name = users)]
+pub struct UserProvision<'a> {
+    pub username: &'a str,
+    pub email: &'a str,
+    pub password_hash: &'a str,
+    pub role: &'a str,
+    pub email_verified_at: NaiveDateTime,
+}
@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::{error::Error, fs, path::PathBuf};
+use uuid::Uuid;
+
+use super::mailer::{MailMessage, Mailer};
+
+// Mailer for tests and local development: every message is logged and, when a directory is set, written to a file.
+// Bodies hold reset, verification and unlock tokens, only the file gets them.
+pub struct FileMailer {
+    pub directory: Option<PathBuf>,
+}
+
+impl FileMailer {
+    pub fn new(directory: Option<PathBuf>) -> Self {
+        FileMailer { directory }
+    }
+}
+
+#[async_trait(?Send)]
+impl Mailer for FileMailer {
+    async fn send(&self, message: &MailMessage) -> Result<(), Box<dyn Error>> {
+        log::info!("Mail to: {}, subject: {}", message.to, message.subject);
+
+        if let Some(directory) = &self.directory {
+            fs::create_dir_all(directory)?;
+            let file_name = format!("{}_{}.eml", Utc::now().format("%Y%m%d%H%M%S"), Uuid::new_v4().as_simple());
+            let content = format!("To: {}\nSubject: {}\n\n{}\n", message.to, message.subject, message.body);
+            fs::write(directory.join(file_name), content)?;
+        }
+
+        Ok(())
+    }
+}
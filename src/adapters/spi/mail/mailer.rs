@@ -0,0 +1,24 @@
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use std::error::Error;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailMessage {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl MailMessage {
+    pub fn new(to: String, subject: String, body: String) -> Self {
+        MailMessage { to, subject, body }
+    }
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait Mailer {
+    async fn send(&self, message: &MailMessage) -> Result<(), Box<dyn Error>>;
+}
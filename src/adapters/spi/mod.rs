@@ -1,2 +1,5 @@
 pub mod db;
+pub mod mail;
+pub mod oidc;
+pub mod rate_limit;
 // pub mod http;
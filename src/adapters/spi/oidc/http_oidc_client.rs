@@ -0,0 +1,225 @@
+use async_trait::async_trait;
+use jsonwebtoken::{
+    decode, decode_header,
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey, Validation,
+};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Deserializer};
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::domain::oidc_entity::OidcIdentityEntity;
+
+use super::oidc_client::{OidcClient, OidcProviderConfig};
+
+// Discovery documents and key sets are fetched again after this long, or right away for an unknown `kid`
+const METADATA_TTL: Duration = Duration::from_secs(3600);
+
+// Asymmetric algorithms only, a client secret is never accepted as an ID token key
+const ID_TOKEN_ALGORITHMS: [Algorithm; 9] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+#[derive(Deserialize)]
+struct ProviderDiscovery {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+struct ProviderMetadata {
+    discovery: ProviderDiscovery,
+    jwks: JwkSet,
+    fetched_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    azp: Option<String>,
+    email: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_flag")]
+    email_verified: bool,
+    name: Option<String>,
+    preferred_username: Option<String>,
+}
+
+// OpenID Connect client for providers supporting discovery, the authorization code flow and PKCE
+pub struct HttpOidcClient {
+    providers: HashMap<String, OidcProviderConfig>,
+    http_client: Client,
+    metadata: RwLock<HashMap<String, Arc<ProviderMetadata>>>,
+}
+
+impl HttpOidcClient {
+    pub fn new(providers: Vec<OidcProviderConfig>) -> Result<Self, reqwest::Error> {
+        Ok(HttpOidcClient {
+            providers: providers.into_iter().map(|provider| (provider.name.clone(), provider)).collect(),
+            http_client: Client::builder().timeout(Duration::from_secs(10)).build()?,
+            metadata: RwLock::new(HashMap::new()),
+        })
+    }
+
+    fn provider(&self, name: &str) -> Result<&OidcProviderConfig, Box<dyn Error>> {
+        self.providers.get(name).ok_or_else(|| format!("Unknown identity provider {}", name).into())
+    }
+
+    async fn metadata(&self, provider: &OidcProviderConfig, refresh: bool) -> Result<Arc<ProviderMetadata>, Box<dyn Error>> {
+        if !refresh {
+            if let Some(metadata) = self.metadata.read().unwrap().get(&provider.name) {
+                if metadata.fetched_at.elapsed() < METADATA_TTL {
+                    return Ok(metadata.clone());
+                }
+            }
+        }
+
+        let discovery_url = format!("{}/.well-known/openid-configuration", provider.issuer.trim_end_matches('/'));
+        let discovery = self.http_client.get(discovery_url).send().await?.error_for_status()?.json::<ProviderDiscovery>().await?;
+        // the discovery document must describe the configured issuer, never another tenant of the same host
+        if discovery.issuer != provider.issuer {
+            return Err(format!("Identity provider {} advertises issuer {}", provider.name, discovery.issuer).into());
+        }
+        let jwks = self.http_client.get(&discovery.jwks_uri).send().await?.error_for_status()?.json::<JwkSet>().await?;
+
+        let metadata = Arc::new(ProviderMetadata {
+            discovery,
+            jwks,
+            fetched_at: Instant::now(),
+        });
+        self.metadata.write().unwrap().insert(provider.name.clone(), metadata.clone());
+        Ok(metadata)
+    }
+
+    async fn validate_id_token(&self, provider: &OidcProviderConfig, metadata: &ProviderMetadata, id_token: &str, nonce: &str) -> Result<IdTokenClaims, Box<dyn Error>> {
+        let header = decode_header(id_token)?;
+        if !ID_TOKEN_ALGORITHMS.contains(&header.alg) {
+            return Err(format!("ID token signed with unsupported algorithm {:?}", header.alg).into());
+        }
+
+        // an unknown kid usually means the provider rotated its keys since the last fetch
+        let jwk = match find_jwk(&metadata.jwks, header.kid.as_deref()) {
+            Some(jwk) => jwk.clone(),
+            None => {
+                let metadata = self.metadata(provider, true).await?;
+                find_jwk(&metadata.jwks, header.kid.as_deref()).cloned().ok_or("ID token signed with an unknown key")?
+            }
+        };
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&metadata.discovery.issuer]);
+        validation.set_audience(&[&provider.client_id]);
+        validation.set_required_spec_claims(&["exp", "iat", "iss", "aud", "sub"]);
+        let claims = decode::<IdTokenClaims>(id_token, &DecodingKey::from_jwk(&jwk)?, &validation)?.claims;
+
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(String::from("ID token nonce mismatch").into());
+        }
+        if claims.azp.as_ref().is_some_and(|azp| azp != &provider.client_id) {
+            return Err(String::from("ID token issued to another client").into());
+        }
+
+        Ok(claims)
+    }
+}
+
+#[async_trait(?Send)]
+impl OidcClient for HttpOidcClient {
+    fn provider_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.providers.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    fn trusts_email(&self, provider: &str) -> bool {
+        self.providers.get(provider).is_some_and(|provider| provider.trust_email)
+    }
+
+    async fn authorization_url(&self, provider: &str, state: &str, nonce: &str, code_challenge: &str) -> Result<String, Box<dyn Error>> {
+        let provider = self.provider(provider)?;
+        let metadata = self.metadata(provider, false).await?;
+
+        let url = Url::parse_with_params(
+            &metadata.discovery.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", provider.client_id.as_str()),
+                ("redirect_uri", provider.redirect_uri.as_str()),
+                ("scope", provider.scopes.as_str()),
+                ("state", state),
+                ("nonce", nonce),
+                ("code_challenge", code_challenge),
+                ("code_challenge_method", "S256"),
+            ],
+        )?;
+        Ok(url.to_string())
+    }
+
+    async fn exchange_code(&self, provider: &str, code: &str, code_verifier: &str, nonce: &str) -> Result<OidcIdentityEntity, Box<dyn Error>> {
+        let provider = self.provider(provider)?;
+        let metadata = self.metadata(provider, false).await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", provider.redirect_uri.as_str()),
+            ("client_id", provider.client_id.as_str()),
+            ("code_verifier", code_verifier),
+        ];
+        if let Some(client_secret) = &provider.client_secret {
+            form.push(("client_secret", client_secret.as_str()));
+        }
+
+        let response = self.http_client.post(&metadata.discovery.token_endpoint).form(&form).send().await?;
+        if !response.status().is_success() {
+            return Err(format!("Token endpoint of {} answered {}", provider.name, response.status()).into());
+        }
+        let id_token = response.json::<TokenResponse>().await?.id_token.ok_or("Token response without an ID token")?;
+        let claims = self.validate_id_token(provider, &metadata, &id_token, nonce).await?;
+
+        Ok(OidcIdentityEntity::new(
+            provider.name.clone(),
+            claims.sub,
+            claims.email,
+            claims.email_verified,
+            claims.name.or(claims.preferred_username),
+        ))
+    }
+}
+
+fn find_jwk<'a>(jwks: &'a JwkSet, kid: Option<&str>) -> Option<&'a Jwk> {
+    match kid {
+        Some(kid) => jwks.find(kid),
+        // without a kid only an unambiguous single key set can be used
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+}
+
+// Some providers send `email_verified` as the string "true"
+fn deserialize_flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Bool(flag) => Ok(flag),
+        serde_json::Value::String(flag) => Ok(flag == "true"),
+        _ => Ok(false),
+    }
+}
@@ -0,0 +1,2 @@
+pub mod oidc_client;
+pub mod http_oidc_client;
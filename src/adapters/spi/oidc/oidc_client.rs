@@ -0,0 +1,30 @@
+use async_trait::async_trait;
+
+use crate::domain::oidc_entity::OidcIdentityEntity;
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use std::error::Error;
+
+#[derive(Debug, Clone)]
+pub struct OidcProviderConfig {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    // Confidential clients only, public clients rely on PKCE alone
+    pub client_secret: Option<String>,
+    pub scopes: String,
+    pub redirect_uri: String,
+    // Whether the verified email of an identity is enough to attach it to an existing account
+    pub trust_email: bool,
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait OidcClient {
+    fn provider_names(&self) -> Vec<String>;
+    fn trusts_email(&self, provider: &str) -> bool;
+    async fn authorization_url(&self, provider: &str, state: &str, nonce: &str, code_challenge: &str) -> Result<String, Box<dyn Error>>;
+    // Redeems the code and returns the identity of a fully validated ID token
+    async fn exchange_code(&self, provider: &str, code: &str, code_verifier: &str, nonce: &str) -> Result<OidcIdentityEntity, Box<dyn Error>>;
+}
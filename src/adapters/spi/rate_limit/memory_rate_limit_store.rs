@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use std::{collections::HashMap, error::Error, sync::Mutex, time::Instant};
+
+use super::rate_limit_store::{RateLimitDecision, RateLimitStore};
+
+// Buckets are swept once the map grows past this many keys
+const SWEEP_THRESHOLD: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+    window: u64,
+}
+
+// Buckets kept in process, every replica counts on its own
+pub struct MemoryRateLimitStore {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl MemoryRateLimitStore {
+    pub fn new() -> Self {
+        MemoryRateLimitStore {
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for MemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait(?Send)]
+impl RateLimitStore for MemoryRateLimitStore {
+    async fn take(&self, key: &str, limit: u64, window: u64) -> Result<RateLimitDecision, Box<dyn Error>> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let now = Instant::now();
+
+        if buckets.len() >= SWEEP_THRESHOLD {
+            // a bucket idle for a whole window is full again, forgetting it changes nothing
+            buckets.retain(|_, bucket| now.duration_since(bucket.updated_at).as_secs() < bucket.window);
+        }
+
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: limit as f64,
+            updated_at: now,
+            window,
+        });
+        let elapsed_seconds = now.duration_since(bucket.updated_at).as_secs_f64();
+        let (tokens, decision) = RateLimitDecision::take(bucket.tokens, elapsed_seconds, limit, window);
+        bucket.tokens = tokens;
+        bucket.updated_at = now;
+        bucket.window = window;
+
+        Ok(decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_should_count_each_key_in_its_own_bucket() {
+        // given a store and a limit of 2 requests per minute
+        let store = MemoryRateLimitStore::new();
+
+        // when one key takes three tokens and another key one
+        let first = store.take("ip:203.0.113.7", 2, 60).await.unwrap();
+        let second = store.take("ip:203.0.113.7", 2, 60).await.unwrap();
+        let third = store.take("ip:203.0.113.7", 2, 60).await.unwrap();
+        let other = store.take("ip:198.51.100.4", 2, 60).await.unwrap();
+
+        // then expect only the third take of the first key refused
+        assert!(first.allowed && first.remaining == 1);
+        assert!(second.allowed && second.remaining == 0);
+        assert!(!third.allowed);
+        assert_eq!(third.retry_after, 30);
+        assert!(other.allowed && other.remaining == 1);
+    }
+}
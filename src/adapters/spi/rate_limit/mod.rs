@@ -0,0 +1,3 @@
+pub mod memory_rate_limit_store;
+pub mod rate_limit_store;
+pub mod redis_rate_limit_store;
@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use std::error::Error;
+
+// Outcome of taking one token from a bucket holding `limit` tokens refilled evenly over `window` seconds
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub remaining: u64,
+    // seconds until the bucket is full again
+    pub reset_after: u64,
+    // seconds until the next token, zero when allowed
+    pub retry_after: u64,
+}
+
+impl RateLimitDecision {
+    pub fn new(allowed: bool, remaining: u64, reset_after: u64, retry_after: u64) -> Self {
+        RateLimitDecision {
+            allowed,
+            remaining,
+            reset_after,
+            retry_after,
+        }
+    }
+
+    // Token bucket step shared by the stores: refill for the elapsed time, then take a token if there is one
+    pub fn take(tokens: f64, elapsed_seconds: f64, limit: u64, window: u64) -> (f64, Self) {
+        let refill_rate = limit as f64 / window as f64;
+        let tokens = (tokens + elapsed_seconds.max(0.0) * refill_rate).min(limit as f64);
+
+        if tokens >= 1.0 {
+            let tokens = tokens - 1.0;
+            let reset_after = ((limit as f64 - tokens) / refill_rate).ceil() as u64;
+            (tokens, RateLimitDecision::new(true, tokens.floor() as u64, reset_after, 0))
+        } else {
+            let reset_after = ((limit as f64 - tokens) / refill_rate).ceil() as u64;
+            let retry_after = ((1.0 - tokens) / refill_rate).ceil().max(1.0) as u64;
+            (tokens, RateLimitDecision::new(false, 0, reset_after, retry_after))
+        }
+    }
+}
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait RateLimitStore {
+    async fn take(&self, key: &str, limit: u64, window: u64) -> Result<RateLimitDecision, Box<dyn Error>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_take_a_token_from_a_full_bucket() {
+        // given a full bucket of 10 tokens per minute
+
+        // when taking one right away
+        let (tokens, decision) = RateLimitDecision::take(10.0, 0.0, 10, 60);
+
+        // then expect 9 left, full again 6 seconds later
+        assert_eq!(tokens, 9.0);
+        assert_eq!(decision, RateLimitDecision::new(true, 9, 6, 0));
+    }
+
+    #[test]
+    fn test_should_refuse_an_empty_bucket_until_the_next_token() {
+        // given an empty bucket of 10 tokens per minute, refilled one token every 6 seconds
+
+        // when taking one right away
+        let (tokens, decision) = RateLimitDecision::take(0.0, 0.0, 10, 60);
+
+        // then expect a refusal telling when the next token and the full bucket come
+        assert_eq!(tokens, 0.0);
+        assert_eq!(decision, RateLimitDecision::new(false, 0, 60, 6));
+    }
+
+    #[test]
+    fn test_should_refill_for_the_elapsed_time() {
+        // given an empty bucket left alone for 12 seconds, two tokens at 10 per minute
+
+        // when taking one
+        let (tokens, decision) = RateLimitDecision::take(0.0, 12.0, 10, 60);
+
+        // then expect one of the two refilled tokens left
+        assert_eq!(tokens, 1.0);
+        assert_eq!(decision, RateLimitDecision::new(true, 1, 54, 0));
+    }
+
+    #[test]
+    fn test_should_not_refill_past_the_limit() {
+        // given a half full bucket left alone for much longer than a window
+
+        // when taking one
+        let (tokens, decision) = RateLimitDecision::take(5.0, 1000.0, 10, 60);
+
+        // then expect the bucket to have been full, not over full
+        assert_eq!(tokens, 9.0);
+        assert_eq!(decision.remaining, 9);
+    }
+
+    #[test]
+    fn test_should_ignore_clock_going_backwards() {
+        // given half a token and an elapsed time below zero
+
+        // when taking one
+        let (tokens, decision) = RateLimitDecision::take(0.5, -5.0, 10, 60);
+
+        // then expect the half token untouched and a wait for the other half
+        assert_eq!(tokens, 0.5);
+        assert_eq!(decision, RateLimitDecision::new(false, 0, 57, 3));
+    }
+}
@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use redis::{aio::ConnectionManager, Client, Script};
+use std::error::Error;
+use tokio::sync::OnceCell;
+
+use super::rate_limit_store::{RateLimitDecision, RateLimitStore};
+
+// Same bucket arithmetic as `RateLimitDecision::take`, run atomically next to the data so replicas share one count.
+// Redis' own clock is used so replicas with skewed clocks agree.
+const TAKE_SCRIPT: &str = r#"
+local limit = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) + tonumber(time[2]) / 1000000
+local rate = limit / window
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(bucket[1]) or limit
+local updated_at = tonumber(bucket[2]) or now
+tokens = math.min(limit, tokens + math.max(0, now - updated_at) * rate)
+
+local allowed = 0
+if tokens >= 1 then
+  tokens = tokens - 1
+  allowed = 1
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', tostring(now))
+redis.call('EXPIRE', KEYS[1], window)
+
+local reset_after = math.ceil((limit - tokens) / rate)
+local retry_after = 0
+if allowed == 0 then
+  retry_after = math.max(1, math.ceil((1 - tokens) / rate))
+end
+return {allowed, math.floor(tokens), reset_after, retry_after}
+"#;
+
+const KEY_PREFIX: &str = "rate_limit:";
+
+// Buckets shared by every replica through Redis, the connection is opened on first use
+pub struct RedisRateLimitStore {
+    client: Client,
+    connection: OnceCell<ConnectionManager>,
+    script: Script,
+}
+
+impl RedisRateLimitStore {
+    pub fn new(redis_url: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(RedisRateLimitStore {
+            client: Client::open(redis_url)?,
+            connection: OnceCell::new(),
+            script: Script::new(TAKE_SCRIPT),
+        })
+    }
+}
+
+#[async_trait(?Send)]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn take(&self, key: &str, limit: u64, window: u64) -> Result<RateLimitDecision, Box<dyn Error>> {
+        let connection = self.connection.get_or_try_init(|| ConnectionManager::new(self.client.clone())).await?;
+        let mut connection = connection.clone();
+
+        let (allowed, remaining, reset_after, retry_after): (u8, u64, u64, u64) =
+            self.script.key(format!("{}{}", KEY_PREFIX, key)).arg(limit).arg(window).invoke_async(&mut connection).await?;
+
+        Ok(RateLimitDecision::new(allowed == 1, remaining, reset_after, retry_after))
+    }
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::api::users::users_payloads::{UserForgotPasswordPayload, UserIdPayload, UserResetPasswordPayload, UserVerifyEmailPayload},
+    domain::{account_token_entity::AccountTokenEntity, user_entity::UserEntity},
+};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait AccountTokensRepositoryAbstract {
+    async fn create_email_verification(&self, user_payload: &UserIdPayload) -> Result<AccountTokenEntity, DomainError>;
+    async fn verify_email(&self, user_payload: &UserVerifyEmailPayload) -> Result<UserEntity, DomainError>;
+    async fn create_password_reset(&self, user_payload: &UserForgotPasswordPayload) -> Result<Option<AccountTokenEntity>, DomainError>;
+    async fn reset_password(&self, user_payload: &UserResetPasswordPayload) -> Result<UserEntity, DomainError>;
+}
@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::api::api_keys::api_keys_payloads::{ApiKeyCreatePayload, ApiKeyDataPayload},
+    domain::api_key_entity::ApiKeyEntity,
+};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait ApiKeysRepositoryAbstract {
+    async fn create_api_key(&self, api_key_payload: &ApiKeyCreatePayload) -> Result<ApiKeyEntity, DomainError>;
+    async fn get_all_api_keys(&self, api_key_payload: &ApiKeyDataPayload) -> Result<Vec<ApiKeyEntity>, DomainError>;
+    async fn revoke_api_key(&self, api_key_payload: &ApiKeyDataPayload) -> Result<ApiKeyEntity, DomainError>;
+    async fn authenticate_api_key(&self, key: &str) -> Result<ApiKeyEntity, DomainError>;
+}
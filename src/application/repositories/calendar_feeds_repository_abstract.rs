@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::calendar_feed_entity::CalendarFeedEntity;
+
+use crate::domain::error::DomainError;
+#[cfg(test)]
+use mockall::{predicate::*, *};
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait CalendarFeedsRepositoryAbstract {
+    // Replaces the token of the user's feed, creating the feed when there is none
+    async fn regenerate_feed(&self, user_id: &str) -> Result<CalendarFeedEntity, DomainError>;
+    async fn get_feed_by_token(&self, token: &str) -> Result<CalendarFeedEntity, DomainError>;
+    async fn revoke_feed(&self, user_id: &str) -> Result<(), DomainError>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait HealthRepositoryAbstract {
+    async fn ping_database(&self) -> Result<(), DomainError>;
+    // versions of the migrations shipped with the binary that the database hasn't run yet
+    async fn get_pending_migrations(&self) -> Result<Vec<String>, DomainError>;
+}
@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+
+use crate::domain::import_entity::{ImportJobEntity, ImportSkippedItem, ImportSource};
+
+use crate::domain::error::DomainError;
+#[cfg(test)]
+use mockall::{predicate::*, *};
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait ImportJobsRepositoryAbstract {
+    // One import at a time per user, a conflict while another one is queued or running
+    async fn queue_import_job(&self, user_id: &str, source: ImportSource, skipped: &[ImportSkippedItem]) -> Result<ImportJobEntity, DomainError>;
+    // Jobs of other users are as missing as unknown ones
+    async fn get_import_job(&self, job_id: &str, user_id: &str) -> Result<ImportJobEntity, DomainError>;
+    async fn start_import_job(&self, job_id: &str, total: usize) -> Result<(), DomainError>;
+    async fn add_import_project(&self, job_id: &str, project_id: &str) -> Result<(), DomainError>;
+    async fn add_import_tasks(&self, job_id: &str, task_ids: &[String]) -> Result<(), DomainError>;
+    async fn complete_import_job(&self, job_id: &str) -> Result<(), DomainError>;
+    async fn fail_import_job(&self, job_id: &str, message: &str) -> Result<(), DomainError>;
+}
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::api::users::users_payloads::{UserLoginAttemptPayload, UserUnlockAccountPayload, UserUnlockPayload},
+    domain::{account_token_entity::AccountTokenEntity, login_throttle_entity::LoginThrottleEntity},
+};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait LoginAttemptsRepositoryAbstract {
+    // Counts the attempt before the password is checked, concurrent attempts each get their own number
+    async fn count_login_attempt(&self, attempt_payload: &UserLoginAttemptPayload) -> Result<LoginThrottleEntity, DomainError>;
+    // Locks the counters this failure brought to the limit, returns an unlock token when a registered account got locked
+    async fn record_failed_login(&self, attempt_payload: &UserLoginAttemptPayload) -> Result<Option<AccountTokenEntity>, DomainError>;
+    async fn record_successful_login(&self, attempt_payload: &UserLoginAttemptPayload) -> Result<(), DomainError>;
+    async fn unlock_account(&self, user_payload: &UserUnlockAccountPayload) -> Result<(), DomainError>;
+    async fn unlock_user(&self, user_payload: &UserUnlockPayload) -> Result<(), DomainError>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::domain::metrics_entity::{DbPoolStatsEntity, TaskStatusCountEntity};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait MetricsRepositoryAbstract {
+    async fn get_db_pool_stats(&self) -> Result<DbPoolStatsEntity, DomainError>;
+    async fn count_open_tasks_by_status(&self) -> Result<Vec<TaskStatusCountEntity>, DomainError>;
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::api::{
+        mfa::mfa_payloads::{MfaCodePayload, MfaLoginPayload, MfaRoleRequirementPayload},
+        users::users_payloads::UserIdPayload,
+    },
+    domain::{
+        mfa_entity::{MfaEnrollmentEntity, MfaRecoveryCodesEntity, MfaRoleRequirementEntity},
+        user_entity::UserEntity,
+    },
+};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait MfaRepositoryAbstract {
+    async fn enroll(&self, user_payload: &UserIdPayload) -> Result<MfaEnrollmentEntity, DomainError>;
+    async fn confirm(&self, mfa_payload: &MfaCodePayload) -> Result<MfaRecoveryCodesEntity, DomainError>;
+    async fn disable(&self, mfa_payload: &MfaCodePayload) -> Result<(), DomainError>;
+    async fn login(&self, mfa_payload: &MfaLoginPayload) -> Result<UserEntity, DomainError>;
+    async fn update_role_requirement(&self, mfa_payload: &MfaRoleRequirementPayload) -> Result<MfaRoleRequirementEntity, DomainError>;
+}
@@ -1,2 +1,15 @@
 pub mod users_repository_abstract;
 pub mod tasks_repository_abstract;
+pub mod projects_repository_abstract;
+pub mod tokens_repository_abstract;
+pub mod sessions_repository_abstract;
+pub mod account_tokens_repository_abstract;
+pub mod mfa_repository_abstract;
+pub mod api_keys_repository_abstract;
+pub mod permissions_repository_abstract;
+pub mod oidc_repository_abstract;
+pub mod login_attempts_repository_abstract;
+pub mod health_repository_abstract;
+pub mod metrics_repository_abstract;
+pub mod calendar_feeds_repository_abstract;
+pub mod import_jobs_repository_abstract;
@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::api::oidc::oidc_payloads::{OidcCallbackPayload, OidcIdentityPayload, OidcLoginStatePayload},
+    domain::{oidc_entity::OidcLoginStateEntity, user_entity::UserEntity},
+};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait OidcRepositoryAbstract {
+    async fn create_login_state(&self, oidc_payload: &OidcLoginStatePayload) -> Result<(), DomainError>;
+    async fn consume_login_state(&self, oidc_payload: &OidcCallbackPayload) -> Result<Option<OidcLoginStateEntity>, DomainError>;
+    async fn login_identity(&self, oidc_payload: &OidcIdentityPayload) -> Result<UserEntity, DomainError>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::{adapters::api::permissions::permissions_payloads::RolePermissionsPayload, domain::permission_entity::RolePermissionsEntity};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait PermissionsRepositoryAbstract {
+    async fn get_all_role_permissions(&self) -> Result<Vec<RolePermissionsEntity>, DomainError>;
+    async fn update_role_permissions(&self, permission_payload: &RolePermissionsPayload) -> Result<RolePermissionsEntity, DomainError>;
+}
@@ -0,0 +1,15 @@
+use async_trait::async_trait;
+
+use crate::domain::project_entity::ProjectEntity;
+
+use crate::domain::error::DomainError;
+#[cfg(test)]
+use mockall::{predicate::*, *};
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait ProjectsRepositoryAbstract {
+    // Unknown ids are left out of the result rather than reported
+    async fn get_projects_by_ids(&self, project_ids: &[String]) -> Result<Vec<ProjectEntity>, DomainError>;
+    async fn post_project<'a>(&self, name: &str, description: Option<&'a str>) -> Result<ProjectEntity, DomainError>;
+}
@@ -0,0 +1,14 @@
+use async_trait::async_trait;
+
+use crate::{adapters::api::sessions::sessions_payloads::SessionDataPayload, domain::session_entity::SessionEntity};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait SessionsRepositoryAbstract {
+    async fn get_all_sessions(&self, session_payload: &SessionDataPayload) -> Result<Vec<SessionEntity>, DomainError>;
+    async fn revoke_session(&self, session_payload: &SessionDataPayload) -> Result<SessionEntity, DomainError>;
+}
@@ -4,14 +4,18 @@ use crate::{adapters::api::tasks::tasks_payloads::*, domain::task_entity::*};
 
 #[cfg(test)]
 use mockall::{predicate::*, *};
-use std::error::Error;
+use crate::domain::error::DomainError;
 
 #[cfg_attr(test, automock)]
 #[async_trait(?Send)]
 pub trait TasksRepositoryAbstract {
-    async fn post_one_task(&self, task_payload: &TaskCreatePayload) -> Result<TaskEntity, Box<dyn Error>>;
-    async fn update_one_task(&self, task_payload: &TaskUpdatePayload) -> Result<TaskEntity, Box<dyn Error>>;
-    async fn get_all_tasks(&self, task_payload: &TaskDataPayload) -> Result<Vec<TaskAllEntity>, Box<dyn Error>>;
-    async fn get_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<TaskEntity, Box<dyn Error>>;
-    async fn delete_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<TaskEntity, Box<dyn Error>>;
+    async fn post_one_task(&self, task_payload: &TaskCreatePayload) -> Result<TaskEntity, DomainError>;
+    async fn update_one_task(&self, task_payload: &TaskUpdatePayload) -> Result<TaskEntity, DomainError>;
+    async fn get_all_tasks(&self, task_payload: &TaskDataPayload) -> Result<Vec<TaskAllEntity>, DomainError>;
+    async fn get_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<Option<TaskEntity>, DomainError>;
+    async fn delete_task_by_id(&self, task_payload: &TaskDataPayload) -> Result<TaskEntity, DomainError>;
+    async fn get_tasks_page(&self, page_payload: &TaskPagePayload) -> Result<Vec<TaskEntity>, DomainError>;
+    async fn get_task_list_page(&self, page_payload: &TaskListPagePayload) -> Result<Vec<TaskAllEntity>, DomainError>;
+    // All or none, in one transaction
+    async fn post_tasks(&self, task_payloads: &[TaskCreatePayload]) -> Result<Vec<TaskEntity>, DomainError>;
 }
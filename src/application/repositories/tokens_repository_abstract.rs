@@ -0,0 +1,19 @@
+use async_trait::async_trait;
+
+use crate::{
+    adapters::api::users::users_payloads::{UserIdPayload, UserLogoutPayload},
+    domain::token_entity::RevocationListEntity,
+};
+
+#[cfg(test)]
+use mockall::{predicate::*, *};
+use crate::domain::error::DomainError;
+
+#[cfg_attr(test, automock)]
+#[async_trait(?Send)]
+pub trait TokensRepositoryAbstract {
+    async fn revoke_token(&self, user_payload: &UserLogoutPayload) -> Result<(), DomainError>;
+    async fn revoke_all_user_tokens(&self, user_payload: &UserIdPayload) -> Result<i64, DomainError>;
+    async fn get_revocation_list(&self) -> Result<RevocationListEntity, DomainError>;
+    async fn purge_expired_tokens(&self) -> Result<usize, DomainError>;
+}
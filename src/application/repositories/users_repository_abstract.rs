@@ -7,16 +7,22 @@ use crate::{
 
 #[cfg(test)]
 use mockall::{predicate::*, *};
-use std::error::Error;
+use crate::domain::error::DomainError;
+
+// Message of the error `get_refresh` returns when a rotated refresh token is replayed, its session is revoked by then
+pub const REFRESH_TOKEN_REUSED: &str = "Refresh token reuse detected, session revoked";
 
 #[cfg_attr(test, automock)]
 #[async_trait(?Send)]
 pub trait UsersRepositoryAbstract {
-    async fn get_refresh(&self, user_payload: &UserRefreshTokenPayload) -> Result<UserAccessTokenEntity, Box<dyn Error>>;
-    async fn register_user(&self, user_payload: &UserRegisterPayload) -> Result<UserEntity, Box<dyn Error>>;
-    async fn login_user(&self, user_payload: &UserLoginPayload) -> Result<UserEntity, Box<dyn Error>>;
-    async fn update_one_user(&self, user_payload: &UserUpdatePayload) -> Result<UserEntity, Box<dyn Error>>;
-    async fn get_all_users(&self) -> Result<Vec<UserAllEntity>, Box<dyn Error>>;
-    async fn get_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, Box<dyn Error>>;
-    async fn delete_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, Box<dyn Error>>;
+    async fn get_refresh(&self, user_payload: &UserRefreshTokenPayload) -> Result<UserAccessTokenEntity, DomainError>;
+    async fn register_user(&self, user_payload: &UserRegisterPayload) -> Result<UserEntity, DomainError>;
+    async fn login_user(&self, user_payload: &UserLoginPayload) -> Result<UserEntity, DomainError>;
+    async fn update_one_user(&self, user_payload: &UserUpdatePayload) -> Result<UserEntity, DomainError>;
+    async fn get_all_users(&self) -> Result<Vec<UserAllEntity>, DomainError>;
+    async fn get_users_page(&self, page_payload: &UserPagePayload) -> Result<Vec<UserAllEntity>, DomainError>;
+    // Unknown ids are left out of the result rather than reported
+    async fn get_users_by_ids(&self, user_ids: &[String]) -> Result<Vec<UserAllEntity>, DomainError>;
+    async fn get_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, DomainError>;
+    async fn delete_user_by_id(&self, user_payload: &UserIdPayload) -> Result<UserEntity, DomainError>;
 }
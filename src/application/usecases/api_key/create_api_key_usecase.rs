@@ -0,0 +1,92 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::api_keys::api_keys_payloads::ApiKeyCreatePayload,
+    application::{repositories::api_keys_repository_abstract::ApiKeysRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{api_key_entity::ApiKeyEntity, error::ApiError},
+};
+
+pub struct CreateApiKeyUseCase<'a> {
+    api_key_payload: &'a ApiKeyCreatePayload,
+    repository: &'a dyn ApiKeysRepositoryAbstract,
+}
+
+impl<'a> CreateApiKeyUseCase<'a> {
+    pub fn new(api_key_payload: &'a ApiKeyCreatePayload, repository: &'a dyn ApiKeysRepositoryAbstract) -> Self {
+        CreateApiKeyUseCase { api_key_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<ApiKeyEntity> for CreateApiKeyUseCase<'a> {
+    #[instrument(name = "CreateApiKeyUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<ApiKeyEntity, ApiError> {
+        let api_key = self.repository.create_api_key(self.api_key_payload).await;
+
+        match api_key {
+            Ok(api_key) => Ok(api_key),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot create API key", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::api_keys_repository_abstract::MockApiKeysRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "create api key" usecase repo with an unexpected random error
+        let mut api_key_repository = MockApiKeysRepositoryAbstract::new();
+        let payload = ApiKeyCreatePayload::new(Some(String::from("id1")), String::from("ci"), None, None);
+        api_key_repository
+            .expect_create_api_key()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let create_api_key_usecase = CreateApiKeyUseCase::new(&payload, &api_key_repository);
+        let data = create_api_key_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot create API key", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_plain_key_once() {
+        // given the "create api key" usecase repo storing a scoped key
+        let mut api_key_repository = MockApiKeysRepositoryAbstract::new();
+        let payload = ApiKeyCreatePayload::new(Some(String::from("id1")), String::from("ci"), Some(vec![String::from("tasks:read")]), None);
+        api_key_repository.expect_create_api_key().times(1).returning(|payload| {
+            Ok(ApiKeyEntity::new(
+                String::from("key1"),
+                payload.user_id.clone().unwrap_or_default(),
+                String::from("customer"),
+                payload.name.clone(),
+                String::from("tt_abcdef"),
+                Some(String::from("tt_abcdefghijklmnopqrstuvwxyz")),
+                payload.permissions.clone().unwrap_or_default(),
+                None,
+                None,
+                None,
+                Utc::now().naive_utc(),
+            ))
+        });
+
+        // when calling usecase
+        let create_api_key_usecase = CreateApiKeyUseCase::new(&payload, &api_key_repository);
+        let data = create_api_key_usecase.execute().await.unwrap();
+
+        // then assert the plain key and its scope are returned
+        assert_eq!(data.key, Some(String::from("tt_abcdefghijklmnopqrstuvwxyz")));
+        assert_eq!(data.permissions, vec![String::from("tasks:read")]);
+    }
+}
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::api_keys::api_keys_payloads::ApiKeyDataPayload,
+    application::{repositories::api_keys_repository_abstract::ApiKeysRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{api_key_entity::ApiKeyEntity, error::ApiError},
+};
+
+pub struct GetAllApiKeysUseCase<'a> {
+    api_key_payload: &'a ApiKeyDataPayload,
+    repository: &'a dyn ApiKeysRepositoryAbstract,
+}
+
+impl<'a> GetAllApiKeysUseCase<'a> {
+    pub fn new(api_key_payload: &'a ApiKeyDataPayload, repository: &'a dyn ApiKeysRepositoryAbstract) -> Self {
+        GetAllApiKeysUseCase { api_key_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<ApiKeyEntity>> for GetAllApiKeysUseCase<'a> {
+    #[instrument(name = "GetAllApiKeysUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<ApiKeyEntity>, ApiError> {
+        let api_keys = self.repository.get_all_api_keys(self.api_key_payload).await;
+
+        match api_keys {
+            Ok(api_keys) => Ok(api_keys),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get all API keys", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::api_keys_repository_abstract::MockApiKeysRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "all api keys" usecase repo with an unexpected random error
+        let mut api_key_repository = MockApiKeysRepositoryAbstract::new();
+        let payload = ApiKeyDataPayload::new(None, Some(String::from("id1")));
+        api_key_repository
+            .expect_get_all_api_keys()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_all_api_keys_usecase = GetAllApiKeysUseCase::new(&payload, &api_key_repository);
+        let data = get_all_api_keys_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot get all API keys", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_list_without_plain_keys() {
+        // given the "all api keys" usecase repo returning a list of 2 keys
+        let mut api_key_repository = MockApiKeysRepositoryAbstract::new();
+        let payload = ApiKeyDataPayload::new(None, Some(String::from("id1")));
+        api_key_repository.expect_get_all_api_keys().times(1).returning(|_| {
+            let now = Utc::now().naive_utc();
+            Ok(vec![
+                ApiKeyEntity::new(
+                    String::from("key1"),
+                    String::from("id1"),
+                    String::from("customer"),
+                    String::from("ci"),
+                    String::from("tt_abcdef"),
+                    None,
+                    vec![],
+                    None,
+                    Some(now),
+                    None,
+                    now,
+                ),
+                ApiKeyEntity::new(
+                    String::from("key2"),
+                    String::from("id1"),
+                    String::from("customer"),
+                    String::from("backup"),
+                    String::from("tt_ghijkl"),
+                    None,
+                    vec![],
+                    None,
+                    None,
+                    None,
+                    now,
+                ),
+            ])
+        });
+
+        // when calling usecase
+        let get_all_api_keys_usecase = GetAllApiKeysUseCase::new(&payload, &api_key_repository);
+        let data = get_all_api_keys_usecase.execute().await.unwrap();
+
+        // then assert the result is the expected list
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].name, "ci");
+        assert!(data.iter().all(|api_key| api_key.key.is_none()));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod create_api_key_usecase;
+pub mod get_all_api_keys_usecase;
+pub mod revoke_one_api_key_usecase;
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::api_keys::api_keys_payloads::ApiKeyDataPayload,
+    application::{repositories::api_keys_repository_abstract::ApiKeysRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{api_key_entity::ApiKeyEntity, error::ApiError},
+};
+
+pub struct RevokeOneApiKeyUseCase<'a> {
+    api_key_payload: &'a ApiKeyDataPayload,
+    repository: &'a dyn ApiKeysRepositoryAbstract,
+}
+
+impl<'a> RevokeOneApiKeyUseCase<'a> {
+    pub fn new(api_key_payload: &'a ApiKeyDataPayload, repository: &'a dyn ApiKeysRepositoryAbstract) -> Self {
+        RevokeOneApiKeyUseCase { api_key_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<ApiKeyEntity> for RevokeOneApiKeyUseCase<'a> {
+    #[instrument(name = "RevokeOneApiKeyUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<ApiKeyEntity, ApiError> {
+        let api_key = self.repository.revoke_api_key(self.api_key_payload).await;
+
+        match api_key {
+            Ok(api_key) => Ok(api_key),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot revoke API key", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::api_keys_repository_abstract::MockApiKeysRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "revoke api key" usecase repo with an unexpected random error
+        let mut api_key_repository = MockApiKeysRepositoryAbstract::new();
+        let payload = ApiKeyDataPayload::new(Some(String::from("key1")), Some(String::from("id1")));
+        api_key_repository
+            .expect_revoke_api_key()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let revoke_one_api_key_usecase = RevokeOneApiKeyUseCase::new(&payload, &api_key_repository);
+        let data = revoke_one_api_key_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot revoke API key", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_revoked_key() {
+        // given the "revoke api key" usecase repo revoking the key
+        let mut api_key_repository = MockApiKeysRepositoryAbstract::new();
+        let payload = ApiKeyDataPayload::new(Some(String::from("key1")), Some(String::from("id1")));
+        api_key_repository.expect_revoke_api_key().times(1).returning(|_| {
+            let now = Utc::now().naive_utc();
+            Ok(ApiKeyEntity::new(
+                String::from("key1"),
+                String::from("id1"),
+                String::new(),
+                String::from("ci"),
+                String::from("tt_abcdef"),
+                None,
+                vec![],
+                None,
+                None,
+                Some(now),
+                now,
+            ))
+        });
+
+        // when calling usecase
+        let revoke_one_api_key_usecase = RevokeOneApiKeyUseCase::new(&payload, &api_key_repository);
+        let data = revoke_one_api_key_usecase.execute().await.unwrap();
+
+        // then assert the key is revoked
+        assert_eq!(data.id, String::from("key1"));
+        assert!(data.revoked_at.is_some());
+    }
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::{calendar::calendar_payloads::CalendarFeedQueryPayload, tasks::tasks_payloads::TaskPagePayload},
+    application::{
+        repositories::{calendar_feeds_repository_abstract::CalendarFeedsRepositoryAbstract, tasks_repository_abstract::TasksRepositoryAbstract},
+        usecases::interfaces::AbstractUseCase,
+        utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::{error::ApiError, task_entity::TaskEntity},
+};
+
+// Tasks read per query while building a feed
+const CALENDAR_FEED_PAGE_SIZE: i64 = 500;
+
+// The tasks with a due date of the feed owner, optionally of one project
+pub struct GetCalendarFeedTasksUseCase<'a> {
+    token: &'a str,
+    query_payload: &'a CalendarFeedQueryPayload,
+    feeds_repository: &'a dyn CalendarFeedsRepositoryAbstract,
+    tasks_repository: &'a dyn TasksRepositoryAbstract,
+}
+
+impl<'a> GetCalendarFeedTasksUseCase<'a> {
+    pub fn new(
+        token: &'a str,
+        query_payload: &'a CalendarFeedQueryPayload,
+        feeds_repository: &'a dyn CalendarFeedsRepositoryAbstract,
+        tasks_repository: &'a dyn TasksRepositoryAbstract,
+    ) -> Self {
+        GetCalendarFeedTasksUseCase {
+            token,
+            query_payload,
+            feeds_repository,
+            tasks_repository,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<TaskEntity>> for GetCalendarFeedTasksUseCase<'a> {
+    #[instrument(name = "GetCalendarFeedTasksUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<TaskEntity>, ApiError> {
+        let feed = match self.feeds_repository.get_feed_by_token(self.token).await {
+            Ok(feed) => feed,
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot read calendar feed", Some(e))),
+        };
+
+        let mut page_payload = TaskPagePayload {
+            user_id: Some(feed.user_id),
+            project_id: self.query_payload.project_id.clone(),
+            after: None,
+            limit: CALENDAR_FEED_PAGE_SIZE,
+            due_only: true,
+        };
+        let mut tasks: Vec<TaskEntity> = Vec::new();
+        loop {
+            let page = match self.tasks_repository.get_tasks_page(&page_payload).await {
+                Ok(page) => page,
+                Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot read calendar feed", Some(e))),
+            };
+            let last_page = (page.len() as i64) < CALENDAR_FEED_PAGE_SIZE;
+            page_payload.after = page.last().map(|task| (task.created_at, task.id.clone()));
+            tasks.extend(page);
+            if last_page {
+                return Ok(tasks);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{calendar_feed_entity::CalendarFeedEntity, error::DomainError};
+    use chrono::Utc;
+
+    use crate::application::repositories::{calendar_feeds_repository_abstract::MockCalendarFeedsRepositoryAbstract, tasks_repository_abstract::MockTasksRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_read_the_due_tasks_of_the_feed_owner() {
+        // given a feed token of user id1 filtered on a project
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = CalendarFeedQueryPayload {
+            project_id: Some(String::from("project1")),
+            ..Default::default()
+        };
+        calendar_feed_repository
+            .expect_get_feed_by_token()
+            .times(1)
+            .returning(|_| Ok(CalendarFeedEntity::new(String::from("id1"), None, Utc::now().naive_utc())));
+        task_repository
+            .expect_get_tasks_page()
+            .withf(|page| page.user_id.as_deref() == Some("id1") && page.project_id.as_deref() == Some("project1") && page.due_only)
+            .times(1)
+            .returning(|_| Ok(Vec::new()));
+
+        // when calling usecase
+        let get_calendar_feed_tasks_usecase = GetCalendarFeedTasksUseCase::new("token", &payload, &calendar_feed_repository, &task_repository);
+        let data = get_calendar_feed_tasks_usecase.execute().await;
+
+        // then no tasks due
+        assert!(data.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_not_found_for_a_revoked_token() {
+        // given a token matching no feed
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = CalendarFeedQueryPayload::default();
+        calendar_feed_repository
+            .expect_get_feed_by_token()
+            .times(1)
+            .returning(|_| Err(DomainError::NotFound(String::from("Calendar feed not found"))));
+        task_repository.expect_get_tasks_page().never();
+
+        // when calling usecase
+        let get_calendar_feed_tasks_usecase = GetCalendarFeedTasksUseCase::new("revoked", &payload, &calendar_feed_repository, &task_repository);
+        let data = get_calendar_feed_tasks_usecase.execute().await;
+
+        // then not found
+        assert_eq!(data.unwrap_err().code, 404);
+    }
+}
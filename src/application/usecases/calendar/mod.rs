@@ -0,0 +1,3 @@
+pub mod get_calendar_feed_tasks_usecase;
+pub mod regenerate_calendar_feed_usecase;
+pub mod revoke_calendar_feed_usecase;
@@ -0,0 +1,121 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::calendar::calendar_payloads::CalendarFeedOwnerPayload,
+    application::{
+        repositories::calendar_feeds_repository_abstract::CalendarFeedsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::{
+        calendar_feed_entity::CalendarFeedEntity,
+        error::{ApiError, DomainError},
+    },
+};
+
+// Issues a new feed URL, the previous one stops working
+pub struct RegenerateCalendarFeedUseCase<'a> {
+    owner_payload: &'a CalendarFeedOwnerPayload,
+    repository: &'a dyn CalendarFeedsRepositoryAbstract,
+}
+
+impl<'a> RegenerateCalendarFeedUseCase<'a> {
+    pub fn new(owner_payload: &'a CalendarFeedOwnerPayload, repository: &'a dyn CalendarFeedsRepositoryAbstract) -> Self {
+        RegenerateCalendarFeedUseCase { owner_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<CalendarFeedEntity> for RegenerateCalendarFeedUseCase<'a> {
+    #[instrument(name = "RegenerateCalendarFeedUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<CalendarFeedEntity, ApiError> {
+        let feed = match feed_owner_id(self.owner_payload, self.repository).await {
+            Ok(user_id) => self.repository.regenerate_feed(&user_id).await,
+            Err(e) => Err(e),
+        };
+
+        match feed {
+            Ok(feed) => Ok(feed),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot regenerate calendar feed", Some(e))),
+        }
+    }
+}
+
+// The signed in user, or the owner of the feed the token belongs to
+pub async fn feed_owner_id(owner_payload: &CalendarFeedOwnerPayload, repository: &dyn CalendarFeedsRepositoryAbstract) -> Result<String, DomainError> {
+    match (&owner_payload.user_id, &owner_payload.token) {
+        (Some(user_id), _) => Ok(user_id.clone()),
+        (None, Some(token)) => repository.get_feed_by_token(token).await.map(|feed| feed.user_id),
+        (None, None) => Err(DomainError::NotFound(String::from("Calendar feed not found"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::calendar_feeds_repository_abstract::MockCalendarFeedsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_regenerate_the_feed_of_the_token_owner() {
+        // given a caller holding only the feed URL
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let payload = CalendarFeedOwnerPayload::from_token(String::from("old"));
+        calendar_feed_repository
+            .expect_get_feed_by_token()
+            .with(eq("old"))
+            .times(1)
+            .returning(|_| Ok(CalendarFeedEntity::new(String::from("id1"), None, Utc::now().naive_utc())));
+        calendar_feed_repository
+            .expect_regenerate_feed()
+            .with(eq("id1"))
+            .times(1)
+            .returning(|user_id| Ok(CalendarFeedEntity::new(user_id.to_string(), Some(String::from("new")), Utc::now().naive_utc())));
+
+        // when calling usecase
+        let regenerate_calendar_feed_usecase = RegenerateCalendarFeedUseCase::new(&payload, &calendar_feed_repository);
+        let data = regenerate_calendar_feed_usecase.execute().await.unwrap();
+
+        // then a new token for the same user
+        assert_eq!((data.user_id.as_str(), data.token.as_deref()), ("id1", Some("new")));
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_not_found_for_an_unknown_token() {
+        // given a token matching no feed
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let payload = CalendarFeedOwnerPayload::from_token(String::from("unknown"));
+        calendar_feed_repository
+            .expect_get_feed_by_token()
+            .times(1)
+            .returning(|_| Err(DomainError::NotFound(String::from("Calendar feed not found"))));
+        calendar_feed_repository.expect_regenerate_feed().never();
+
+        // when calling usecase
+        let regenerate_calendar_feed_usecase = RegenerateCalendarFeedUseCase::new(&payload, &calendar_feed_repository);
+        let data = regenerate_calendar_feed_usecase.execute().await;
+
+        // then not found
+        assert_eq!(data.unwrap_err().code, 404);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "regenerate calendar feed" usecase repo with an unexpected random error
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let payload = CalendarFeedOwnerPayload::from_user_id(String::from("id1"));
+        calendar_feed_repository
+            .expect_regenerate_feed()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let regenerate_calendar_feed_usecase = RegenerateCalendarFeedUseCase::new(&payload, &calendar_feed_repository);
+        let data = regenerate_calendar_feed_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot regenerate calendar feed", data.unwrap_err().message);
+    }
+}
@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::calendar::calendar_payloads::CalendarFeedOwnerPayload,
+    application::{
+        repositories::calendar_feeds_repository_abstract::CalendarFeedsRepositoryAbstract,
+        usecases::{calendar::regenerate_calendar_feed_usecase::feed_owner_id, interfaces::AbstractUseCase},
+        utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::error::ApiError,
+};
+
+// The feed URL stops working until a new one is generated
+pub struct RevokeCalendarFeedUseCase<'a> {
+    owner_payload: &'a CalendarFeedOwnerPayload,
+    repository: &'a dyn CalendarFeedsRepositoryAbstract,
+}
+
+impl<'a> RevokeCalendarFeedUseCase<'a> {
+    pub fn new(owner_payload: &'a CalendarFeedOwnerPayload, repository: &'a dyn CalendarFeedsRepositoryAbstract) -> Self {
+        RevokeCalendarFeedUseCase { owner_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for RevokeCalendarFeedUseCase<'a> {
+    #[instrument(name = "RevokeCalendarFeedUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        let revoked = match feed_owner_id(self.owner_payload, self.repository).await {
+            Ok(user_id) => self.repository.revoke_feed(&user_id).await,
+            Err(e) => Err(e),
+        };
+
+        match revoked {
+            Ok(()) => Ok(()),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot revoke calendar feed", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use mockall::predicate::eq;
+    use std::io::{Error, ErrorKind};
+
+    use crate::domain::calendar_feed_entity::CalendarFeedEntity;
+
+    use crate::application::repositories::calendar_feeds_repository_abstract::MockCalendarFeedsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_revoke_the_feed_of_the_token_owner() {
+        // given a caller holding only the feed URL
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let payload = CalendarFeedOwnerPayload::from_token(String::from("leaked"));
+        calendar_feed_repository
+            .expect_get_feed_by_token()
+            .with(eq("leaked"))
+            .times(1)
+            .returning(|_| Ok(CalendarFeedEntity::new(String::from("id1"), None, Utc::now().naive_utc())));
+        calendar_feed_repository.expect_revoke_feed().with(eq("id1")).times(1).returning(|_| Ok(()));
+
+        // when calling usecase
+        let revoke_calendar_feed_usecase = RevokeCalendarFeedUseCase::new(&payload, &calendar_feed_repository);
+        let data = revoke_calendar_feed_usecase.execute().await;
+
+        // then the feed of its owner is gone
+        assert!(data.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "revoke calendar feed" usecase repo with an unexpected random error
+        let mut calendar_feed_repository = MockCalendarFeedsRepositoryAbstract::new();
+        let payload = CalendarFeedOwnerPayload::from_user_id(String::from("id1"));
+        calendar_feed_repository
+            .expect_revoke_feed()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let revoke_calendar_feed_usecase = RevokeCalendarFeedUseCase::new(&payload, &calendar_feed_repository);
+        let data = revoke_calendar_feed_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot revoke calendar feed", data.unwrap_err().message);
+    }
+}
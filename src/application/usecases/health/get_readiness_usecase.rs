@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::health_repository_abstract::HealthRepositoryAbstract, usecases::interfaces::AbstractUseCase},
+    domain::{
+        error::ApiError,
+        health_entity::{HealthCheckEntity, ReadinessEntity},
+    },
+};
+
+pub struct GetReadinessUseCase<'a> {
+    repository: &'a dyn HealthRepositoryAbstract,
+}
+
+impl<'a> GetReadinessUseCase<'a> {
+    pub fn new(repository: &'a dyn HealthRepositoryAbstract) -> Self {
+        GetReadinessUseCase { repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<ReadinessEntity> for GetReadinessUseCase<'a> {
+    // A failing dependency is an answer, not an error: the probe gets every check with the reason it failed.
+    // Error details stay in the logs since the endpoint is public.
+    #[instrument(name = "GetReadinessUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<ReadinessEntity, ApiError> {
+        if let Err(e) = self.repository.ping_database().await {
+            log::error!("Readiness check, database unreachable: {}", e);
+            return Ok(ReadinessEntity {
+                checks: vec![
+                    HealthCheckEntity::new("database", false, Some(String::from("Cannot check out a connection"))),
+                    HealthCheckEntity::new("migrations", false, Some(String::from("Database unreachable"))),
+                ],
+            });
+        }
+
+        let migrations = match self.repository.get_pending_migrations().await {
+            Ok(pending) if pending.is_empty() => HealthCheckEntity::new("migrations", true, None),
+            Ok(pending) => HealthCheckEntity::new("migrations", false, Some(format!("Pending migrations: {}", pending.join(", ")))),
+            Err(e) => {
+                log::error!("Readiness check, cannot list migrations: {}", e);
+                HealthCheckEntity::new("migrations", false, Some(String::from("Cannot list migrations")))
+            }
+        };
+
+        Ok(ReadinessEntity {
+            checks: vec![HealthCheckEntity::new("database", true, None), migrations],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::health_repository_abstract::MockHealthRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_not_be_ready_when_unexpected_repo_error() {
+        // given the "readiness" usecase repo with an unreachable database
+        let mut health_repository = MockHealthRepositoryAbstract::new();
+        health_repository
+            .expect_ping_database()
+            .times(1)
+            .returning(|| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+        health_repository.expect_get_pending_migrations().times(0);
+
+        // when calling usecase
+        let get_readiness_usecase = GetReadinessUseCase::new(&health_repository);
+        let data = get_readiness_usecase.execute().await.unwrap();
+
+        // then not ready, without leaking the error
+        assert!(!data.is_ready());
+        assert!(!data.checks[0].healthy);
+        assert_eq!(data.checks[0].details, Some(String::from("Cannot check out a connection")));
+    }
+
+    #[actix_rt::test]
+    async fn test_should_not_be_ready_with_pending_migrations() {
+        // given the "readiness" usecase repo with one migration left to run
+        let mut health_repository = MockHealthRepositoryAbstract::new();
+        health_repository.expect_ping_database().times(1).returning(|| Ok(()));
+        health_repository.expect_get_pending_migrations().times(1).returning(|| Ok(vec![String::from("20261019150000")]));
+
+        // when calling usecase
+        let get_readiness_usecase = GetReadinessUseCase::new(&health_repository);
+        let data = get_readiness_usecase.execute().await.unwrap();
+
+        // then the database is fine but the service is not ready
+        assert!(!data.is_ready());
+        assert!(data.checks[0].healthy);
+        assert_eq!(data.checks[1].details, Some(String::from("Pending migrations: 20261019150000")));
+    }
+
+    #[actix_rt::test]
+    async fn test_should_be_ready() {
+        // given the "readiness" usecase repo with a reachable and up to date database
+        let mut health_repository = MockHealthRepositoryAbstract::new();
+        health_repository.expect_ping_database().times(1).returning(|| Ok(()));
+        health_repository.expect_get_pending_migrations().times(1).returning(|| Ok(Vec::new()));
+
+        // when calling usecase
+        let get_readiness_usecase = GetReadinessUseCase::new(&health_repository);
+        let data = get_readiness_usecase.execute().await.unwrap();
+
+        // then ready
+        assert!(data.is_ready());
+        assert_eq!(data.checks.len(), 2);
+    }
+}
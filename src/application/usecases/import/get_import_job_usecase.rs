@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::import_jobs_repository_abstract::ImportJobsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, import_entity::ImportJobEntity},
+};
+
+// Progress and report of an import of the user, finished ones are kept for a while only
+pub struct GetImportJobUseCase<'a> {
+    job_id: &'a str,
+    user_id: &'a str,
+    repository: &'a dyn ImportJobsRepositoryAbstract,
+}
+
+impl<'a> GetImportJobUseCase<'a> {
+    pub fn new(job_id: &'a str, user_id: &'a str, repository: &'a dyn ImportJobsRepositoryAbstract) -> Self {
+        GetImportJobUseCase { job_id, user_id, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<ImportJobEntity> for GetImportJobUseCase<'a> {
+    #[instrument(name = "GetImportJobUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<ImportJobEntity, ApiError> {
+        let import_job = self.repository.get_import_job(self.job_id, self.user_id).await;
+
+        match import_job {
+            Ok(import_job) => Ok(import_job),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get the import", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::import_jobs_repository_abstract::MockImportJobsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "get import job" usecase repo with an unexpected random error
+        let mut import_job_repository = MockImportJobsRepositoryAbstract::new();
+        import_job_repository
+            .expect_get_import_job()
+            .withf(|job_id, user_id| job_id == "job1" && user_id == "id1")
+            .times(1)
+            .returning(|_, _| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_import_job_usecase = GetImportJobUseCase::new("job1", "id1", &import_job_repository);
+        let data = get_import_job_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot get the import", data.unwrap_err().message);
+    }
+}
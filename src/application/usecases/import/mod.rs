@@ -0,0 +1,3 @@
+pub mod get_import_job_usecase;
+pub mod queue_import_usecase;
+pub mod run_import_usecase;
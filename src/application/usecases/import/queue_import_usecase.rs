@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::import_jobs_repository_abstract::ImportJobsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{
+        error::ApiError,
+        import_entity::{ImportJobEntity, ImportSkippedItem, ImportSource},
+    },
+};
+
+// Records an import before it runs, with what the file holds that can't be imported. One at a time per user.
+pub struct QueueImportUseCase<'a> {
+    user_id: &'a str,
+    source: ImportSource,
+    skipped: &'a [ImportSkippedItem],
+    repository: &'a dyn ImportJobsRepositoryAbstract,
+}
+
+impl<'a> QueueImportUseCase<'a> {
+    pub fn new(user_id: &'a str, source: ImportSource, skipped: &'a [ImportSkippedItem], repository: &'a dyn ImportJobsRepositoryAbstract) -> Self {
+        QueueImportUseCase {
+            user_id,
+            source,
+            skipped,
+            repository,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<ImportJobEntity> for QueueImportUseCase<'a> {
+    #[instrument(name = "QueueImportUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<ImportJobEntity, ApiError> {
+        let import_job = self.repository.queue_import_job(self.user_id, self.source, self.skipped).await;
+
+        match import_job {
+            Ok(import_job) => Ok(import_job),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot start the import", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::import_jobs_repository_abstract::MockImportJobsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "queue import" usecase repo with an unexpected random error
+        let mut import_job_repository = MockImportJobsRepositoryAbstract::new();
+        import_job_repository
+            .expect_queue_import_job()
+            .times(1)
+            .returning(|_, _, _| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let queue_import_usecase = QueueImportUseCase::new("id1", ImportSource::Trello, &[], &import_job_repository);
+        let data = queue_import_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot start the import", data.unwrap_err().message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_refuse_a_second_import_of_the_user() {
+        // given an import of the user already running
+        let mut import_job_repository = MockImportJobsRepositoryAbstract::new();
+        import_job_repository
+            .expect_queue_import_job()
+            .withf(|user_id, source, skipped| user_id == "id1" && *source == ImportSource::Notion && skipped.len() == 1)
+            .times(1)
+            .returning(|_, _, _| Err(DomainError::Conflict(String::from("An import is already running, wait for it to finish"))));
+
+        // when calling usecase
+        let skipped = [ImportSkippedItem::new("Label", "not a task field")];
+        let queue_import_usecase = QueueImportUseCase::new("id1", ImportSource::Notion, &skipped, &import_job_repository);
+        let error = queue_import_usecase.execute().await.unwrap_err();
+
+        // then conflict
+        assert_eq!(error.code, 409);
+        assert_eq!(error.message, "An import is already running, wait for it to finish");
+    }
+}
@@ -0,0 +1,230 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::{imports::imports_payloads::ImportPayload, tasks::tasks_payloads::TaskCreatePayload},
+    application::{
+        repositories::{
+            import_jobs_repository_abstract::ImportJobsRepositoryAbstract, projects_repository_abstract::ProjectsRepositoryAbstract, tasks_repository_abstract::TasksRepositoryAbstract,
+        },
+        usecases::interfaces::AbstractUseCase,
+        utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::{
+        error::{ApiError, DomainError},
+        task_entity::TaskEntity,
+    },
+};
+
+// Tasks written per transaction, the job progress moves by this much
+pub const IMPORT_CHUNK_SIZE: usize = 100;
+
+// Writes a queued import: one project per board, project or database, then its tasks owned by the user. A failure
+// stops the job and keeps what was already written, the job lists it. The progress is recorded as it goes, for
+// `GET /imports/{job_id}` on any instance.
+pub struct RunImportUseCase<'a> {
+    job_id: &'a str,
+    user_id: &'a str,
+    import_payload: &'a ImportPayload,
+    import_jobs_repository: &'a dyn ImportJobsRepositoryAbstract,
+    projects_repository: &'a dyn ProjectsRepositoryAbstract,
+    tasks_repository: &'a dyn TasksRepositoryAbstract,
+}
+
+impl<'a> RunImportUseCase<'a> {
+    pub fn new(
+        job_id: &'a str,
+        user_id: &'a str,
+        import_payload: &'a ImportPayload,
+        import_jobs_repository: &'a dyn ImportJobsRepositoryAbstract,
+        projects_repository: &'a dyn ProjectsRepositoryAbstract,
+        tasks_repository: &'a dyn TasksRepositoryAbstract,
+    ) -> Self {
+        RunImportUseCase {
+            job_id,
+            user_id,
+            import_payload,
+            import_jobs_repository,
+            projects_repository,
+            tasks_repository,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<TaskEntity>> for RunImportUseCase<'a> {
+    #[instrument(name = "RunImportUseCase::execute", skip_all, fields(job_id = self.job_id))]
+    async fn execute(&self) -> Result<Vec<TaskEntity>, ApiError> {
+        let imported = self.import().await;
+        if let Err(error) = &imported {
+            if let Err(e) = self.import_jobs_repository.fail_import_job(self.job_id, &error.message).await {
+                log::error!("Cannot record the failure of import {}: {}", self.job_id, e);
+            }
+        }
+        imported
+    }
+}
+
+impl RunImportUseCase<'_> {
+    async fn import(&self) -> Result<Vec<TaskEntity>, ApiError> {
+        self.import_jobs_repository
+            .start_import_job(self.job_id, self.import_payload.task_count())
+            .await
+            .map_err(progress_error)?;
+        let mut created = Vec::new();
+
+        for project_payload in &self.import_payload.projects {
+            let project = self
+                .projects_repository
+                .post_project(&project_payload.name, project_payload.description.as_deref())
+                .await
+                .map_err(|e| ErrorHandlingUtils::application_error("Cannot import projects", Some(e)))?;
+            self.import_jobs_repository.add_import_project(self.job_id, &project.id).await.map_err(progress_error)?;
+
+            for chunk in project_payload.tasks.chunks(IMPORT_CHUNK_SIZE) {
+                let task_payloads: Vec<TaskCreatePayload> = chunk
+                    .iter()
+                    .map(|task_payload| TaskCreatePayload {
+                        user_id: Some(self.user_id.to_string()),
+                        project_id: Some(project.id.clone()),
+                        ..task_payload.clone()
+                    })
+                    .collect();
+                let tasks = self
+                    .tasks_repository
+                    .post_tasks(&task_payloads)
+                    .await
+                    .map_err(|e| ErrorHandlingUtils::application_error("Cannot import tasks", Some(e)))?;
+                let task_ids: Vec<String> = tasks.iter().map(|task| task.id.clone()).collect();
+                self.import_jobs_repository.add_import_tasks(self.job_id, &task_ids).await.map_err(progress_error)?;
+                created.extend(tasks);
+            }
+        }
+
+        self.import_jobs_repository.complete_import_job(self.job_id).await.map_err(progress_error)?;
+        Ok(created)
+    }
+}
+
+fn progress_error(error: DomainError) -> ApiError {
+    ErrorHandlingUtils::application_error("Cannot record the import progress", Some(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        adapters::api::imports::imports_payloads::ImportProjectPayload,
+        application::repositories::{
+            import_jobs_repository_abstract::MockImportJobsRepositoryAbstract, projects_repository_abstract::MockProjectsRepositoryAbstract,
+            tasks_repository_abstract::MockTasksRepositoryAbstract,
+        },
+        domain::project_entity::ProjectEntity,
+        test_support::TaskEntityBuilder,
+    };
+
+    fn import_payload(task_count: usize) -> ImportPayload {
+        ImportPayload {
+            projects: vec![ImportProjectPayload {
+                name: String::from("Board"),
+                description: None,
+                tasks: (0..task_count)
+                    .map(|index| TaskCreatePayload::new(None, None, format!("Card {}", index), None, None, None, None, None, None, None))
+                    .collect(),
+            }],
+            skipped: Vec::new(),
+        }
+    }
+
+    fn created_tasks(task_payloads: &[TaskCreatePayload]) -> Vec<TaskEntity> {
+        task_payloads
+            .iter()
+            .map(|task_payload| {
+                TaskEntityBuilder::new("task")
+                    .user_id(&task_payload.user_id.clone().unwrap_or_default())
+                    .project_id(&task_payload.project_id.clone().unwrap_or_default())
+                    .title(&task_payload.title)
+                    .build()
+            })
+            .collect()
+    }
+
+    #[actix_rt::test]
+    async fn test_should_write_tasks_in_chunks_and_report_progress() {
+        // given a board of 150 cards
+        let mut project_repository = MockProjectsRepositoryAbstract::new();
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let mut import_job_repository = MockImportJobsRepositoryAbstract::new();
+        let payload = import_payload(150);
+        import_job_repository.expect_start_import_job().withf(|job_id, total| job_id == "job1" && *total == 150).times(1).returning(|_, _| Ok(()));
+        import_job_repository
+            .expect_add_import_project()
+            .withf(|job_id, project_id| job_id == "job1" && project_id == "project1")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        import_job_repository.expect_add_import_tasks().withf(|_, task_ids| task_ids.len() == 100).times(1).returning(|_, _| Ok(()));
+        import_job_repository.expect_add_import_tasks().withf(|_, task_ids| task_ids.len() == 50).times(1).returning(|_, _| Ok(()));
+        import_job_repository.expect_complete_import_job().times(1).returning(|_| Ok(()));
+        import_job_repository.expect_fail_import_job().never();
+        project_repository
+            .expect_post_project()
+            .withf(|name, description| name == "Board" && description.is_none())
+            .times(1)
+            .returning(|name, _| {
+                Ok(ProjectEntity::new(
+                    String::from("project1"),
+                    name.to_string(),
+                    None,
+                    Utc::now().naive_utc(),
+                    Utc::now().naive_utc(),
+                ))
+            });
+        task_repository
+            .expect_post_tasks()
+            .withf(|task_payloads| {
+                task_payloads
+                    .iter()
+                    .all(|task| task.user_id.as_deref() == Some("id1") && task.project_id.as_deref() == Some("project1"))
+            })
+            .times(2)
+            .returning(|task_payloads| Ok(created_tasks(task_payloads)));
+
+        // when calling usecase
+        let run_import_usecase = RunImportUseCase::new("job1", "id1", &payload, &import_job_repository, &project_repository, &task_repository);
+        let data = run_import_usecase.execute().await.unwrap();
+
+        // then every task written and the job completed
+        assert_eq!(data.len(), 150);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_fail_the_job_when_unexpected_repo_error() {
+        // given the "run import" usecase repo with an unexpected random error
+        let mut project_repository = MockProjectsRepositoryAbstract::new();
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let mut import_job_repository = MockImportJobsRepositoryAbstract::new();
+        let payload = import_payload(1);
+        import_job_repository.expect_start_import_job().times(1).returning(|_, _| Ok(()));
+        import_job_repository
+            .expect_fail_import_job()
+            .withf(|job_id, message| job_id == "job1" && message == "Cannot import projects")
+            .times(1)
+            .returning(|_, _| Ok(()));
+        import_job_repository.expect_complete_import_job().never();
+        project_repository
+            .expect_post_project()
+            .times(1)
+            .returning(|_, _| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+        task_repository.expect_post_tasks().never();
+
+        // when calling usecase
+        let run_import_usecase = RunImportUseCase::new("job1", "id1", &payload, &import_job_repository, &project_repository, &task_repository);
+        let data = run_import_usecase.execute().await;
+
+        // then exception, also kept on the job
+        assert_eq!("Cannot import projects", data.unwrap_err().message);
+    }
+}
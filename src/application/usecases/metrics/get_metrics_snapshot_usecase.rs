@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::metrics_repository_abstract::MetricsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, metrics_entity::MetricsSnapshotEntity},
+};
+
+pub struct GetMetricsSnapshotUseCase<'a> {
+    repository: &'a dyn MetricsRepositoryAbstract,
+}
+
+impl<'a> GetMetricsSnapshotUseCase<'a> {
+    pub fn new(repository: &'a dyn MetricsRepositoryAbstract) -> Self {
+        GetMetricsSnapshotUseCase { repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<MetricsSnapshotEntity> for GetMetricsSnapshotUseCase<'a> {
+    #[instrument(name = "GetMetricsSnapshotUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<MetricsSnapshotEntity, ApiError> {
+        let db_pool = match self.repository.get_db_pool_stats().await {
+            Ok(db_pool) => db_pool,
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot collect metrics", Some(e))),
+        };
+
+        match self.repository.count_open_tasks_by_status().await {
+            Ok(open_tasks_by_status) => Ok(MetricsSnapshotEntity { db_pool, open_tasks_by_status }),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot collect metrics", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::metrics_repository_abstract::MockMetricsRepositoryAbstract;
+    use crate::domain::metrics_entity::{DbPoolStatsEntity, TaskStatusCountEntity};
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "metrics snapshot" usecase repo with an unexpected random error
+        let mut metrics_repository = MockMetricsRepositoryAbstract::new();
+        metrics_repository.expect_get_db_pool_stats().times(1).returning(|| Ok(DbPoolStatsEntity::default()));
+        metrics_repository
+            .expect_count_open_tasks_by_status()
+            .times(1)
+            .returning(|| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_metrics_snapshot_usecase = GetMetricsSnapshotUseCase::new(&metrics_repository);
+        let data = get_metrics_snapshot_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot collect metrics", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_snapshot() {
+        // given the "metrics snapshot" usecase repo with a busy pool and open tasks
+        let mut metrics_repository = MockMetricsRepositoryAbstract::new();
+        metrics_repository.expect_get_db_pool_stats().times(1).returning(|| {
+            Ok(DbPoolStatsEntity {
+                connections: 4,
+                idle_connections: 1,
+                max_size: 10,
+            })
+        });
+        metrics_repository
+            .expect_count_open_tasks_by_status()
+            .times(1)
+            .returning(|| Ok(vec![TaskStatusCountEntity::new(String::from("In Progress: (Doing)"), 3)]));
+
+        // when calling usecase
+        let get_metrics_snapshot_usecase = GetMetricsSnapshotUseCase::new(&metrics_repository);
+        let data = get_metrics_snapshot_usecase.execute().await.unwrap();
+
+        // then assert the result is the expected snapshot
+        assert_eq!(data.db_pool.connections, 4);
+        assert_eq!(data.open_tasks_by_status.len(), 1);
+        assert_eq!(data.open_tasks_by_status[0].count, 3);
+    }
+}
@@ -0,0 +1 @@
+pub mod get_metrics_snapshot_usecase;
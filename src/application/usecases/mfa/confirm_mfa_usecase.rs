@@ -0,0 +1,77 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::mfa::mfa_payloads::MfaCodePayload,
+    application::{repositories::mfa_repository_abstract::MfaRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, mfa_entity::MfaRecoveryCodesEntity},
+};
+
+pub struct ConfirmMfaUseCase<'a> {
+    mfa_payload: &'a MfaCodePayload,
+    repository: &'a dyn MfaRepositoryAbstract,
+}
+
+impl<'a> ConfirmMfaUseCase<'a> {
+    pub fn new(mfa_payload: &'a MfaCodePayload, repository: &'a dyn MfaRepositoryAbstract) -> Self {
+        ConfirmMfaUseCase { mfa_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<MfaRecoveryCodesEntity> for ConfirmMfaUseCase<'a> {
+    #[instrument(name = "ConfirmMfaUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<MfaRecoveryCodesEntity, ApiError> {
+        let recovery_codes = self.repository.confirm(self.mfa_payload).await;
+
+        match recovery_codes {
+            Ok(recovery_codes) => Ok(recovery_codes),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot confirm two-factor authentication", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::mfa_repository_abstract::MockMfaRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "confirm mfa" usecase repo with an unexpected random error
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaCodePayload::new(Some(String::from("id1")), String::from("123456"));
+        mfa_repository.expect_confirm().times(1).returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let confirm_mfa_usecase = ConfirmMfaUseCase::new(&payload, &mfa_repository);
+        let data = confirm_mfa_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot confirm two-factor authentication", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_recovery_codes() {
+        // given the "confirm mfa" usecase repo accepting the code
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaCodePayload::new(Some(String::from("id1")), String::from("123456"));
+        mfa_repository
+            .expect_confirm()
+            .times(1)
+            .returning(|_| Ok(MfaRecoveryCodesEntity::new(vec![String::from("abcde-12345"), String::from("fghij-67890")])));
+
+        // when calling usecase
+        let confirm_mfa_usecase = ConfirmMfaUseCase::new(&payload, &mfa_repository);
+        let data = confirm_mfa_usecase.execute().await.unwrap();
+
+        // then assert the recovery codes are returned
+        assert_eq!(data.recovery_codes.len(), 2);
+        assert_eq!(data.recovery_codes[0], "abcde-12345");
+    }
+}
@@ -0,0 +1,75 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::mfa::mfa_payloads::MfaCodePayload,
+    application::{repositories::mfa_repository_abstract::MfaRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::error::ApiError,
+};
+
+pub struct DisableMfaUseCase<'a> {
+    mfa_payload: &'a MfaCodePayload,
+    repository: &'a dyn MfaRepositoryAbstract,
+}
+
+impl<'a> DisableMfaUseCase<'a> {
+    pub fn new(mfa_payload: &'a MfaCodePayload, repository: &'a dyn MfaRepositoryAbstract) -> Self {
+        DisableMfaUseCase { mfa_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for DisableMfaUseCase<'a> {
+    #[instrument(name = "DisableMfaUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        let disabled = self.repository.disable(self.mfa_payload).await;
+
+        match disabled {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot disable two-factor authentication", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+
+    use crate::application::repositories::mfa_repository_abstract::MockMfaRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_repo_message_when_repo_error() {
+        // given the "disable mfa" usecase repo refusing because the role requires 2FA
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaCodePayload::new(Some(String::from("id1")), String::from("123456"));
+        mfa_repository
+            .expect_disable()
+            .times(1)
+            .returning(|_| Err(DomainError::Forbidden(String::from("Two-factor authentication is required for your role"))));
+
+        // when calling usecase
+        let disable_mfa_usecase = DisableMfaUseCase::new(&payload, &mfa_repository);
+        let data = disable_mfa_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Two-factor authentication is required for your role", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_disable_mfa() {
+        // given the "disable mfa" usecase repo accepting the code
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaCodePayload::new(Some(String::from("id1")), String::from("123456"));
+        mfa_repository.expect_disable().times(1).returning(|_| Ok(()));
+
+        // when calling usecase
+        let disable_mfa_usecase = DisableMfaUseCase::new(&payload, &mfa_repository);
+        let data = disable_mfa_usecase.execute().await;
+
+        // then assert success
+        assert!(data.is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserIdPayload,
+    application::{repositories::mfa_repository_abstract::MfaRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, mfa_entity::MfaEnrollmentEntity},
+};
+
+pub struct EnrollMfaUseCase<'a> {
+    user_payload: &'a UserIdPayload,
+    repository: &'a dyn MfaRepositoryAbstract,
+}
+
+impl<'a> EnrollMfaUseCase<'a> {
+    pub fn new(user_payload: &'a UserIdPayload, repository: &'a dyn MfaRepositoryAbstract) -> Self {
+        EnrollMfaUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<MfaEnrollmentEntity> for EnrollMfaUseCase<'a> {
+    #[instrument(name = "EnrollMfaUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<MfaEnrollmentEntity, ApiError> {
+        let enrollment = self.repository.enroll(self.user_payload).await;
+
+        match enrollment {
+            Ok(enrollment) => Ok(enrollment),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot enroll two-factor authentication", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::mfa_repository_abstract::MockMfaRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "enroll mfa" usecase repo with an unexpected random error
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = UserIdPayload::new(String::from("id1"));
+        mfa_repository.expect_enroll().times(1).returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let enroll_mfa_usecase = EnrollMfaUseCase::new(&payload, &mfa_repository);
+        let data = enroll_mfa_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot enroll two-factor authentication", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_otpauth_uri() {
+        // given the "enroll mfa" usecase repo returning a pending secret
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = UserIdPayload::new(String::from("id1"));
+        mfa_repository.expect_enroll().times(1).returning(|_| {
+            Ok(MfaEnrollmentEntity::new(
+                String::from("JBSWY3DPEHPK3PXP"),
+                String::from("otpauth://totp/Task%20Tracker:test1%40gmail.com?secret=JBSWY3DPEHPK3PXP&issuer=Task%20Tracker"),
+            ))
+        });
+
+        // when calling usecase
+        let enroll_mfa_usecase = EnrollMfaUseCase::new(&payload, &mfa_repository);
+        let data = enroll_mfa_usecase.execute().await.unwrap();
+
+        // then assert the uri carries the secret
+        assert_eq!(data.secret, "JBSWY3DPEHPK3PXP");
+        assert!(data.otpauth_uri.starts_with("otpauth://totp/"));
+    }
+}
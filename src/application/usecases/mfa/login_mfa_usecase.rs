@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::mfa::mfa_payloads::MfaLoginPayload,
+    application::{repositories::mfa_repository_abstract::MfaRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, user_entity::UserEntity},
+};
+
+pub struct LoginMfaUseCase<'a> {
+    mfa_payload: &'a MfaLoginPayload,
+    repository: &'a dyn MfaRepositoryAbstract,
+}
+
+impl<'a> LoginMfaUseCase<'a> {
+    pub fn new(mfa_payload: &'a MfaLoginPayload, repository: &'a dyn MfaRepositoryAbstract) -> Self {
+        LoginMfaUseCase { mfa_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<UserEntity> for LoginMfaUseCase<'a> {
+    #[instrument(name = "LoginMfaUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<UserEntity, ApiError> {
+        let user = self.repository.login(self.mfa_payload).await;
+
+        match user {
+            Ok(user) => Ok(user),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot sign in", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+
+    use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::mfa_repository_abstract::MockMfaRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_return_repo_message_when_code_is_invalid() {
+        // given the "login mfa" usecase repo rejecting the code
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaLoginPayload::new(String::from("challenge123"), String::from("000000"));
+        mfa_repository
+            .expect_login()
+            .times(1)
+            .returning(|_| Err(DomainError::Unauthorized(String::from("Invalid two-factor code"))));
+
+        // when calling usecase
+        let login_mfa_usecase = LoginMfaUseCase::new(&payload, &mfa_repository);
+        let data = login_mfa_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Invalid two-factor code", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_user_with_tokens() {
+        // given the "login mfa" usecase repo accepting the code
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaLoginPayload::new(String::from("challenge123"), String::from("123456"));
+        mfa_repository.expect_login().times(1).returning(|_| {
+            let now = Utc::now().naive_utc();
+            let mut user = UserEntity::new(
+                String::from("id1"),
+                String::from("User 1"),
+                String::from("test1@gmail.com"),
+                String::from("hash"),
+                UserRolePayload::Admin.to_string(),
+                None,
+                None,
+                None,
+                None,
+                now,
+                now,
+                now,
+            );
+            user.access_token = Some(String::from("thisisaccesstoken123"));
+            user.refresh_token = Some(String::from("thisisrefreshtoken123"));
+            Ok(user)
+        });
+
+        // when calling usecase
+        let login_mfa_usecase = LoginMfaUseCase::new(&payload, &mfa_repository);
+        let data = login_mfa_usecase.execute().await.unwrap();
+
+        // then assert the full token pair is issued
+        assert_eq!(data.id, String::from("id1"));
+        assert!(data.access_token.is_some());
+        assert!(data.mfa_token.is_none());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod enroll_mfa_usecase;
+pub mod confirm_mfa_usecase;
+pub mod disable_mfa_usecase;
+pub mod login_mfa_usecase;
+pub mod update_mfa_role_requirement_usecase;
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::mfa::mfa_payloads::MfaRoleRequirementPayload,
+    application::{repositories::mfa_repository_abstract::MfaRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, mfa_entity::MfaRoleRequirementEntity},
+};
+
+pub struct UpdateMfaRoleRequirementUseCase<'a> {
+    mfa_payload: &'a MfaRoleRequirementPayload,
+    repository: &'a dyn MfaRepositoryAbstract,
+}
+
+impl<'a> UpdateMfaRoleRequirementUseCase<'a> {
+    pub fn new(mfa_payload: &'a MfaRoleRequirementPayload, repository: &'a dyn MfaRepositoryAbstract) -> Self {
+        UpdateMfaRoleRequirementUseCase { mfa_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<MfaRoleRequirementEntity> for UpdateMfaRoleRequirementUseCase<'a> {
+    #[instrument(name = "UpdateMfaRoleRequirementUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<MfaRoleRequirementEntity, ApiError> {
+        let requirement = self.repository.update_role_requirement(self.mfa_payload).await;
+
+        match requirement {
+            Ok(requirement) => Ok(requirement),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot update two-factor requirement", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::mfa_repository_abstract::MockMfaRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "update mfa role requirement" usecase repo with an unexpected random error
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaRoleRequirementPayload::new(UserRolePayload::Admin, true, Some(String::from("id1")));
+        mfa_repository
+            .expect_update_role_requirement()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let update_mfa_role_requirement_usecase = UpdateMfaRoleRequirementUseCase::new(&payload, &mfa_repository);
+        let data = update_mfa_role_requirement_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot update two-factor requirement", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_updated_requirement() {
+        // given the "update mfa role requirement" usecase repo storing the requirement
+        let mut mfa_repository = MockMfaRepositoryAbstract::new();
+        let payload = MfaRoleRequirementPayload::new(UserRolePayload::Admin, true, Some(String::from("id1")));
+        mfa_repository.expect_update_role_requirement().times(1).returning(|payload| {
+            Ok(MfaRoleRequirementEntity::new(payload.role.to_string(), payload.required, payload.updated_by.clone(), Utc::now().naive_utc()))
+        });
+
+        // when calling usecase
+        let update_mfa_role_requirement_usecase = UpdateMfaRoleRequirementUseCase::new(&payload, &mfa_repository);
+        let data = update_mfa_role_requirement_usecase.execute().await.unwrap();
+
+        // then assert the requirement is stored for the role
+        assert_eq!(data.role, "admin");
+        assert!(data.required);
+    }
+}
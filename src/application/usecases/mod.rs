@@ -1,3 +1,13 @@
 pub mod user;
 pub mod task;
+pub mod project;
+pub mod import;
+pub mod calendar;
+pub mod session;
 pub mod interfaces;
+pub mod mfa;
+pub mod api_key;
+pub mod permission;
+pub mod oidc;
+pub mod health;
+pub mod metrics;
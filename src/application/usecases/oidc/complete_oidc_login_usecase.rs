@@ -0,0 +1,222 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::{
+        api::oidc::oidc_payloads::{OidcCallbackPayload, OidcIdentityPayload},
+        spi::oidc::oidc_client::OidcClient,
+    },
+    application::{repositories::oidc_repository_abstract::OidcRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{
+        error::{ApiError, DomainError},
+        user_entity::UserEntity,
+    },
+};
+
+pub struct CompleteOidcLoginUseCase<'a> {
+    oidc_payload: &'a OidcCallbackPayload,
+    repository: &'a dyn OidcRepositoryAbstract,
+    oidc_client: &'a dyn OidcClient,
+}
+
+impl<'a> CompleteOidcLoginUseCase<'a> {
+    pub fn new(oidc_payload: &'a OidcCallbackPayload, repository: &'a dyn OidcRepositoryAbstract, oidc_client: &'a dyn OidcClient) -> Self {
+        CompleteOidcLoginUseCase {
+            oidc_payload,
+            repository,
+            oidc_client,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<UserEntity> for CompleteOidcLoginUseCase<'a> {
+    #[instrument(name = "CompleteOidcLoginUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<UserEntity, ApiError> {
+        let provider = &self.oidc_payload.provider;
+        if !self.oidc_client.provider_names().contains(provider) {
+            return Err(ErrorHandlingUtils::not_found_error("Identity provider not found"));
+        }
+
+        // the state is used up first, a declined or replayed callback cannot be retried
+        let login_state = match self.repository.consume_login_state(self.oidc_payload).await {
+            Ok(Some(login_state)) => login_state,
+            Ok(None) => return Err(ErrorHandlingUtils::unauthorized_error()),
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot complete single sign-on", Some(e))),
+        };
+
+        let code = match (&self.oidc_payload.error, &self.oidc_payload.code) {
+            (None, Some(code)) => code,
+            _ => return Err(ErrorHandlingUtils::unauthorized_error()),
+        };
+
+        let identity = match self.oidc_client.exchange_code(provider, code, &login_state.code_verifier, &login_state.nonce).await {
+            Ok(identity) => identity,
+            Err(e) => {
+                log::warn!("Single sign-on with {} rejected: {}", provider, e);
+                return Err(ErrorHandlingUtils::unauthorized_error());
+            }
+        };
+
+        let identity_payload = OidcIdentityPayload {
+            provider: identity.provider,
+            subject: identity.subject,
+            email: identity.email,
+            email_verified: identity.email_verified,
+            trust_email: self.oidc_client.trusts_email(provider),
+            name: identity.name,
+            ip_address: self.oidc_payload.ip_address.clone(),
+            user_agent: self.oidc_payload.user_agent.clone(),
+        };
+
+        // why an identity is refused stays in the logs, it would tell which emails have an account
+        match self.repository.login_identity(&identity_payload).await {
+            Ok(user) => Ok(user),
+            Err(DomainError::Infrastructure(e)) => Err(ErrorHandlingUtils::application_error("Cannot complete single sign-on", Some(DomainError::Infrastructure(e)))),
+            Err(e) => {
+                log::warn!("Single sign-on with {} refused: {}", provider, e);
+                Err(ErrorHandlingUtils::forbidden_error())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::oidc_client_with_provider;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        adapters::api::users::users_payloads::UserRolePayload,
+        application::repositories::oidc_repository_abstract::MockOidcRepositoryAbstract,
+        domain::oidc_entity::{OidcIdentityEntity, OidcLoginStateEntity},
+    };
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "complete oidc login" usecase repo with an unexpected random error
+        let mut oidc_repository = MockOidcRepositoryAbstract::new();
+        let oidc_client = oidc_client_with_provider();
+        let payload = OidcCallbackPayload::new(String::from("corp"), Some(String::from("code1")), Some(String::from("state1")));
+        oidc_repository
+            .expect_consume_login_state()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let complete_oidc_login_usecase = CompleteOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = complete_oidc_login_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot complete single sign-on", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_not_tell_why_identity_was_refused() {
+        // given the "complete oidc login" usecase repo refusing an identity whose email already has an account
+        let mut oidc_repository = MockOidcRepositoryAbstract::new();
+        let mut oidc_client = oidc_client_with_provider();
+        let payload = OidcCallbackPayload::new(String::from("corp"), Some(String::from("code1")), Some(String::from("state1")));
+        oidc_repository
+            .expect_consume_login_state()
+            .times(1)
+            .returning(|_| Ok(Some(OidcLoginStateEntity::new(String::from("corp"), String::from("nonce1"), String::from("verifier1")))));
+        oidc_client
+            .expect_exchange_code()
+            .times(1)
+            .returning(|_, _, _, _| Ok(OidcIdentityEntity::new(String::from("corp"), String::from("subject1"), Some(String::from("test1@gmail.com")), true, None)));
+        oidc_repository
+            .expect_login_identity()
+            .withf(|identity| !identity.trust_email)
+            .times(1)
+            .returning(|_| Err(DomainError::Conflict(String::from("An account already uses this email"))));
+
+        // when calling usecase
+        let complete_oidc_login_usecase = CompleteOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = complete_oidc_login_usecase.execute().await;
+
+        // then forbidden with a fixed message
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!(403, result.code);
+        assert_eq!("Error: resource not allowed", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_reject_unknown_state_without_redeeming_code() {
+        // given the "complete oidc login" usecase repo not knowing the state
+        let mut oidc_repository = MockOidcRepositoryAbstract::new();
+        let mut oidc_client = oidc_client_with_provider();
+        let payload = OidcCallbackPayload::new(String::from("corp"), Some(String::from("code1")), Some(String::from("replayed")));
+        oidc_repository.expect_consume_login_state().times(1).returning(|_| Ok(None));
+        oidc_client.expect_exchange_code().times(0);
+
+        // when calling usecase
+        let complete_oidc_login_usecase = CompleteOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = complete_oidc_login_usecase.execute().await;
+
+        // then unauthorized
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!(401, result.code);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_signed_in_user() {
+        // given the "complete oidc login" usecase with a valid state and ID token
+        let mut oidc_repository = MockOidcRepositoryAbstract::new();
+        let mut oidc_client = oidc_client_with_provider();
+        let payload = OidcCallbackPayload::new(String::from("corp"), Some(String::from("code1")), Some(String::from("state1")));
+        oidc_repository
+            .expect_consume_login_state()
+            .times(1)
+            .returning(|_| Ok(Some(OidcLoginStateEntity::new(String::from("corp"), String::from("nonce1"), String::from("verifier1")))));
+        oidc_client
+            .expect_exchange_code()
+            .withf(|provider, code, code_verifier, nonce| provider == "corp" && code == "code1" && code_verifier == "verifier1" && nonce == "nonce1")
+            .times(1)
+            .returning(|_, _, _, _| {
+                Ok(OidcIdentityEntity::new(
+                    String::from("corp"),
+                    String::from("subject1"),
+                    Some(String::from("test1@gmail.com")),
+                    true,
+                    Some(String::from("User 1")),
+                ))
+            });
+        oidc_repository
+            .expect_login_identity()
+            .withf(|identity| identity.subject == "subject1" && identity.email_verified)
+            .times(1)
+            .returning(|identity| {
+                Ok(UserEntity {
+                    id: String::from("id1"),
+                    username: identity.name.clone().unwrap_or_default(),
+                    email: identity.email.clone().unwrap_or_default(),
+                    password: String::new(),
+                    role: UserRolePayload::Customer.to_string(),
+                    refresh_token: Some(String::from("thisisrefreshtoken123")),
+                    access_token: Some(String::from("thisisaccesstoken123")),
+                    fcm_token: None,
+                    email_verified_at: Some(Utc::now().naive_utc()),
+                    mfa_token: None,
+                    mfa_enrollment_required: false,
+                    last_login: Utc::now().naive_utc(),
+                    updated_at: Utc::now().naive_utc(),
+                    created_at: Utc::now().naive_utc(),
+                })
+            });
+
+        // when calling usecase
+        let complete_oidc_login_usecase = CompleteOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = complete_oidc_login_usecase.execute().await.unwrap();
+
+        // then assert the result is the signed in user
+        assert_eq!(data.id, String::from("id1"));
+        assert_eq!(data.access_token, Some(String::from("thisisaccesstoken123")));
+    }
+}
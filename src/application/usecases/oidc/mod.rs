@@ -0,0 +1,2 @@
+pub mod start_oidc_login_usecase;
+pub mod complete_oidc_login_usecase;
@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sha2::{Digest, Sha256};
+use tracing::instrument;
+
+use crate::{
+    adapters::{
+        api::oidc::oidc_payloads::{OidcLoginStatePayload, OidcProviderPayload},
+        spi::oidc::oidc_client::OidcClient,
+    },
+    application::{
+        repositories::oidc_repository_abstract::OidcRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{error_handling_utils::ErrorHandlingUtils, token_hash::generate_secret},
+    },
+    domain::{error::ApiError, oidc_entity::OidcAuthorizationEntity},
+};
+
+pub struct StartOidcLoginUseCase<'a> {
+    oidc_payload: &'a OidcProviderPayload,
+    repository: &'a dyn OidcRepositoryAbstract,
+    oidc_client: &'a dyn OidcClient,
+}
+
+impl<'a> StartOidcLoginUseCase<'a> {
+    pub fn new(oidc_payload: &'a OidcProviderPayload, repository: &'a dyn OidcRepositoryAbstract, oidc_client: &'a dyn OidcClient) -> Self {
+        StartOidcLoginUseCase {
+            oidc_payload,
+            repository,
+            oidc_client,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<OidcAuthorizationEntity> for StartOidcLoginUseCase<'a> {
+    #[instrument(name = "StartOidcLoginUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<OidcAuthorizationEntity, ApiError> {
+        let provider = &self.oidc_payload.provider;
+        if !self.oidc_client.provider_names().contains(provider) {
+            return Err(ErrorHandlingUtils::not_found_error("Identity provider not found"));
+        }
+
+        // the verifier and nonce stay server side, only the state and the challenge travel through the browser
+        let login_state = OidcLoginStatePayload::new(provider.clone(), generate_secret(48), generate_secret(48), generate_secret(64));
+        if let Err(e) = self.repository.create_login_state(&login_state).await {
+            return Err(ErrorHandlingUtils::application_error("Cannot start single sign-on", Some(e)));
+        }
+
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(login_state.code_verifier.as_bytes()));
+        match self.oidc_client.authorization_url(provider, &login_state.state, &login_state.nonce, &code_challenge).await {
+            Ok(authorization_url) => Ok(OidcAuthorizationEntity::new(authorization_url)),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot reach identity provider", Some(e.into()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{application::repositories::oidc_repository_abstract::MockOidcRepositoryAbstract, test_support::oidc_client_with_provider};
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "start oidc login" usecase repo with an unexpected random error
+        let mut oidc_repository = MockOidcRepositoryAbstract::new();
+        let oidc_client = oidc_client_with_provider();
+        let payload = OidcProviderPayload::new(String::from("corp"));
+        oidc_repository
+            .expect_create_login_state()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let start_oidc_login_usecase = StartOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = start_oidc_login_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot start single sign-on", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_not_found_for_unknown_provider() {
+        // given the "start oidc login" usecase with a single configured provider
+        let oidc_repository = MockOidcRepositoryAbstract::new();
+        let oidc_client = oidc_client_with_provider();
+        let payload = OidcProviderPayload::new(String::from("other"));
+
+        // when calling usecase for another provider
+        let start_oidc_login_usecase = StartOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = start_oidc_login_usecase.execute().await;
+
+        // then not found, without any state stored
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!(404, result.code);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_authorization_url_with_pkce_challenge() {
+        // given the "start oidc login" usecase repo storing the login state
+        let mut oidc_repository = MockOidcRepositoryAbstract::new();
+        let mut oidc_client = oidc_client_with_provider();
+        let payload = OidcProviderPayload::new(String::from("corp"));
+        oidc_repository
+            .expect_create_login_state()
+            .withf(|login_state| login_state.provider == "corp" && login_state.state != login_state.nonce && login_state.code_verifier.len() >= 43)
+            .times(1)
+            .returning(|_| Ok(()));
+        oidc_client
+            .expect_authorization_url()
+            .withf(|provider, state, _, code_challenge| provider == "corp" && state.len() == 48 && code_challenge.len() == 43)
+            .times(1)
+            .returning(|_, state, _, _| Ok(format!("https://idp.example.com/authorize?state={}", state)));
+
+        // when calling usecase
+        let start_oidc_login_usecase = StartOidcLoginUseCase::new(&payload, &oidc_repository, &oidc_client);
+        let data = start_oidc_login_usecase.execute().await.unwrap();
+
+        // then assert the result is the provider authorization url
+        assert!(data.authorization_url.starts_with("https://idp.example.com/authorize?state="));
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::permissions_repository_abstract::PermissionsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, permission_entity::RolePermissionsEntity},
+};
+
+pub struct GetAllRolePermissionsUseCase<'a> {
+    repository: &'a dyn PermissionsRepositoryAbstract,
+}
+
+impl<'a> GetAllRolePermissionsUseCase<'a> {
+    pub fn new(repository: &'a dyn PermissionsRepositoryAbstract) -> Self {
+        GetAllRolePermissionsUseCase { repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<RolePermissionsEntity>> for GetAllRolePermissionsUseCase<'a> {
+    #[instrument(name = "GetAllRolePermissionsUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<RolePermissionsEntity>, ApiError> {
+        let role_permissions = self.repository.get_all_role_permissions().await;
+
+        match role_permissions {
+            Ok(role_permissions) => Ok(role_permissions),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get all role permissions", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::permissions_repository_abstract::MockPermissionsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "get all role permissions" usecase repo with an unexpected random error
+        let mut permissions_repository = MockPermissionsRepositoryAbstract::new();
+        permissions_repository
+            .expect_get_all_role_permissions()
+            .times(1)
+            .returning(|| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_all_role_permissions_usecase = GetAllRolePermissionsUseCase::new(&permissions_repository);
+        let data = get_all_role_permissions_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot get all role permissions", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_permissions_grouped_by_role() {
+        // given the "get all role permissions" usecase repo returning two roles
+        let mut permissions_repository = MockPermissionsRepositoryAbstract::new();
+        permissions_repository.expect_get_all_role_permissions().times(1).returning(|| {
+            Ok(vec![
+                RolePermissionsEntity::new(String::from("admin"), vec![String::from("tasks:read"), String::from("tasks:read:any")]),
+                RolePermissionsEntity::new(String::from("customer"), vec![String::from("tasks:read")]),
+            ])
+        });
+
+        // when calling usecase
+        let get_all_role_permissions_usecase = GetAllRolePermissionsUseCase::new(&permissions_repository);
+        let data = get_all_role_permissions_usecase.execute().await.unwrap();
+
+        // then assert every role is returned with its grants
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[0].role, "admin");
+        assert_eq!(data[0].permissions.len(), 2);
+        assert_eq!(data[1].permissions, vec![String::from("tasks:read")]);
+    }
+}
@@ -0,0 +1,2 @@
+pub mod get_all_role_permissions_usecase;
+pub mod update_role_permissions_usecase;
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::permissions::permissions_payloads::RolePermissionsPayload,
+    application::{repositories::permissions_repository_abstract::PermissionsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, permission_entity::RolePermissionsEntity},
+};
+
+pub struct UpdateRolePermissionsUseCase<'a> {
+    permission_payload: &'a RolePermissionsPayload,
+    repository: &'a dyn PermissionsRepositoryAbstract,
+}
+
+impl<'a> UpdateRolePermissionsUseCase<'a> {
+    pub fn new(permission_payload: &'a RolePermissionsPayload, repository: &'a dyn PermissionsRepositoryAbstract) -> Self {
+        UpdateRolePermissionsUseCase { permission_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<RolePermissionsEntity> for UpdateRolePermissionsUseCase<'a> {
+    #[instrument(name = "UpdateRolePermissionsUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<RolePermissionsEntity, ApiError> {
+        let role_permissions = self.repository.update_role_permissions(self.permission_payload).await;
+
+        match role_permissions {
+            Ok(role_permissions) => Ok(role_permissions),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot update role permissions", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::permissions_repository_abstract::MockPermissionsRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "update role permissions" usecase repo with an unexpected random error
+        let mut permissions_repository = MockPermissionsRepositoryAbstract::new();
+        let payload = RolePermissionsPayload::new(UserRolePayload::Author, vec![String::from("tasks:read")]);
+        permissions_repository
+            .expect_update_role_permissions()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let update_role_permissions_usecase = UpdateRolePermissionsUseCase::new(&payload, &permissions_repository);
+        let data = update_role_permissions_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot update role permissions", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_updated_role_permissions() {
+        // given the "update role permissions" usecase repo replacing the grants of a role
+        let mut permissions_repository = MockPermissionsRepositoryAbstract::new();
+        let payload = RolePermissionsPayload::new(UserRolePayload::Author, vec![String::from("tasks:read"), String::from("tasks:read:any")]);
+        permissions_repository
+            .expect_update_role_permissions()
+            .times(1)
+            .returning(|payload| Ok(RolePermissionsEntity::new(payload.role.to_string(), payload.permissions.clone())));
+
+        // when calling usecase
+        let update_role_permissions_usecase = UpdateRolePermissionsUseCase::new(&payload, &permissions_repository);
+        let data = update_role_permissions_usecase.execute().await.unwrap();
+
+        // then assert the role holds the new grants
+        assert_eq!(data.role, "author");
+        assert_eq!(data.permissions, vec![String::from("tasks:read"), String::from("tasks:read:any")]);
+    }
+}
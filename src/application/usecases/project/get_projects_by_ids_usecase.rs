@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::projects_repository_abstract::ProjectsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, project_entity::ProjectEntity},
+};
+
+// Batch lookup behind the GraphQL project loader, one query for every project a response refers to
+pub struct GetProjectsByIdsUseCase<'a> {
+    project_ids: &'a [String],
+    repository: &'a dyn ProjectsRepositoryAbstract,
+}
+
+impl<'a> GetProjectsByIdsUseCase<'a> {
+    pub fn new(project_ids: &'a [String], repository: &'a dyn ProjectsRepositoryAbstract) -> Self {
+        GetProjectsByIdsUseCase { project_ids, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<ProjectEntity>> for GetProjectsByIdsUseCase<'a> {
+    #[instrument(name = "GetProjectsByIdsUseCase::execute", skip_all, fields(count = self.project_ids.len()))]
+    async fn execute(&self) -> Result<Vec<ProjectEntity>, ApiError> {
+        if self.project_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match self.repository.get_projects_by_ids(self.project_ids).await {
+            Ok(projects) => Ok(projects),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get projects", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::projects_repository_abstract::MockProjectsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_error_with_generic_message_when_unexpected_repo_error() {
+        // given the "projects by ids" usecase repo with an unexpected random error
+        let mut project_repository = MockProjectsRepositoryAbstract::new();
+        let project_ids = vec![String::from("id1")];
+        project_repository
+            .expect_get_projects_by_ids()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_projects_by_ids_usecase = GetProjectsByIdsUseCase::new(&project_ids, &project_repository);
+        let data = get_projects_by_ids_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot get projects", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_projects() {
+        // given the "projects by ids" usecase repo returning one project
+        let mut project_repository = MockProjectsRepositoryAbstract::new();
+        let project_ids = vec![String::from("id1")];
+        project_repository.expect_get_projects_by_ids().times(1).returning(|_| {
+            let now = Utc::now().naive_utc();
+            Ok(vec![ProjectEntity::new(String::from("id1"), String::from("Website"), None, now, now)])
+        });
+
+        // when calling usecase
+        let get_projects_by_ids_usecase = GetProjectsByIdsUseCase::new(&project_ids, &project_repository);
+        let data = get_projects_by_ids_usecase.execute().await.unwrap();
+
+        // then assert the project is returned
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].name, "Website");
+    }
+}
@@ -0,0 +1 @@
+pub mod get_projects_by_ids_usecase;
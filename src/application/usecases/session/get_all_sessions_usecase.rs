@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::sessions::sessions_payloads::SessionDataPayload,
+    application::{repositories::sessions_repository_abstract::SessionsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, session_entity::SessionEntity},
+};
+
+pub struct GetAllSessionsUseCase<'a> {
+    session_payload: &'a SessionDataPayload,
+    repository: &'a dyn SessionsRepositoryAbstract,
+}
+
+impl<'a> GetAllSessionsUseCase<'a> {
+    pub fn new(session_payload: &'a SessionDataPayload, repository: &'a dyn SessionsRepositoryAbstract) -> Self {
+        GetAllSessionsUseCase { session_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<SessionEntity>> for GetAllSessionsUseCase<'a> {
+    #[instrument(name = "GetAllSessionsUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<SessionEntity>, ApiError> {
+        let sessions = self.repository.get_all_sessions(self.session_payload).await;
+
+        match sessions {
+            Ok(sessions) => Ok(sessions),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get all sessions", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::NaiveDateTime;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::sessions_repository_abstract::MockSessionsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_error_with_generic_message_when_unexpected_repo_error() {
+        // given the "all sessions" usecase repo with an unexpected random error
+        let mut session_repository = MockSessionsRepositoryAbstract::new();
+        let payload = SessionDataPayload::new(None, Some(String::from("id1")));
+        session_repository
+            .expect_get_all_sessions()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_all_sessions_usecase = GetAllSessionsUseCase::new(&payload, &session_repository);
+        let data = get_all_sessions_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot get all sessions", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_list() {
+        // given the "all sessions" usecase repo returning a list of 2 entities
+        let mut session_repository = MockSessionsRepositoryAbstract::new();
+        let payload = SessionDataPayload::new(None, Some(String::from("id1")));
+        session_repository.expect_get_all_sessions().times(1).returning(|_| {
+            Ok(vec![
+                SessionEntity::new(
+                    String::from("session1"),
+                    String::from("id1"),
+                    Some(String::from("Laptop")),
+                    Some(String::from("127.0.0.1")),
+                    None,
+                    None,
+                    NaiveDateTime::default(),
+                    NaiveDateTime::default(),
+                ),
+                SessionEntity::new(
+                    String::from("session2"),
+                    String::from("id1"),
+                    Some(String::from("Phone")),
+                    None,
+                    None,
+                    None,
+                    NaiveDateTime::default(),
+                    NaiveDateTime::default(),
+                ),
+            ])
+        });
+
+        // when calling usecase
+        let get_all_sessions_usecase = GetAllSessionsUseCase::new(&payload, &session_repository);
+        let data = get_all_sessions_usecase.execute().await.unwrap();
+
+        // then assert the result is the expected list
+        assert_eq!(data.len(), 2);
+        assert_eq!(data[1].device_name, Some(String::from("Phone")));
+    }
+}
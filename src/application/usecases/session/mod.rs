@@ -0,0 +1,2 @@
+pub mod get_all_sessions_usecase;
+pub mod revoke_one_session_usecase;
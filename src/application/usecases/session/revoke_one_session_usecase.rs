@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::sessions::sessions_payloads::SessionDataPayload,
+    application::{repositories::sessions_repository_abstract::SessionsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, session_entity::SessionEntity},
+};
+
+pub struct RevokeOneSessionUseCase<'a> {
+    session_payload: &'a SessionDataPayload,
+    repository: &'a dyn SessionsRepositoryAbstract,
+}
+
+impl<'a> RevokeOneSessionUseCase<'a> {
+    pub fn new(session_payload: &'a SessionDataPayload, repository: &'a dyn SessionsRepositoryAbstract) -> Self {
+        RevokeOneSessionUseCase { session_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<SessionEntity> for RevokeOneSessionUseCase<'a> {
+    #[instrument(name = "RevokeOneSessionUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<SessionEntity, ApiError> {
+        let session = self.repository.revoke_session(self.session_payload).await;
+
+        match session {
+            Ok(session) => Ok(session),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot revoke single session", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::NaiveDateTime;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::sessions_repository_abstract::MockSessionsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_error_with_generic_message_when_unexpected_repo_error() {
+        // given the "revoke session" usecase repo with an unexpected random error
+        let mut session_repository = MockSessionsRepositoryAbstract::new();
+        let payload = SessionDataPayload::new(Some(String::from("session1")), Some(String::from("id1")));
+        session_repository
+            .expect_revoke_session()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let revoke_one_session_usecase = RevokeOneSessionUseCase::new(&payload, &session_repository);
+        let data = revoke_one_session_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot revoke single session", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_one_result() {
+        // given the "revoke session" usecase repo returning the revoked session
+        let mut session_repository = MockSessionsRepositoryAbstract::new();
+        let payload = SessionDataPayload::new(Some(String::from("session1")), Some(String::from("id1")));
+        session_repository.expect_revoke_session().times(1).returning(|_| {
+            Ok(SessionEntity::new(
+                String::from("session1"),
+                String::from("id1"),
+                Some(String::from("Laptop")),
+                None,
+                None,
+                Some(NaiveDateTime::default()),
+                NaiveDateTime::default(),
+                NaiveDateTime::default(),
+            ))
+        });
+
+        // when calling usecase
+        let revoke_one_session_usecase = RevokeOneSessionUseCase::new(&payload, &session_repository);
+        let data = revoke_one_session_usecase.execute().await.unwrap();
+
+        // then assert the result is the revoked session
+        assert_eq!(data.id, String::from("session1"));
+        assert!(data.revoked_at.is_some());
+    }
+}
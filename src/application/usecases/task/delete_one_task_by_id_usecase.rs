@@ -1,26 +1,45 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::tasks::tasks_payloads::TaskDataPayload,
-    application::{repositories::tasks_repository_abstract::TasksRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
     domain::{error::ApiError, task_entity::TaskEntity},
 };
 
 pub struct DeleteOneTaskByIdUseCase<'a> {
     task_payload: &'a TaskDataPayload,
+    policy: &'a TaskPolicy,
     repository: &'a dyn TasksRepositoryAbstract,
 }
 
 impl<'a> DeleteOneTaskByIdUseCase<'a> {
-    pub fn new(task_payload: &'a TaskDataPayload, repository: &'a dyn TasksRepositoryAbstract) -> Self {
-        DeleteOneTaskByIdUseCase { task_payload, repository }
+    pub fn new(task_payload: &'a TaskDataPayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        DeleteOneTaskByIdUseCase { task_payload, policy, repository }
     }
 }
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<TaskEntity> for DeleteOneTaskByIdUseCase<'a> {
+    #[instrument(name = "DeleteOneTaskByIdUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<TaskEntity, ApiError> {
-        let task = self.repository.delete_task_by_id(&self.task_payload).await;
+        match self.repository.get_task_by_id(self.task_payload).await {
+            Ok(Some(task)) if self.policy.can_delete(&task) => {}
+            Ok(_) => return Err(ErrorHandlingUtils::not_found_error("Task not found")),
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot delete single task", Some(e))),
+        }
+
+        // the owner is checked again by the deletion, the task may have been handed over since it was read
+        let task_payload = TaskDataPayload {
+            user_id: self.policy.deletable_owner(self.task_payload.user_id.as_ref()),
+            task_id: self.task_payload.task_id.clone(),
+            sort: self.task_payload.sort,
+        };
+        let task = self.repository.delete_task_by_id(&task_payload).await;
 
         match task {
             Ok(task) => Ok(task),
@@ -32,66 +51,96 @@ impl<'a> AbstractUseCase<TaskEntity> for DeleteOneTaskByIdUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
+    use std::collections::HashSet;
     use std::io::{Error, ErrorKind};
 
+    use crate::test_support::task_owned_by;
+
     use crate::{
-        adapters::api::{
-            tasks::tasks_payloads::TaskDataPayload,
-            tasks::tasks_payloads::{TaskPriorityPayload, TaskStatusPayload, TaskStatusToDoPayload, TaskTypePayload},
-        },
-        application::repositories::tasks_repository_abstract::MockTasksRepositoryAbstract,
-        domain::task_entity::TaskEntity,
+        adapters::api::tasks::tasks_payloads::TaskDataPayload,
+        application::{repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, utils::access_control::extractors::claims::Permission},
     };
 
     #[actix_rt::test]
     async fn test_should_return_error_with_generic_message_when_unexpected_repo_error() {
-        // given the "all tasks" usecase repo with an unexpected random error
+        // given the "delete one task by id" usecase repo with an unexpected random error
         let mut task_repository = MockTasksRepositoryAbstract::new();
         let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
         task_repository
             .expect_delete_task_by_id()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
-        let delete_one_task_by_id_usecase = DeleteOneTaskByIdUseCase::new(&payload, &task_repository);
+        let delete_one_task_by_id_usecase = DeleteOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
         let data = delete_one_task_by_id_usecase.execute().await;
 
         // then exception
         assert!(data.is_err());
         let result = data.unwrap_err();
-        assert_eq!("Cannot get single task", result.message);
+        assert_eq!("Cannot delete single task", result.message);
     }
 
     #[actix_rt::test]
     async fn test_should_return_one_result() {
-        // given the "one task by id" usecase repo returning one result
+        // given the "delete one task by id" usecase repo deleting a task of another user, as a caller allowed to delete any task
         let mut task_repository = MockTasksRepositoryAbstract::new();
-        let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
-        task_repository.expect_get_task_by_id().times(1).returning(|_| {
-            Ok(TaskEntity {
-                id: String::from("id1"),
-                user_id: String::from("id1"),
-                project_id: String::from("id1"),
-                title: String::from("task1"),
-                typ: TaskTypePayload::Work.to_string(),
-                priority: TaskPriorityPayload::Low.to_string(),
-                status: TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted).to_string(),
-                description: String::from(""),
-                duration: 1,
-                due_date: 321472382,
-                task_list: [].to_vec(),
-                updated_at: todo!(),
-                created_at: todo!(),
-            })
-        });
+        let payload = TaskDataPayload::new(Some(String::from("id1")), None);
+        let policy = TaskPolicy::new(String::from("id2"), HashSet::from([Permission::TasksDeleteAny]));
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
+        task_repository.expect_delete_task_by_id().times(1).returning(|_| Ok(task_owned_by("id1")));
 
         // when calling usecase
-        let get_one_task_by_id_usecase = DeleteOneTaskByIdUseCase::new(&payload, &task_repository);
-        let data = get_one_task_by_id_usecase.execute().await.unwrap();
+        let delete_one_task_by_id_usecase = DeleteOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
+        let data = delete_one_task_by_id_usecase.execute().await.unwrap();
 
         // then assert the result is the expected entity
         assert_eq!(data.id, String::from("id1"));
         assert_eq!(data.title, "task1");
     }
+
+    #[actix_rt::test]
+    async fn test_should_not_delete_task_of_another_user() {
+        // given the "delete one task by id" usecase repo returning a task owned by someone else
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskDataPayload::new(Some(String::from("id1")), None);
+        let policy = TaskPolicy::new(String::from("id2"), HashSet::from([Permission::TasksReadAny]));
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
+        task_repository.expect_delete_task_by_id().times(0);
+
+        // when calling usecase
+        let delete_one_task_by_id_usecase = DeleteOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
+        let data = delete_one_task_by_id_usecase.execute().await;
+
+        // then the task is reported as missing and left untouched
+        let result = data.unwrap_err();
+        assert_eq!(result.code, 404);
+        assert_eq!("Task not found", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_delete_only_a_task_still_owned_by_the_caller() {
+        // given the "delete one task by id" usecase repo whose task was handed over after it was read
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskDataPayload::new(Some(String::from("task1")), None);
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::from([Permission::TasksDelete]));
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
+        task_repository
+            .expect_delete_task_by_id()
+            .withf(|payload| payload.user_id == Some(String::from("id1")))
+            .times(1)
+            .returning(|_| Err(DomainError::NotFound(String::from("Task not found"))));
+
+        // when calling usecase
+        let delete_one_task_by_id_usecase = DeleteOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
+        let data = delete_one_task_by_id_usecase.execute().await;
+
+        // then the deletion is filtered on the caller and the task reported as missing
+        let result = data.unwrap_err();
+        assert_eq!(result.code, 404);
+        assert_eq!("Task not found", result.message);
+    }
 }
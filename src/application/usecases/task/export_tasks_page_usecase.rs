@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::tasks::tasks_payloads::{TaskExportPayload, TaskPagePayload},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
+    domain::{error::ApiError, task_entity::TaskEntity},
+};
+
+// Tasks per page, an export holds one page in memory at a time. A shorter page is the last one.
+pub const EXPORT_PAGE_SIZE: usize = 500;
+
+// One page of an export, the page after `after` or the first one. Callers without `tasks:read:any` only ever export
+// their own tasks, whatever the filters say.
+pub struct ExportTasksPageUseCase<'a> {
+    task_payload: &'a TaskExportPayload,
+    after: Option<&'a TaskEntity>,
+    policy: &'a TaskPolicy,
+    repository: &'a dyn TasksRepositoryAbstract,
+}
+
+impl<'a> ExportTasksPageUseCase<'a> {
+    pub fn new(task_payload: &'a TaskExportPayload, after: Option<&'a TaskEntity>, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        ExportTasksPageUseCase {
+            task_payload,
+            after,
+            policy,
+            repository,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<TaskEntity>> for ExportTasksPageUseCase<'a> {
+    #[instrument(name = "ExportTasksPageUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<TaskEntity>, ApiError> {
+        let page_payload = TaskPagePayload {
+            user_id: self.policy.readable_owner(self.task_payload.user_id.as_ref()),
+            project_id: self.task_payload.project_id.clone(),
+            after: self.after.map(|task| (task.created_at, task.id.clone())),
+            limit: EXPORT_PAGE_SIZE as i64,
+            due_only: false,
+        };
+        let tasks = self.repository.get_tasks_page(&page_payload).await;
+
+        match tasks {
+            Ok(tasks) => Ok(tasks),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot export tasks", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use mockall::predicate::eq;
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        application::{repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, utils::access_control::extractors::claims::Permission},
+        test_support::TaskEntityBuilder,
+    };
+
+    fn task(id: &str) -> TaskEntity {
+        TaskEntityBuilder::new(id).build()
+    }
+
+    #[actix_rt::test]
+    async fn test_should_only_export_own_tasks_without_read_any() {
+        // given a caller without `tasks:read:any` asking for someone else's tasks after a first page
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskExportPayload {
+            user_id: Some(String::from("id2")),
+            ..Default::default()
+        };
+        let last = task("task1");
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository
+            .expect_get_tasks_page()
+            .with(eq(TaskPagePayload {
+                user_id: Some(String::from("id1")),
+                project_id: None,
+                after: Some((last.created_at, String::from("task1"))),
+                limit: EXPORT_PAGE_SIZE as i64,
+                due_only: false,
+            }))
+            .times(1)
+            .returning(|_| Ok(vec![task("task2")]));
+
+        // when calling usecase
+        let export_tasks_page_usecase = ExportTasksPageUseCase::new(&payload, Some(&last), &policy, &task_repository);
+        let data = export_tasks_page_usecase.execute().await.unwrap();
+
+        // then the next page of the caller's own tasks
+        assert_eq!(data.len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_export_a_whole_project_with_read_any() {
+        // given a caller with `tasks:read:any` exporting a project
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskExportPayload {
+            project_id: Some(String::from("project1")),
+            ..Default::default()
+        };
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::from([Permission::TasksReadAny]));
+        task_repository
+            .expect_get_tasks_page()
+            .withf(|page| page.user_id.is_none() && page.project_id.as_deref() == Some("project1") && page.after.is_none())
+            .times(1)
+            .returning(|_| Ok(Vec::new()));
+
+        // when calling usecase
+        let export_tasks_page_usecase = ExportTasksPageUseCase::new(&payload, None, &policy, &task_repository);
+        let data = export_tasks_page_usecase.execute().await;
+
+        // then an empty first page
+        assert!(data.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "export tasks page" usecase repo with an unexpected random error
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskExportPayload::default();
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository
+            .expect_get_tasks_page()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let export_tasks_page_usecase = ExportTasksPageUseCase::new(&payload, None, &policy, &task_repository);
+        let data = export_tasks_page_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot export tasks", data.unwrap_err().message);
+    }
+}
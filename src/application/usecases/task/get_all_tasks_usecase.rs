@@ -1,26 +1,38 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::tasks::tasks_payloads::TaskDataPayload,
-    application::{repositories::tasks_repository_abstract::TasksRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
     domain::{error::ApiError, task_entity::*},
 };
 
 pub struct GetAllTasksUseCase<'a> {
     task_payload: &'a TaskDataPayload,
+    policy: &'a TaskPolicy,
     repository: &'a dyn TasksRepositoryAbstract,
 }
 
 impl<'a> GetAllTasksUseCase<'a> {
-    pub fn new(task_payload: &'a TaskDataPayload, repository: &'a dyn TasksRepositoryAbstract) -> Self {
-        GetAllTasksUseCase { task_payload, repository }
+    pub fn new(task_payload: &'a TaskDataPayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        GetAllTasksUseCase { task_payload, policy, repository }
     }
 }
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<Vec<TaskAllEntity>> for GetAllTasksUseCase<'a> {
+    #[instrument(name = "GetAllTasksUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<Vec<TaskAllEntity>, ApiError> {
-        let tasks = self.repository.get_all_tasks(&self.task_payload).await;
+        let task_payload = TaskDataPayload {
+            user_id: self.policy.readable_owner(self.task_payload.user_id.as_ref()),
+            task_id: self.task_payload.task_id.clone(),
+            sort: self.task_payload.sort,
+        };
+        let tasks = self.repository.get_all_tasks(&task_payload).await;
 
         match tasks {
             Ok(tasks) => Ok(tasks),
@@ -32,6 +44,8 @@ impl<'a> AbstractUseCase<Vec<TaskAllEntity>> for GetAllTasksUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
+    use std::collections::HashSet;
     use std::io::{Error, ErrorKind};
 
     use crate::{application::repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, domain::task_entity::TaskAllEntity};
@@ -41,13 +55,14 @@ mod tests {
         // given the "all tasks" usecase repo with an unexpected random error
         let mut task_repository = MockTasksRepositoryAbstract::new();
         let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
         task_repository
             .expect_get_all_tasks()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
-        let get_all_tasks_usecase = GetAllTasksUseCase::new(&payload, &task_repository);
+        let get_all_tasks_usecase = GetAllTasksUseCase::new(&payload, &policy, &task_repository);
         let data = get_all_tasks_usecase.execute().await;
 
         // then exception
@@ -61,10 +76,11 @@ mod tests {
         // given the "all tasks" usecase repo returning an empty list
         let mut task_repository = MockTasksRepositoryAbstract::new();
         let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
         task_repository.expect_get_all_tasks().times(1).returning(|_| Ok(Vec::<TaskAllEntity>::new()));
 
         // when calling usecase
-        let get_all_tasks_usecase = GetAllTasksUseCase::new(&payload, &task_repository);
+        let get_all_tasks_usecase = GetAllTasksUseCase::new(&payload, &policy, &task_repository);
         let data = get_all_tasks_usecase.execute().await.unwrap();
 
         // then assert the result is an empty list
@@ -76,6 +92,7 @@ mod tests {
         // given the "all tasks" usecase repo returning a list of 2 entities
         let mut task_repository = MockTasksRepositoryAbstract::new();
         let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
         task_repository.expect_get_all_tasks().times(1).returning(|_| {
             Ok(vec![
                 TaskAllEntity {
@@ -83,6 +100,8 @@ mod tests {
                     user_id: String::from("id1"),
                     title: String::from("Task 1"),
                     project_id: String::from("id1"),
+                    priority: TaskPriority::Low,
+                    status: TaskStatus::None,
                     description: todo!(),
                     updated_at: todo!(),
                     created_at: todo!(),
@@ -92,6 +111,8 @@ mod tests {
                     user_id: String::from("id1"),
                     title: String::from("Task 2"),
                     project_id: String::from("id1"),
+                    priority: TaskPriority::Low,
+                    status: TaskStatus::None,
                     description: todo!(),
                     updated_at: todo!(),
                     created_at: todo!(),
@@ -100,7 +121,7 @@ mod tests {
         });
 
         // when calling usecase
-        let get_all_tasks_usecase = GetAllTasksUseCase::new(&payload, &task_repository);
+        let get_all_tasks_usecase = GetAllTasksUseCase::new(&payload, &policy, &task_repository);
         let data = get_all_tasks_usecase.execute().await.unwrap();
 
         // then assert the result is an empty list
@@ -1,29 +1,37 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::tasks::tasks_payloads::TaskDataPayload,
-    application::{repositories::tasks_repository_abstract::TasksRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
     domain::{error::ApiError, task_entity::TaskEntity},
 };
 
 pub struct GetOneTaskByIdUseCase<'a> {
     task_payload: &'a TaskDataPayload,
+    policy: &'a TaskPolicy,
     repository: &'a dyn TasksRepositoryAbstract,
 }
 
 impl<'a> GetOneTaskByIdUseCase<'a> {
-    pub fn new(task_payload: &'a TaskDataPayload, repository: &'a dyn TasksRepositoryAbstract) -> Self {
-        GetOneTaskByIdUseCase { task_payload, repository }
+    pub fn new(task_payload: &'a TaskDataPayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        GetOneTaskByIdUseCase { task_payload, policy, repository }
     }
 }
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<TaskEntity> for GetOneTaskByIdUseCase<'a> {
+    #[instrument(name = "GetOneTaskByIdUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<TaskEntity, ApiError> {
         let task = self.repository.get_task_by_id(&self.task_payload).await;
 
         match task {
-            Ok(task) => Ok(task),
+            Ok(Some(task)) if self.policy.can_read(&task) => Ok(task),
+            Ok(_) => Err(ErrorHandlingUtils::not_found_error("Task not found")),
             Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get single task", Some(e))),
         }
     }
@@ -32,12 +40,15 @@ impl<'a> AbstractUseCase<TaskEntity> for GetOneTaskByIdUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
+    use std::collections::HashSet;
     use std::io::{Error, ErrorKind};
 
+    use crate::test_support::task_owned_by;
+
     use crate::{
-        adapters::api::{tasks::tasks_payloads::TaskDataPayload, tasks::tasks_payloads::*},
+        adapters::api::tasks::tasks_payloads::TaskDataPayload,
         application::repositories::tasks_repository_abstract::MockTasksRepositoryAbstract,
-        domain::task_entity::TaskEntity,
     };
 
     #[actix_rt::test]
@@ -45,13 +56,14 @@ mod tests {
         // given the "all tasks" usecase repo with an unexpected random error
         let mut task_repository = MockTasksRepositoryAbstract::new();
         let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
         task_repository
             .expect_get_task_by_id()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
-        let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&payload, &task_repository);
+        let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
         let data = get_one_task_by_id_usecase.execute().await;
 
         // then exception
@@ -65,30 +77,33 @@ mod tests {
         // given the "one task by id" usecase repo returning one result
         let mut task_repository = MockTasksRepositoryAbstract::new();
         let payload = TaskDataPayload::new(Some(String::from("id1")), Some(String::from("id1")));
-        task_repository.expect_get_task_by_id().times(1).returning(|_| {
-            Ok(TaskEntity {
-                id: String::from("id1"),
-                user_id: String::from("id1"),
-                project_id: String::from("id1"),
-                title: String::from("task1"),
-                typ: TaskTypePayload::Work.to_string(),
-                priority: TaskPriorityPayload::Low.to_string(),
-                status: TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted).to_string(),
-                description: String::from(""),
-                duration: 1,
-                due_date: 321472382,
-                task_list: [].to_vec(),
-                updated_at: todo!(),
-                created_at: todo!(),
-            })
-        });
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
 
         // when calling usecase
-        let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&payload, &task_repository);
+        let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
         let data = get_one_task_by_id_usecase.execute().await.unwrap();
 
         // then assert the result is the expected entity
         assert_eq!(data.id, String::from("id1"));
         assert_eq!(data.title, "task1");
     }
+
+    #[actix_rt::test]
+    async fn test_should_hide_task_of_another_user() {
+        // given the "one task by id" usecase repo returning a task owned by someone else
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskDataPayload::new(Some(String::from("id1")), None);
+        let policy = TaskPolicy::new(String::from("id2"), HashSet::new());
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
+
+        // when calling usecase
+        let get_one_task_by_id_usecase = GetOneTaskByIdUseCase::new(&payload, &policy, &task_repository);
+        let data = get_one_task_by_id_usecase.execute().await;
+
+        // then the task is reported as missing
+        let result = data.unwrap_err();
+        assert_eq!(result.code, 404);
+        assert_eq!("Task not found", result.message);
+    }
 }
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::tasks::tasks_payloads::TaskListPagePayload,
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
+    domain::{error::ApiError, task_entity::TaskAllEntity},
+};
+
+// One page of a task list. Callers without `tasks:read:any` only ever list their own tasks, whatever `user_id` says.
+pub struct GetTaskListPageUseCase<'a> {
+    page_payload: &'a TaskListPagePayload,
+    policy: &'a TaskPolicy,
+    repository: &'a dyn TasksRepositoryAbstract,
+}
+
+impl<'a> GetTaskListPageUseCase<'a> {
+    pub fn new(page_payload: &'a TaskListPagePayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        GetTaskListPageUseCase { page_payload, policy, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<TaskAllEntity>> for GetTaskListPageUseCase<'a> {
+    #[instrument(name = "GetTaskListPageUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<TaskAllEntity>, ApiError> {
+        let page_payload = TaskListPagePayload {
+            user_id: self.policy.readable_owner(self.page_payload.user_id.as_ref()),
+            ..self.page_payload.clone()
+        };
+        let tasks = self.repository.get_task_list_page(&page_payload).await;
+
+        match tasks {
+            Ok(tasks) => Ok(tasks),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get all tasks", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use mockall::predicate::eq;
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        adapters::api::tasks::tasks_payloads::{TaskListCursor, TaskSortPayload},
+        application::{repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, utils::access_control::extractors::claims::Permission},
+        domain::task_entity::TaskPriority,
+    };
+
+    fn page_payload(user_id: Option<&str>) -> TaskListPagePayload {
+        TaskListPagePayload {
+            user_id: user_id.map(String::from),
+            sort: Some(TaskSortPayload::PriorityDesc),
+            after: Some(TaskListCursor {
+                priority: TaskPriority::High,
+                created_at: chrono::DateTime::from_timestamp(0, 0).unwrap().naive_utc(),
+                id: String::from("task1"),
+            }),
+            limit: 11,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_should_only_list_own_tasks_without_read_any() {
+        // given a caller without `tasks:read:any` asking for the next page of someone else's tasks
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = page_payload(Some("id2"));
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository
+            .expect_get_task_list_page()
+            .with(eq(page_payload(Some("id1"))))
+            .times(1)
+            .returning(|_| Ok(Vec::new()));
+
+        // when calling usecase
+        let get_task_list_page_usecase = GetTaskListPageUseCase::new(&payload, &policy, &task_repository);
+        let data = get_task_list_page_usecase.execute().await;
+
+        // then the same page of the caller's own tasks
+        assert!(data.unwrap().is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_list_every_owner_with_read_any() {
+        // given a caller with `tasks:read:any` without an owner filter
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = page_payload(None);
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::from([Permission::TasksReadAny]));
+        task_repository.expect_get_task_list_page().with(eq(page_payload(None))).times(1).returning(|_| Ok(Vec::new()));
+
+        // when calling usecase
+        let get_task_list_page_usecase = GetTaskListPageUseCase::new(&payload, &policy, &task_repository);
+        let data = get_task_list_page_usecase.execute().await;
+
+        // then the page is asked as is
+        assert!(data.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "task list page" usecase repo with an unexpected random error
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = page_payload(None);
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository
+            .expect_get_task_list_page()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_task_list_page_usecase = GetTaskListPageUseCase::new(&payload, &policy, &task_repository);
+        let data = get_task_list_page_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot get all tasks", data.unwrap_err().message);
+    }
+}
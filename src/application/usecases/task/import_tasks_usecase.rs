@@ -0,0 +1,215 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::tasks::tasks_payloads::{TaskCreatePayload, TaskImportPayload},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
+    domain::{
+        error::{ApiError, DomainError, FieldError},
+        task_entity::TaskImportReport,
+    },
+};
+
+// Creates the tasks of an import file at once. Rows are held to the same rules as `PostOneTaskUseCase`, any invalid
+// row fails the whole import, a dry run only reports.
+pub struct ImportTasksUseCase<'a> {
+    import_payload: &'a TaskImportPayload,
+    policy: &'a TaskPolicy,
+    repository: &'a dyn TasksRepositoryAbstract,
+}
+
+impl<'a> ImportTasksUseCase<'a> {
+    pub fn new(import_payload: &'a TaskImportPayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        ImportTasksUseCase { import_payload, policy, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<TaskImportReport> for ImportTasksUseCase<'a> {
+    #[instrument(name = "ImportTasksUseCase::execute", skip_all, fields(rows = self.import_payload.rows.len(), dry_run = self.import_payload.dry_run))]
+    async fn execute(&self) -> Result<TaskImportReport, ApiError> {
+        let mut task_payloads: Vec<TaskCreatePayload> = Vec::new();
+        let mut errors = Vec::new();
+        for row in &self.import_payload.rows {
+            match &row.task {
+                Ok(task) if !self.policy.can_assign(task.user_id.as_ref()) => {
+                    errors.push(row_error(row.row, FieldError::new("user_id", "forbidden", "cannot create tasks for another user")));
+                }
+                Ok(task) => task_payloads.push(task.clone()),
+                Err(fields) => errors.extend(fields.iter().cloned().map(|field| row_error(row.row, field))),
+            }
+        }
+
+        if !self.import_payload.dry_run && !errors.is_empty() {
+            let error = DomainError::Validation {
+                message: String::from("Invalid rows, nothing was imported"),
+                fields: errors,
+            };
+            return Err(ErrorHandlingUtils::application_error("Invalid rows, nothing was imported", Some(error)));
+        }
+
+        let created = match self.import_payload.dry_run || task_payloads.is_empty() {
+            true => Vec::new(),
+            false => match self.repository.post_tasks(&task_payloads).await {
+                Ok(tasks) => tasks,
+                Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot import tasks", Some(e))),
+            },
+        };
+
+        Ok(TaskImportReport {
+            dry_run: self.import_payload.dry_run,
+            rows: self.import_payload.rows.len(),
+            valid_rows: task_payloads.len(),
+            created,
+            errors,
+        })
+    }
+}
+
+fn row_error(row: usize, field: FieldError) -> FieldError {
+    let prefix = format!("rows[{}]", row);
+    FieldError {
+        field: match field.field.is_empty() {
+            true => prefix,
+            false => format!("{}.{}", prefix, field.field),
+        },
+        ..field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{adapters::api::tasks::tasks_payloads::TaskImportRowPayload, application::repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, test_support::TaskEntityBuilder};
+
+    fn create_payload(user_id: &str, title: &str) -> TaskCreatePayload {
+        TaskCreatePayload::new(Some(String::from(user_id)), None, String::from(title), None, None, None, None, None, None, None)
+    }
+
+    fn import_payload(dry_run: bool) -> TaskImportPayload {
+        TaskImportPayload {
+            rows: vec![
+                TaskImportRowPayload {
+                    row: 1,
+                    task: Ok(create_payload("id1", "Mine")),
+                },
+                TaskImportRowPayload {
+                    row: 2,
+                    task: Ok(create_payload("id2", "Someone else's")),
+                },
+                TaskImportRowPayload {
+                    row: 3,
+                    task: Err(vec![FieldError::new("title", "blank", "must not be blank")]),
+                },
+            ],
+            dry_run,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_should_report_invalid_rows_on_a_dry_run() {
+        // given a dry run with a row for another user and a blank title
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = import_payload(true);
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository.expect_post_tasks().never();
+
+        // when calling usecase
+        let import_tasks_usecase = ImportTasksUseCase::new(&payload, &policy, &task_repository);
+        let report = import_tasks_usecase.execute().await.unwrap();
+
+        // then both rows reported and nothing created
+        assert_eq!(report.rows, 3);
+        assert_eq!(report.valid_rows, 1);
+        assert!(report.created.is_empty());
+        let fields: Vec<&str> = report.errors.iter().map(|error| error.field.as_str()).collect();
+        assert_eq!(fields, vec!["rows[2].user_id", "rows[3].title"]);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_import_nothing_when_a_row_is_invalid() {
+        // given the same rows for real
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = import_payload(false);
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository.expect_post_tasks().never();
+
+        // when calling usecase
+        let import_tasks_usecase = ImportTasksUseCase::new(&payload, &policy, &task_repository);
+        let error = import_tasks_usecase.execute().await.unwrap_err();
+
+        // then a validation failure listing the rows
+        assert_eq!(error.code, 422);
+        assert_eq!(error.fields.len(), 2);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_create_every_row_at_once() {
+        // given two valid rows
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskImportPayload {
+            rows: vec![
+                TaskImportRowPayload {
+                    row: 1,
+                    task: Ok(create_payload("id1", "One")),
+                },
+                TaskImportRowPayload {
+                    row: 2,
+                    task: Ok(create_payload("id1", "Two")),
+                },
+            ],
+            dry_run: false,
+        };
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository
+            .expect_post_tasks()
+            .withf(|task_payloads| task_payloads.len() == 2)
+            .times(1)
+            .returning(|task_payloads| {
+                Ok(task_payloads
+                    .iter()
+                    .map(|task_payload| TaskEntityBuilder::new("task").title(&task_payload.title).build())
+                    .collect())
+            });
+
+        // when calling usecase
+        let import_tasks_usecase = ImportTasksUseCase::new(&payload, &policy, &task_repository);
+        let report = import_tasks_usecase.execute().await.unwrap();
+
+        // then both tasks created
+        assert_eq!(report.created.len(), 2);
+        assert!(report.errors.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "import tasks" usecase repo with an unexpected random error
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskImportPayload {
+            rows: vec![TaskImportRowPayload {
+                row: 1,
+                task: Ok(create_payload("id1", "One")),
+            }],
+            dry_run: false,
+        };
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository
+            .expect_post_tasks()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let import_tasks_usecase = ImportTasksUseCase::new(&payload, &policy, &task_repository);
+        let data = import_tasks_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot import tasks", data.unwrap_err().message);
+    }
+}
@@ -3,3 +3,6 @@ pub mod get_one_task_by_id_usecase;
 pub mod update_one_task_usecase;
 pub mod post_one_task_usecase;
 pub mod delete_one_task_by_id_usecase;
+pub mod export_tasks_page_usecase;
+pub mod get_task_list_page_usecase;
+pub mod import_tasks_usecase;
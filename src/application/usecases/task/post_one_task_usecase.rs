@@ -1,25 +1,36 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::tasks::tasks_payloads::TaskCreatePayload,
-    application::{repositories::tasks_repository_abstract::TasksRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
     domain::{error::ApiError, task_entity::TaskEntity},
 };
 
 pub struct PostOneTaskUseCase<'a> {
     task_payload: &'a TaskCreatePayload,
+    policy: &'a TaskPolicy,
     repository: &'a dyn TasksRepositoryAbstract,
 }
 
 impl<'a> PostOneTaskUseCase<'a> {
-    pub fn new(task_payload: &'a TaskCreatePayload, repository: &'a dyn TasksRepositoryAbstract) -> Self {
-        PostOneTaskUseCase { task_payload, repository }
+    pub fn new(task_payload: &'a TaskCreatePayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        PostOneTaskUseCase { task_payload, policy, repository }
     }
 }
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<TaskEntity> for PostOneTaskUseCase<'a> {
+    #[instrument(name = "PostOneTaskUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<TaskEntity, ApiError> {
+        if !self.policy.can_assign(self.task_payload.user_id.as_ref()) {
+            return Err(ErrorHandlingUtils::forbidden_error());
+        }
+
         let task = self.repository.post_one_task(&self.task_payload).await;
 
         match task {
@@ -32,10 +43,12 @@ impl<'a> AbstractUseCase<TaskEntity> for PostOneTaskUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
+    use std::collections::HashSet;
     use std::io::{Error, ErrorKind};
 
     use crate::{
-        adapters::api::tasks::tasks_payloads::{TaskPriorityPayload, TaskStatusPayload, TaskStatusToDoPayload, TaskTypePayload},
+        domain::task_entity::{TaskPriority, TaskStatus, TaskStatusToDo, TaskType},
         application::{repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, usecases::task::post_one_task_usecase::PostOneTaskUseCase},
     };
 
@@ -47,21 +60,22 @@ mod tests {
             Some(String::from("id1")),
             Some(String::from("id1")),
             String::from("user1"),
-            Some(TaskTypePayload::Work),
-            Some(TaskPriorityPayload::Low),
-            Some(TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted)),
+            Some(TaskType::Work),
+            Some(TaskPriority::Low),
+            Some(TaskStatus::ToDo(TaskStatusToDo::NotStarted)),
             Some(String::from("")),
             Some(321472382),
             Some(1),
             Some([].to_vec()),
         );
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
         task_repository
             .expect_post_one_task()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
-        let post_one_task_usecase = PostOneTaskUseCase::new(&payload, &task_repository);
+        let post_one_task_usecase = PostOneTaskUseCase::new(&payload, &policy, &task_repository);
         let data = post_one_task_usecase.execute().await;
 
         // then exception
@@ -78,23 +92,24 @@ mod tests {
             Some(String::from("id1")),
             Some(String::from("id1")),
             String::from("user1"),
-            Some(TaskTypePayload::Work),
-            Some(TaskPriorityPayload::Low),
-            Some(TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted)),
+            Some(TaskType::Work),
+            Some(TaskPriority::Low),
+            Some(TaskStatus::ToDo(TaskStatusToDo::NotStarted)),
             Some(String::from("")),
             Some(321472382),
             Some(1),
             Some([].to_vec()),
         );
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
         task_repository.expect_post_one_task().times(1).returning(|_| {
             Ok(TaskEntity {
                 id: String::from("task1"),
                 user_id: String::from("id1"),
                 project_id: String::from("id1"),
                 title: String::from("task1"),
-                typ: TaskTypePayload::Work.to_string(),
-                priority: TaskPriorityPayload::Low.to_string(),
-                status: TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted).to_string(),
+                typ: TaskType::Work,
+                priority: TaskPriority::Low,
+                status: TaskStatus::ToDo(TaskStatusToDo::NotStarted),
                 description: String::from(""),
                 duration: 1,
                 due_date: 321472382,
@@ -105,7 +120,7 @@ mod tests {
         });
 
         // when calling usecase
-        let get_one_task_by_id_usecase = PostOneTaskUseCase::new(&payload, &task_repository);
+        let get_one_task_by_id_usecase = PostOneTaskUseCase::new(&payload, &policy, &task_repository);
         let data = get_one_task_by_id_usecase.execute().await.unwrap();
 
         // then assert the result is the expected entity
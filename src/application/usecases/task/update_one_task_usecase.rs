@@ -1,25 +1,43 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
-    adapters::api::tasks::tasks_payloads::TaskUpdatePayload,
-    application::{repositories::tasks_repository_abstract::TasksRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    adapters::api::tasks::tasks_payloads::{TaskDataPayload, TaskUpdatePayload},
+    application::{
+        repositories::tasks_repository_abstract::TasksRepositoryAbstract,
+        usecases::interfaces::AbstractUseCase,
+        utils::{access_control::task_policy::TaskPolicy, error_handling_utils::ErrorHandlingUtils},
+    },
     domain::{error::ApiError, task_entity::TaskEntity},
 };
 
 pub struct UpdateOneTaskUseCase<'a> {
     task_payload: &'a TaskUpdatePayload,
+    policy: &'a TaskPolicy,
     repository: &'a dyn TasksRepositoryAbstract,
 }
 
 impl<'a> UpdateOneTaskUseCase<'a> {
-    pub fn new(task_payload: &'a TaskUpdatePayload, repository: &'a dyn TasksRepositoryAbstract) -> Self {
-        UpdateOneTaskUseCase { task_payload, repository }
+    pub fn new(task_payload: &'a TaskUpdatePayload, policy: &'a TaskPolicy, repository: &'a dyn TasksRepositoryAbstract) -> Self {
+        UpdateOneTaskUseCase { task_payload, policy, repository }
     }
 }
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<TaskEntity> for UpdateOneTaskUseCase<'a> {
+    #[instrument(name = "UpdateOneTaskUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<TaskEntity, ApiError> {
+        let task_data_payload = TaskDataPayload::new(Some(self.task_payload.task_id.clone()), None);
+        match self.repository.get_task_by_id(&task_data_payload).await {
+            Ok(Some(task)) if self.policy.can_write(&task) => {
+                if !self.policy.can_assign(self.task_payload.user_id.as_ref()) {
+                    return Err(ErrorHandlingUtils::forbidden_error());
+                }
+            }
+            Ok(_) => return Err(ErrorHandlingUtils::not_found_error("Task not found")),
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot update single task", Some(e))),
+        }
+
         let task = self.repository.update_one_task(&self.task_payload).await;
 
         match task {
@@ -32,8 +50,13 @@ impl<'a> AbstractUseCase<TaskEntity> for UpdateOneTaskUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
+    use std::collections::HashSet;
     use std::io::{Error, ErrorKind};
 
+    use crate::test_support::task_owned_by;
+
+    use crate::domain::task_entity::{TaskPriority, TaskStatus, TaskStatusToDo, TaskType};
     use crate::{adapters::api::tasks::tasks_payloads::*, application::{repositories::tasks_repository_abstract::MockTasksRepositoryAbstract, usecases::task::update_one_task_usecase::UpdateOneTaskUseCase}};
 
     #[actix_rt::test]
@@ -45,21 +68,23 @@ mod tests {
             Some(String::from("id1")),
             Some(String::from("id1")),
             Some(String::from("id1")),
-            Some(TaskTypePayload::Work),
-            Some(TaskPriorityPayload::Low),
-            Some(TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted)),
+            Some(TaskType::Work),
+            Some(TaskPriority::Low),
+            Some(TaskStatus::ToDo(TaskStatusToDo::NotStarted)),
             Some(String::from("")),
             Some(321472382),
             Some(1),
             Some([].to_vec()),
         );
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
         task_repository
             .expect_update_one_task()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
-        let update_one_task_usecase = UpdateOneTaskUseCase::new(&payload, &task_repository);
+        let update_one_task_usecase = UpdateOneTaskUseCase::new(&payload, &policy, &task_repository);
         let data = update_one_task_usecase.execute().await;
 
         // then exception
@@ -77,38 +102,43 @@ mod tests {
             Some(String::from("id1")),
             Some(String::from("id1")),
             Some(String::from("id1")),
-            Some(TaskTypePayload::Work),
-            Some(TaskPriorityPayload::Low),
-            Some(TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted)),
+            Some(TaskType::Work),
+            Some(TaskPriority::Low),
+            Some(TaskStatus::ToDo(TaskStatusToDo::NotStarted)),
             Some(String::from("")),
             Some(321472382),
             Some(1),
             Some([].to_vec()),
         );
-        task_repository.expect_update_one_task().times(1).returning(|_| {
-            Ok(TaskEntity {
-                id: String::from("id1"),
-                user_id: String::from("id1"),
-                project_id: String::from("id1"),
-                title: String::from("task1"),
-                typ: TaskTypePayload::Work.to_string(),
-                priority: TaskPriorityPayload::Low.to_string(),
-                status: TaskStatusPayload::ToDo(TaskStatusToDoPayload::NotStarted).to_string(),
-                description: String::from(""),
-                duration: 1,
-                due_date: 321472382,
-                task_list: [].to_vec(),
-                updated_at: todo!(),
-                created_at: todo!(),
-            })
-        });
+        let policy = TaskPolicy::new(String::from("id1"), HashSet::new());
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
+        task_repository.expect_update_one_task().times(1).returning(|_| Ok(task_owned_by("id1")));
 
         // when calling usecase
-        let get_one_task_by_id_usecase = UpdateOneTaskUseCase::new(&payload, &task_repository);
+        let get_one_task_by_id_usecase = UpdateOneTaskUseCase::new(&payload, &policy, &task_repository);
         let data = get_one_task_by_id_usecase.execute().await.unwrap();
 
         // then assert the result is the expected entity
         assert_eq!(data.id, String::from("id1"));
         assert_eq!(data.title, "task1");
     }
+
+    #[actix_rt::test]
+    async fn test_should_not_update_task_of_another_user() {
+        // given the "update one task" usecase repo returning a task owned by someone else
+        let mut task_repository = MockTasksRepositoryAbstract::new();
+        let payload = TaskUpdatePayload::new(String::from("id1"), None, None, Some(String::from("task2")), None, None, None, None, None, None, None);
+        let policy = TaskPolicy::new(String::from("id2"), HashSet::new());
+        task_repository.expect_get_task_by_id().times(1).returning(|_| Ok(Some(task_owned_by("id1"))));
+        task_repository.expect_update_one_task().times(0);
+
+        // when calling usecase
+        let update_one_task_usecase = UpdateOneTaskUseCase::new(&payload, &policy, &task_repository);
+        let data = update_one_task_usecase.execute().await;
+
+        // then the task is reported as missing and left untouched
+        let result = data.unwrap_err();
+        assert_eq!(result.code, 404);
+        assert_eq!("Task not found", result.message);
+    }
 }
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::users::users_payloads::UserIdPayload,
@@ -19,6 +20,7 @@ impl<'a> DeleteOneUserByIdUseCase<'a> {
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<UserEntity> for DeleteOneUserByIdUseCase<'a> {
+    #[instrument(name = "DeleteOneUserByIdUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<UserEntity, ApiError> {
         let user = self.repository.delete_user_by_id(&self.user_payload).await;
 
@@ -32,6 +34,7 @@ impl<'a> AbstractUseCase<UserEntity> for DeleteOneUserByIdUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
     use std::io::{Error, ErrorKind};
 
     use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::users_repository_abstract::MockUsersRepositoryAbstract, domain::user_entity::UserEntity};
@@ -44,7 +47,7 @@ mod tests {
         user_repository
             .expect_update_one_user()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
         let delete_one_user_by_id_usecase = DeleteOneUserByIdUseCase::new(&payload, &user_repository);
@@ -71,6 +74,9 @@ mod tests {
                 refresh_token: Some(String::from("thisisaccesstoken123")),
                 access_token: Some(String::from("thisisaccesstoken123")),
                 fcm_token: Some(String::from("thisisfcmtoken123")),
+                email_verified_at: None,
+                mfa_token: None,
+                mfa_enrollment_required: false,
                 last_login: todo!(),
                 updated_at: todo!(),
                 created_at: todo!(),
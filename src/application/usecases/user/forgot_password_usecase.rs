@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::{
+        api::users::users_payloads::UserForgotPasswordPayload,
+        spi::mail::mailer::{MailMessage, Mailer},
+    },
+    application::{
+        repositories::account_tokens_repository_abstract::AccountTokensRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::error::ApiError,
+};
+
+pub struct ForgotPasswordUseCase<'a> {
+    user_payload: &'a UserForgotPasswordPayload,
+    base_url: &'a str,
+    repository: &'a dyn AccountTokensRepositoryAbstract,
+    mailer: &'a dyn Mailer,
+}
+
+impl<'a> ForgotPasswordUseCase<'a> {
+    pub fn new(user_payload: &'a UserForgotPasswordPayload, base_url: &'a str, repository: &'a dyn AccountTokensRepositoryAbstract, mailer: &'a dyn Mailer) -> Self {
+        ForgotPasswordUseCase {
+            user_payload,
+            base_url,
+            repository,
+            mailer,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for ForgotPasswordUseCase<'a> {
+    #[instrument(name = "ForgotPasswordUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        let account_token = match self.repository.create_password_reset(self.user_payload).await {
+            Ok(account_token) => account_token,
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot request password reset", Some(e))),
+        };
+
+        // unknown emails succeed silently so the endpoint cannot be used to enumerate accounts
+        let account_token = match account_token {
+            Some(account_token) => account_token,
+            None => return Ok(()),
+        };
+
+        let message = MailMessage::new(
+            account_token.email,
+            String::from("Reset your password"),
+            format!(
+                "Hi {},\n\nA password reset was requested for your account. Open the link below to choose a new password:\n{}/reset-password?token={}\n\nThe link expires at {} UTC. If you did not request this, you can ignore this email.",
+                account_token.username, self.base_url, account_token.token, account_token.expires_at
+            ),
+        );
+
+        // a failure must look like an unknown email too, it is only logged
+        if let Err(e) = self.mailer.send(&message).await {
+            log::error!("Cannot send password reset email: {}", e);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        adapters::spi::mail::mailer::MockMailer, application::repositories::account_tokens_repository_abstract::MockAccountTokensRepositoryAbstract,
+        domain::account_token_entity::AccountTokenEntity,
+    };
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "forgot password" usecase repo with an unexpected random error
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let mailer = MockMailer::new();
+        let payload = UserForgotPasswordPayload::new(String::from("test1@gmail.com"));
+        account_token_repository
+            .expect_create_password_reset()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let forgot_password_usecase = ForgotPasswordUseCase::new(&payload, "http://localhost", &account_token_repository, &mailer);
+        let data = forgot_password_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot request password reset", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_send_mail_only_for_known_email() {
+        // given the "forgot password" usecase repo knowing one email only
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let mut mailer = MockMailer::new();
+        account_token_repository.expect_create_password_reset().times(2).returning(|payload| {
+            if payload.email != "test1@gmail.com" {
+                return Ok(None);
+            }
+            Ok(Some(AccountTokenEntity::new(
+                String::from("id1"),
+                String::from("test1@gmail.com"),
+                String::from("User 1"),
+                String::from("secret123"),
+                Utc::now().naive_utc(),
+            )))
+        });
+        mailer
+            .expect_send()
+            .withf(|message| message.to == "test1@gmail.com" && message.body.contains("http://localhost/reset-password?token=secret123"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // when calling usecase for a known and an unknown email
+        let known_payload = UserForgotPasswordPayload::new(String::from("test1@gmail.com"));
+        let unknown_payload = UserForgotPasswordPayload::new(String::from("nobody@gmail.com"));
+        let known = ForgotPasswordUseCase::new(&known_payload, "http://localhost", &account_token_repository, &mailer).execute().await;
+        let unknown = ForgotPasswordUseCase::new(&unknown_payload, "http://localhost", &account_token_repository, &mailer)
+            .execute()
+            .await;
+
+        // then both succeed and a single mail was sent
+        assert!(known.is_ok());
+        assert!(unknown.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn test_should_succeed_when_the_mail_cannot_be_sent() {
+        // given a known email and a failing mailer
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let mut mailer = MockMailer::new();
+        let payload = UserForgotPasswordPayload::new(String::from("test1@gmail.com"));
+        account_token_repository.expect_create_password_reset().times(1).returning(|_| {
+            Ok(Some(AccountTokenEntity::new(
+                String::from("id1"),
+                String::from("test1@gmail.com"),
+                String::from("User 1"),
+                String::from("secret123"),
+                Utc::now().naive_utc(),
+            )))
+        });
+        mailer.expect_send().times(1).returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "smtp down"))));
+
+        // when calling usecase
+        let forgot_password_usecase = ForgotPasswordUseCase::new(&payload, "http://localhost", &account_token_repository, &mailer);
+        let data = forgot_password_usecase.execute().await;
+
+        // then the answer is the one of an unknown email
+        assert!(data.is_ok());
+    }
+}
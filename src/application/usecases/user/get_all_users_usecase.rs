@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     application::{repositories::users_repository_abstract::UsersRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
@@ -17,6 +18,7 @@ impl<'a> GetAllUsersUseCase<'a> {
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<Vec<UserAllEntity>> for GetAllUsersUseCase<'a> {
+    #[instrument(name = "GetAllUsersUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<Vec<UserAllEntity>, ApiError> {
         let users = self.repository.get_all_users().await;
 
@@ -30,6 +32,7 @@ impl<'a> AbstractUseCase<Vec<UserAllEntity>> for GetAllUsersUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
     use std::io::{Error, ErrorKind};
 
     use crate::{
@@ -44,7 +47,7 @@ mod tests {
             .expect_get_all_users()
             .with()
             .times(1)
-            .returning(|| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
         let get_all_users_usecase = GetAllUsersUseCase::new(&user_repository);
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::users::users_payloads::UserIdPayload, application::{repositories::users_repository_abstract::UsersRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils}, domain::{error::ApiError, user_entity::UserEntity}
@@ -17,6 +18,7 @@ impl<'a> GetOneUserByIdUseCase<'a> {
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<UserEntity> for GetOneUserByIdUseCase<'a> {
+    #[instrument(name = "GetOneUserByIdUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<UserEntity, ApiError> {
         let user = self.repository.get_user_by_id(&self.user_payload).await;
 
@@ -30,6 +32,7 @@ impl<'a> AbstractUseCase<UserEntity> for GetOneUserByIdUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
     use std::io::{Error, ErrorKind};
 
     use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::users_repository_abstract::MockUsersRepositoryAbstract, domain::user_entity::UserEntity};
@@ -42,7 +45,7 @@ mod tests {
         user_repository
             .expect_get_user_by_id()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
         let get_one_user_by_id_usecase = GetOneUserByIdUseCase::new(&payload, &user_repository);
@@ -69,6 +72,9 @@ mod tests {
                 refresh_token: Some(String::from("thisisaccesstoken123")),
                 access_token: Some(String::from("thisisaccesstoken123")),
                 fcm_token: Some(String::from("thisisfcmtoken123")),
+                email_verified_at: None,
+                mfa_token: None,
+                mfa_enrollment_required: false,
                 last_login: todo!(),
                 updated_at: todo!(),
                 created_at: todo!(),
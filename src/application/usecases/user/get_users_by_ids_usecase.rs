@@ -0,0 +1,102 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    application::{repositories::users_repository_abstract::UsersRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, user_entity::UserAllEntity},
+};
+
+// Batch lookup behind the GraphQL user loader, one query for every user a response refers to
+pub struct GetUsersByIdsUseCase<'a> {
+    user_ids: &'a [String],
+    repository: &'a dyn UsersRepositoryAbstract,
+}
+
+impl<'a> GetUsersByIdsUseCase<'a> {
+    pub fn new(user_ids: &'a [String], repository: &'a dyn UsersRepositoryAbstract) -> Self {
+        GetUsersByIdsUseCase { user_ids, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<UserAllEntity>> for GetUsersByIdsUseCase<'a> {
+    #[instrument(name = "GetUsersByIdsUseCase::execute", skip_all, fields(count = self.user_ids.len()))]
+    async fn execute(&self) -> Result<Vec<UserAllEntity>, ApiError> {
+        if self.user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match self.repository.get_users_by_ids(self.user_ids).await {
+            Ok(users) => Ok(users),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get users", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        adapters::api::users::users_payloads::UserRolePayload, application::repositories::users_repository_abstract::MockUsersRepositoryAbstract, domain::user_entity::UserAllEntity,
+    };
+
+    #[actix_rt::test]
+    async fn test_should_return_error_with_generic_message_when_unexpected_repo_error() {
+        // given the "users by ids" usecase repo with an unexpected random error
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let user_ids = vec![String::from("id1")];
+        user_repository
+            .expect_get_users_by_ids()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_users_by_ids_usecase = GetUsersByIdsUseCase::new(&user_ids, &user_repository);
+        let data = get_users_by_ids_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot get users", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_not_query_without_ids() {
+        // given the "users by ids" usecase repo expecting no call
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        user_repository.expect_get_users_by_ids().times(0);
+
+        // when calling usecase with no id
+        let get_users_by_ids_usecase = GetUsersByIdsUseCase::new(&[], &user_repository);
+        let data = get_users_by_ids_usecase.execute().await.unwrap();
+
+        // then assert the result is an empty list
+        assert_eq!(data.len(), 0);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_found_users() {
+        // given the "users by ids" usecase repo knowing only one of the ids
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let user_ids = vec![String::from("id1"), String::from("id2")];
+        user_repository.expect_get_users_by_ids().times(1).returning(|_| {
+            Ok(vec![UserAllEntity {
+                id: String::from("id1"),
+                username: String::from("User 1"),
+                email: String::from("test1@gmail.com"),
+                role: UserRolePayload::Customer.to_string(),
+            }])
+        });
+
+        // when calling usecase
+        let get_users_by_ids_usecase = GetUsersByIdsUseCase::new(&user_ids, &user_repository);
+        let data = get_users_by_ids_usecase.execute().await.unwrap();
+
+        // then assert only the known user is returned
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].id, "id1");
+    }
+}
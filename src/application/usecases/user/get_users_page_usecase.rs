@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserPagePayload,
+    application::{repositories::users_repository_abstract::UsersRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::{error::ApiError, user_entity::UserAllEntity},
+};
+
+pub struct GetUsersPageUseCase<'a> {
+    page_payload: &'a UserPagePayload,
+    repository: &'a dyn UsersRepositoryAbstract,
+}
+
+impl<'a> GetUsersPageUseCase<'a> {
+    pub fn new(page_payload: &'a UserPagePayload, repository: &'a dyn UsersRepositoryAbstract) -> Self {
+        GetUsersPageUseCase { page_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<Vec<UserAllEntity>> for GetUsersPageUseCase<'a> {
+    #[instrument(name = "GetUsersPageUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<Vec<UserAllEntity>, ApiError> {
+        let users = self.repository.get_users_page(self.page_payload).await;
+
+        match users {
+            Ok(users) => Ok(users),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot get all users", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use mockall::predicate::eq;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::users_repository_abstract::MockUsersRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_return_error_with_generic_message_when_unexpected_repo_error() {
+        // given the "users page" usecase repo with an unexpected random error
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let payload = UserPagePayload { after: None, limit: 11 };
+        user_repository
+            .expect_get_users_page()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let get_users_page_usecase = GetUsersPageUseCase::new(&payload, &user_repository);
+        let data = get_users_page_usecase.execute().await;
+
+        // then exception
+        assert_eq!("Cannot get all users", data.unwrap_err().message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_the_page_after_the_cursor() {
+        // given the "users page" usecase repo returning the user after id1
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let payload = UserPagePayload {
+            after: Some(String::from("id1")),
+            limit: 11,
+        };
+        user_repository.expect_get_users_page().with(eq(payload.clone())).times(1).returning(|_| {
+            Ok(vec![UserAllEntity {
+                id: String::from("id2"),
+                username: String::from("User 2"),
+                email: String::from("test2@gmail.com"),
+                role: UserRolePayload::Customer.to_string(),
+            }])
+        });
+
+        // when calling usecase
+        let get_users_page_usecase = GetUsersPageUseCase::new(&payload, &user_repository);
+        let data = get_users_page_usecase.execute().await.unwrap();
+
+        // then assert the page holds that user
+        assert_eq!(data.len(), 1);
+        assert_eq!(data[0].id, "id2");
+    }
+}
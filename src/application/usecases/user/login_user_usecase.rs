@@ -1,30 +1,103 @@
 use async_trait::async_trait;
+use std::time::Duration;
+use tracing::instrument;
 
 use crate::{
-    adapters::api::users::users_payloads::UserLoginPayload,
-    application::{repositories::users_repository_abstract::UsersRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
-    domain::{error::ApiError, user_entity::UserEntity},
+    adapters::{
+        api::users::users_payloads::{UserLoginAttemptPayload, UserLoginPayload},
+        spi::mail::mailer::{MailMessage, Mailer},
+    },
+    application::{
+        repositories::{login_attempts_repository_abstract::LoginAttemptsRepositoryAbstract, users_repository_abstract::UsersRepositoryAbstract},
+        usecases::interfaces::AbstractUseCase,
+        utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::{
+        account_token_entity::AccountTokenEntity,
+        error::{ApiError, DomainError},
+        user_entity::UserEntity,
+    },
 };
 
 pub struct LoginUserUseCase<'a> {
     user_payload: &'a UserLoginPayload,
+    base_url: &'a str,
     repository: &'a dyn UsersRepositoryAbstract,
+    login_attempts_repository: &'a dyn LoginAttemptsRepositoryAbstract,
+    mailer: &'a dyn Mailer,
 }
 
 impl<'a> LoginUserUseCase<'a> {
-    pub fn new(user_payload: &'a UserLoginPayload, repository: &'a dyn UsersRepositoryAbstract) -> Self {
-        LoginUserUseCase { user_payload, repository }
+    pub fn new(
+        user_payload: &'a UserLoginPayload,
+        base_url: &'a str,
+        repository: &'a dyn UsersRepositoryAbstract,
+        login_attempts_repository: &'a dyn LoginAttemptsRepositoryAbstract,
+        mailer: &'a dyn Mailer,
+    ) -> Self {
+        LoginUserUseCase {
+            user_payload,
+            base_url,
+            repository,
+            login_attempts_repository,
+            mailer,
+        }
+    }
+
+    async fn send_unlock_email(&self, account_token: AccountTokenEntity) {
+        let message = MailMessage::new(
+            account_token.email,
+            String::from("Your account has been locked"),
+            format!(
+                "Hi {},\n\nYour account was locked after too many failed sign-in attempts. It unlocks by itself shortly, or right away from the link below:\n{}/unlock-account?token={}\n\nThe link expires at {} UTC. If these attempts were not yours, consider changing your password.",
+                account_token.username, self.base_url, account_token.token, account_token.expires_at
+            ),
+        );
+
+        if let Err(e) = self.mailer.send(&message).await {
+            log::warn!("Cannot send account unlock email: {}", e);
+        }
     }
 }
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<UserEntity> for LoginUserUseCase<'a> {
+    #[instrument(name = "LoginUserUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<UserEntity, ApiError> {
+        let attempt_payload = UserLoginAttemptPayload::new(self.user_payload.email.clone(), self.user_payload.ip_address.clone());
+
+        let throttle = match self.login_attempts_repository.count_login_attempt(&attempt_payload).await {
+            Ok(throttle) => throttle,
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot sign in", Some(e))),
+        };
+
+        // the same answer whether or not the account exists, so locks do not reveal registered emails
+        if throttle.locked_until.is_some() || throttle.exhausted {
+            return Err(ErrorHandlingUtils::too_many_requests_error("Too many failed attempts, try again later"));
+        }
+        if throttle.delay_seconds > 0 {
+            tokio::time::sleep(Duration::from_secs(throttle.delay_seconds)).await;
+        }
+
         let user = self.repository.login_user(&self.user_payload).await;
 
         match user {
-            Ok(user) => Ok(user),
-            Err(e) => Err(ErrorHandlingUtils::application_error(&e.to_string(), Some(e))),
+            Ok(user) => {
+                if let Err(e) = self.login_attempts_repository.record_successful_login(&attempt_payload).await {
+                    log::warn!("Cannot reset failed sign-in attempts: {}", e);
+                }
+                Ok(user)
+            }
+            Err(e) => {
+                if let DomainError::Unauthorized(_) = e {
+                    match self.login_attempts_repository.record_failed_login(&attempt_payload).await {
+                        Ok(Some(account_token)) => self.send_unlock_email(account_token).await,
+                        Ok(None) => {}
+                        Err(e) => log::warn!("Cannot record failed sign-in attempt: {}", e),
+                    }
+                }
+                Err(ErrorHandlingUtils::application_error("Cannot sign in", Some(e)))
+            }
         }
     }
 }
@@ -34,20 +107,35 @@ mod tests {
     use super::*;
     use std::io::{Error, ErrorKind};
 
+    use chrono::{Duration as ChronoDuration, Utc};
+
     use crate::{
-        adapters::api::users::users_payloads::{UserLoginPayload, UserRolePayload},
-        application::repositories::users_repository_abstract::MockUsersRepositoryAbstract,
+        adapters::{
+            api::users::users_payloads::{UserLoginPayload, UserRolePayload},
+            spi::mail::mailer::MockMailer,
+        },
+        application::repositories::{login_attempts_repository_abstract::MockLoginAttemptsRepositoryAbstract, users_repository_abstract::MockUsersRepositoryAbstract},
+        domain::login_throttle_entity::LoginThrottleEntity,
     };
 
+    fn login_attempts_repository_without_failures() -> MockLoginAttemptsRepositoryAbstract {
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        login_attempts_repository.expect_count_login_attempt().returning(|_| Ok(LoginThrottleEntity::default()));
+        login_attempts_repository
+    }
+
     #[actix_rt::test]
     async fn test_should_return_generic_message_when_unexpected_repo_error() {
         // given the "all user users" usecase repo with an unexpected random error
         let mut user_repository = MockUsersRepositoryAbstract::new();
+        let mut login_attempts_repository = login_attempts_repository_without_failures();
+        let mailer = MockMailer::new();
         let payload = UserLoginPayload::new(String::from("user1@gmail.com"), String::from("Test1234"));
-        user_repository.expect_login_user().times(1).returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+        user_repository.expect_login_user().times(1).returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+        login_attempts_repository.expect_record_failed_login().times(0);
 
         // when calling usecase
-        let login_user_usecase = LoginUserUseCase::new(&payload, &user_repository);
+        let login_user_usecase = LoginUserUseCase::new(&payload, "http://localhost", &user_repository, &login_attempts_repository, &mailer);
         let data = login_user_usecase.execute().await;
 
         // then exception
@@ -60,7 +148,10 @@ mod tests {
     async fn test_should_return_one_result() {
         // given the "one user user by id" usecase repo returning one result
         let mut user_repository = MockUsersRepositoryAbstract::new();
+        let mut login_attempts_repository = login_attempts_repository_without_failures();
+        let mailer = MockMailer::new();
         let payload = UserLoginPayload::new(String::from("user1@gmail.com"), String::from("Test1234"));
+        login_attempts_repository.expect_record_successful_login().times(1).returning(|_| Ok(()));
         user_repository.expect_login_user().times(1).returning(|_| {
             Ok(UserEntity {
                 id: String::from("id1"),
@@ -71,6 +162,9 @@ mod tests {
                 refresh_token: Some(String::from("thisisaccesstoken123")),
                 access_token: Some(String::from("thisisaccesstoken123")),
                 fcm_token: Some(String::from("thisisfcmtoken123")),
+                email_verified_at: None,
+                mfa_token: None,
+                mfa_enrollment_required: false,
                 last_login: todo!(),
                 updated_at: todo!(),
                 created_at: todo!(),
@@ -78,11 +172,93 @@ mod tests {
         });
 
         // when calling usecase
-        let get_one_user_by_id_usecase = LoginUserUseCase::new(&payload, &user_repository);
+        let get_one_user_by_id_usecase = LoginUserUseCase::new(&payload, "http://localhost", &user_repository, &login_attempts_repository, &mailer);
         let data = get_one_user_by_id_usecase.execute().await.unwrap();
 
         // then assert the result is the expected entity
         assert_eq!(data.id, String::from("id1"));
         assert_eq!(data.email, String::from("test1@gmail.com"));
     }
+
+    #[actix_rt::test]
+    async fn test_should_refuse_locked_login_without_checking_password() {
+        // given the "login user" usecase with a locked account
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        let mailer = MockMailer::new();
+        let payload = UserLoginPayload::new(String::from("user1@gmail.com"), String::from("Test1234"));
+        login_attempts_repository
+            .expect_count_login_attempt()
+            .times(1)
+            .returning(|_| Ok(LoginThrottleEntity::new(Some(Utc::now().naive_utc() + ChronoDuration::minutes(15)), 0)));
+        user_repository.expect_login_user().times(0);
+
+        // when calling usecase
+        let login_user_usecase = LoginUserUseCase::new(&payload, "http://localhost", &user_repository, &login_attempts_repository, &mailer);
+        let data = login_user_usecase.execute().await;
+
+        // then too many requests
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!(429, result.code);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_refuse_attempts_over_the_limit_without_checking_password() {
+        // given the "login user" usecase with an attempt counted over the limit, the lock not set yet
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        let mailer = MockMailer::new();
+        let payload = UserLoginPayload::new(String::from("user1@gmail.com"), String::from("Test1234"));
+        login_attempts_repository.expect_count_login_attempt().times(1).returning(|_| {
+            Ok(LoginThrottleEntity {
+                exhausted: true,
+                ..LoginThrottleEntity::default()
+            })
+        });
+        login_attempts_repository.expect_record_failed_login().times(0);
+        user_repository.expect_login_user().times(0);
+
+        // when calling usecase
+        let login_user_usecase = LoginUserUseCase::new(&payload, "http://localhost", &user_repository, &login_attempts_repository, &mailer);
+        let data = login_user_usecase.execute().await;
+
+        // then too many requests
+        let result = data.unwrap_err();
+        assert_eq!(429, result.code);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_send_unlock_email_when_failure_locks_account() {
+        // given the "login user" usecase with a wrong password locking the account
+        let mut user_repository = MockUsersRepositoryAbstract::new();
+        let mut login_attempts_repository = login_attempts_repository_without_failures();
+        let mut mailer = MockMailer::new();
+        let payload = UserLoginPayload::new(String::from("test1@gmail.com"), String::from("Wrong1234"));
+        user_repository.expect_login_user().times(1).returning(|_| Err(DomainError::Unauthorized(String::from("Invalid email or password!"))));
+        login_attempts_repository.expect_record_failed_login().times(1).returning(|_| {
+            Ok(Some(AccountTokenEntity::new(
+                String::from("id1"),
+                String::from("test1@gmail.com"),
+                String::from("User 1"),
+                String::from("unlocktoken123"),
+                Utc::now().naive_utc(),
+            )))
+        });
+        mailer
+            .expect_send()
+            .withf(|message| message.to == "test1@gmail.com" && message.body.contains("http://localhost/unlock-account?token=unlocktoken123"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // when calling usecase
+        let login_user_usecase = LoginUserUseCase::new(&payload, "http://localhost", &user_repository, &login_attempts_repository, &mailer);
+        let data = login_user_usecase.execute().await;
+
+        // then the invalid credentials message is kept
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!(401, result.code);
+        assert_eq!("Invalid email or password!", result.message);
+    }
 }
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserIdPayload,
+    application::{repositories::tokens_repository_abstract::TokensRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::error::ApiError,
+};
+
+pub struct LogoutAllUserUseCase<'a> {
+    user_payload: &'a UserIdPayload,
+    repository: &'a dyn TokensRepositoryAbstract,
+}
+
+impl<'a> LogoutAllUserUseCase<'a> {
+    pub fn new(user_payload: &'a UserIdPayload, repository: &'a dyn TokensRepositoryAbstract) -> Self {
+        LogoutAllUserUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<i64> for LogoutAllUserUseCase<'a> {
+    #[instrument(name = "LogoutAllUserUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<i64, ApiError> {
+        let revoked_at = self.repository.revoke_all_user_tokens(self.user_payload).await;
+
+        match revoked_at {
+            Ok(revoked_at) => Ok(revoked_at),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot logout user from all devices", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::tokens_repository_abstract::MockTokensRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "logout all" usecase repo with an unexpected random error
+        let mut token_repository = MockTokensRepositoryAbstract::new();
+        let payload = UserIdPayload::new(String::from("id1"));
+        token_repository
+            .expect_revoke_all_user_tokens()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let logout_all_user_usecase = LogoutAllUserUseCase::new(&payload, &token_repository);
+        let data = logout_all_user_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot logout user from all devices", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_revocation_time() {
+        // given the "logout all" usecase repo revoking every token of the user
+        let mut token_repository = MockTokensRepositoryAbstract::new();
+        let payload = UserIdPayload::new(String::from("id1"));
+        token_repository.expect_revoke_all_user_tokens().times(1).returning(|_| Ok(1727000000123));
+
+        // when calling usecase
+        let logout_all_user_usecase = LogoutAllUserUseCase::new(&payload, &token_repository);
+        let data = logout_all_user_usecase.execute().await.unwrap();
+
+        // then assert the revocation time is returned
+        assert_eq!(data, 1727000000123);
+    }
+}
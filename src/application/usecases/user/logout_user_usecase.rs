@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserLogoutPayload,
+    application::{repositories::tokens_repository_abstract::TokensRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils},
+    domain::error::ApiError,
+};
+
+pub struct LogoutUserUseCase<'a> {
+    user_payload: &'a UserLogoutPayload,
+    repository: &'a dyn TokensRepositoryAbstract,
+}
+
+impl<'a> LogoutUserUseCase<'a> {
+    pub fn new(user_payload: &'a UserLogoutPayload, repository: &'a dyn TokensRepositoryAbstract) -> Self {
+        LogoutUserUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for LogoutUserUseCase<'a> {
+    #[instrument(name = "LogoutUserUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        let result = self.repository.revoke_token(self.user_payload).await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot logout user", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::tokens_repository_abstract::MockTokensRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "logout user" usecase repo with an unexpected random error
+        let mut token_repository = MockTokensRepositoryAbstract::new();
+        let payload = UserLogoutPayload::new(Some(String::from("id1")), Some(String::from("jti1")), Some(1727000000), None);
+        token_repository
+            .expect_revoke_token()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let logout_user_usecase = LogoutUserUseCase::new(&payload, &token_repository);
+        let data = logout_user_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot logout user", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_revoke_token() {
+        // given the "logout user" usecase repo revoking the token
+        let mut token_repository = MockTokensRepositoryAbstract::new();
+        let payload = UserLogoutPayload::new(Some(String::from("id1")), Some(String::from("jti1")), Some(1727000000), None);
+        token_repository
+            .expect_revoke_token()
+            .withf(|payload| payload.jti == Some(String::from("jti1")))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // when calling usecase
+        let logout_user_usecase = LogoutUserUseCase::new(&payload, &token_repository);
+        let data = logout_user_usecase.execute().await;
+
+        // then assert the token was revoked
+        assert!(data.is_ok());
+    }
+}
@@ -1,7 +1,17 @@
 pub mod register_user_usecase;
 pub mod login_user_usecase;
+pub mod logout_user_usecase;
+pub mod logout_all_user_usecase;
 pub mod refresh_token_user_usecase;
 pub mod update_one_user_usecase;
 pub mod get_all_users_usecase;
+pub mod get_users_page_usecase;
 pub mod get_one_user_by_id_usecase;
+pub mod get_users_by_ids_usecase;
 pub mod delete_one_user_by_id_usecase;
+pub mod send_verification_email_usecase;
+pub mod verify_email_usecase;
+pub mod forgot_password_usecase;
+pub mod reset_password_usecase;
+pub mod unlock_account_usecase;
+pub mod unlock_user_usecase;
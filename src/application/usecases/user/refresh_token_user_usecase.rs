@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::users::users_payloads::UserRefreshTokenPayload,
@@ -19,12 +20,13 @@ impl<'a> RefreshTokenUserUseCase<'a> {
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<UserAccessTokenEntity> for RefreshTokenUserUseCase<'a> {
+    #[instrument(name = "RefreshTokenUserUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<UserAccessTokenEntity, ApiError> {
         let token = self.repository.get_refresh(&self.user_payload).await;
 
         match token {
             Ok(token) => Ok(token),
-            Err(e) => Err(ErrorHandlingUtils::application_error(&e.to_string(), Some(e))),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot refresh token", Some(e))),
         }
     }
 }
@@ -32,6 +34,7 @@ impl<'a> AbstractUseCase<UserAccessTokenEntity> for RefreshTokenUserUseCase<'a>
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
     use std::io::{Error, ErrorKind};
 
     use crate::{adapters::api::users::users_payloads::UserRefreshTokenPayload, application::repositories::users_repository_abstract::MockUsersRepositoryAbstract};
@@ -44,7 +47,7 @@ mod tests {
         user_repository
             .expect_get_refresh()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
         let refresh_token_user_usecase = RefreshTokenUserUseCase::new(&payload, &user_repository);
@@ -64,6 +67,7 @@ mod tests {
         user_repository.expect_get_refresh().times(1).returning(|_| {
             Ok(UserAccessTokenEntity {
                 access_token: String::from("thisisaccesstoken123"),
+                refresh_token: String::from("thisisrefreshtoken456"),
             })
         });
 
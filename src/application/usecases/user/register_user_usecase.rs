@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use tracing::instrument;
 
 use crate::{
     adapters::api::users::users_payloads::UserRegisterPayload,
@@ -19,12 +20,13 @@ impl<'a> RegisterUserUseCase<'a> {
 
 #[async_trait(?Send)]
 impl<'a> AbstractUseCase<UserEntity> for RegisterUserUseCase<'a> {
+    #[instrument(name = "RegisterUserUseCase::execute", skip_all)]
     async fn execute(&self) -> Result<UserEntity, ApiError> {
         let user = self.repository.register_user(&self.user_payload).await;
 
         match user {
             Ok(user) => Ok(user),
-            Err(e) => Err(ErrorHandlingUtils::application_error(&e.to_string(), Some(e))),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot register user", Some(e))),
         }
     }
 }
@@ -32,6 +34,7 @@ impl<'a> AbstractUseCase<UserEntity> for RegisterUserUseCase<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::error::DomainError;
     use std::io::{Error, ErrorKind};
 
     use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::users_repository_abstract::MockUsersRepositoryAbstract};
@@ -44,7 +47,7 @@ mod tests {
         user_repository
             .expect_register_user()
             .times(1)
-            .returning(|_| Err(Box::new(Error::new(ErrorKind::Other, "oh no!"))));
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
 
         // when calling usecase
         let post_one_user_usecase = RegisterUserUseCase::new(&payload, &user_repository);
@@ -73,6 +76,9 @@ mod tests {
                 refresh_token: Some(String::from("thisisaccesstoken123")),
                 access_token: Some(String::from("thisisaccesstoken123")),
                 fcm_token: Some(String::from("thisisfcmtoken123")),
+                email_verified_at: None,
+                mfa_token: None,
+                mfa_enrollment_required: false,
                 last_login: todo!(),
                 updated_at: todo!(),
                 created_at: todo!(),
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserResetPasswordPayload,
+    application::{
+        repositories::account_tokens_repository_abstract::AccountTokensRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::{error::ApiError, user_entity::UserEntity},
+};
+
+pub struct ResetPasswordUseCase<'a> {
+    user_payload: &'a UserResetPasswordPayload,
+    repository: &'a dyn AccountTokensRepositoryAbstract,
+}
+
+impl<'a> ResetPasswordUseCase<'a> {
+    pub fn new(user_payload: &'a UserResetPasswordPayload, repository: &'a dyn AccountTokensRepositoryAbstract) -> Self {
+        ResetPasswordUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<UserEntity> for ResetPasswordUseCase<'a> {
+    #[instrument(name = "ResetPasswordUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<UserEntity, ApiError> {
+        let user = self.repository.reset_password(self.user_payload).await;
+
+        match user {
+            Ok(user) => Ok(user),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot reset password", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::account_tokens_repository_abstract::MockAccountTokensRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "reset password" usecase repo with an unexpected random error
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let payload = UserResetPasswordPayload::new(String::from("secret123"), String::from("Test12345!"));
+        account_token_repository
+            .expect_reset_password()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let reset_password_usecase = ResetPasswordUseCase::new(&payload, &account_token_repository);
+        let data = reset_password_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot reset password", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_updated_user() {
+        // given the "reset password" usecase repo accepting the token
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let payload = UserResetPasswordPayload::new(String::from("secret123"), String::from("Test12345!"));
+        account_token_repository.expect_reset_password().times(1).returning(|_| {
+            let now = Utc::now().naive_utc();
+            Ok(UserEntity::new(
+                String::from("id1"),
+                String::from("User 1"),
+                String::from("test1@gmail.com"),
+                String::from("newhash"),
+                UserRolePayload::Customer.to_string(),
+                None,
+                None,
+                None,
+                None,
+                now,
+                now,
+                now,
+            ))
+        });
+
+        // when calling usecase
+        let reset_password_usecase = ResetPasswordUseCase::new(&payload, &account_token_repository);
+        let data = reset_password_usecase.execute().await.unwrap();
+
+        // then assert the updated user is returned
+        assert_eq!(data.id, String::from("id1"));
+        assert_eq!(data.password, "newhash");
+    }
+}
@@ -0,0 +1,120 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::{
+        api::users::users_payloads::UserIdPayload,
+        spi::mail::mailer::{MailMessage, Mailer},
+    },
+    application::{
+        repositories::account_tokens_repository_abstract::AccountTokensRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::error::ApiError,
+};
+
+pub struct SendVerificationEmailUseCase<'a> {
+    user_payload: &'a UserIdPayload,
+    base_url: &'a str,
+    repository: &'a dyn AccountTokensRepositoryAbstract,
+    mailer: &'a dyn Mailer,
+}
+
+impl<'a> SendVerificationEmailUseCase<'a> {
+    pub fn new(user_payload: &'a UserIdPayload, base_url: &'a str, repository: &'a dyn AccountTokensRepositoryAbstract, mailer: &'a dyn Mailer) -> Self {
+        SendVerificationEmailUseCase {
+            user_payload,
+            base_url,
+            repository,
+            mailer,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for SendVerificationEmailUseCase<'a> {
+    #[instrument(name = "SendVerificationEmailUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        let account_token = match self.repository.create_email_verification(self.user_payload).await {
+            Ok(account_token) => account_token,
+            Err(e) => return Err(ErrorHandlingUtils::application_error("Cannot create email verification", Some(e))),
+        };
+
+        let message = MailMessage::new(
+            account_token.email,
+            String::from("Verify your email address"),
+            format!(
+                "Hi {},\n\nPlease confirm your email address by opening the link below:\n{}/verify-email?token={}\n\nThe link expires at {} UTC.",
+                account_token.username, self.base_url, account_token.token, account_token.expires_at
+            ),
+        );
+
+        match self.mailer.send(&message).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot send verification email", Some(e.into()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{
+        adapters::spi::mail::mailer::MockMailer, application::repositories::account_tokens_repository_abstract::MockAccountTokensRepositoryAbstract,
+        domain::account_token_entity::AccountTokenEntity,
+    };
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "send verification email" usecase repo with an unexpected random error
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let mut mailer = MockMailer::new();
+        let payload = UserIdPayload::new(String::from("id1"));
+        account_token_repository
+            .expect_create_email_verification()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+        mailer.expect_send().times(0);
+
+        // when calling usecase
+        let send_verification_email_usecase = SendVerificationEmailUseCase::new(&payload, "http://localhost", &account_token_repository, &mailer);
+        let data = send_verification_email_usecase.execute().await;
+
+        // then exception and no mail sent
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot create email verification", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_send_mail_with_verification_link() {
+        // given the "send verification email" usecase repo returning a fresh token
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let mut mailer = MockMailer::new();
+        let payload = UserIdPayload::new(String::from("id1"));
+        account_token_repository.expect_create_email_verification().times(1).returning(|_| {
+            Ok(AccountTokenEntity::new(
+                String::from("id1"),
+                String::from("test1@gmail.com"),
+                String::from("User 1"),
+                String::from("secret123"),
+                Utc::now().naive_utc(),
+            ))
+        });
+        mailer
+            .expect_send()
+            .withf(|message| message.to == "test1@gmail.com" && message.body.contains("http://localhost/verify-email?token=secret123"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // when calling usecase
+        let send_verification_email_usecase = SendVerificationEmailUseCase::new(&payload, "http://localhost", &account_token_repository, &mailer);
+        let data = send_verification_email_usecase.execute().await;
+
+        // then assert the mail was sent
+        assert!(data.is_ok());
+    }
+}
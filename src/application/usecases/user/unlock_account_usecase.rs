@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserUnlockAccountPayload,
+    application::{
+        repositories::login_attempts_repository_abstract::LoginAttemptsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::error::ApiError,
+};
+
+pub struct UnlockAccountUseCase<'a> {
+    user_payload: &'a UserUnlockAccountPayload,
+    repository: &'a dyn LoginAttemptsRepositoryAbstract,
+}
+
+impl<'a> UnlockAccountUseCase<'a> {
+    pub fn new(user_payload: &'a UserUnlockAccountPayload, repository: &'a dyn LoginAttemptsRepositoryAbstract) -> Self {
+        UnlockAccountUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for UnlockAccountUseCase<'a> {
+    #[instrument(name = "UnlockAccountUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        match self.repository.unlock_account(self.user_payload).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot unlock account", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::login_attempts_repository_abstract::MockLoginAttemptsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "unlock account" usecase repo with an unexpected random error
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        let payload = UserUnlockAccountPayload::new(String::from("unlocktoken123"));
+        login_attempts_repository
+            .expect_unlock_account()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let unlock_account_usecase = UnlockAccountUseCase::new(&payload, &login_attempts_repository);
+        let data = unlock_account_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot unlock account", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_unlock_account() {
+        // given the "unlock account" usecase repo accepting the token
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        let payload = UserUnlockAccountPayload::new(String::from("unlocktoken123"));
+        login_attempts_repository
+            .expect_unlock_account()
+            .withf(|payload| payload.token == "unlocktoken123")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // when calling usecase
+        let unlock_account_usecase = UnlockAccountUseCase::new(&payload, &login_attempts_repository);
+        let data = unlock_account_usecase.execute().await;
+
+        // then assert the account is unlocked
+        assert!(data.is_ok());
+    }
+}
@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserUnlockPayload,
+    application::{
+        repositories::login_attempts_repository_abstract::LoginAttemptsRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::error::ApiError,
+};
+
+pub struct UnlockUserUseCase<'a> {
+    user_payload: &'a UserUnlockPayload,
+    repository: &'a dyn LoginAttemptsRepositoryAbstract,
+}
+
+impl<'a> UnlockUserUseCase<'a> {
+    pub fn new(user_payload: &'a UserUnlockPayload, repository: &'a dyn LoginAttemptsRepositoryAbstract) -> Self {
+        UnlockUserUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<()> for UnlockUserUseCase<'a> {
+    #[instrument(name = "UnlockUserUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<(), ApiError> {
+        match self.repository.unlock_user(self.user_payload).await {
+            Ok(_) => Ok(()),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot unlock user", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use std::io::{Error, ErrorKind};
+
+    use crate::application::repositories::login_attempts_repository_abstract::MockLoginAttemptsRepositoryAbstract;
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "unlock user" usecase repo with an unexpected random error
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        let payload = UserUnlockPayload::new(String::from("id1"), Some(String::from("admin1")));
+        login_attempts_repository
+            .expect_unlock_user()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let unlock_user_usecase = UnlockUserUseCase::new(&payload, &login_attempts_repository);
+        let data = unlock_user_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot unlock user", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_unlock_user_on_behalf_of_admin() {
+        // given the "unlock user" usecase repo unlocking the user
+        let mut login_attempts_repository = MockLoginAttemptsRepositoryAbstract::new();
+        let payload = UserUnlockPayload::new(String::from("id1"), Some(String::from("admin1")));
+        login_attempts_repository
+            .expect_unlock_user()
+            .withf(|payload| payload.user_id == "id1" && payload.unlocked_by.as_deref() == Some("admin1"))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        // when calling usecase
+        let unlock_user_usecase = UnlockUserUseCase::new(&payload, &login_attempts_repository);
+        let data = unlock_user_usecase.execute().await;
+
+        // then assert the user is unlocked
+        assert!(data.is_ok());
+    }
+}
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    adapters::api::users::users_payloads::UserVerifyEmailPayload,
+    application::{
+        repositories::account_tokens_repository_abstract::AccountTokensRepositoryAbstract, usecases::interfaces::AbstractUseCase, utils::error_handling_utils::ErrorHandlingUtils,
+    },
+    domain::{error::ApiError, user_entity::UserEntity},
+};
+
+pub struct VerifyEmailUseCase<'a> {
+    user_payload: &'a UserVerifyEmailPayload,
+    repository: &'a dyn AccountTokensRepositoryAbstract,
+}
+
+impl<'a> VerifyEmailUseCase<'a> {
+    pub fn new(user_payload: &'a UserVerifyEmailPayload, repository: &'a dyn AccountTokensRepositoryAbstract) -> Self {
+        VerifyEmailUseCase { user_payload, repository }
+    }
+}
+
+#[async_trait(?Send)]
+impl<'a> AbstractUseCase<UserEntity> for VerifyEmailUseCase<'a> {
+    #[instrument(name = "VerifyEmailUseCase::execute", skip_all)]
+    async fn execute(&self) -> Result<UserEntity, ApiError> {
+        let user = self.repository.verify_email(self.user_payload).await;
+
+        match user {
+            Ok(user) => Ok(user),
+            Err(e) => Err(ErrorHandlingUtils::application_error("Cannot verify email", Some(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::error::DomainError;
+    use chrono::Utc;
+    use std::io::{Error, ErrorKind};
+
+    use crate::{adapters::api::users::users_payloads::UserRolePayload, application::repositories::account_tokens_repository_abstract::MockAccountTokensRepositoryAbstract};
+
+    #[actix_rt::test]
+    async fn test_should_return_generic_message_when_unexpected_repo_error() {
+        // given the "verify email" usecase repo with an unexpected random error
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let payload = UserVerifyEmailPayload::new(String::from("secret123"));
+        account_token_repository
+            .expect_verify_email()
+            .times(1)
+            .returning(|_| Err(DomainError::Infrastructure(Box::new(Error::new(ErrorKind::Other, "oh no!")))));
+
+        // when calling usecase
+        let verify_email_usecase = VerifyEmailUseCase::new(&payload, &account_token_repository);
+        let data = verify_email_usecase.execute().await;
+
+        // then exception
+        assert!(data.is_err());
+        let result = data.unwrap_err();
+        assert_eq!("Cannot verify email", result.message);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_return_verified_user() {
+        // given the "verify email" usecase repo accepting the token
+        let mut account_token_repository = MockAccountTokensRepositoryAbstract::new();
+        let payload = UserVerifyEmailPayload::new(String::from("secret123"));
+        account_token_repository.expect_verify_email().times(1).returning(|_| {
+            let now = Utc::now().naive_utc();
+            Ok(UserEntity::new(
+                String::from("id1"),
+                String::from("User 1"),
+                String::from("test1@gmail.com"),
+                String::from("hash"),
+                UserRolePayload::Customer.to_string(),
+                None,
+                None,
+                None,
+                Some(now),
+                now,
+                now,
+                now,
+            ))
+        });
+
+        // when calling usecase
+        let verify_email_usecase = VerifyEmailUseCase::new(&payload, &account_token_repository);
+        let data = verify_email_usecase.execute().await.unwrap();
+
+        // then assert the user is now verified
+        assert_eq!(data.id, String::from("id1"));
+        assert!(data.email_verified_at.is_some());
+    }
+}
@@ -6,81 +6,43 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-use actix_web::{dev::Payload, Error, FromRequest, HttpRequest};
+use actix_web::{dev::Payload, web, Error, FromRequest, HttpRequest};
 use actix_web_httpauth::extractors::bearer::Config;
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
-
-use crate::{adapters::api::shared::error_presenter::ErrorResponse, infrastructure::env_source};
+use uuid::Uuid;
+
+use crate::{
+    adapters::{
+        api::shared::app_state::AppState,
+        spi::db::db_api_keys_repository::API_KEY_PREFIX,
+    },
+    application::repositories::api_keys_repository_abstract::ApiKeysRepositoryAbstract,
+    domain::api_key_entity::ApiKeyEntity,
+};
 
-use super::extractors::claims::{Claims, ClientError, Permission, Role};
+use super::{
+    extractors::claims::{Claims, ClientError, Permission, Role, TokenType},
+    key_ring::KeyRing,
+};
 
 pub struct AuthUseCase;
-pub struct AuthCheckUseCase;
 
 impl AuthUseCase {
-    pub fn check_role(role: &str) -> HashSet<Permission> {
-        let mut permissions = AuthUseCase::no();
-
-        match Role::from_str(role) {
-            Ok(Role::SuperAdmin) => {
-                permissions = AuthUseCase::all_read_down_to_superadmin();
-            }
-            Ok(Role::Admin) => {
-                permissions = AuthUseCase::all_read_down_to_admin();
-            }
-            Ok(Role::Author) => {
-                permissions = AuthUseCase::all_read_down_to_author();
-            }
-            Ok(Role::Customer) => {
-                permissions = AuthUseCase::all_read_down_to_customer();
-            }
-            Err(_) => {
-                println!("Invalid role: {}", role);
-            }
-        }
-
-        permissions
-    }
-
-    pub fn all_read_down_to_superadmin() -> HashSet<Permission> {
-        let mut permissions = HashSet::new();
-        permissions.insert(Permission::Read("superadmin-tasks".to_string()));
-        permissions.insert(Permission::Read("admin-tasks".to_string()));
-        permissions.insert(Permission::Read("author-tasks".to_string()));
-        permissions.insert(Permission::Read("customer-tasks".to_string()));
-        permissions
-    }
-
-    pub fn all_read_down_to_admin() -> HashSet<Permission> {
-        let mut permissions = HashSet::new();
-        permissions.insert(Permission::Read("admin-tasks".to_string()));
-        permissions.insert(Permission::Read("author-tasks".to_string()));
-        permissions.insert(Permission::Read("customer-tasks".to_string()));
-        permissions
-    }
-
-    pub fn all_read_down_to_author() -> HashSet<Permission> {
-        let mut permissions = HashSet::new();
-        permissions.insert(Permission::Read("author-tasks".to_string()));
-        permissions.insert(Permission::Read("customer-tasks".to_string()));
-        permissions
-    }
-
-    pub fn all_read_down_to_customer() -> HashSet<Permission> {
-        let mut permissions = HashSet::new();
-        permissions.insert(Permission::Read("customer-tasks".to_string()));
-        permissions
-    }
-
     pub fn no() -> HashSet<Permission> {
-        let permissions = HashSet::new();
-        permissions
+        HashSet::new()
     }
 
-    // Generate a token with a secret key, including roles and permissions
-    pub fn generate_token(user_id: &str, role_str: &str, expiration: usize, permissions: Option<HashSet<Permission>>) -> Result<String, jsonwebtoken::errors::Error> {
-        let secret = env_source::get_jwt_secret();
-        let expiration_time = SystemTime::now().duration_since(UNIX_EPOCH).unwrap() + Duration::from_secs(expiration as u64); // 1-hour expiration
+    // Generate a token signed with the current key of the key ring, including roles and permissions
+    pub fn generate_token(
+        user_id: &str,
+        role_str: &str,
+        expiration: usize,
+        permissions: Option<HashSet<Permission>>,
+        session_id: Option<&str>,
+        token_type: TokenType,
+    ) -> Result<String, jsonwebtoken::errors::Error> {
+        let key_ring = KeyRing::global();
+        let issued_at = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let expiration_time = issued_at + Duration::from_secs(expiration as u64); // 1-hour expiration
 
         let role = match Role::from_str(role_str) {
             Ok(it) => it,
@@ -91,80 +53,79 @@ impl AuthUseCase {
             role,
             permissions,
             exp: expiration_time.as_secs() as usize,
+            iat: issued_at.as_secs() as usize,
+            iat_ms: Some(issued_at.as_millis() as u64),
+            nbf: issued_at.as_secs() as usize,
+            iss: key_ring.issuer().to_string(),
+            aud: key_ring.audience().to_string(),
+            jti: Uuid::new_v4().to_string(),
+            sid: session_id.map(String::from),
+            api_key_id: None,
+            typ: token_type,
         };
 
-        encode(&Header::default(), &claims, &EncodingKey::from_secret(&secret))
+        key_ring.sign(&claims)
     }
 
-    // Validate the token with the secret key
-    pub fn validate_token(token: &str) -> Result<Claims, ClientError> {
-        let secret = env_source::get_jwt_secret();
-        let validation = Validation::new(Algorithm::HS256);
-        decode::<Claims>(token, &DecodingKey::from_secret(&secret), &validation)
-            .map_err(ClientError::Decode)
-            .map(|data| data.claims)
-    }
-}
-
-impl AuthCheckUseCase {
-    pub fn check_permission_up_to_customer(claims: Claims) -> Result<(), ErrorResponse> {
-        let allowed_roles = vec![Role::SuperAdmin, Role::Admin, Role::Author, Role::Customer];
-        if !claims.validate_roles(&allowed_roles) {
-            return Err(ErrorResponse::auth_default());
+    // Build the claims of a request authenticated with an API key, scoped to the key's permissions
+    pub fn api_key_claims(api_key: ApiKeyEntity) -> Claims {
+        let permissions = api_key.permissions.iter().filter_map(|permission| Permission::from_str(permission).ok()).collect();
+        let key_ring = KeyRing::global();
+        let issued_at = api_key.created_at.and_utc().timestamp() as usize;
+        Claims {
+            sub: api_key.user_id,
+            role: Role::from_str(&api_key.user_role).unwrap_or_default(),
+            permissions: Some(permissions),
+            exp: api_key.expires_at.map(|expires_at| expires_at.and_utc().timestamp() as usize).unwrap_or(usize::MAX),
+            iat: issued_at,
+            iat_ms: None,
+            nbf: issued_at,
+            iss: key_ring.issuer().to_string(),
+            aud: key_ring.audience().to_string(),
+            jti: format!("api_key:{}", api_key.id),
+            sid: None,
+            api_key_id: Some(api_key.id),
+            typ: TokenType::Access,
         }
-        println!("Role validated: SuperAdmin, Admin, Author, Customer");
-
-        if !claims.validate_permissions(&AuthUseCase::all_read_down_to_customer()) {
-            return Err(ErrorResponse::auth_default());
-        }
-
-        println!("Role validated: Success");
-        Ok(())
     }
 
-    pub fn check_permission_up_to_author(claims: Claims) -> Result<(), ErrorResponse> {
-        let allowed_roles = vec![Role::SuperAdmin, Role::Admin, Role::Author];
-        if !claims.validate_roles(&allowed_roles) {
-            return Err(ErrorResponse::auth_default());
-        }
-        println!("Role validated: SuperAdmin, Admin, Author");
-
-        if !claims.validate_permissions(&AuthUseCase::all_read_down_to_author()) {
-            return Err(ErrorResponse::auth_default());
-        }
-
-        println!("Role validated: Success");
-        Ok(())
+    // Validate the signature, issuer, audience and validity window of the token
+    pub fn validate_token(token: &str) -> Result<Claims, ClientError> {
+        KeyRing::global().verify(token).map_err(ClientError::Decode)
     }
 
-    pub fn check_permission_up_to_admin(claims: Claims) -> Result<(), ErrorResponse> {
-        let allowed_roles = vec![Role::SuperAdmin, Role::Admin];
-        if !claims.validate_roles(&allowed_roles) {
-            return Err(ErrorResponse::auth_default());
+    // Claims of a bearer token or API key, whichever transport carried them (HTTP headers, gRPC metadata)
+    pub async fn authenticate(state: Option<&AppState>, bearer_token: Option<String>, api_key: Option<String>) -> Result<Claims, ClientError> {
+        // API keys come either in their own header or as a bearer token with the key prefix
+        let api_key = api_key.or_else(|| bearer_token.clone().filter(|token| token.starts_with(API_KEY_PREFIX)));
+        if let Some(key) = api_key {
+            let state = state.ok_or_else(|| ClientError::NotFound(String::from("Invalid or expired API key")))?;
+            return match state.api_keys_repository.authenticate_api_key(&key).await {
+                Ok(api_key) => Ok(AuthUseCase::api_key_claims(api_key)),
+                Err(_) => Err(ClientError::NotFound(String::from("Invalid or expired API key"))),
+            };
         }
-        println!("Role validated: SuperAdmin, Admin");
 
-        if !claims.validate_permissions(&AuthUseCase::all_read_down_to_admin()) {
-            return Err(ErrorResponse::auth_default());
-        }
-
-        println!("Role validated: Success");
-        Ok(())
-    }
+        if let Some(token) = bearer_token {
+            let claims = AuthUseCase::validate_token(&token)?;
+            if claims.typ != TokenType::Access {
+                return Err(ClientError::NotFound(String::from("Refresh tokens can't authenticate requests")));
+            }
 
-    pub fn check_permission_up_to_superadmin(claims: Claims) -> Result<(), ErrorResponse> {
-        let allowed_roles = vec![Role::SuperAdmin];
-        if !claims.validate_roles(&allowed_roles) {
-            return Err(ErrorResponse::auth_default());
-        }
-        println!("Role validated: SuperAdmin");
+            // Reject tokens revoked by logout, password or role change
+            if let Some(state) = state {
+                state.revocation_cache.refresh_if_stale(&state.tokens_repository).await;
+                if state.revocation_cache.is_revoked(&claims) {
+                    return Err(ClientError::Revoked);
+                }
+            }
 
-        if !claims.validate_permissions(&AuthUseCase::all_read_down_to_superadmin()) {
-            return Err(ErrorResponse::auth_default());
+            Ok(claims)
+        } else {
+            // Correctly create AuthenticationError using Config
+            let config = Config::default();
+            Err(ClientError::Authentication(actix_web_httpauth::extractors::AuthenticationError::from(config)))
         }
-
-        println!("Role validated: Success");
-        Ok(())
     }
 }
 
@@ -180,18 +141,9 @@ impl FromRequest for Claims {
             .and_then(|h| h.to_str().ok())
             .and_then(|h| h.strip_prefix("Bearer "))
             .map(String::from);
+        let api_key_header = req.headers().get("X-Api-Key").and_then(|h| h.to_str().ok()).map(String::from);
+        let app_state = req.app_data::<web::Data<AppState>>().cloned();
 
-        Box::pin(async move {
-            if let Some(token) = auth_header {
-                match AuthUseCase::validate_token(&token) {
-                    Ok(claims) => Ok(claims),
-                    Err(err) => Err(err.into()),
-                }
-            } else {
-                // Correctly create AuthenticationError using Config
-                let config = Config::default();
-                Err(ClientError::Authentication(actix_web_httpauth::extractors::AuthenticationError::from(config)).into())
-            }
-        })
+        Box::pin(async move { Ok(AuthUseCase::authenticate(app_state.as_ref().map(|state| state.get_ref()), auth_header, api_key_header).await?) })
     }
 }
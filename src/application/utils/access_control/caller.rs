@@ -0,0 +1,35 @@
+use std::collections::HashSet;
+
+use crate::{adapters::api::shared::app_state::AppState, application::utils::error_handling_utils::ErrorHandlingUtils, domain::error::ApiError};
+
+use super::{
+    extractors::claims::{Claims, Permission},
+    task_policy::TaskPolicy,
+};
+
+// A caller authenticated once for a whole GraphQL request, websocket or gRPC call. Unlike `Authorized`, which checks
+// the permission of a route, each operation asks for its own.
+pub struct Caller {
+    pub claims: Claims,
+    // Grants of the caller's role when the request came in, as for `Authorized`
+    role_permissions: HashSet<Permission>,
+}
+
+impl Caller {
+    pub async fn resolve(state: &AppState, claims: Claims) -> Self {
+        state.permission_cache.refresh_if_stale(&state.permissions_repository).await;
+        let role_permissions = state.permission_cache.role_permissions(&claims.role);
+        Caller { claims, role_permissions }
+    }
+
+    pub fn require(&self, permission: Permission) -> Result<(), ApiError> {
+        match self.claims.has_permission(permission, &self.role_permissions) {
+            true => Ok(()),
+            false => Err(ErrorHandlingUtils::forbidden_error()),
+        }
+    }
+
+    pub fn task_policy(&self) -> TaskPolicy {
+        TaskPolicy::from_claims(&self.claims, &self.role_permissions)
+    }
+}
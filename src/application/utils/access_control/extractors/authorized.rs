@@ -0,0 +1,101 @@
+use std::{collections::HashSet, future::Future, marker::PhantomData, ops::Deref, pin::Pin};
+
+use actix_web::{dev::Payload, web, Error, FromRequest, HttpRequest};
+
+use crate::adapters::api::shared::{app_state::AppState, error_presenter::ErrorResponse};
+
+use super::claims::{Claims, Permission};
+
+pub trait RequiredPermission {
+    const PERMISSION: Permission;
+}
+
+// Claims of a caller holding the permission `P`, handlers declare it in their signature: `auth: Authorized<require::TasksWriteAny>`
+pub struct Authorized<P: RequiredPermission> {
+    pub claims: Claims,
+    // Grants of the caller's role when the request came in, for checks finer than the route's permission
+    pub role_permissions: HashSet<Permission>,
+    permission: PhantomData<P>,
+}
+
+impl<P: RequiredPermission> Authorized<P> {
+    pub fn into_inner(self) -> Claims {
+        self.claims
+    }
+}
+
+impl<P: RequiredPermission> AsRef<Claims> for Authorized<P> {
+    fn as_ref(&self) -> &Claims {
+        &self.claims
+    }
+}
+
+impl<P: RequiredPermission> Deref for Authorized<P> {
+    type Target = Claims;
+
+    fn deref(&self) -> &Self::Target {
+        &self.claims
+    }
+}
+
+impl<P: RequiredPermission + 'static> FromRequest for Authorized<P> {
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let claims = Claims::from_request(req, payload);
+        let app_state = req.app_data::<web::Data<AppState>>().cloned();
+
+        Box::pin(async move {
+            let claims = claims.await?;
+            let state = app_state.ok_or_else(ErrorResponse::auth_default)?;
+
+            state.permission_cache.refresh_if_stale(&state.permissions_repository).await;
+            let role_permissions = state.permission_cache.role_permissions(&claims.role);
+            if !claims.has_permission(P::PERMISSION, &role_permissions) {
+                return Err(ErrorResponse::auth_default().into());
+            }
+
+            Ok(Authorized {
+                claims,
+                role_permissions,
+                permission: PhantomData,
+            })
+        })
+    }
+}
+
+macro_rules! permission_guards {
+    ($($permission:ident),* $(,)?) => {
+        $(
+            pub struct $permission;
+
+            impl RequiredPermission for $permission {
+                const PERMISSION: Permission = Permission::$permission;
+            }
+        )*
+    };
+}
+
+// One marker type per catalogue entry
+pub mod require {
+    use super::{Permission, RequiredPermission};
+
+    permission_guards!(
+        TasksRead,
+        TasksReadAny,
+        TasksWrite,
+        TasksWriteAny,
+        TasksDelete,
+        TasksDeleteAny,
+        AccountRead,
+        AccountWrite,
+        AccountDelete,
+        UsersRead,
+        UsersWrite,
+        UsersRole,
+        UsersDelete,
+        MfaManage,
+        PermissionsManage,
+    );
+}
@@ -45,21 +45,132 @@ impl FromStr for Role {
     }
 }
 
-// Permissions like read, write, delete actions for resources
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+// Permission catalogue: "<resource>:<action>" acts on the caller's own data, a trailing ":any" on everybody's
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub enum Permission {
-    Read(String),   // e.g., "read:admin-tasks"
-    Write(String),  // e.g., "write:admin-tasks"
-    Delete(String), // e.g., "delete:admin-tasks"
+    TasksRead,
+    TasksReadAny,
+    TasksWrite,
+    TasksWriteAny,
+    TasksDelete,
+    TasksDeleteAny,
+    AccountRead,
+    AccountWrite,
+    AccountDelete,
+    UsersRead,
+    UsersWrite,
+    UsersRole,
+    UsersDelete,
+    MfaManage,
+    PermissionsManage,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 15] = [
+        Permission::TasksRead,
+        Permission::TasksReadAny,
+        Permission::TasksWrite,
+        Permission::TasksWriteAny,
+        Permission::TasksDelete,
+        Permission::TasksDeleteAny,
+        Permission::AccountRead,
+        Permission::AccountWrite,
+        Permission::AccountDelete,
+        Permission::UsersRead,
+        Permission::UsersWrite,
+        Permission::UsersRole,
+        Permission::UsersDelete,
+        Permission::MfaManage,
+        Permission::PermissionsManage,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::TasksRead => "tasks:read",
+            Permission::TasksReadAny => "tasks:read:any",
+            Permission::TasksWrite => "tasks:write",
+            Permission::TasksWriteAny => "tasks:write:any",
+            Permission::TasksDelete => "tasks:delete",
+            Permission::TasksDeleteAny => "tasks:delete:any",
+            Permission::AccountRead => "account:read",
+            Permission::AccountWrite => "account:write",
+            Permission::AccountDelete => "account:delete",
+            Permission::UsersRead => "users:read",
+            Permission::UsersWrite => "users:write",
+            Permission::UsersRole => "users:role",
+            Permission::UsersDelete => "users:delete",
+            Permission::MfaManage => "mfa:manage",
+            Permission::PermissionsManage => "permissions:manage",
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Permission::ALL
+            .into_iter()
+            .find(|permission| permission.as_str() == s)
+            .ok_or_else(|| format!("Invalid permission: {}", s))
+    }
+}
+
+impl TryFrom<String> for Permission {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Permission::from_str(&value)
+    }
+}
+
+impl From<Permission> for String {
+    fn from(permission: Permission) -> Self {
+        permission.as_str().to_string()
+    }
+}
+
+// A refresh token only buys a new pair of tokens, it can't authenticate a request
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    #[default]
+    Access,
+    Refresh,
 }
 
 // JWT Claims structure to include both role and permissions
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String,                              // Subject (user ID)
     pub role: Role,                               // User role
     pub permissions: Option<HashSet<Permission>>, // Optional specific permissions
     pub exp: usize,                               // Expiry timestamp
+    #[serde(default)]
+    pub iat: usize, // Issued at timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat_ms: Option<u64>, // Issued at in milliseconds, orders the token against a revocation of the same second
+    #[serde(default)]
+    pub nbf: usize, // Not valid before timestamp
+    #[serde(default)]
+    pub iss: String, // Issuer
+    #[serde(default)]
+    pub aud: String, // Audience
+    #[serde(default)]
+    pub jti: String, // Token identifier, used for revocation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>, // Session the token was issued for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_id: Option<String>, // API key the request was authenticated with
+    #[serde(default)]
+    pub typ: TokenType, // Access or refresh token
 }
 
 #[derive(Debug, Display)]
@@ -72,9 +183,22 @@ pub enum ClientError {
     NotFound(String),
     #[display(fmt = "unsupported_algorithm")]
     UnsupportedAlgortithm(AlgorithmParameters),
+    #[display(fmt = "revoked")]
+    Revoked,
+}
+
+impl AsRef<Claims> for Claims {
+    fn as_ref(&self) -> &Claims {
+        self
+    }
 }
 
 impl Claims {
+    // Tokens issued before `iat_ms` existed count as issued at the start of their second
+    pub fn issued_at_millis(&self) -> i64 {
+        self.iat_ms.map_or(self.iat as i64 * 1000, |iat_ms| iat_ms as i64)
+    }
+
     // Check if the user has the required role and permissions
     pub fn validate_roles(&self, allowed_roles: &[Role]) -> bool {
         allowed_roles.contains(&self.role)
@@ -82,6 +206,11 @@ impl Claims {
 
     // Check if the user has the required permissions
     pub fn validate_permissions(&self, required_permissions: &HashSet<Permission>) -> bool {
-        self.permissions.as_ref().map_or(false, |permissions| permissions.is_superset(required_permissions))
+        self.permissions.as_ref().is_some_and(|permissions| permissions.is_superset(required_permissions))
+    }
+
+    // Check one permission against the token and the role's current grants, so revoked grants apply before the token expires
+    pub fn has_permission(&self, permission: Permission, role_permissions: &HashSet<Permission>) -> bool {
+        role_permissions.contains(&permission) && self.permissions.as_ref().is_some_and(|permissions| permissions.contains(&permission))
     }
 }
@@ -1 +1,3 @@
 pub mod claims;
+pub mod authorized;
+pub mod signed_in;
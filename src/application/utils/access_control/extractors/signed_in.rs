@@ -0,0 +1,48 @@
+use std::{future::Future, ops::Deref, pin::Pin};
+
+use actix_web::{dev::Payload, Error, FromRequest, HttpRequest};
+
+use crate::adapters::api::shared::error_presenter::ErrorResponse;
+
+use super::claims::Claims;
+
+// Caller signed in with a token, not with an API key: a leaked key must not take over the account. Credentials and
+// sessions are managed this way only, `claims: SignedIn` or `auth: SignedIn<Authorized<require::AccountWrite>>`
+pub struct SignedIn<A = Claims>(A);
+
+impl<A> SignedIn<A> {
+    pub fn into_inner(self) -> A {
+        self.0
+    }
+}
+
+impl<A> Deref for SignedIn<A> {
+    type Target = A;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<A> FromRequest for SignedIn<A>
+where
+    A: FromRequest + AsRef<Claims> + 'static,
+    A::Future: 'static,
+    A::Error: Into<Error>,
+{
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let inner = A::from_request(req, payload);
+
+        Box::pin(async move {
+            let inner = inner.await.map_err(Into::into)?;
+            if inner.as_ref().api_key_id.is_some() {
+                return Err(ErrorResponse::forbidden("Not allowed with an API key").into());
+            }
+
+            Ok(SignedIn(inner))
+        })
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    sync::OnceLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    decode, decode_header, encode,
+    errors::{Error as JwtError, ErrorKind},
+    jwk::{AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm, OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType},
+    Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use openssl::pkey::{Id, PKey};
+
+use crate::infrastructure::settings::AuthSettings;
+
+use super::extractors::claims::Claims;
+
+// kid of the shared HS256 secret, used when no key directory is configured
+pub const SECRET_KID: &str = "secret";
+
+const MIN_RSA_BITS: u32 = 2048;
+
+static KEY_RING: OnceLock<KeyRing> = OnceLock::new();
+
+pub struct SigningKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    // Public half published in the JWKS, shared secrets have none
+    jwk: Option<Jwk>,
+}
+
+impl SigningKey {
+    pub fn from_secret(kid: &str, secret: &[u8]) -> Self {
+        SigningKey {
+            kid: kid.to_string(),
+            algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            jwk: None,
+        }
+    }
+
+    // PEM encoded private key, RSA keys sign with RS256 and Ed25519 keys with EdDSA
+    pub fn from_pem(kid: &str, pem: &[u8]) -> Result<Self, String> {
+        let key_error = |e: &dyn std::fmt::Display| format!("JWT key {}: {}", kid, e);
+        let private_key = PKey::private_key_from_pem(pem).map_err(|e| key_error(&e))?;
+
+        match private_key.id() {
+            Id::RSA => {
+                let rsa = private_key.rsa().map_err(|e| key_error(&e))?;
+                if private_key.bits() < MIN_RSA_BITS {
+                    return Err(key_error(&format!("RSA keys need at least {} bits", MIN_RSA_BITS)));
+                }
+                let (modulus, exponent) = (rsa.n().to_vec(), rsa.e().to_vec());
+                Ok(SigningKey {
+                    kid: kid.to_string(),
+                    algorithm: Algorithm::RS256,
+                    encoding_key: EncodingKey::from_rsa_pem(pem).map_err(|e| key_error(&e))?,
+                    decoding_key: DecodingKey::from_rsa_raw_components(&modulus, &exponent),
+                    jwk: Some(SigningKey::public_jwk(
+                        kid,
+                        KeyAlgorithm::RS256,
+                        AlgorithmParameters::RSA(RSAKeyParameters {
+                            key_type: RSAKeyType::RSA,
+                            n: URL_SAFE_NO_PAD.encode(&modulus),
+                            e: URL_SAFE_NO_PAD.encode(&exponent),
+                        }),
+                    )),
+                })
+            }
+            Id::ED25519 => {
+                let public_key = private_key.raw_public_key().map_err(|e| key_error(&e))?;
+                Ok(SigningKey {
+                    kid: kid.to_string(),
+                    algorithm: Algorithm::EdDSA,
+                    encoding_key: EncodingKey::from_ed_pem(pem).map_err(|e| key_error(&e))?,
+                    decoding_key: DecodingKey::from_ed_der(&public_key),
+                    jwk: Some(SigningKey::public_jwk(
+                        kid,
+                        KeyAlgorithm::EdDSA,
+                        AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                            key_type: OctetKeyPairType::OctetKeyPair,
+                            curve: EllipticCurve::Ed25519,
+                            x: URL_SAFE_NO_PAD.encode(&public_key),
+                        }),
+                    )),
+                })
+            }
+            _ => Err(key_error(&"only RSA and Ed25519 keys are supported")),
+        }
+    }
+
+    fn public_jwk(kid: &str, key_algorithm: KeyAlgorithm, algorithm: AlgorithmParameters) -> Jwk {
+        Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(key_algorithm),
+                key_id: Some(kid.to_string()),
+                ..Default::default()
+            },
+            algorithm,
+        }
+    }
+}
+
+// Every key tokens may be signed with, identified by the `kid` header. Only `signing_kid` signs new tokens,
+// the others keep verifying until the tokens they signed expire, which is how keys are rotated.
+pub struct KeyRing {
+    keys: BTreeMap<String, SigningKey>,
+    signing_kid: String,
+    issuer: String,
+    audience: String,
+}
+
+impl KeyRing {
+    pub fn new(keys: Vec<SigningKey>, signing_kid: Option<String>, issuer: String, audience: String) -> Result<Self, String> {
+        let keys: BTreeMap<String, SigningKey> = keys.into_iter().map(|key| (key.kid.clone(), key)).collect();
+        // without an explicit kid the last key in name order signs, so date prefixed key names rotate on their own
+        let signing_kid = match signing_kid {
+            Some(kid) => kid,
+            None => keys.keys().last().cloned().ok_or_else(|| String::from("no JWT signing key found"))?,
+        };
+        if !keys.contains_key(&signing_kid) {
+            return Err(format!("JWT signing key {} is not loaded", signing_kid));
+        }
+
+        Ok(KeyRing {
+            keys,
+            signing_kid,
+            issuer,
+            audience,
+        })
+    }
+
+    // Every `<kid>.pem` private key of the keys directory, or the shared secret when the directory isn't set
+    pub fn from_settings(auth: &AuthSettings) -> Result<Self, String> {
+        let keys = match (&auth.jwt_keys_dir, &auth.jwt_secret) {
+            (Some(dir), _) => KeyRing::load_dir(dir)?,
+            (None, Some(secret)) => vec![SigningKey::from_secret(SECRET_KID, secret.as_bytes())],
+            (None, None) => return Err(String::from("no JWT secret or keys directory configured")),
+        };
+        KeyRing::new(keys, auth.jwt_signing_kid.clone(), auth.jwt_issuer.clone(), auth.jwt_audience.clone())
+    }
+
+    // Loads the process wide key ring, the server calls it at startup so a bad key fails fast
+    pub fn init(auth: &AuthSettings) -> Result<&'static KeyRing, String> {
+        if let Some(key_ring) = KEY_RING.get() {
+            return Ok(key_ring);
+        }
+        let key_ring = KeyRing::from_settings(auth)?;
+        Ok(KEY_RING.get_or_init(|| key_ring))
+    }
+
+    pub fn global() -> &'static KeyRing {
+        KEY_RING.get().expect("KeyRing::init must run before tokens are signed or verified")
+    }
+
+    fn load_dir(dir: &str) -> Result<Vec<SigningKey>, String> {
+        let entries = fs::read_dir(dir).map_err(|e| format!("JWT keys directory {}: {}", dir, e))?;
+        let mut keys = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| format!("JWT keys directory {}: {}", dir, e))?.path();
+            if path.extension().is_some_and(|extension| extension == "pem") {
+                let kid = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                let pem = fs::read(&path).map_err(|e| format!("JWT key {}: {}", kid, e))?;
+                keys.push(SigningKey::from_pem(&kid, &pem)?);
+            }
+        }
+        Ok(keys)
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    pub fn audience(&self) -> &str {
+        &self.audience
+    }
+
+    pub fn sign(&self, claims: &Claims) -> Result<String, JwtError> {
+        let key = &self.keys[&self.signing_kid];
+        let mut header = Header::new(key.algorithm);
+        header.kid = Some(key.kid.clone());
+        encode(&header, claims, &key.encoding_key)
+    }
+
+    // The key is picked by `kid` and only accepts its own algorithm, tokens without a known `kid` are rejected
+    pub fn verify(&self, token: &str) -> Result<Claims, JwtError> {
+        let header = decode_header(token)?;
+        let key = header.kid.as_ref().and_then(|kid| self.keys.get(kid)).ok_or(ErrorKind::InvalidToken)?;
+
+        let mut validation = Validation::new(key.algorithm);
+        validation.set_issuer(&[&self.issuer]);
+        validation.set_audience(&[&self.audience]);
+        validation.set_required_spec_claims(&["exp", "iat", "nbf", "iss", "aud", "sub"]);
+        validation.validate_nbf = true;
+        let claims = decode::<Claims>(token, &key.decoding_key, &validation)?.claims;
+
+        // a token issued in the future comes from a forged or badly skewed issuer
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if claims.iat as u64 > now + validation.leeway {
+            return Err(ErrorKind::ImmatureSignature.into());
+        }
+
+        Ok(claims)
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        JwkSet {
+            keys: self.keys.values().filter_map(|key| key.jwk.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::rsa::Rsa;
+
+    use crate::application::utils::access_control::extractors::claims::{Role, TokenType};
+
+    const ISSUER: &str = "tasktracker";
+    const AUDIENCE: &str = "tasktracker-api";
+
+    fn now() -> usize {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as usize
+    }
+
+    fn claims() -> Claims {
+        let now = now();
+        Claims {
+            sub: String::from("user1"),
+            role: Role::Customer,
+            permissions: None,
+            exp: now + 3600,
+            iat: now,
+            iat_ms: None,
+            nbf: now,
+            iss: String::from(ISSUER),
+            aud: String::from(AUDIENCE),
+            jti: String::from("jti1"),
+            sid: None,
+            api_key_id: None,
+            typ: TokenType::Access,
+        }
+    }
+
+    fn rsa_key(kid: &str, bits: u32) -> Result<SigningKey, String> {
+        let pem = PKey::from_rsa(Rsa::generate(bits).unwrap()).unwrap().private_key_to_pem_pkcs8().unwrap();
+        SigningKey::from_pem(kid, &pem)
+    }
+
+    fn ed25519_key(kid: &str) -> SigningKey {
+        let pem = PKey::generate_ed25519().unwrap().private_key_to_pem_pkcs8().unwrap();
+        SigningKey::from_pem(kid, &pem).unwrap()
+    }
+
+    fn key_ring(keys: Vec<SigningKey>, signing_kid: &str) -> KeyRing {
+        KeyRing::new(keys, Some(String::from(signing_kid)), String::from(ISSUER), String::from(AUDIENCE)).unwrap()
+    }
+
+    fn error_kind(result: Result<Claims, JwtError>) -> ErrorKind {
+        result.map(|_| ()).unwrap_err().into_kind()
+    }
+
+    #[test]
+    fn test_should_sign_and_verify_with_every_algorithm() {
+        for (key, algorithm) in [
+            (rsa_key("rsa", 2048).unwrap(), Algorithm::RS256),
+            (ed25519_key("ed"), Algorithm::EdDSA),
+            (SigningKey::from_secret(SECRET_KID, b"testsecret"), Algorithm::HS256),
+        ] {
+            // given a key ring signing with one key
+            let kid = key.kid.clone();
+            let key_ring = key_ring(vec![key], &kid);
+
+            // when signing then verifying a token
+            let token = key_ring.sign(&claims()).unwrap();
+            let header = decode_header(&token).unwrap();
+            let verified = key_ring.verify(&token).unwrap();
+
+            // then the header names the key and the claims come back
+            assert_eq!((header.alg, header.kid.as_deref()), (algorithm, Some(kid.as_str())));
+            assert_eq!(verified.sub, "user1");
+        }
+    }
+
+    #[test]
+    fn test_should_keep_verifying_tokens_of_a_rotated_key() {
+        // given a token signed with the key about to be rotated out
+        let old_key_pem = PKey::generate_ed25519().unwrap().private_key_to_pem_pkcs8().unwrap();
+        let before = key_ring(vec![SigningKey::from_pem("2026-01", &old_key_pem).unwrap()], "2026-01");
+        let old_token = before.sign(&claims()).unwrap();
+
+        // when a newer key joins the ring without an explicit signing kid
+        let after = KeyRing::new(
+            vec![SigningKey::from_pem("2026-01", &old_key_pem).unwrap(), ed25519_key("2026-02")],
+            None,
+            String::from(ISSUER),
+            String::from(AUDIENCE),
+        )
+        .unwrap();
+        let new_token = after.sign(&claims()).unwrap();
+
+        // then the last key in name order signs and tokens of both keys verify
+        assert_eq!(decode_header(&new_token).unwrap().kid.as_deref(), Some("2026-02"));
+        assert_eq!(after.verify(&new_token).unwrap().sub, "user1");
+        assert_eq!(after.verify(&old_token).unwrap().sub, "user1");
+    }
+
+    #[test]
+    fn test_should_reject_unknown_or_missing_kid() {
+        // given a token of a key the ring does not hold and one without kid
+        let key_ring = key_ring(vec![SigningKey::from_secret(SECRET_KID, b"testsecret")], SECRET_KID);
+        let unknown = self::key_ring(vec![SigningKey::from_secret("other", b"testsecret")], "other").sign(&claims()).unwrap();
+        let missing = encode(&Header::new(Algorithm::HS256), &claims(), &EncodingKey::from_secret(b"testsecret")).unwrap();
+
+        // when verifying them
+        let unknown = key_ring.verify(&unknown);
+        let missing = key_ring.verify(&missing);
+
+        // then both are refused
+        assert_eq!(error_kind(unknown), ErrorKind::InvalidToken);
+        assert_eq!(error_kind(missing), ErrorKind::InvalidToken);
+    }
+
+    #[test]
+    fn test_should_check_issuer_audience_and_validity_window() {
+        // given a key ring and tokens each breaking one rule
+        let key_ring = key_ring(vec![SigningKey::from_secret(SECRET_KID, b"testsecret")], SECRET_KID);
+        let in_an_hour = now() + 3600;
+        let cases = [
+            (
+                Claims {
+                    iss: String::from("someone-else"),
+                    ..claims()
+                },
+                ErrorKind::InvalidIssuer,
+            ),
+            (
+                Claims {
+                    aud: String::from("another-api"),
+                    ..claims()
+                },
+                ErrorKind::InvalidAudience,
+            ),
+            (Claims { nbf: in_an_hour, ..claims() }, ErrorKind::ImmatureSignature),
+            (Claims { iat: in_an_hour, ..claims() }, ErrorKind::ImmatureSignature),
+            (Claims { exp: now() - 3600, ..claims() }, ErrorKind::ExpiredSignature),
+        ];
+
+        for (claims, kind) in cases {
+            // when verifying the token
+            let result = key_ring.verify(&key_ring.sign(&claims).unwrap());
+
+            // then it is refused for that rule
+            assert_eq!(error_kind(result), kind);
+        }
+    }
+
+    #[test]
+    fn test_should_refuse_rsa_keys_under_2048_bits() {
+        // given a 1024 bit RSA key
+        // when loading it
+        let key = rsa_key("weak", 1024);
+
+        // then it is refused
+        assert!(key.map(|_| ()).unwrap_err().contains("at least 2048 bits"));
+    }
+}
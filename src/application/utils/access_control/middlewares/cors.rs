@@ -1,10 +1,95 @@
-use actix_web::http::{header, Method};
+use std::str::FromStr;
+
 use actix_cors::Cors;
+use actix_web::http::{header::HeaderName, Method};
+
+// An allowed origin, "https://app.example.com", "https://*.example.com" or "*" for any origin.
+// A wildcard stands for any run of characters except "/", it never reaches into the path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OriginPattern {
+    pattern: String,
+}
+
+impl OriginPattern {
+    pub fn is_any(&self) -> bool {
+        self.pattern == "*"
+    }
+
+    pub fn matches(&self, origin: &str) -> bool {
+        if self.is_any() {
+            return true;
+        }
+        let origin = origin.to_lowercase();
+        let mut parts = self.pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        let Some(mut rest) = origin.strip_prefix(first) else {
+            return false;
+        };
+
+        let parts = parts.collect::<Vec<&str>>();
+        for (index, part) in parts.iter().enumerate() {
+            let is_last = index == parts.len() - 1;
+            let found = match is_last {
+                // the last part must close the origin, anchor it at the end
+                true => rest.len().checked_sub(part.len()).filter(|start| rest[*start..] == **part),
+                false => rest.find(part),
+            };
+            let Some(start) = found else {
+                return false;
+            };
+            if start == 0 || rest[..start].contains('/') {
+                return false;
+            }
+            rest = &rest[start + part.len()..];
+        }
+        rest.is_empty()
+    }
+}
+
+impl FromStr for OriginPattern {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let pattern = input.trim().trim_end_matches('/').to_lowercase();
+        if pattern != "*" && !(pattern.starts_with("http://") || pattern.starts_with("https://")) {
+            return Err(format!("Invalid CORS origin \"{}\", expected <scheme>://<host>[:<port>] or *", input));
+        }
+        if pattern.contains("**") {
+            return Err(format!("Invalid CORS origin \"{}\", wildcards must be separated", input));
+        }
+        Ok(OriginPattern { pattern })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    // no origin means cross-origin requests are refused
+    pub allowed_origins: Vec<OriginPattern>,
+    pub allowed_methods: Vec<Method>,
+    pub allowed_headers: Vec<HeaderName>,
+    // response headers the browser lets the frontend read
+    pub exposed_headers: Vec<HeaderName>,
+    pub allow_credentials: bool,
+    pub max_age: usize,
+}
+
+// Answers preflight requests and refuses actual requests from an origin that isn't allowed.
+// Requests without an Origin header (curl, mobile clients) are not cross-origin and pass untouched.
+pub fn cors(config: &CorsConfig) -> Cors {
+    let origins = config.allowed_origins.clone();
+    let mut cors = Cors::default()
+        .allowed_origin_fn(move |origin, _| origin.to_str().is_ok_and(|origin| origins.iter().any(|pattern| pattern.matches(origin))))
+        .allowed_methods(config.allowed_methods.clone())
+        .allowed_headers(config.allowed_headers.clone())
+        .max_age(config.max_age)
+        // the browser would only hide the response, the request itself must not reach the handler
+        .block_on_origin_mismatch(true);
 
-pub fn cors(client_origin_url: &str) -> Cors {
-    Cors::default()
-        .allowed_origin(client_origin_url)
-        .allowed_methods([Method::GET, Method::POST, Method::PATCH])
-        .allowed_headers([header::AUTHORIZATION, header::CONTENT_TYPE])
-        .max_age(86_400)
+    if !config.exposed_headers.is_empty() {
+        cors = cors.expose_headers(config.exposed_headers.clone());
+    }
+    if config.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+    cors
 }
@@ -1,5 +1,175 @@
-use actix_web::middleware::Logger;
+use std::{
+    borrow::Cow,
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+    time::Instant,
+};
 
-pub fn logger() -> Logger {
-    Logger::default()
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header,
+    Error,
+};
+
+use crate::{
+    adapters::api::{
+        health::health_controllers::{LIVENESS_PATH, READINESS_PATH, VERSION_PATH},
+        metrics::metrics_controllers::METRICS_PATH,
+    },
+    application::utils::access_control::middlewares::rate_limit::{client_ip, TrustedProxies},
+};
+
+// Probes and scrapes hit the service every few seconds, logging them would drown the real traffic
+const EXCLUDED_PATHS: [&str; 4] = [LIVENESS_PATH, READINESS_PATH, VERSION_PATH, METRICS_PATH];
+// The segment after it is the token of a calendar feed, the only credential of the feed
+const CALENDAR_FEED_PATH: &str = "/api/v1/calendar/feed/";
+
+pub struct RequestLogger {
+    trusted_proxies: TrustedProxies,
+}
+
+// One line per request once answered, inside the request span so it carries the request id.
+// Query strings are left out, some links hold tokens, and so are calendar feed tokens of paths.
+pub fn logger(trusted_proxies: TrustedProxies) -> RequestLogger {
+    RequestLogger { trusted_proxies }
+}
+
+// The path of a request as logged, `/api/v1/calendar/feed/{token}.ics` for a calendar feed
+pub fn loggable_path(path: &str) -> Cow<'_, str> {
+    let Some(rest) = path.strip_prefix(CALENDAR_FEED_PATH) else {
+        return Cow::Borrowed(path);
+    };
+    let (token, tail) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let extension = if token.ends_with(".ics") { ".ics" } else { "" };
+    Cow::Owned(format!("{}{{token}}{}{}", CALENDAR_FEED_PATH, extension, tail))
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestLogger
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestLoggerMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestLoggerMiddleware {
+            service: Rc::new(service),
+            trusted_proxies: self.trusted_proxies.clone(),
+        }))
+    }
+}
+
+pub struct RequestLoggerMiddleware<S> {
+    service: Rc<S>,
+    trusted_proxies: TrustedProxies,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestLoggerMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        if EXCLUDED_PATHS.contains(&req.path()) {
+            return Box::pin(service.call(req));
+        }
+
+        let method = req.method().to_string();
+        let path = loggable_path(req.path()).into_owned();
+        let client_ip = client_ip(req.request(), &self.trusted_proxies).map_or_else(|| String::from("-"), |ip| ip.to_string());
+        let user_agent = req.headers().get(header::USER_AGENT).and_then(|value| value.to_str().ok()).unwrap_or("-").to_string();
+        let started_at = Instant::now();
+
+        Box::pin(async move {
+            let result = service.call(req).await;
+            let status = match &result {
+                Ok(response) => response.status(),
+                Err(e) => e.as_response_error().status_code(),
+            };
+            let latency_ms = started_at.elapsed().as_secs_f64() * 1000.0;
+            if status.is_server_error() {
+                tracing::error!(%method, %path, status = status.as_u16(), latency_ms, %client_ip, %user_agent, "request failed");
+            } else {
+                tracing::info!(%method, %path, status = status.as_u16(), latency_ms, %client_ip, %user_agent, "request completed");
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    use actix_web::{
+        test::{call_service, init_service, TestRequest},
+        web, App, HttpResponse,
+    };
+    use tracing_subscriber::{fmt, layer::SubscriberExt};
+
+    use crate::application::utils::access_control::middlewares::request_id::request_id;
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_should_leave_calendar_feed_tokens_out_of_paths() {
+        assert_eq!(loggable_path("/api/v1/calendar/feed/s3cr3t.ics"), "/api/v1/calendar/feed/{token}.ics");
+        assert_eq!(loggable_path("/api/v1/calendar/feed/s3cr3t"), "/api/v1/calendar/feed/{token}");
+        assert_eq!(loggable_path("/api/v1/calendar/feed/s3cr3t/regenerate"), "/api/v1/calendar/feed/{token}/regenerate");
+        assert_eq!(loggable_path("/api/v1/calendar/feed_own"), "/api/v1/calendar/feed_own");
+        assert_eq!(loggable_path("/api/v1/tasks/all"), "/api/v1/tasks/all");
+    }
+
+    #[actix_rt::test]
+    async fn test_should_not_log_calendar_feed_tokens() {
+        // given the request span and the access log writing to memory
+        let output = Output::default();
+        let writer = output.clone();
+        let layer = fmt::layer().with_ansi(false).with_writer(move || writer.clone());
+        let _subscriber = tracing::subscriber::set_default(tracing_subscriber::registry().with(layer));
+        let app = init_service(
+            App::new()
+                .wrap(logger(TrustedProxies::default()))
+                .wrap(request_id())
+                .route("/api/v1/calendar/feed/{token}.ics", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        // when a calendar app fetches a feed
+        let request = TestRequest::get().uri("/api/v1/calendar/feed/s3cr3tt0k3n.ics").to_request();
+        call_service(&app, request).await;
+
+        // then the request is logged without its token
+        let logged = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert!(logged.contains("request completed"));
+        assert!(logged.contains("/api/v1/calendar/feed/{token}.ics"));
+        assert!(!logged.contains("s3cr3tt0k3n"));
+    }
 }
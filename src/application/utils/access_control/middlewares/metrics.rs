@@ -0,0 +1,79 @@
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+    sync::Arc,
+    time::Instant,
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+
+use crate::application::utils::metrics::Metrics;
+
+// Requests no route matched share one series, their raw paths are whatever clients send
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+pub struct RequestMetrics {
+    metrics: Arc<Metrics>,
+}
+
+// Counts every request with its latency, by method, route pattern and status class
+pub fn request_metrics(metrics: Arc<Metrics>) -> RequestMetrics {
+    RequestMetrics { metrics }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware {
+            service: Rc::new(service),
+            metrics: self.metrics.clone(),
+        }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: Rc<S>,
+    metrics: Arc<Metrics>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let metrics = self.metrics.clone();
+        let method = req.method().to_string();
+        let started_at = Instant::now();
+
+        Box::pin(async move {
+            let result = service.call(req).await;
+            let (route, status) = match &result {
+                Ok(response) => (response.request().match_pattern(), response.status()),
+                Err(e) => (None, e.as_response_error().status_code()),
+            };
+            metrics.record_request(&method, route.as_deref().unwrap_or(UNMATCHED_ROUTE), status.as_u16(), started_at.elapsed());
+            result
+        })
+    }
+}
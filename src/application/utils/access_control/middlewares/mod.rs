@@ -1,4 +1,7 @@
 pub mod cors;
 pub mod err_handlers;
 pub mod logger;
+pub mod metrics;
+pub mod rate_limit;
 pub mod security_headers;
+pub mod request_id;
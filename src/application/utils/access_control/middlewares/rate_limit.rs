@@ -0,0 +1,385 @@
+use std::{
+    future::{ready, Future, Ready},
+    net::IpAddr,
+    pin::Pin,
+    rc::Rc,
+    str::FromStr,
+    sync::Arc,
+};
+
+use actix_web::{
+    body::EitherBody,
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, HeaderName, HeaderValue},
+        Method, StatusCode,
+    },
+    Error, HttpRequest, HttpResponse,
+};
+
+use crate::adapters::{
+    api::shared::error_presenter::ErrorPresenter,
+    spi::{
+        db::db_api_keys_repository::API_KEY_PREFIX,
+        rate_limit::rate_limit_store::{RateLimitDecision, RateLimitStore},
+    },
+};
+use crate::application::utils::access_control::auth_usecase::AuthUseCase;
+use crate::domain::error::error_codes;
+
+const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+const RATE_LIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+// `limit` requests per `window` seconds, written "100/60"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitRule {
+    pub limit: u64,
+    pub window: u64,
+}
+
+impl FromStr for RateLimitRule {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid rate limit \"{}\", expected <requests>/<seconds>", input);
+        let (limit, window) = input.trim().split_once('/').ok_or_else(invalid)?;
+        let limit = limit.trim().parse::<u64>().map_err(|_| invalid())?;
+        let window = window.trim().parse::<u64>().map_err(|_| invalid())?;
+        if limit == 0 || window == 0 {
+            return Err(invalid());
+        }
+        Ok(RateLimitRule { limit, window })
+    }
+}
+
+// Own bucket for the requests under a path, written "POST /api/v1/users/login=5/60" (the method is optional)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteRateLimit {
+    pub method: Option<Method>,
+    pub path_prefix: String,
+    pub rule: RateLimitRule,
+}
+
+impl RouteRateLimit {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        self.method.as_ref().is_none_or(|data| data.as_str() == method) && path.starts_with(&self.path_prefix)
+    }
+
+    fn scope(&self) -> String {
+        match &self.method {
+            Some(method) => format!("{} {}", method, self.path_prefix),
+            None => self.path_prefix.clone(),
+        }
+    }
+}
+
+impl FromStr for RouteRateLimit {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (route, rule) = input
+            .trim()
+            .rsplit_once('=')
+            .ok_or_else(|| format!("Invalid route rate limit \"{}\", expected [METHOD] <path>=<requests>/<seconds>", input))?;
+        let (method, path_prefix) = match route.trim().split_once(' ') {
+            Some((method, path)) => (Some(Method::from_str(&method.to_uppercase()).map_err(|e| e.to_string())?), path.trim()),
+            None => (None, route.trim()),
+        };
+        Ok(RouteRateLimit {
+            method,
+            path_prefix: path_prefix.to_string(),
+            rule: rule.parse()?,
+        })
+    }
+}
+
+// Proxies whose X-Forwarded-For entries are believed, none unless listed
+#[derive(Debug, Clone, Default)]
+pub struct TrustedProxies {
+    pub addresses: Vec<IpAddr>,
+}
+
+impl TrustedProxies {
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.addresses.contains(ip)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub default_rule: RateLimitRule,
+    // first match wins, list the narrow prefixes first
+    pub routes: Vec<RouteRateLimit>,
+    pub trusted_proxies: TrustedProxies,
+}
+
+impl RateLimitConfig {
+    // Bucket scope and rule of a request, gRPC calls are matched as `POST /<service>/<method>`
+    pub fn rule_for(&self, method: &str, path: &str) -> (String, RateLimitRule) {
+        match self.routes.iter().find(|route| route.matches(method, path)) {
+            Some(route) => (route.scope(), route.rule),
+            None => (String::from("default"), self.default_rule),
+        }
+    }
+}
+
+pub struct RateLimiter {
+    config: Rc<RateLimitConfig>,
+    store: Arc<dyn RateLimitStore + Send + Sync>,
+}
+
+// Token bucket per client, signed in users are counted by user id and everyone else by IP
+pub fn rate_limiter(config: RateLimitConfig, store: Arc<dyn RateLimitStore + Send + Sync>) -> RateLimiter {
+    RateLimiter { config: Rc::new(config), store }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = RateLimiterMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RateLimiterMiddleware {
+            service: Rc::new(service),
+            config: self.config.clone(),
+            store: self.store.clone(),
+        }))
+    }
+}
+
+pub struct RateLimiterMiddleware<S> {
+    service: Rc<S>,
+    config: Rc<RateLimitConfig>,
+    store: Arc<dyn RateLimitStore + Send + Sync>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimiterMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let store = self.store.clone();
+        let (scope, rule) = self.config.rule_for(req.method().as_str(), req.path());
+        let authorization = req.headers().get(header::AUTHORIZATION).and_then(|h| h.to_str().ok());
+        let key = format!("{}|{}", scope, client_key(authorization, client_ip(req.request(), &self.config.trusted_proxies)));
+
+        Box::pin(async move {
+            let decision = match store.take(&key, rule.limit, rule.window).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    // an unavailable store must not take the API down with it
+                    log::error!("Rate limit store failed, letting the request through: {}", e);
+                    return service.call(req).await.map(ServiceResponse::map_into_left_body);
+                }
+            };
+
+            if !decision.allowed {
+                let mut response = HttpResponse::build(StatusCode::TOO_MANY_REQUESTS);
+                response.insert_header((header::RETRY_AFTER, decision.retry_after));
+                let response = response.json(ErrorPresenter {
+                    code: StatusCode::TOO_MANY_REQUESTS.as_u16(),
+                    error_code: error_codes::TOO_MANY_REQUESTS.to_string(),
+                    message: String::from("Too many requests, try again later"),
+                    data: None,
+                });
+                let mut response = req.into_response(response).map_into_right_body();
+                insert_rate_limit_headers(response.headers_mut(), &rule, &decision);
+                return Ok(response);
+            }
+
+            let mut response = service.call(req).await?.map_into_left_body();
+            insert_rate_limit_headers(response.headers_mut(), &rule, &decision);
+            Ok(response)
+        })
+    }
+}
+
+fn insert_rate_limit_headers(headers: &mut actix_web::http::header::HeaderMap, rule: &RateLimitRule, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from(rule.limit));
+    headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from(decision.remaining));
+    headers.insert(RATE_LIMIT_RESET, HeaderValue::from(decision.reset_after));
+    if let Ok(policy) = HeaderValue::from_str(&format!("{};w={}", rule.limit, rule.window)) {
+        headers.insert(RATE_LIMIT_POLICY, policy);
+    }
+}
+
+// Names the client a bucket belongs to, from its `authorization` header or metadata and its address
+pub fn client_key(authorization: Option<&str>, client_ip: Option<IpAddr>) -> String {
+    // only the signature is checked here, revoked tokens are refused later by the extractors anyway.
+    // API keys need a database lookup to name their owner and are counted by IP instead.
+    let subject = authorization
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .filter(|token| !token.starts_with(API_KEY_PREFIX))
+        .and_then(|token| AuthUseCase::validate_token(token).ok())
+        .map(|claims| claims.sub);
+
+    match subject {
+        Some(subject) => format!("user:{}", subject),
+        None => format!("ip:{}", client_ip.map(|ip| ip.to_string()).unwrap_or_default()),
+    }
+}
+
+pub fn client_ip(req: &HttpRequest, trusted_proxies: &TrustedProxies) -> Option<IpAddr> {
+    let peer = req.peer_addr().map(|addr| addr.ip())?;
+    let forwarded_for = req.headers().get_all(HeaderName::from_static("x-forwarded-for")).filter_map(|h| h.to_str().ok());
+    Some(forwarded_client_ip(peer, forwarded_for, trusted_proxies))
+}
+
+// Walks X-Forwarded-For from the nearest hop, the first address that is not one of our proxies is the client
+pub fn forwarded_client_ip<'a>(peer: IpAddr, forwarded_for: impl Iterator<Item = &'a str>, trusted_proxies: &TrustedProxies) -> IpAddr {
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+
+    let forwarded = forwarded_for.flat_map(|h| h.split(',')).filter_map(|ip| ip.trim().parse::<IpAddr>().ok()).collect::<Vec<IpAddr>>();
+
+    let mut client = peer;
+    for ip in forwarded.into_iter().rev() {
+        client = ip;
+        if !trusted_proxies.contains(&ip) {
+            break;
+        }
+    }
+    client
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use std::net::SocketAddr;
+
+    fn request_from(peer: &str, forwarded_for: &[&str]) -> HttpRequest {
+        let mut request = TestRequest::default().peer_addr(SocketAddr::new(peer.parse().unwrap(), 40000));
+        for header in forwarded_for {
+            request = request.append_header(("X-Forwarded-For", *header));
+        }
+        request.to_http_request()
+    }
+
+    fn trusting(addresses: &[&str]) -> TrustedProxies {
+        TrustedProxies {
+            addresses: addresses.iter().map(|ip| ip.parse().unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_should_parse_rule() {
+        // given a rule with spaces around its numbers
+        let input = " 100 / 60 ";
+
+        // when parsing it
+        let rule = RateLimitRule::from_str(input).unwrap();
+
+        // then expect the limit and the window
+        assert_eq!(rule, RateLimitRule { limit: 100, window: 60 });
+    }
+
+    #[test]
+    fn test_should_refuse_invalid_rules() {
+        // given rules without a window, with zeros or with words
+        for input in ["100", "0/60", "100/0", "many/60", "-1/60"] {
+            // when parsing them
+            let result = RateLimitRule::from_str(input);
+
+            // then expect an error naming the expected format
+            assert!(result.unwrap_err().contains("expected <requests>/<seconds>"), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_should_parse_route_with_and_without_method() {
+        // given a route spec with a lower case method and one without method
+        let with_method = RouteRateLimit::from_str("post /api/v1/users/login=5/60").unwrap();
+        let without_method = RouteRateLimit::from_str("/api/v1/tasks=50/10").unwrap();
+
+        // when matching requests against them
+        // then expect the method to be checked only when given
+        assert_eq!(with_method.method, Some(Method::POST));
+        assert_eq!(with_method.rule, RateLimitRule { limit: 5, window: 60 });
+        assert!(with_method.matches("POST", "/api/v1/users/login"));
+        assert!(!with_method.matches("GET", "/api/v1/users/login"));
+        assert_eq!(with_method.scope(), "POST /api/v1/users/login");
+        assert_eq!(without_method.method, None);
+        assert!(without_method.matches("DELETE", "/api/v1/tasks/1"));
+        assert!(!without_method.matches("GET", "/api/v1/projects"));
+        assert_eq!(without_method.scope(), "/api/v1/tasks");
+    }
+
+    #[test]
+    fn test_should_refuse_invalid_routes() {
+        // given a route without a rule, one with an invalid method and one with an invalid rule
+        // when parsing them
+        // then expect errors
+        assert!(RouteRateLimit::from_str("/api/v1/tasks").is_err());
+        assert!(RouteRateLimit::from_str("P@ST /api/v1/tasks=5/60").is_err());
+        assert!(RouteRateLimit::from_str("/api/v1/tasks=5").is_err());
+    }
+
+    #[test]
+    fn test_should_trust_only_listed_proxies() {
+        // given no listed proxy and a list of one
+        let nobody = TrustedProxies::default();
+        let one = trusting(&["10.0.0.1"]);
+
+        // when checking loopback, private and listed addresses
+        // then expect only the listed one trusted
+        assert!(!nobody.contains(&"127.0.0.1".parse().unwrap()));
+        assert!(!nobody.contains(&"10.0.0.1".parse().unwrap()));
+        assert!(one.contains(&"10.0.0.1".parse().unwrap()));
+        assert!(!one.contains(&"10.0.0.2".parse().unwrap()));
+        assert!(!one.contains(&"::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_should_ignore_forwarded_header_from_untrusted_peer() {
+        // given a peer that is not a proxy of ours claiming to forward someone
+        let request = request_from("203.0.113.7", &["198.51.100.4"]);
+
+        // when naming the client
+        let ip = client_ip(&request, &trusting(&["10.0.0.1"]));
+
+        // then expect the peer itself
+        assert_eq!(ip, Some("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_should_walk_forwarded_header_from_the_nearest_hop() {
+        // given two proxies of ours, the client prepending a forged address
+        let request = request_from("10.0.0.1", &["192.0.2.66, 203.0.113.7", "10.0.0.2"]);
+
+        // when naming the client
+        let ip = client_ip(&request, &trusting(&["10.0.0.1", "10.0.0.2"]));
+
+        // then expect the first untrusted address from the right, not the forged one
+        assert_eq!(ip, Some("203.0.113.7".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_should_fall_back_to_last_trusted_hop() {
+        // given a trusted peer forwarding only trusted addresses and garbage
+        let request = request_from("10.0.0.1", &["not-an-ip, 10.0.0.2"]);
+
+        // when naming the client
+        let ip = client_ip(&request, &trusting(&["10.0.0.1", "10.0.0.2"]));
+
+        // then expect the farthest trusted address
+        assert_eq!(ip, Some("10.0.0.2".parse().unwrap()));
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    future::{ready, Future, Ready},
+    pin::Pin,
+    rc::Rc,
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error::InternalError,
+    http::header::{HeaderName, HeaderValue},
+    Error, HttpMessage,
+};
+use tracing::{field, Instrument};
+use uuid::Uuid;
+
+use crate::application::utils::access_control::middlewares::logger::loggable_path;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+const TRACEPARENT_HEADER: &str = "traceparent";
+// Longer ids, or ids with other characters, are replaced so a client can't forge or flood log lines
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+// Id of the current request, available to handlers through the request extensions
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+pub struct RequestIdentifier;
+
+// Keeps the X-Request-Id sent by the client or a proxy, or generates one, echoes it on the response and
+// runs the request in a span holding it, so every log line and trace of the request can be found from it
+pub fn request_id() -> RequestIdentifier {
+    RequestIdentifier
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdentifier
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddleware { service: Rc::new(service) }))
+    }
+}
+
+pub struct RequestIdMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let request_id = incoming_request_id(&req).unwrap_or_else(|| Uuid::new_v4().to_string());
+        let traceparent = req.headers().get(TRACEPARENT_HEADER).and_then(|value| value.to_str().ok()).map(String::from);
+        let span = tracing::info_span!(
+            "http_request",
+            request_id = %request_id,
+            method = %req.method(),
+            path = %loggable_path(req.path()),
+            route = field::Empty,
+            status = field::Empty,
+            traceparent = traceparent.as_deref(),
+        );
+        req.extensions_mut().insert(RequestId(request_id.clone()));
+        let header_value = HeaderValue::from_str(&request_id).ok();
+
+        Box::pin(
+            async move {
+                let current = tracing::Span::current();
+                match service.call(req).await {
+                    Ok(mut response) => {
+                        if let Some(route) = response.request().match_pattern() {
+                            current.record("route", route.as_str());
+                        }
+                        current.record("status", response.status().as_u16());
+                        if let Some(value) = header_value {
+                            response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                        }
+                        Ok(response)
+                    }
+                    // errors of the inner services get their response built here, so it carries the id too
+                    Err(e) => {
+                        let mut error_response = e.error_response();
+                        current.record("status", error_response.status().as_u16());
+                        if let Some(value) = header_value {
+                            error_response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                        }
+                        Err(InternalError::from_response(e, error_response).into())
+                    }
+                }
+            }
+            .instrument(span),
+        )
+    }
+}
+
+fn incoming_request_id(req: &ServiceRequest) -> Option<String> {
+    valid_request_id(req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?)
+}
+
+// The id a client or a proxy sent, unless it could forge or flood log lines
+pub fn valid_request_id(value: &str) -> Option<String> {
+    let value = value.trim();
+    let valid = !value.is_empty() && value.len() <= MAX_REQUEST_ID_LENGTH && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    valid.then(|| value.to_string())
+}
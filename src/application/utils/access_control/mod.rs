@@ -1,3 +1,9 @@
 pub mod extractors;
 pub mod middlewares;
 pub mod auth_usecase;
+pub mod revocation_cache;
+pub mod permission_cache;
+pub mod ttl_cache;
+pub mod task_policy;
+pub mod caller;
+pub mod key_ring;
@@ -0,0 +1,44 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::application::repositories::permissions_repository_abstract::PermissionsRepositoryAbstract;
+
+use super::{
+    extractors::claims::{Permission, Role},
+    ttl_cache::TtlCache,
+};
+
+// Role -> permissions mapping, reloaded from the database once it gets older than `ttl`
+pub struct PermissionCache {
+    roles: TtlCache<HashMap<String, HashSet<Permission>>>,
+}
+
+impl PermissionCache {
+    pub fn new(ttl: Duration) -> Self {
+        PermissionCache { roles: TtlCache::new(ttl) }
+    }
+
+    // Force a reload on the next check, e.g. after a super admin edited the mapping
+    pub fn mark_stale(&self) {
+        self.roles.mark_stale();
+    }
+
+    pub async fn refresh_if_stale(&self, repository: &dyn PermissionsRepositoryAbstract) {
+        let load = async {
+            repository.get_all_role_permissions().await.map(|entities| {
+                entities
+                    .into_iter()
+                    .map(|entity| (entity.role, entity.permissions.iter().filter_map(|permission| Permission::from_str(permission).ok()).collect()))
+                    .collect()
+            })
+        };
+        self.roles.refresh_if_stale("role permissions", load).await;
+    }
+
+    pub fn role_permissions(&self, role: &Role) -> HashSet<Permission> {
+        self.roles.read(|roles| roles.get(&role.to_string()).cloned().unwrap_or_default())
+    }
+}
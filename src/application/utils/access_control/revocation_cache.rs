@@ -0,0 +1,122 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{application::repositories::tokens_repository_abstract::TokensRepositoryAbstract, domain::token_entity::RevocationListEntity};
+
+use super::{extractors::claims::Claims, ttl_cache::TtlCache};
+
+// Token revocation list, reloaded from the database once it gets older than `ttl`
+pub struct RevocationCache {
+    list: TtlCache<RevocationListEntity>,
+}
+
+impl RevocationCache {
+    pub fn new(ttl: Duration) -> Self {
+        RevocationCache { list: TtlCache::new(ttl) }
+    }
+
+    // Force a reload on the next check, e.g. after a password or role change
+    pub fn mark_stale(&self) {
+        self.list.mark_stale();
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.list.is_stale()
+    }
+
+    pub async fn refresh_if_stale(&self, repository: &dyn TokensRepositoryAbstract) {
+        self.list.refresh_if_stale("token revocation list", repository.get_revocation_list()).await;
+    }
+
+    pub fn revoke_token(&self, jti: &str, expires_at: i64) {
+        let now = now_timestamp();
+        self.list.update(|list| {
+            list.tokens.retain(|_, exp| *exp > now);
+            list.tokens.insert(jti.to_string(), expires_at);
+        });
+    }
+
+    pub fn revoke_session(&self, session_id: &str, revoked_at: i64) {
+        self.list.update(|list| {
+            list.sessions.insert(session_id.to_string(), revoked_at);
+        });
+    }
+
+    // `revoked_at` in milliseconds, a token issued later in the same second stays valid
+    pub fn revoke_user(&self, user_id: &str, revoked_at: i64) {
+        self.list.update(|list| {
+            list.users.insert(user_id.to_string(), revoked_at);
+        });
+    }
+
+    pub fn is_revoked(&self, claims: &Claims) -> bool {
+        self.list.read(|list| {
+            if list.tokens.contains_key(&claims.jti) {
+                return true;
+            }
+
+            if claims.sid.as_ref().is_some_and(|sid| list.sessions.contains_key(sid)) {
+                return true;
+            }
+
+            list.users.get(&claims.sub).is_some_and(|revoked_at| claims.issued_at_millis() <= *revoked_at)
+        })
+    }
+}
+
+fn now_timestamp() -> i64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::utils::access_control::extractors::claims::{Role, TokenType};
+
+    fn claims(iat_ms: u64) -> Claims {
+        Claims {
+            sub: String::from("user1"),
+            role: Role::Customer,
+            permissions: None,
+            exp: usize::MAX,
+            iat: (iat_ms / 1000) as usize,
+            iat_ms: Some(iat_ms),
+            nbf: (iat_ms / 1000) as usize,
+            iss: String::new(),
+            aud: String::new(),
+            jti: String::from("jti1"),
+            sid: None,
+            api_key_id: None,
+            typ: TokenType::Access,
+        }
+    }
+
+    #[test]
+    fn test_should_accept_tokens_issued_after_a_user_revocation_of_the_same_second() {
+        // given every token of user1 revoked at 1727000000.400
+        let cache = RevocationCache::new(Duration::from_secs(30));
+        cache.revoke_user("user1", 1_727_000_000_400);
+
+        // when checking tokens issued just before and just after, within the same second
+        let before = cache.is_revoked(&claims(1_727_000_000_100));
+        let after = cache.is_revoked(&claims(1_727_000_000_700));
+
+        // then only the earlier one is revoked
+        assert!(before);
+        assert!(!after);
+    }
+
+    #[test]
+    fn test_should_treat_tokens_without_milliseconds_as_issued_at_the_start_of_their_second() {
+        // given a token from before `iat_ms` issued in the second of the revocation
+        let cache = RevocationCache::new(Duration::from_secs(30));
+        cache.revoke_user("user1", 1_727_000_000_400);
+        let mut legacy = claims(1_727_000_000_700);
+        legacy.iat_ms = None;
+
+        // when checking it
+        let revoked = cache.is_revoked(&legacy);
+
+        // then it is revoked
+        assert!(revoked);
+    }
+}
@@ -0,0 +1,65 @@
+use std::collections::HashSet;
+
+use crate::domain::task_entity::TaskEntity;
+
+use super::extractors::claims::{Claims, Permission};
+
+// Decides which tasks a caller may see or change: their own, or any task with the matching `:any` permission.
+// Tasks outside the policy are reported as missing so their ids can't be probed.
+pub struct TaskPolicy {
+    user_id: String,
+    permissions: HashSet<Permission>,
+}
+
+impl TaskPolicy {
+    pub fn new(user_id: String, permissions: HashSet<Permission>) -> Self {
+        TaskPolicy { user_id, permissions }
+    }
+
+    // Only keeps the permissions both the token and the role's current grants agree on
+    pub fn from_claims(claims: &Claims, role_permissions: &HashSet<Permission>) -> Self {
+        let permissions = Permission::ALL
+            .iter()
+            .copied()
+            .filter(|permission| claims.has_permission(*permission, role_permissions))
+            .collect();
+        TaskPolicy::new(claims.sub.clone(), permissions)
+    }
+
+    pub fn can_read(&self, task: &TaskEntity) -> bool {
+        self.is_owner(&task.user_id) || self.permissions.contains(&Permission::TasksReadAny)
+    }
+
+    pub fn can_write(&self, task: &TaskEntity) -> bool {
+        self.is_owner(&task.user_id) || self.permissions.contains(&Permission::TasksWriteAny)
+    }
+
+    pub fn can_delete(&self, task: &TaskEntity) -> bool {
+        self.is_owner(&task.user_id) || self.permissions.contains(&Permission::TasksDeleteAny)
+    }
+
+    // Creating a task for someone else, or handing one over, needs `tasks:write:any`
+    pub fn can_assign(&self, owner_id: Option<&String>) -> bool {
+        owner_id.is_none_or(|owner_id| self.is_owner(owner_id)) || self.permissions.contains(&Permission::TasksWriteAny)
+    }
+
+    // Owner filter for task lists, callers without `tasks:read:any` only ever list their own tasks
+    pub fn readable_owner(&self, requested: Option<&String>) -> Option<String> {
+        match self.permissions.contains(&Permission::TasksReadAny) {
+            true => requested.cloned(),
+            false => Some(self.user_id.clone()),
+        }
+    }
+
+    // Owner filter for deletions, callers without `tasks:delete:any` only ever delete their own tasks
+    pub fn deletable_owner(&self, requested: Option<&String>) -> Option<String> {
+        match self.permissions.contains(&Permission::TasksDeleteAny) {
+            true => requested.cloned(),
+            false => Some(self.user_id.clone()),
+        }
+    }
+
+    fn is_owner(&self, owner_id: &str) -> bool {
+        self.user_id == owner_id
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+// In-process copy of a value kept in the database, reloaded once it gets older than `ttl`
+pub struct TtlCache<T> {
+    state: RwLock<TtlCacheState<T>>,
+    ttl: Duration,
+}
+
+struct TtlCacheState<T> {
+    value: T,
+    loaded_at: Option<Instant>,
+}
+
+impl<T: Default> TtlCache<T> {
+    pub fn new(ttl: Duration) -> Self {
+        TtlCache {
+            state: RwLock::new(TtlCacheState {
+                value: T::default(),
+                loaded_at: None,
+            }),
+            ttl,
+        }
+    }
+
+    pub fn is_stale(&self) -> bool {
+        let state = self.state.read().unwrap();
+        state.loaded_at.is_none_or(|loaded_at| loaded_at.elapsed() >= self.ttl)
+    }
+
+    // Force a reload on the next refresh
+    pub fn mark_stale(&self) {
+        self.state.write().unwrap().loaded_at = None;
+    }
+
+    pub fn replace(&self, value: T) {
+        let mut state = self.state.write().unwrap();
+        state.value = value;
+        state.loaded_at = Some(Instant::now());
+    }
+
+    // Keeps the previous value when the load fails, `what` names it in the log
+    pub async fn refresh_if_stale<F, E>(&self, what: &str, load: F)
+    where
+        F: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        if !self.is_stale() {
+            return;
+        }
+
+        match load.await {
+            Ok(value) => self.replace(value),
+            Err(e) => log::error!("Failed to reload {}: {}", what, e),
+        }
+    }
+
+    pub fn read<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.state.read().unwrap().value)
+    }
+
+    // Local changes, kept until the next reload replaces them with the database copy
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.state.write().unwrap().value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, ErrorKind};
+
+    #[actix_rt::test]
+    async fn test_should_reload_only_when_stale() {
+        // given a cache loaded once
+        let cache: TtlCache<u32> = TtlCache::new(Duration::from_secs(30));
+        cache.refresh_if_stale("number", async { Ok::<u32, Error>(1) }).await;
+
+        // when refreshing again before the ttl and after marking it stale
+        cache.refresh_if_stale("number", async { Ok::<u32, Error>(2) }).await;
+        let fresh = cache.read(|value| *value);
+        cache.mark_stale();
+        cache.refresh_if_stale("number", async { Ok::<u32, Error>(3) }).await;
+
+        // then only the forced reload is applied
+        assert_eq!(fresh, 1);
+        assert_eq!(cache.read(|value| *value), 3);
+    }
+
+    #[actix_rt::test]
+    async fn test_should_keep_the_previous_value_when_the_reload_fails() {
+        // given a cache with a zero ttl, always stale
+        let cache: TtlCache<u32> = TtlCache::new(Duration::ZERO);
+        cache.replace(1);
+
+        // when the reload fails
+        cache.refresh_if_stale("number", async { Err::<u32, Error>(Error::new(ErrorKind::Other, "oh no!")) }).await;
+
+        // then the previous value stays
+        assert_eq!(cache.read(|value| *value), 1);
+        assert!(cache.is_stale());
+    }
+}
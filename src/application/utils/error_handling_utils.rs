@@ -1,15 +1,37 @@
-use crate::domain::error::ApiError;
-use std::error::Error;
+use crate::domain::error::{error_codes, ApiError, DomainError};
 
 pub struct ErrorHandlingUtils {}
 
 impl ErrorHandlingUtils {
-    pub fn application_error(error_message: &str, error: Option<Box<dyn Error>>) -> ApiError {
+    // `error_message` says what the use case couldn't do. A typed repository error replaces it with its own
+    // message and status, an infrastructure failure keeps it and answers 500, the cause is logged only.
+    pub fn application_error(error_message: &str, error: Option<DomainError>) -> ApiError {
         ErrorHandlingUtils::log_error(error_message, &error);
-        ApiError {
-            code: 400,
-            message: String::from(error_message),
-            error,
+        match error {
+            None => ApiError {
+                code: 400,
+                error_code: error_codes::BAD_REQUEST,
+                message: String::from(error_message),
+                fields: Vec::new(),
+                error: None,
+            },
+            Some(error) => {
+                let message = match &error {
+                    DomainError::Infrastructure(_) => String::from(error_message),
+                    other => other.to_string(),
+                };
+                let fields = match &error {
+                    DomainError::Validation { fields, .. } => fields.clone(),
+                    _ => Vec::new(),
+                };
+                ApiError {
+                    code: error.status_code(),
+                    error_code: error.error_code(),
+                    message,
+                    fields,
+                    error: Some(error),
+                }
+            }
         }
     }
     pub fn unauthorized_error() -> ApiError {
@@ -17,7 +39,9 @@ impl ErrorHandlingUtils {
         ErrorHandlingUtils::log_error(unauthorized_message, &None);
         ApiError {
             code: 401,
+            error_code: error_codes::UNAUTHORIZED,
             message: String::from(unauthorized_message),
+            fields: Vec::new(),
             error: None,
         }
     }
@@ -26,15 +50,40 @@ impl ErrorHandlingUtils {
         ErrorHandlingUtils::log_error(forbdden_message, &None);
         ApiError {
             code: 403,
+            error_code: error_codes::FORBIDDEN,
             message: String::from(forbdden_message),
+            fields: Vec::new(),
+            error: None,
+        }
+    }
+    pub fn not_found_error(not_found_message: &str) -> ApiError {
+        ErrorHandlingUtils::log_error(not_found_message, &None);
+        ApiError {
+            code: 404,
+            error_code: error_codes::NOT_FOUND,
+            message: String::from(not_found_message),
+            fields: Vec::new(),
+            error: None,
+        }
+    }
+
+    pub fn too_many_requests_error(too_many_requests_message: &str) -> ApiError {
+        ErrorHandlingUtils::log_error(too_many_requests_message, &None);
+        ApiError {
+            code: 429,
+            error_code: error_codes::TOO_MANY_REQUESTS,
+            message: String::from(too_many_requests_message),
+            fields: Vec::new(),
             error: None,
         }
     }
 
-    fn log_error(message: &str, err: &Option<Box<dyn Error>>) {
-        println!("Error: {}", message);
-        if let Some(error) = err {
-            println!("Stack: {}", error.to_string());
+    // Logged in the span of the failing use case, the cause stays out of the response but not out of the logs
+    fn log_error(message: &str, err: &Option<DomainError>) {
+        match err {
+            Some(error @ DomainError::Infrastructure(_)) => tracing::error!(error = %error, "{}", message),
+            Some(error) => tracing::warn!(error = %error, "{}", message),
+            None => tracing::warn!("{}", message),
         }
     }
 }
@@ -0,0 +1,318 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use crate::domain::metrics_entity::MetricsSnapshotEntity;
+
+// Prometheus' default buckets, in seconds
+const DURATION_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LoginOutcome {
+    Success,
+    // wrong password or unknown account
+    Failure,
+    // refused by the brute-force protection before the password was checked
+    Locked,
+}
+
+impl LoginOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LoginOutcome::Success => "success",
+            LoginOutcome::Failure => "failure",
+            LoginOutcome::Locked => "locked",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Histogram {
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Histogram {
+            buckets: vec![0; DURATION_BUCKETS.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS) {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        let separator = if labels.is_empty() { "" } else { "," };
+        for (count, bound) in self.buckets.iter().zip(DURATION_BUCKETS) {
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, separator, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{{}{}le=\"+Inf\"}} {}", name, labels, separator, self.count);
+        let labels = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+        let _ = writeln!(out, "{}_sum{} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{} {}", name, labels, self.count);
+    }
+}
+
+// Process wide counters rendered in the Prometheus text format. Routes are recorded by their pattern
+// ("/api/v1/tasks/one"), never by the raw path, so ids in URLs can't blow up the number of series.
+#[derive(Debug)]
+pub struct Metrics {
+    // (method, route, status class)
+    http_requests: Mutex<BTreeMap<(String, String, String), u64>>,
+    // (method, route)
+    http_durations: Mutex<BTreeMap<(String, String), Histogram>>,
+    db_pool_wait: Mutex<Histogram>,
+    db_pool_timeouts: AtomicU64,
+    logins: Mutex<BTreeMap<LoginOutcome, u64>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Metrics::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics {
+            http_requests: Mutex::new(BTreeMap::new()),
+            http_durations: Mutex::new(BTreeMap::new()),
+            db_pool_wait: Mutex::new(Histogram::new()),
+            db_pool_timeouts: AtomicU64::new(0),
+            logins: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn record_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+        let status_class = format!("{}xx", status / 100);
+        if let Ok(mut requests) = self.http_requests.lock() {
+            *requests.entry((method.to_string(), route.to_string(), status_class)).or_default() += 1;
+        }
+        if let Ok(mut durations) = self.http_durations.lock() {
+            durations
+                .entry((method.to_string(), route.to_string()))
+                .or_insert_with(Histogram::new)
+                .observe(duration.as_secs_f64());
+        }
+    }
+
+    pub fn record_login(&self, outcome: LoginOutcome) {
+        if let Ok(mut logins) = self.logins.lock() {
+            *logins.entry(outcome).or_default() += 1;
+        }
+    }
+
+    pub fn record_db_pool_wait(&self, duration: Duration) {
+        if let Ok(mut wait) = self.db_pool_wait.lock() {
+            wait.observe(duration.as_secs_f64());
+        }
+    }
+
+    pub fn record_db_pool_timeout(&self) {
+        self.db_pool_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // The snapshot holds what is read from the database at scrape time, it is left out when it couldn't be taken
+    pub fn render(&self, snapshot: Option<&MetricsSnapshotEntity>) -> String {
+        let mut out = String::new();
+
+        header(&mut out, "http_requests_total", "counter", "HTTP requests by route and status class");
+        if let Ok(requests) = self.http_requests.lock() {
+            for ((method, route, status), count) in requests.iter() {
+                let _ = writeln!(
+                    out,
+                    "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                    escape(method),
+                    escape(route),
+                    status,
+                    count
+                );
+            }
+        }
+
+        header(&mut out, "http_request_duration_seconds", "histogram", "HTTP request latency by route");
+        if let Ok(durations) = self.http_durations.lock() {
+            for ((method, route), histogram) in durations.iter() {
+                histogram.render(&mut out, "http_request_duration_seconds", &format!("method=\"{}\",route=\"{}\"", escape(method), escape(route)));
+            }
+        }
+
+        header(&mut out, "login_attempts_total", "counter", "Password sign-in attempts by outcome");
+        if let Ok(logins) = self.logins.lock() {
+            for outcome in [LoginOutcome::Success, LoginOutcome::Failure, LoginOutcome::Locked] {
+                let _ = writeln!(
+                    out,
+                    "login_attempts_total{{result=\"{}\"}} {}",
+                    outcome.as_str(),
+                    logins.get(&outcome).copied().unwrap_or_default()
+                );
+            }
+        }
+
+        header(&mut out, "db_pool_wait_seconds", "histogram", "Time spent waiting to check out a database connection");
+        if let Ok(wait) = self.db_pool_wait.lock() {
+            wait.render(&mut out, "db_pool_wait_seconds", "");
+        }
+        header(&mut out, "db_pool_timeouts_total", "counter", "Database connection checkouts that timed out");
+        let _ = writeln!(out, "db_pool_timeouts_total {}", self.db_pool_timeouts.load(Ordering::Relaxed));
+
+        if let Some(snapshot) = snapshot {
+            let pool = &snapshot.db_pool;
+            header(&mut out, "db_pool_connections", "gauge", "Open database connections by state");
+            let _ = writeln!(out, "db_pool_connections{{state=\"in_use\"}} {}", pool.connections.saturating_sub(pool.idle_connections));
+            let _ = writeln!(out, "db_pool_connections{{state=\"idle\"}} {}", pool.idle_connections);
+            header(&mut out, "db_pool_max_connections", "gauge", "Size limit of the database connection pool");
+            let _ = writeln!(out, "db_pool_max_connections {}", pool.max_size);
+
+            header(&mut out, "tasks_open", "gauge", "Tasks not completed yet by status");
+            for count in &snapshot.open_tasks_by_status {
+                let _ = writeln!(out, "tasks_open{{status=\"{}\"}} {}", escape(&count.status), count.count);
+            }
+        }
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+// Label values may hold anything, the text format only needs these three escaped
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::metrics_entity::{DbPoolStatsEntity, TaskStatusCountEntity};
+
+    #[test]
+    fn test_should_render_every_family_with_help_and_type() {
+        // given fresh metrics with one successful sign-in
+        let metrics = Metrics::new();
+        metrics.record_login(LoginOutcome::Success);
+
+        // when rendering them without a database snapshot
+        let output = metrics.render(None);
+
+        // then expect the text format, families without samples keeping their header and histograms their buckets
+        let buckets: String = ["0.005", "0.01", "0.025", "0.05", "0.1", "0.25", "0.5", "1", "2.5", "5", "10", "+Inf"]
+            .iter()
+            .map(|bound| format!("db_pool_wait_seconds_bucket{{le=\"{}\"}} 0\n", bound))
+            .collect();
+        let expected = format!(
+            "# HELP http_requests_total HTTP requests by route and status class\n\
+             # TYPE http_requests_total counter\n\
+             # HELP http_request_duration_seconds HTTP request latency by route\n\
+             # TYPE http_request_duration_seconds histogram\n\
+             # HELP login_attempts_total Password sign-in attempts by outcome\n\
+             # TYPE login_attempts_total counter\n\
+             login_attempts_total{{result=\"success\"}} 1\n\
+             login_attempts_total{{result=\"failure\"}} 0\n\
+             login_attempts_total{{result=\"locked\"}} 0\n\
+             # HELP db_pool_wait_seconds Time spent waiting to check out a database connection\n\
+             # TYPE db_pool_wait_seconds histogram\n\
+             {}\
+             db_pool_wait_seconds_sum 0\n\
+             db_pool_wait_seconds_count 0\n\
+             # HELP db_pool_timeouts_total Database connection checkouts that timed out\n\
+             # TYPE db_pool_timeouts_total counter\n\
+             db_pool_timeouts_total 0\n",
+            buckets
+        );
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_should_count_requests_in_cumulative_buckets() {
+        // given two requests of one route, 250 ms and 2 s long, the first on a bucket bound
+        let metrics = Metrics::new();
+        metrics.record_request("GET", "/api/v1/tasks/one", 200, Duration::from_millis(250));
+        metrics.record_request("GET", "/api/v1/tasks/one", 404, Duration::from_secs(2));
+
+        // when rendering them
+        let output = metrics.render(None);
+
+        // then expect one counter per status class and each bucket counting every request up to its bound
+        let labels = "method=\"GET\",route=\"/api/v1/tasks/one\"";
+        for line in [
+            format!("http_requests_total{{{},status=\"2xx\"}} 1", labels),
+            format!("http_requests_total{{{},status=\"4xx\"}} 1", labels),
+            format!("http_request_duration_seconds_bucket{{{},le=\"0.1\"}} 0", labels),
+            format!("http_request_duration_seconds_bucket{{{},le=\"0.25\"}} 1", labels),
+            format!("http_request_duration_seconds_bucket{{{},le=\"1\"}} 1", labels),
+            format!("http_request_duration_seconds_bucket{{{},le=\"2.5\"}} 2", labels),
+            format!("http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 2", labels),
+            format!("http_request_duration_seconds_sum{{{}}} 2.25", labels),
+            format!("http_request_duration_seconds_count{{{}}} 2", labels),
+        ] {
+            assert!(output.lines().any(|rendered| rendered == line), "missing {}", line);
+        }
+    }
+
+    #[test]
+    fn test_should_escape_label_values() {
+        // given a route holding a quote, a backslash and a line break
+        let metrics = Metrics::new();
+        metrics.record_request("GET", "/a\"b\\c\nd", 200, Duration::from_millis(1));
+
+        // when rendering it
+        let output = metrics.render(None);
+
+        // then expect them escaped, the sample staying on one line
+        assert!(output.contains("http_requests_total{method=\"GET\",route=\"/a\\\"b\\\\c\\nd\",status=\"2xx\"} 1\n"));
+        assert!(!output.lines().any(|line| line == "d\",status=\"2xx\"} 1"));
+    }
+
+    #[test]
+    fn test_should_render_database_snapshot() {
+        // given a snapshot of the pool and of the open tasks
+        let metrics = Metrics::new();
+        let snapshot = MetricsSnapshotEntity {
+            db_pool: DbPoolStatsEntity {
+                connections: 5,
+                idle_connections: 2,
+                max_size: 10,
+            },
+            open_tasks_by_status: vec![TaskStatusCountEntity::new(String::from("TODO"), 7), TaskStatusCountEntity::new(String::from("IN_PROGRESS"), 3)],
+        };
+
+        // when rendering the metrics with it
+        let output = metrics.render(Some(&snapshot));
+
+        // then expect the gauges after the process counters
+        assert!(output.ends_with(
+            "# HELP db_pool_connections Open database connections by state\n\
+             # TYPE db_pool_connections gauge\n\
+             db_pool_connections{state=\"in_use\"} 3\n\
+             db_pool_connections{state=\"idle\"} 2\n\
+             # HELP db_pool_max_connections Size limit of the database connection pool\n\
+             # TYPE db_pool_max_connections gauge\n\
+             db_pool_max_connections 10\n\
+             # HELP tasks_open Tasks not completed yet by status\n\
+             # TYPE tasks_open gauge\n\
+             tasks_open{status=\"TODO\"} 7\n\
+             tasks_open{status=\"IN_PROGRESS\"} 3\n"
+        ));
+    }
+}
@@ -1,3 +1,7 @@
 pub mod error_handling_utils;
 pub mod validate_params;
+pub mod token_hash;
 pub mod access_control;
+pub mod totp;
+pub mod metrics;
+pub mod task_events;
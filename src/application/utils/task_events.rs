@@ -0,0 +1,52 @@
+use tokio::sync::broadcast;
+
+use crate::domain::task_entity::{TaskChange, TaskChangeKind, TaskEntity};
+
+// In-process fan out of task changes to live subscribers. Subscribers too slow to keep up with `capacity`
+// changes skip the ones they missed, and changes made by other instances of the server are not seen.
+pub struct TaskEvents {
+    sender: broadcast::Sender<TaskChange>,
+}
+
+impl TaskEvents {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        TaskEvents { sender }
+    }
+
+    pub fn publish(&self, kind: TaskChangeKind, task: &TaskEntity) {
+        // no receiver is not an error, nobody is listening
+        let _ = self.sender.send(TaskChange { kind, task: task.clone() });
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskChange> {
+        self.sender.subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TaskEntityBuilder;
+
+    fn task(id: &str) -> TaskEntity {
+        TaskEntityBuilder::new(id).user_id("user1").build()
+    }
+
+    #[actix_rt::test]
+    async fn test_should_deliver_changes_published_after_subscribing() {
+        // given a subscriber
+        let events = TaskEvents::new(8);
+        events.publish(TaskChangeKind::Created, &task("before"));
+        let mut receiver = events.subscribe();
+
+        // when a task is updated
+        events.publish(TaskChangeKind::Updated, &task("task1"));
+
+        // then expect only that change
+        let change = receiver.recv().await.unwrap();
+        assert_eq!(change.kind, TaskChangeKind::Updated);
+        assert_eq!(change.task.id, "task1");
+        assert!(receiver.try_recv().is_err());
+    }
+}
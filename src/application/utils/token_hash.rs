@@ -0,0 +1,21 @@
+use rand::{distributions::Alphanumeric, Rng};
+use sha2::{Digest, Sha256};
+
+pub fn hash_token(token: &str) -> String {
+    /*
+     *  token hashing:
+     *    - tokens are stored as hex encoded SHA-256 digests, never in plain text
+     *    - tokens are high entropy, so a fast unsalted digest is enough for lookups
+     */
+
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+pub fn generate_secret(length: usize) -> String {
+    /*
+     *  secret generation:
+     *    - alphanumeric characters from a cryptographically secure generator
+     */
+
+    rand::thread_rng().sample_iter(&Alphanumeric).take(length).map(char::from).collect()
+}
@@ -0,0 +1,50 @@
+use rand::RngCore;
+use std::time::{SystemTime, UNIX_EPOCH};
+use totp_rs::{Algorithm, Secret, TOTP};
+
+const TOTP_DIGITS: usize = 6;
+const TOTP_STEP: u64 = 30;
+const TOTP_ISSUER: &str = "Task Tracker";
+
+pub fn generate_totp_secret() -> String {
+    /*
+     *  totp secret:
+     *    - 160 random bits as recommended by RFC 4226, stored base32 encoded
+     */
+
+    let mut secret = [0u8; 20];
+    rand::thread_rng().fill_bytes(&mut secret);
+    Secret::Raw(secret.to_vec()).to_encoded().to_string()
+}
+
+pub fn otpauth_uri(secret: &str, account_name: &str) -> Result<String, String> {
+    Ok(totp(secret, account_name)?.get_url())
+}
+
+pub fn verify_totp_code(secret: &str, code: &str, last_used_step: Option<i64>) -> Result<Option<i64>, String> {
+    /*
+     *  totp verification (RFC 6238):
+     *    - SHA1, 6 digits, 30 seconds steps, one step of clock skew accepted on each side
+     *    - returns the matched step so callers can refuse replays of an already used code
+     */
+
+    let totp = totp(secret, "")?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|e| e.to_string())?.as_secs();
+    let current_step = (now / TOTP_STEP) as i64;
+
+    for step in [current_step - 1, current_step, current_step + 1] {
+        if last_used_step.is_some_and(|last| step <= last) {
+            continue;
+        }
+        if totp.generate(step as u64 * TOTP_STEP) == code.trim() {
+            return Ok(Some(step));
+        }
+    }
+
+    Ok(None)
+}
+
+fn totp(secret: &str, account_name: &str) -> Result<TOTP, String> {
+    let secret = Secret::Encoded(secret.to_string()).to_bytes().map_err(|e| format!("{:?}", e))?;
+    TOTP::new(Algorithm::SHA1, TOTP_DIGITS, 0, TOTP_STEP, secret, Some(TOTP_ISSUER.to_string()), account_name.to_string()).map_err(|e| e.to_string())
+}
@@ -1,3 +1,8 @@
+use std::borrow::Cow;
+
+use uuid::Uuid;
+use validator::ValidationError;
+
 pub fn is_email(email: &str) -> bool {
     /*
      *  email validation:
@@ -30,3 +35,50 @@ pub fn is_username(username: &str) -> bool {
     let re = regex::Regex::new(r"^[a-zA-Z0-9_]{3,20}$").unwrap();
     re.is_match(username)
 }
+
+/*
+ *  rules for the payload validators (`#[validate(custom(function = ...))]`), each failure carries
+ *  a machine readable code and a message for the client
+ */
+
+pub fn email(value: &str) -> Result<(), ValidationError> {
+    match is_email(value) {
+        true => Ok(()),
+        false => Err(ValidationError::new("invalid_email").with_message(Cow::from("must be a valid email address"))),
+    }
+}
+
+pub fn password(value: &str) -> Result<(), ValidationError> {
+    match is_password(value) {
+        true => Ok(()),
+        false => Err(ValidationError::new("weak_password").with_message(Cow::from("must be at least 8 characters, without spaces"))),
+    }
+}
+
+pub fn username(value: &str) -> Result<(), ValidationError> {
+    match is_username(value) {
+        true => Ok(()),
+        false => Err(ValidationError::new("invalid_username").with_message(Cow::from("must be 3 to 20 letters, numbers or underscores"))),
+    }
+}
+
+pub fn uuid(value: &str) -> Result<(), ValidationError> {
+    match Uuid::parse_str(value) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(ValidationError::new("invalid_uuid").with_message(Cow::from("must be a valid UUID"))),
+    }
+}
+
+pub fn not_blank(value: &str) -> Result<(), ValidationError> {
+    match value.trim().is_empty() {
+        true => Err(ValidationError::new("required").with_message(Cow::from("must not be empty"))),
+        false => Ok(()),
+    }
+}
+
+pub fn not_blank_items(values: &[String]) -> Result<(), ValidationError> {
+    match values.iter().any(|value| value.trim().is_empty()) {
+        true => Err(ValidationError::new("required").with_message(Cow::from("must not contain empty items"))),
+        false => Ok(()),
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+
+// Plain text single-use token, only ever handed to the mailer
+#[derive(Debug, Clone)]
+pub struct AccountTokenEntity {
+    pub user_id: String,
+    pub email: String,
+    pub username: String,
+    pub token: String,
+    pub expires_at: NaiveDateTime,
+}
+
+impl AccountTokenEntity {
+    pub fn new(user_id: String, email: String, username: String, token: String, expires_at: NaiveDateTime) -> Self {
+        AccountTokenEntity {
+            user_id,
+            email,
+            username,
+            token,
+            expires_at,
+        }
+    }
+}
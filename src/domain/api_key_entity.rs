@@ -0,0 +1,48 @@
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyEntity {
+    pub id: String,
+    pub user_id: String,
+    pub user_role: String,
+    pub name: String,
+    pub prefix: String,
+    // Plain text key, only set right after creation
+    pub key: Option<String>,
+    pub permissions: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+    pub last_used_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+impl ApiKeyEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        user_id: String,
+        user_role: String,
+        name: String,
+        prefix: String,
+        key: Option<String>,
+        permissions: Vec<String>,
+        expires_at: Option<NaiveDateTime>,
+        last_used_at: Option<NaiveDateTime>,
+        revoked_at: Option<NaiveDateTime>,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        ApiKeyEntity {
+            id,
+            user_id,
+            user_role,
+            name,
+            prefix,
+            key,
+            permissions,
+            expires_at,
+            last_used_at,
+            revoked_at,
+            created_at,
+        }
+    }
+}
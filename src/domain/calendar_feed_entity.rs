@@ -0,0 +1,15 @@
+use chrono::NaiveDateTime;
+
+// The secret calendar URL of a user, `token` is only known right after it is generated
+#[derive(Debug, Clone)]
+pub struct CalendarFeedEntity {
+    pub user_id: String,
+    pub token: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl CalendarFeedEntity {
+    pub fn new(user_id: String, token: Option<String>, created_at: NaiveDateTime) -> Self {
+        CalendarFeedEntity { user_id, token, created_at }
+    }
+}
@@ -1,10 +1,114 @@
 use std::{error::Error, fmt};
 
+// One invalid input, `code` is machine readable ("required", "too_short"...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, code: &str, message: &str) -> Self {
+        FieldError {
+            field: field.to_string(),
+            code: code.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+// What went wrong, as repositories and use cases see it. Every variant but `Infrastructure` carries a message
+// the client may read, infrastructure causes are only logged.
+#[derive(Debug)]
+pub enum DomainError {
+    NotFound(String),
+    Conflict(String),
+    Validation { message: String, fields: Vec<FieldError> },
+    Unauthorized(String),
+    Forbidden(String),
+    Infrastructure(Box<dyn Error>),
+}
+
+impl DomainError {
+    pub fn validation(message: &str) -> Self {
+        DomainError::Validation {
+            message: message.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn status_code(&self) -> u16 {
+        match self {
+            DomainError::NotFound(_) => 404,
+            DomainError::Conflict(_) => 409,
+            DomainError::Validation { .. } => 422,
+            DomainError::Unauthorized(_) => 401,
+            DomainError::Forbidden(_) => 403,
+            DomainError::Infrastructure(_) => 500,
+        }
+    }
+
+    // Stable identifiers, clients branch on them rather than on messages
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            DomainError::NotFound(_) => error_codes::NOT_FOUND,
+            DomainError::Conflict(_) => error_codes::CONFLICT,
+            DomainError::Validation { .. } => error_codes::VALIDATION_FAILED,
+            DomainError::Unauthorized(_) => error_codes::UNAUTHORIZED,
+            DomainError::Forbidden(_) => error_codes::FORBIDDEN,
+            DomainError::Infrastructure(_) => error_codes::INTERNAL_ERROR,
+        }
+    }
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DomainError::NotFound(message)
+            | DomainError::Conflict(message)
+            | DomainError::Validation { message, .. }
+            | DomainError::Unauthorized(message)
+            | DomainError::Forbidden(message) => write!(f, "{}", message),
+            DomainError::Infrastructure(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for DomainError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DomainError::Infrastructure(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+// Failures of the external services (mail, identity providers...)
+impl From<Box<dyn Error>> for DomainError {
+    fn from(error: Box<dyn Error>) -> Self {
+        DomainError::Infrastructure(error)
+    }
+}
+
+pub mod error_codes {
+    pub const BAD_REQUEST: &str = "bad_request";
+    pub const UNAUTHORIZED: &str = "unauthorized";
+    pub const FORBIDDEN: &str = "forbidden";
+    pub const NOT_FOUND: &str = "not_found";
+    pub const CONFLICT: &str = "conflict";
+    pub const VALIDATION_FAILED: &str = "validation_failed";
+    pub const TOO_MANY_REQUESTS: &str = "too_many_requests";
+    pub const INTERNAL_ERROR: &str = "internal_error";
+}
+
 #[derive(Debug)]
 pub struct ApiError {
     pub code: u16,
+    pub error_code: &'static str,
     pub message: String,
-    pub error: Option<Box<dyn Error>>,
+    pub fields: Vec<FieldError>,
+    pub error: Option<DomainError>,
 }
 
 // Implement std::fmt::Display for AppError
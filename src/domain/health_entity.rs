@@ -0,0 +1,28 @@
+// Outcome of one dependency probed by the readiness check
+#[derive(Debug, Clone)]
+pub struct HealthCheckEntity {
+    pub name: String,
+    pub healthy: bool,
+    pub details: Option<String>,
+}
+
+impl HealthCheckEntity {
+    pub fn new(name: &str, healthy: bool, details: Option<String>) -> Self {
+        HealthCheckEntity {
+            name: name.to_string(),
+            healthy,
+            details,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReadinessEntity {
+    pub checks: Vec<HealthCheckEntity>,
+}
+
+impl ReadinessEntity {
+    pub fn is_ready(&self) -> bool {
+        self.checks.iter().all(|check| check.healthy)
+    }
+}
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// The tool an import file was exported from
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    // board JSON export
+    Trello,
+    // project CSV export
+    Todoist,
+    // database CSV export
+    Notion,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportJobState {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ImportSource {
+    pub const ALL: [ImportSource; 3] = [ImportSource::Trello, ImportSource::Todoist, ImportSource::Notion];
+
+    // Stored label, as in the `source` query parameter
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportSource::Trello => "trello",
+            ImportSource::Todoist => "todoist",
+            ImportSource::Notion => "notion",
+        }
+    }
+}
+
+impl ImportJobState {
+    pub const ALL: [ImportJobState; 4] = [ImportJobState::Queued, ImportJobState::Running, ImportJobState::Completed, ImportJobState::Failed];
+    pub const UNFINISHED: [ImportJobState; 2] = [ImportJobState::Queued, ImportJobState::Running];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportJobState::Queued => "queued",
+            ImportJobState::Running => "running",
+            ImportJobState::Completed => "completed",
+            ImportJobState::Failed => "failed",
+        }
+    }
+}
+
+impl FromStr for ImportSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ImportSource::ALL.into_iter().find(|source| source.as_str() == s).ok_or_else(|| format!("ImportSource: unknown value {}", s))
+    }
+}
+
+impl FromStr for ImportJobState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ImportJobState::ALL.into_iter().find(|state| state.as_str() == s).ok_or_else(|| format!("ImportJobState: unknown value {}", s))
+    }
+}
+
+// Something of the export that has no place in a task, or a task that couldn't be created
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportSkippedItem {
+    pub item: String,
+    pub reason: String,
+}
+
+impl ImportSkippedItem {
+    pub fn new(item: &str, reason: &str) -> Self {
+        ImportSkippedItem {
+            item: item.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+}
+
+// An import running in the background, `processed` out of `total` tasks are written so far
+#[derive(Debug, Clone)]
+pub struct ImportJobEntity {
+    pub id: String,
+    pub user_id: String,
+    pub source: ImportSource,
+    pub state: ImportJobState,
+    pub total: usize,
+    pub processed: usize,
+    pub project_ids: Vec<String>,
+    pub task_ids: Vec<String>,
+    pub skipped: Vec<ImportSkippedItem>,
+    pub error: Option<String>,
+    pub updated_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl ImportJobEntity {
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, ImportJobState::Completed | ImportJobState::Failed)
+    }
+}
@@ -0,0 +1,21 @@
+use chrono::NaiveDateTime;
+
+// Combined state of the account and IP counters for a sign-in attempt
+#[derive(Debug, Clone, Default)]
+pub struct LoginThrottleEntity {
+    pub locked_until: Option<NaiveDateTime>,
+    // Wait imposed before the password is checked
+    pub delay_seconds: u64,
+    // The attempt is over the limit, the failure that reached it is about to lock the counter
+    pub exhausted: bool,
+}
+
+impl LoginThrottleEntity {
+    pub fn new(locked_until: Option<NaiveDateTime>, delay_seconds: u64) -> Self {
+        LoginThrottleEntity {
+            locked_until,
+            delay_seconds,
+            exhausted: false,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+#[derive(Debug, Clone, Default)]
+pub struct DbPoolStatsEntity {
+    // open connections, idle ones included
+    pub connections: u32,
+    pub idle_connections: u32,
+    pub max_size: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TaskStatusCountEntity {
+    pub status: String,
+    pub count: i64,
+}
+
+impl TaskStatusCountEntity {
+    pub fn new(status: String, count: i64) -> Self {
+        TaskStatusCountEntity { status, count }
+    }
+}
+
+// Values read from the database when metrics are scraped
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshotEntity {
+    pub db_pool: DbPoolStatsEntity,
+    pub open_tasks_by_status: Vec<TaskStatusCountEntity>,
+}
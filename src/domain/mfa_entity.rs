@@ -0,0 +1,45 @@
+use chrono::NaiveDateTime;
+
+// Pending enrollment, the secret is only shown until the first code is confirmed
+#[derive(Debug, Clone)]
+pub struct MfaEnrollmentEntity {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+impl MfaEnrollmentEntity {
+    pub fn new(secret: String, otpauth_uri: String) -> Self {
+        MfaEnrollmentEntity { secret, otpauth_uri }
+    }
+}
+
+// Plain text recovery codes, returned once and stored hashed
+#[derive(Debug, Clone)]
+pub struct MfaRecoveryCodesEntity {
+    pub recovery_codes: Vec<String>,
+}
+
+impl MfaRecoveryCodesEntity {
+    pub fn new(recovery_codes: Vec<String>) -> Self {
+        MfaRecoveryCodesEntity { recovery_codes }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MfaRoleRequirementEntity {
+    pub role: String,
+    pub required: bool,
+    pub updated_by: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl MfaRoleRequirementEntity {
+    pub fn new(role: String, required: bool, updated_by: Option<String>, updated_at: NaiveDateTime) -> Self {
+        MfaRoleRequirementEntity {
+            role,
+            required,
+            updated_by,
+            updated_at,
+        }
+    }
+}
@@ -1,3 +1,16 @@
 pub mod task_entity;
+pub mod import_entity;
+pub mod calendar_feed_entity;
 pub mod user_entity;
+pub mod project_entity;
+pub mod session_entity;
+pub mod token_entity;
+pub mod account_token_entity;
+pub mod mfa_entity;
+pub mod api_key_entity;
+pub mod permission_entity;
+pub mod oidc_entity;
+pub mod login_throttle_entity;
+pub mod health_entity;
+pub mod metrics_entity;
 pub mod error;
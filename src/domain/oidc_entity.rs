@@ -0,0 +1,46 @@
+// Pending authorization request, matched back by the `state` parameter of the callback
+#[derive(Debug, Clone)]
+pub struct OidcLoginStateEntity {
+    pub provider: String,
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+impl OidcLoginStateEntity {
+    pub fn new(provider: String, nonce: String, code_verifier: String) -> Self {
+        OidcLoginStateEntity { provider, nonce, code_verifier }
+    }
+}
+
+// Identity asserted by an ID token whose signature, issuer, audience and nonce were checked
+#[derive(Debug, Clone)]
+pub struct OidcIdentityEntity {
+    pub provider: String,
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+impl OidcIdentityEntity {
+    pub fn new(provider: String, subject: String, email: Option<String>, email_verified: bool, name: Option<String>) -> Self {
+        OidcIdentityEntity {
+            provider,
+            subject,
+            email,
+            email_verified,
+            name,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OidcAuthorizationEntity {
+    pub authorization_url: String,
+}
+
+impl OidcAuthorizationEntity {
+    pub fn new(authorization_url: String) -> Self {
+        OidcAuthorizationEntity { authorization_url }
+    }
+}
@@ -0,0 +1,11 @@
+#[derive(Debug, Clone)]
+pub struct RolePermissionsEntity {
+    pub role: String,
+    pub permissions: Vec<String>,
+}
+
+impl RolePermissionsEntity {
+    pub fn new(role: String, permissions: Vec<String>) -> Self {
+        RolePermissionsEntity { role, permissions }
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Clone)]
+pub struct ProjectEntity {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub updated_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl ProjectEntity {
+    pub fn new(id: String, name: String, description: Option<String>, updated_at: NaiveDateTime, created_at: NaiveDateTime) -> Self {
+        ProjectEntity {
+            id,
+            name,
+            description,
+            updated_at,
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::NaiveDateTime;
+
+#[derive(Debug, Clone)]
+pub struct SessionEntity {
+    pub id: String,
+    pub user_id: String,
+    pub device_name: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub revoked_at: Option<NaiveDateTime>,
+    pub last_used_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+}
+
+impl SessionEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: String,
+        user_id: String,
+        device_name: Option<String>,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+        revoked_at: Option<NaiveDateTime>,
+        last_used_at: NaiveDateTime,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        SessionEntity {
+            id,
+            user_id,
+            device_name,
+            ip_address,
+            user_agent,
+            revoked_at,
+            last_used_at,
+            created_at,
+        }
+    }
+}
@@ -1,4 +1,131 @@
+use std::{fmt, str::FromStr};
+
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::error::FieldError;
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TaskType {
+    #[default]
+    None,
+    Personal,
+    Work,
+}
+
+// Declared from lowest to highest, comparisons and the database enum both follow this order
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskPriority {
+    #[default]
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TaskStatusToDo {
+    #[default]
+    None,
+    NotStarted,
+    Document,
+    Bug,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TaskStatusInProgress {
+    #[default]
+    None,
+    Doing,
+    Testing,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TaskStatus {
+    #[default]
+    None,
+    ToDo(TaskStatusToDo),
+    InProgress(TaskStatusInProgress),
+    Completed,
+}
+
+impl TaskType {
+    pub const ALL: [TaskType; 3] = [TaskType::None, TaskType::Personal, TaskType::Work];
+
+    // Stored label, also the value of the `task_type` database enum
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskType::None => "none",
+            TaskType::Personal => "personal",
+            TaskType::Work => "work",
+        }
+    }
+}
+
+impl TaskPriority {
+    pub const ALL: [TaskPriority; 4] = [TaskPriority::None, TaskPriority::Low, TaskPriority::Medium, TaskPriority::High];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskPriority::None => "none",
+            TaskPriority::Low => "low",
+            TaskPriority::Medium => "medium",
+            TaskPriority::High => "high",
+        }
+    }
+}
+
+impl TaskStatus {
+    pub const ALL: [TaskStatus; 9] = [
+        TaskStatus::None,
+        TaskStatus::ToDo(TaskStatusToDo::None),
+        TaskStatus::ToDo(TaskStatusToDo::NotStarted),
+        TaskStatus::ToDo(TaskStatusToDo::Document),
+        TaskStatus::ToDo(TaskStatusToDo::Bug),
+        TaskStatus::InProgress(TaskStatusInProgress::None),
+        TaskStatus::InProgress(TaskStatusInProgress::Doing),
+        TaskStatus::InProgress(TaskStatusInProgress::Testing),
+        TaskStatus::Completed,
+    ];
+
+    // The sub-status is flattened into the label, the database enum has no nesting
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::None => "none",
+            TaskStatus::ToDo(TaskStatusToDo::None) => "to_do",
+            TaskStatus::ToDo(TaskStatusToDo::NotStarted) => "to_do_not_started",
+            TaskStatus::ToDo(TaskStatusToDo::Document) => "to_do_document",
+            TaskStatus::ToDo(TaskStatusToDo::Bug) => "to_do_bug",
+            TaskStatus::InProgress(TaskStatusInProgress::None) => "in_progress",
+            TaskStatus::InProgress(TaskStatusInProgress::Doing) => "in_progress_doing",
+            TaskStatus::InProgress(TaskStatusInProgress::Testing) => "in_progress_testing",
+            TaskStatus::Completed => "completed",
+        }
+    }
+}
+
+macro_rules! impl_task_label {
+    ($($name:ident),*) => {
+        $(
+            impl fmt::Display for $name {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    write!(f, "{}", self.as_str())
+                }
+            }
+
+            impl FromStr for $name {
+                type Err = String;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    $name::ALL.into_iter().find(|value| value.as_str() == s).ok_or_else(|| format!("{}: unknown value {}", stringify!($name), s))
+                }
+            }
+        )*
+    };
+}
+
+impl_task_label!(TaskType, TaskPriority, TaskStatus);
 
 #[derive(Debug, Clone)]
 pub struct TaskEntity {
@@ -6,9 +133,9 @@ pub struct TaskEntity {
     pub user_id: String,
     pub project_id: String,
     pub title: String,
-    pub typ: String,
-    pub priority: String,
-    pub status: String,
+    pub typ: TaskType,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: String,
     pub duration: i32,
     pub due_date: i64,
@@ -23,9 +150,9 @@ impl TaskEntity {
         user_id: String,
         project_id: String,
         title: String,
-        typ: String,
-        priority: String,
-        status: String,
+        typ: TaskType,
+        priority: TaskPriority,
+        status: TaskStatus,
         description: String,
         duration: i32,
         due_date: i64,
@@ -57,17 +184,22 @@ pub struct TaskAllEntity {
     pub user_id: String,
     pub project_id: String,
     pub title: String,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: String,
     pub updated_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
 }
 
 impl TaskAllEntity {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: String,
         user_id: String,
         project_id: String,
         title: String,
+        priority: TaskPriority,
+        status: TaskStatus,
         description: String,
         updated_at: NaiveDateTime,
         created_at: NaiveDateTime,
@@ -77,9 +209,36 @@ impl TaskAllEntity {
             user_id,
             project_id,
             title,
+            priority,
+            status,
             description,
             updated_at,
             created_at,
         }
     }
 }
+
+// Outcome of an import. Nothing is created on a dry run, nor when any row is invalid.
+#[derive(Debug, Clone)]
+pub struct TaskImportReport {
+    pub dry_run: bool,
+    pub rows: usize,
+    pub valid_rows: usize,
+    pub created: Vec<TaskEntity>,
+    // Row problems, under "rows[<row>].<field>"
+    pub errors: Vec<FieldError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskChangeKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+// A task as it was right after the change, or right before for a deletion
+#[derive(Debug, Clone)]
+pub struct TaskChange {
+    pub kind: TaskChangeKind,
+    pub task: TaskEntity,
+}
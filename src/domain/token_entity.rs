@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+
+// Expiry of revoked tokens and revocation time of sessions in seconds, revocation time of users in milliseconds
+#[derive(Debug, Clone, Default)]
+pub struct RevocationListEntity {
+    pub tokens: HashMap<String, i64>,
+    pub users: HashMap<String, i64>,
+    pub sessions: HashMap<String, i64>,
+}
+
+impl RevocationListEntity {
+    pub fn new(tokens: HashMap<String, i64>, users: HashMap<String, i64>, sessions: HashMap<String, i64>) -> Self {
+        RevocationListEntity { tokens, users, sessions }
+    }
+}
@@ -14,6 +14,11 @@ pub struct UserEntity {
     pub access_token: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fcm_token: Option<String>,
+    pub email_verified_at: Option<NaiveDateTime>,
+    // Set instead of the tokens when the login still has to pass the second factor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mfa_token: Option<String>,
+    pub mfa_enrollment_required: bool,
     pub last_login: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
@@ -29,6 +34,7 @@ impl UserEntity {
         refresh_token: Option<String>,
         access_token: Option<String>,
         fcm_token: Option<String>,
+        email_verified_at: Option<NaiveDateTime>,
         last_login: NaiveDateTime,
         updated_at: NaiveDateTime,
         created_at: NaiveDateTime,
@@ -42,6 +48,9 @@ impl UserEntity {
             refresh_token,
             access_token,
             fcm_token,
+            email_verified_at,
+            mfa_token: None,
+            mfa_enrollment_required: false,
             last_login,
             updated_at,
             created_at,
@@ -76,14 +85,17 @@ impl UserAllEntity {
 #[derive(Debug, Clone)]
 pub struct UserAccessTokenEntity {
     pub access_token: String,
+    pub refresh_token: String,
 }
 
 impl UserAccessTokenEntity {
     pub fn new(
         access_token: String,
+        refresh_token: String,
     ) -> Self {
         UserAccessTokenEntity {
             access_token,
+            refresh_token,
         }
     }
 }
@@ -1,55 +1,303 @@
-use std::{env, net::TcpListener, sync::Arc};
+use std::{env, net::TcpListener, path::PathBuf, sync::Arc, time::Duration};
 
 use crate::{
     adapters::{
         self,
-        api::shared::app_state::AppState,
-        spi::db::{db_connection::DbConnection, db_tasks_repository::TasksRepository, db_users_repository::UsersRepository},
+        api::{
+            grpc::grpc_layers,
+            shared::{app_state::AppState, blocking::run_blocking},
+        },
+        spi::{
+            db::{
+                db_account_tokens_repository::AccountTokensRepository, db_api_keys_repository::ApiKeysRepository, db_calendar_feeds_repository::CalendarFeedsRepository, db_connection::DbConnection, db_health_repository::HealthRepository, db_import_jobs_repository::ImportJobsRepository, db_metrics_repository::MetricsRepository, db_login_attempts_repository::LoginAttemptsRepository, db_mfa_repository::MfaRepository, db_oidc_repository::OidcRepository, db_permissions_repository::PermissionsRepository, db_projects_repository::ProjectsRepository, db_sessions_repository::SessionsRepository, db_tasks_repository::TasksRepository, db_tokens_repository::TokensRepository,
+                db_users_repository::UsersRepository,
+            },
+            mail::{file_mailer::FileMailer, mailer::Mailer, smtp_mailer::SmtpMailer},
+            oidc::{
+                http_oidc_client::HttpOidcClient,
+                oidc_client::{OidcClient, OidcProviderConfig},
+            },
+            rate_limit::{memory_rate_limit_store::MemoryRateLimitStore, rate_limit_store::RateLimitStore, redis_rate_limit_store::RedisRateLimitStore},
+        },
+    },
+    application::utils::{
+        access_control::{
+            key_ring::KeyRing,
+            middlewares::{
+                cors, logger, metrics as request_metrics,
+                rate_limit::{self, RateLimitConfig},
+                request_id, security_headers,
+            },
+            permission_cache::PermissionCache,
+            revocation_cache::RevocationCache,
+        },
+        metrics::Metrics,
+        task_events::TaskEvents,
     },
-    application::utils::access_control::middlewares::{logger, security_headers},
 };
-use actix_web::dev::Server;
-use actix_web::{web, App, HttpServer};
+use actix_web::dev::{Server, ServerHandle};
+use actix_web::{rt, web, App, HttpServer};
+use tokio::{sync::oneshot, task::JoinHandle};
+use tonic::transport::server::TcpIncoming;
+use settings::{MailerKind, RateLimitStoreKind, Settings};
+use crate::application::repositories::tokens_repository_abstract::TokensRepositoryAbstract;
 
-pub mod env_source;
+pub mod settings;
+pub mod telemetry;
 
-pub fn server(listener: TcpListener, db_name: &str) -> Result<Server, std::io::Error> {
+// Changes a live subscriber may fall behind by before it starts skipping some
+const TASK_EVENTS_CAPACITY: usize = 256;
+// How long a finished import stays readable
+const IMPORT_JOBS_RETENTION: Duration = Duration::from_secs(60 * 60);
+// How often revoked tokens past their expiry are deleted
+const REVOKED_TOKENS_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+pub fn server(listener: TcpListener, grpc_listener: Option<TcpListener>, settings: Settings) -> Result<Server, std::io::Error> {
     env::set_var("RUST_BACKTRACE", "1");
-    env::set_var("RUST_LOG", "actix_web=debug");
 
-    let _try_init = env_logger::try_init();
+    telemetry::init(&settings.log)?;
+
+    KeyRing::init(&settings.auth).map_err(std::io::Error::other)?;
 
-    let db_connection = Arc::new(DbConnection { db_name: db_name.to_string() });
+    let metrics = Arc::new(Metrics::new());
+    let db_connection = Arc::new(DbConnection::new(&settings.database.url, &settings.database.name, settings.database.pool_max_size).with_metrics(metrics.clone()));
+    let token_settings = settings.auth.tokens;
     // let http_connection = HttpConnection {};
 
+    let rate_limit_config = settings.rate_limit.to_config().map_err(std::io::Error::other)?;
+    let rate_limit_store = rate_limit_store(&settings)?;
+    let cors_config = settings.cors.to_config().map_err(std::io::Error::other)?;
+
     let data = web::Data::new(AppState {
-        app_name: String::from("Task Tracker API"),
+        app_name: settings.app.name.clone(),
         // cats_repository: CatFactsRepository {
         //     http_connection,
         //     source: dotenv::var("CATS_SOURCE").expect("CATS_SOURCE must be set"),
         // },
         users_repository: UsersRepository {
             db_connection: db_connection.clone(),
+            token_settings,
         },
         tasks_repository: TasksRepository {
             db_connection: db_connection.clone(),
         },
+        projects_repository: ProjectsRepository {
+            db_connection: db_connection.clone(),
+        },
+        tokens_repository: TokensRepository {
+            db_connection: db_connection.clone(),
+            token_settings,
+        },
+        sessions_repository: SessionsRepository {
+            db_connection: db_connection.clone(),
+        },
+        account_tokens_repository: AccountTokensRepository {
+            db_connection: db_connection.clone(),
+            token_settings,
+        },
+        mfa_repository: MfaRepository {
+            db_connection: db_connection.clone(),
+            token_settings,
+        },
+        api_keys_repository: ApiKeysRepository {
+            db_connection: db_connection.clone(),
+        },
+        permissions_repository: PermissionsRepository {
+            db_connection: db_connection.clone(),
+        },
+        oidc_repository: OidcRepository {
+            db_connection: db_connection.clone(),
+            token_settings,
+        },
+        login_attempts_repository: LoginAttemptsRepository {
+            db_connection: db_connection.clone(),
+            token_settings,
+            throttle_settings: settings.auth.login_throttle,
+        },
+        health_repository: HealthRepository {
+            db_connection: db_connection.clone(),
+        },
+        metrics_repository: MetricsRepository {
+            db_connection: db_connection.clone(),
+        },
+        calendar_feeds_repository: CalendarFeedsRepository {
+            db_connection: db_connection.clone(),
+        },
+        import_jobs_repository: ImportJobsRepository {
+            db_connection: db_connection.clone(),
+            retention: IMPORT_JOBS_RETENTION,
+        },
+        mailer: mailer(&settings)?,
+        oidc_client: oidc_client(&settings)?,
+        revocation_cache: RevocationCache::new(Duration::from_secs(settings.auth.caches.revocation_ttl)),
+        permission_cache: PermissionCache::new(Duration::from_secs(settings.auth.caches.permission_ttl)),
+        metrics: metrics.clone(),
+        task_events: TaskEvents::new(TASK_EVENTS_CAPACITY),
+        trusted_proxies: rate_limit_config.trusted_proxies.clone(),
+        settings,
     });
 
     let port = listener.local_addr().unwrap().port();
+    let db_name = data.settings.database.name.clone();
+
+    let grpc = match grpc_listener {
+        Some(grpc_listener) => Some(grpc_server(grpc_listener, data.clone(), rate_limit_config.clone(), rate_limit_store.clone())?),
+        None => None,
+    };
+    purge_revoked_tokens(data.clone());
 
     let server = HttpServer::new(move || {
         App::new()
             .app_data(data.clone())
-            // .wrap(middlewares::cors(&config.client_origin_url))
+            .wrap(rate_limit::rate_limiter(rate_limit_config.clone(), rate_limit_store.clone()))
+            // outside the rate limiter so preflights are not counted and a 429 stays readable by the frontend
+            .wrap(cors::cors(&cors_config))
+            .wrap(request_metrics::request_metrics(metrics.clone()))
             .wrap(security_headers::security_headers())
-            .wrap(logger::logger())
+            .wrap(logger::logger(rate_limit_config.trusted_proxies.clone()))
+            // outermost, every other layer runs inside the request span
+            .wrap(request_id::request_id())
             .configure(adapters::api::shared::routes::routes)
     })
+    // both servers stop together, see `stop_on_signal`
+    .disable_signals()
     .listen(listener)?
     .run();
+    rt::spawn(stop_on_signal(server.handle(), grpc));
 
-    println!("Server running on port : {}, db_name : {}", port, db_name);
+    tracing::info!(port, db_name, "Server running");
 
     Ok(server)
 }
+
+struct GrpcServer {
+    shutdown: oneshot::Sender<()>,
+    stopped: JoinHandle<()>,
+}
+
+// Runs alongside the HTTP server on the same runtime, from the state the HTTP handlers use and behind the same
+// middlewares, in the same order
+fn grpc_server(
+    listener: TcpListener,
+    data: web::Data<AppState>,
+    rate_limit_config: RateLimitConfig,
+    rate_limit_store: Arc<dyn RateLimitStore + Send + Sync>,
+) -> Result<GrpcServer, std::io::Error> {
+    let port = listener.local_addr()?.port();
+    listener.set_nonblocking(true)?;
+    let incoming = TcpIncoming::from(tokio::net::TcpListener::from_std(listener)?);
+    let (shutdown, shutdown_requested) = oneshot::channel::<()>();
+
+    let server = tonic::transport::Server::builder()
+        // outermost, every other layer runs inside the call span
+        .layer(grpc_layers::RequestIdLayer)
+        .layer(grpc_layers::AccessLogLayer::new(rate_limit_config.trusted_proxies.clone()))
+        .layer(grpc_layers::MetricsLayer::new(data.metrics.clone()))
+        .layer(grpc_layers::RateLimitLayer::new(rate_limit_config, rate_limit_store))
+        .add_routes(adapters::api::grpc::grpc_services::routes(data));
+    let stopped = rt::spawn(async move {
+        if let Err(e) = server.serve_with_incoming_shutdown(incoming, async { shutdown_requested.await.unwrap_or_default() }).await {
+            tracing::error!(error = %e, "gRPC server stopped");
+        }
+    });
+    tracing::info!(port, "gRPC server running");
+
+    Ok(GrpcServer { shutdown, stopped })
+}
+
+// The HTTP server's own signal handling would leave the gRPC calls in flight to be cut when the runtime goes away.
+// Same signals and meaning instead: SIGTERM stops gracefully, both servers stop taking requests, the gRPC calls in
+// flight finish, then the HTTP server waits for its own. SIGINT and SIGQUIT stop right away.
+async fn stop_on_signal(http: ServerHandle, grpc: Option<GrpcServer>) {
+    let graceful = shutdown_signal().await;
+    tracing::info!(graceful, "Shutting down");
+
+    http.pause().await;
+    if let Some(grpc) = grpc {
+        let _ = grpc.shutdown.send(());
+        if graceful {
+            let _ = grpc.stopped.await;
+        }
+    }
+    http.stop(graceful).await;
+}
+
+// Whether the signal asks for a graceful stop
+async fn shutdown_signal() -> bool {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let (Ok(mut terminate), Ok(mut interrupt), Ok(mut quit)) = (signal(SignalKind::terminate()), signal(SignalKind::interrupt()), signal(SignalKind::quit())) else {
+            tracing::error!("Cannot listen to shutdown signals");
+            return std::future::pending().await;
+        };
+        tokio::select! {
+            _ = terminate.recv() => true,
+            _ = interrupt.recv() => false,
+            _ = quit.recv() => false,
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        false
+    }
+}
+
+// Revoked tokens fail validation once expired anyway, the revocation list only needs the live ones
+fn purge_revoked_tokens(data: web::Data<AppState>) {
+    rt::spawn(async move {
+        let mut interval = rt::time::interval(REVOKED_TOKENS_PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            // the diesel call blocks, up to the pool timeout when the database is down, so it stays off the workers
+            let data = data.clone();
+            let purge = run_blocking(move || async move { data.tokens_repository.purge_expired_tokens().await.map_err(|e| e.to_string()) });
+            if let Err(e) = purge.await.map_err(|e| e.to_string()).and_then(|result| result) {
+                tracing::error!(error = %e, "Failed to purge expired revoked tokens");
+            }
+        }
+    });
+}
+
+fn mailer(settings: &Settings) -> Result<Box<dyn Mailer + Send + Sync>, std::io::Error> {
+    let mail = &settings.mail;
+    match mail.mailer {
+        MailerKind::Smtp => {
+            let mailer = SmtpMailer::new(&mail.smtp_host.clone().unwrap_or_default(), mail.smtp_port, mail.smtp_username.clone(), mail.smtp_password.clone(), &mail.from)
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            Ok(Box::new(mailer))
+        }
+        MailerKind::File => Ok(Box::new(FileMailer::new(mail.dir.clone().map(PathBuf::from)))),
+    }
+}
+
+fn rate_limit_store(settings: &Settings) -> Result<Arc<dyn RateLimitStore + Send + Sync>, std::io::Error> {
+    match settings.rate_limit.store {
+        RateLimitStoreKind::Redis => {
+            let store = RedisRateLimitStore::new(&settings.rate_limit.redis_url.clone().unwrap_or_default()).map_err(|e| std::io::Error::other(e.to_string()))?;
+            Ok(Arc::new(store))
+        }
+        RateLimitStoreKind::Memory => Ok(Arc::new(MemoryRateLimitStore::new())),
+    }
+}
+
+fn oidc_client(settings: &Settings) -> Result<Box<dyn OidcClient + Send + Sync>, std::io::Error> {
+    let providers = settings
+        .oidc
+        .providers
+        .iter()
+        .map(|provider| OidcProviderConfig {
+            name: provider.name.clone(),
+            issuer: provider.issuer.clone(),
+            client_id: provider.client_id.clone(),
+            client_secret: provider.client_secret.clone(),
+            scopes: provider.scopes.clone(),
+            redirect_uri: provider.redirect_uri.clone().unwrap_or_else(|| format!("{}/api/v1/oidc/{}/callback", settings.app.base_url, provider.name)),
+            trust_email: provider.trust_email,
+        })
+        .collect();
+
+    Ok(Box::new(HttpOidcClient::new(providers).map_err(std::io::Error::other)?))
+}
@@ -0,0 +1,980 @@
+use std::{env, fmt, fs, path::Path, str::FromStr};
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use toml::Value;
+use tracing_subscriber::EnvFilter;
+
+use actix_web::http::{header::HeaderName, Method};
+
+use crate::application::utils::access_control::middlewares::{
+    cors::{CorsConfig, OriginPattern},
+    rate_limit::{RateLimitConfig, RouteRateLimit, TrustedProxies},
+};
+
+// Read when SETTINGS_FILE is not set, the file is optional
+const DEFAULT_SETTINGS_FILE: &str = "config/settings.toml";
+// Shared secrets shorter than this are refused outside development
+const MIN_PROD_JWT_SECRET_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    #[default]
+    Dev,
+    Test,
+    Prod,
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Profile::Dev => write!(f, "dev"),
+            Profile::Test => write!(f, "test"),
+            Profile::Prod => write!(f, "prod"),
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "dev" | "development" => Ok(Profile::Dev),
+            "test" => Ok(Profile::Test),
+            "prod" | "production" => Ok(Profile::Prod),
+            _ => Err(format!("unknown profile \"{}\", expected dev, test or prod", input)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub profile: Profile,
+    pub app: AppSettings,
+    pub server: ServerSettings,
+    pub grpc: GrpcSettings,
+    pub database: DatabaseSettings,
+    pub auth: AuthSettings,
+    pub log: LogSettings,
+    pub mail: MailSettings,
+    pub rate_limit: RateLimitSettings,
+    pub cors: CorsSettings,
+    pub metrics: MetricsSettings,
+    pub oidc: OidcSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    pub name: String,
+    // Public address used in the links sent by mail
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSettings {
+    pub host: String,
+    pub port: u16,
+}
+
+// gRPC is served on its own port, on the host of the HTTP server, only when the port is set
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GrpcSettings {
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSettings {
+    // Server address without the database name
+    pub url: String,
+    pub name: String,
+    pub pool_max_size: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSettings {
+    pub jwt_secret: Option<String>,
+    // Directory of `<kid>.pem` private keys, tokens fall back to HS256 with the secret when unset
+    pub jwt_keys_dir: Option<String>,
+    pub jwt_signing_kid: Option<String>,
+    pub jwt_issuer: String,
+    pub jwt_audience: String,
+    pub tokens: TokenSettings,
+    pub login_throttle: LoginThrottleSettings,
+    pub caches: AuthCacheSettings,
+}
+
+// Lifetimes in seconds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TokenSettings {
+    pub access_token_ttl: u64,
+    pub refresh_token_ttl: u64,
+    pub email_verification_ttl: u64,
+    pub password_reset_ttl: u64,
+    pub account_unlock_ttl: u64,
+}
+
+impl Default for TokenSettings {
+    fn default() -> Self {
+        TokenSettings {
+            access_token_ttl: 3600,
+            refresh_token_ttl: 3600 * 24,
+            email_verification_ttl: 3600 * 24,
+            password_reset_ttl: 3600,
+            account_unlock_ttl: 3600 * 24,
+        }
+    }
+}
+
+// Seconds an in-process copy is used before being read again, also how long a change made by another replica may go unseen
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct AuthCacheSettings {
+    pub revocation_ttl: u64,
+    pub permission_ttl: u64,
+}
+
+impl Default for AuthCacheSettings {
+    fn default() -> Self {
+        AuthCacheSettings {
+            revocation_ttl: 30,
+            permission_ttl: 30,
+        }
+    }
+}
+
+// Failed sign-ins per account and per IP, durations in seconds
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct LoginThrottleSettings {
+    // failures are forgotten after this long without a new one
+    pub failure_window: u64,
+    pub lock_duration: u64,
+    // cap of the wait that doubles with each failure
+    pub max_delay: u64,
+}
+
+impl Default for LoginThrottleSettings {
+    fn default() -> Self {
+        LoginThrottleSettings {
+            failure_window: 3600,
+            lock_duration: 900,
+            max_delay: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    // one JSON object per line, for the log collector
+    Json,
+    // multi-line and colored, for a terminal
+    Pretty,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            _ => Err(format!("unknown log format \"{}\", expected json or pretty", input)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogSettings {
+    // tracing filter directives, RUST_LOG still wins when set
+    pub level: String,
+    pub format: LogFormat,
+    // OTLP/HTTP collector base address, e.g. http://localhost:4318, spans are only exported when set
+    pub otlp_endpoint: Option<String>,
+    // service.name of the exported spans
+    pub service_name: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MailerKind {
+    // logs every mail and writes it to `dir` when set
+    File,
+    Smtp,
+}
+
+impl FromStr for MailerKind {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "smtp" => Ok(MailerKind::Smtp),
+            "file" => Ok(MailerKind::File),
+            _ => Err(format!("unknown mailer \"{}\", expected smtp or file", input)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailSettings {
+    pub mailer: MailerKind,
+    pub from: String,
+    pub dir: Option<String>,
+    pub smtp_host: Option<String>,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitStoreKind {
+    Memory,
+    Redis,
+}
+
+impl FromStr for RateLimitStoreKind {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "memory" => Ok(RateLimitStoreKind::Memory),
+            "redis" => Ok(RateLimitStoreKind::Redis),
+            _ => Err(format!("unknown rate limit store \"{}\", expected memory or redis", input)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateLimitSettings {
+    // <requests>/<seconds> for every route without its own entry
+    pub default: String,
+    // "[METHOD] <path prefix>=<requests>/<seconds>", the first match wins
+    pub routes: Vec<String>,
+    // X-Forwarded-For is only believed from these, ignored when empty
+    pub trusted_proxies: Vec<String>,
+    pub store: RateLimitStoreKind,
+    pub redis_url: Option<String>,
+}
+
+impl RateLimitSettings {
+    pub fn to_config(&self) -> Result<RateLimitConfig, String> {
+        let routes = self.routes.iter().map(|route| RouteRateLimit::from_str(route)).collect::<Result<Vec<RouteRateLimit>, String>>()?;
+        let trusted_proxies = self
+            .trusted_proxies
+            .iter()
+            .map(|ip| ip.trim().parse().map_err(|_| format!("invalid trusted proxy address \"{}\"", ip.trim())))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(RateLimitConfig {
+            default_rule: self.default.parse()?,
+            routes,
+            trusted_proxies: TrustedProxies { addresses: trusted_proxies },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorsSettings {
+    // "https://app.example.com", "https://*.example.com" or "*", cross-origin requests are refused when empty
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub exposed_headers: Vec<String>,
+    // cookies and Authorization headers, cannot be combined with the "*" origin
+    pub allow_credentials: bool,
+    // seconds a browser may cache a preflight answer
+    pub max_age: usize,
+}
+
+impl CorsSettings {
+    pub fn to_config(&self) -> Result<CorsConfig, String> {
+        let allowed_origins = self
+            .allowed_origins
+            .iter()
+            .map(|origin| OriginPattern::from_str(origin))
+            .collect::<Result<Vec<OriginPattern>, String>>()?;
+        if self.allow_credentials && allowed_origins.iter().any(OriginPattern::is_any) {
+            return Err(String::from("credentials cannot be allowed for the \"*\" origin, list the origins instead"));
+        }
+        let allowed_methods = self
+            .allowed_methods
+            .iter()
+            .map(|method| Method::from_str(&method.trim().to_uppercase()).map_err(|_| format!("invalid method \"{}\"", method.trim())))
+            .collect::<Result<Vec<Method>, String>>()?;
+
+        Ok(CorsConfig {
+            allowed_origins,
+            allowed_methods,
+            allowed_headers: header_names(&self.allowed_headers)?,
+            exposed_headers: header_names(&self.exposed_headers)?,
+            allow_credentials: self.allow_credentials,
+            max_age: self.max_age,
+        })
+    }
+}
+
+fn header_names(headers: &[String]) -> Result<Vec<HeaderName>, String> {
+    headers
+        .iter()
+        .map(|header| HeaderName::from_str(header.trim()).map_err(|_| format!("invalid header \"{}\"", header.trim())))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MetricsSettings {
+    // bearer token Prometheus must send to scrape /metrics, required in prod, the endpoint is open when unset elsewhere
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OidcSettings {
+    pub providers: Vec<OidcProviderSettings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcProviderSettings {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: String,
+    // defaults to <base_url>/api/v1/oidc/<name>/callback
+    pub redirect_uri: Option<String>,
+    // lets a first sign-in attach to the password account owning the same verified email
+    #[serde(default)]
+    pub trust_email: bool,
+}
+
+fn default_oidc_scopes() -> String {
+    String::from("openid email profile")
+}
+
+// Every problem found while loading, reported together so a bad deployment is fixed in one go
+#[derive(Debug)]
+pub struct SettingsError {
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid settings:")?;
+        for error in &self.errors {
+            write!(f, "\n  - {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+impl From<SettingsError> for std::io::Error {
+    fn from(error: SettingsError) -> Self {
+        std::io::Error::other(error.to_string())
+    }
+}
+
+impl Settings {
+    pub fn defaults(profile: Profile) -> Self {
+        Settings {
+            profile,
+            app: AppSettings {
+                name: String::from("Task Tracker API"),
+                base_url: String::from("http://localhost:8080"),
+            },
+            server: ServerSettings {
+                host: String::from("0.0.0.0"),
+                port: 8080,
+            },
+            grpc: GrpcSettings::default(),
+            database: DatabaseSettings {
+                url: String::new(),
+                name: String::new(),
+                pool_max_size: 10,
+            },
+            auth: AuthSettings {
+                jwt_secret: None,
+                jwt_keys_dir: None,
+                jwt_signing_kid: None,
+                jwt_issuer: String::from("tasktracker_backend"),
+                jwt_audience: String::from("tasktracker_backend"),
+                tokens: TokenSettings::default(),
+                login_throttle: LoginThrottleSettings::default(),
+                caches: AuthCacheSettings::default(),
+            },
+            log: LogSettings {
+                level: match profile {
+                    Profile::Prod => String::from("info"),
+                    _ => String::from("info,tasktracker_backend=debug"),
+                },
+                format: match profile {
+                    Profile::Prod => LogFormat::Json,
+                    _ => LogFormat::Pretty,
+                },
+                otlp_endpoint: None,
+                service_name: String::from("tasktracker_backend"),
+            },
+            mail: MailSettings {
+                mailer: MailerKind::File,
+                from: String::from("Task Tracker <no-reply@localhost>"),
+                dir: None,
+                smtp_host: None,
+                smtp_port: 587,
+                smtp_username: None,
+                smtp_password: None,
+            },
+            rate_limit: RateLimitSettings {
+                default: String::from("100/60"),
+                routes: Vec::new(),
+                trusted_proxies: Vec::new(),
+                store: RateLimitStoreKind::Memory,
+                redis_url: None,
+            },
+            cors: CorsSettings {
+                allowed_origins: match profile {
+                    Profile::Dev => vec![String::from("http://localhost:*"), String::from("http://127.0.0.1:*")],
+                    _ => Vec::new(),
+                },
+                allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE"].map(String::from).to_vec(),
+                allowed_headers: ["Authorization", "Content-Type", "Accept"].map(String::from).to_vec(),
+                exposed_headers: ["RateLimit-Limit", "RateLimit-Remaining", "RateLimit-Reset", "RateLimit-Policy", "Retry-After"]
+                    .map(String::from)
+                    .to_vec(),
+                allow_credentials: false,
+                max_age: 86_400,
+            },
+            metrics: MetricsSettings::default(),
+            oidc: OidcSettings::default(),
+        }
+    }
+
+    // Profile defaults, then the settings file with its [profiles.<profile>] table, then the environment
+    pub fn load() -> Result<Self, SettingsError> {
+        Settings::load_with(|_| {})
+    }
+
+    // Same as `load`, `overrides` pins values before validation, e.g. the database of an integration test
+    pub fn load_with(overrides: impl FnOnce(&mut Settings)) -> Result<Self, SettingsError> {
+        let mut errors = Vec::new();
+        let profile = match env_value("APP_PROFILE") {
+            Some(profile) => Profile::from_str(&profile).unwrap_or_else(|e| {
+                errors.push(format!("APP_PROFILE: {}", e));
+                Profile::default()
+            }),
+            None => Profile::default(),
+        };
+
+        let mut settings = match Settings::from_file(profile) {
+            Ok(settings) => settings,
+            Err(e) => {
+                errors.push(e);
+                Settings::defaults(profile)
+            }
+        };
+        settings.apply_env(&mut errors);
+        overrides(&mut settings);
+        errors.extend(settings.validate());
+
+        match errors.is_empty() {
+            true => Ok(settings),
+            false => Err(SettingsError { errors }),
+        }
+    }
+
+    fn from_file(profile: Profile) -> Result<Self, String> {
+        let path = env_value("SETTINGS_FILE");
+        let path = match &path {
+            Some(path) => Path::new(path),
+            None if Path::new(DEFAULT_SETTINGS_FILE).exists() => Path::new(DEFAULT_SETTINGS_FILE),
+            None => return Ok(Settings::defaults(profile)),
+        };
+
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Settings::from_toml(&content, profile).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    fn from_toml(content: &str, profile: Profile) -> Result<Self, String> {
+        let mut file = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let profiles = file.remove("profiles");
+
+        let mut merged = Value::try_from(Settings::defaults(profile)).map_err(|e| e.to_string())?;
+        merge(&mut merged, Value::Table(file));
+        if let Some(overrides) = profiles.as_ref().and_then(|profiles| profiles.get(profile.to_string())) {
+            merge(&mut merged, overrides.clone());
+        }
+        if let Value::Table(table) = &mut merged {
+            table.insert(String::from("profile"), Value::String(profile.to_string()));
+        }
+
+        merged.try_into().map_err(|e: toml::de::Error| e.message().to_string())
+    }
+
+    // Variable names predate the settings file and are kept so existing deployments keep working
+    fn apply_env(&mut self, errors: &mut Vec<String>) {
+        override_value(&mut self.app.name, "APP_NAME", errors);
+        override_value(&mut self.app.base_url, "APP_BASE_URL", errors);
+        override_value(&mut self.server.host, "SERVER_HOST", errors);
+        override_value(&mut self.server.port, "SERVER_PORT", errors);
+        if let Some(port) = env_value("GRPC_PORT") {
+            match port.trim().parse() {
+                Ok(port) => self.grpc.port = Some(port),
+                Err(e) => errors.push(format!("GRPC_PORT: {}", e)),
+            }
+        }
+        override_value(&mut self.database.url, "DATABASE_URL", errors);
+        override_value(&mut self.database.name, "DATABASE_NAME", errors);
+        override_value(&mut self.database.pool_max_size, "DATABASE_POOL_MAX_SIZE", errors);
+
+        override_option(&mut self.auth.jwt_secret, "JWT_SECRET");
+        override_option(&mut self.auth.jwt_keys_dir, "JWT_KEYS_DIR");
+        override_option(&mut self.auth.jwt_signing_kid, "JWT_SIGNING_KID");
+        override_value(&mut self.auth.jwt_issuer, "JWT_ISSUER", errors);
+        override_value(&mut self.auth.jwt_audience, "JWT_AUDIENCE", errors);
+        override_value(&mut self.auth.tokens.access_token_ttl, "ACCESS_TOKEN_TTL", errors);
+        override_value(&mut self.auth.tokens.refresh_token_ttl, "REFRESH_TOKEN_TTL", errors);
+        override_value(&mut self.auth.tokens.email_verification_ttl, "EMAIL_VERIFICATION_TTL", errors);
+        override_value(&mut self.auth.tokens.password_reset_ttl, "PASSWORD_RESET_TTL", errors);
+        override_value(&mut self.auth.tokens.account_unlock_ttl, "ACCOUNT_UNLOCK_TTL", errors);
+        override_value(&mut self.auth.login_throttle.failure_window, "LOGIN_FAILURE_WINDOW", errors);
+        override_value(&mut self.auth.login_throttle.lock_duration, "LOGIN_LOCK_DURATION", errors);
+        override_value(&mut self.auth.login_throttle.max_delay, "LOGIN_MAX_DELAY", errors);
+        override_value(&mut self.auth.caches.revocation_ttl, "REVOCATION_CACHE_TTL", errors);
+        override_value(&mut self.auth.caches.permission_ttl, "PERMISSION_CACHE_TTL", errors);
+
+        override_value(&mut self.log.level, "RUST_LOG", errors);
+        override_value(&mut self.log.format, "LOG_FORMAT", errors);
+        // standard OpenTelemetry variables
+        override_option(&mut self.log.otlp_endpoint, "OTEL_EXPORTER_OTLP_ENDPOINT");
+        override_value(&mut self.log.service_name, "OTEL_SERVICE_NAME", errors);
+
+        override_value(&mut self.mail.mailer, "MAILER", errors);
+        override_value(&mut self.mail.from, "MAIL_FROM", errors);
+        override_option(&mut self.mail.dir, "MAIL_DIR");
+        override_option(&mut self.mail.smtp_host, "SMTP_HOST");
+        override_value(&mut self.mail.smtp_port, "SMTP_PORT", errors);
+        override_option(&mut self.mail.smtp_username, "SMTP_USERNAME");
+        override_option(&mut self.mail.smtp_password, "SMTP_PASSWORD");
+
+        override_value(&mut self.rate_limit.default, "RATE_LIMIT_DEFAULT", errors);
+        override_list(&mut self.rate_limit.routes, "RATE_LIMIT_ROUTES");
+        override_list(&mut self.rate_limit.trusted_proxies, "RATE_LIMIT_TRUSTED_PROXIES");
+        override_value(&mut self.rate_limit.store, "RATE_LIMIT_STORE", errors);
+        override_option(&mut self.rate_limit.redis_url, "REDIS_URL");
+
+        override_list(&mut self.cors.allowed_origins, "CORS_ALLOWED_ORIGINS");
+        override_list(&mut self.cors.allowed_methods, "CORS_ALLOWED_METHODS");
+        override_list(&mut self.cors.allowed_headers, "CORS_ALLOWED_HEADERS");
+        override_list(&mut self.cors.exposed_headers, "CORS_EXPOSED_HEADERS");
+        override_value(&mut self.cors.allow_credentials, "CORS_ALLOW_CREDENTIALS", errors);
+        override_value(&mut self.cors.max_age, "CORS_MAX_AGE", errors);
+
+        override_option(&mut self.metrics.token, "METRICS_TOKEN");
+
+        // OIDC_PROVIDERS replaces the providers of the file, each one configured through its own OIDC_<NAME>_* variables
+        if let Some(names) = env_value("OIDC_PROVIDERS") {
+            self.oidc.providers = names
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .map(|name| {
+                    let prefix = format!("OIDC_{}", name.to_uppercase().replace('-', "_"));
+                    let value = |key: &str| env_value(&format!("{}_{}", prefix, key));
+                    let mut provider = OidcProviderSettings {
+                        name: name.to_string(),
+                        issuer: value("ISSUER").unwrap_or_default(),
+                        client_id: value("CLIENT_ID").unwrap_or_default(),
+                        client_secret: value("CLIENT_SECRET"),
+                        scopes: value("SCOPES").unwrap_or_else(default_oidc_scopes),
+                        redirect_uri: value("REDIRECT_URI"),
+                        trust_email: false,
+                    };
+                    override_value(&mut provider.trust_email, &format!("{}_TRUST_EMAIL", prefix), errors);
+                    provider
+                })
+                .collect();
+        }
+    }
+
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        if Url::parse(&self.app.base_url).is_err() {
+            errors.push(format!("app.base_url (APP_BASE_URL) \"{}\" is not a valid URL", self.app.base_url));
+        }
+        if self.grpc.port == Some(self.server.port) {
+            errors.push(String::from("grpc.port (GRPC_PORT) must differ from server.port (SERVER_PORT)"));
+        }
+        if self.database.url.is_empty() {
+            errors.push(String::from("database.url (DATABASE_URL) must be set"));
+        }
+        if self.database.name.is_empty() {
+            errors.push(String::from("database.name (DATABASE_NAME) must be set"));
+        }
+        if self.database.pool_max_size == 0 {
+            errors.push(String::from("database.pool_max_size (DATABASE_POOL_MAX_SIZE) must be at least 1"));
+        }
+
+        match (&self.auth.jwt_keys_dir, &self.auth.jwt_secret) {
+            (None, None) => errors.push(String::from("auth.jwt_secret (JWT_SECRET) or auth.jwt_keys_dir (JWT_KEYS_DIR) must be set")),
+            (None, Some(secret)) if self.profile == Profile::Prod && secret.len() < MIN_PROD_JWT_SECRET_LENGTH => errors.push(format!(
+                "auth.jwt_secret (JWT_SECRET) must be at least {} characters in prod, or use auth.jwt_keys_dir (JWT_KEYS_DIR)",
+                MIN_PROD_JWT_SECRET_LENGTH
+            )),
+            _ => {}
+        }
+        let tokens = &self.auth.tokens;
+        for (name, ttl) in [
+            ("access_token_ttl", tokens.access_token_ttl),
+            ("refresh_token_ttl", tokens.refresh_token_ttl),
+            ("email_verification_ttl", tokens.email_verification_ttl),
+            ("password_reset_ttl", tokens.password_reset_ttl),
+            ("account_unlock_ttl", tokens.account_unlock_ttl),
+        ] {
+            if ttl == 0 {
+                errors.push(format!("auth.tokens.{} must be greater than 0", name));
+            }
+        }
+        if tokens.refresh_token_ttl < tokens.access_token_ttl {
+            errors.push(String::from("auth.tokens.refresh_token_ttl must not be shorter than auth.tokens.access_token_ttl"));
+        }
+        let login_throttle = &self.auth.login_throttle;
+        for (name, duration) in [("failure_window", login_throttle.failure_window), ("lock_duration", login_throttle.lock_duration)] {
+            if duration == 0 {
+                errors.push(format!("auth.login_throttle.{} must be greater than 0", name));
+            }
+        }
+
+        if EnvFilter::try_new(&self.log.level).is_err() {
+            errors.push(format!("log.level (RUST_LOG) \"{}\" is not a valid filter", self.log.level));
+        }
+        if let Some(endpoint) = &self.log.otlp_endpoint {
+            if Url::parse(endpoint).is_err() {
+                errors.push(format!("log.otlp_endpoint (OTEL_EXPORTER_OTLP_ENDPOINT) \"{}\" is not a valid URL", endpoint));
+            }
+        }
+
+        if self.mail.mailer == MailerKind::Smtp && self.mail.smtp_host.is_none() {
+            errors.push(String::from("mail.smtp_host (SMTP_HOST) must be set when the mailer is smtp"));
+        }
+
+        if let Err(e) = self.rate_limit.to_config() {
+            errors.push(format!("rate_limit: {}", e));
+        }
+        if self.rate_limit.store == RateLimitStoreKind::Redis && self.rate_limit.redis_url.is_none() {
+            errors.push(String::from("rate_limit.redis_url (REDIS_URL) must be set when the rate limit store is redis"));
+        }
+
+        if let Err(e) = self.cors.to_config() {
+            errors.push(format!("cors: {}", e));
+        }
+
+        if self.profile == Profile::Prod && self.metrics.token.is_none() {
+            errors.push(String::from("metrics.token (METRICS_TOKEN) must be set in prod, /metrics would be open to anyone"));
+        }
+
+        for provider in &self.oidc.providers {
+            if provider.issuer.is_empty() || provider.client_id.is_empty() {
+                errors.push(format!("oidc provider \"{}\" needs an issuer and a client_id", provider.name));
+            }
+        }
+
+        errors
+    }
+
+    pub fn bind_address(&self) -> String {
+        format!("{}:{}", self.server.host, self.server.port)
+    }
+
+    pub fn grpc_bind_address(&self) -> Option<String> {
+        self.grpc.port.map(|port| format!("{}:{}", self.server.host, port))
+    }
+}
+
+// Empty variables count as unset, as in the blank entries of .env.example
+fn env_value(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.trim().is_empty())
+}
+
+fn override_value<T: FromStr>(target: &mut T, key: &str, errors: &mut Vec<String>)
+where
+    T::Err: fmt::Display,
+{
+    if let Some(value) = env_value(key) {
+        match value.trim().parse() {
+            Ok(value) => *target = value,
+            Err(e) => errors.push(format!("{}: {}", key, e)),
+        }
+    }
+}
+
+fn override_option(target: &mut Option<String>, key: &str) {
+    if let Some(value) = env_value(key) {
+        *target = Some(value);
+    }
+}
+
+fn override_list(target: &mut Vec<String>, key: &str) {
+    if let Some(value) = env_value(key) {
+        *target = value.split(',').map(str::trim).filter(|item| !item.is_empty()).map(String::from).collect();
+    }
+}
+
+// Tables are merged key by key, anything else in `overrides` replaces the value
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Table(base), Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    // the environment is shared by the whole test binary, tests changing it take turns
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_env<T>(vars: &[(&str, &str)], f: impl FnOnce() -> T) -> T {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        for (key, value) in vars {
+            env::set_var(key, value);
+        }
+        let result = f();
+        for (key, _) in vars {
+            env::remove_var(key);
+        }
+        result
+    }
+
+    fn valid_settings(profile: Profile) -> Settings {
+        let mut settings = Settings::defaults(profile);
+        settings.database.url = String::from("postgres://postgres@127.0.0.1:5432");
+        settings.database.name = String::from("tasktracker");
+        settings.auth.jwt_secret = Some("s".repeat(MIN_PROD_JWT_SECRET_LENGTH));
+        settings.metrics.token = Some(String::from("scrape-secret"));
+        settings
+    }
+
+    #[test]
+    fn test_should_merge_file_then_profile_table_over_defaults() {
+        // given a file changing a few keys, and more for prod
+        let content = r#"
+            [server]
+            port = 9090
+
+            [auth.tokens]
+            access_token_ttl = 600
+
+            [rate_limit]
+            routes = ["POST /api/v1/users/login=5/60"]
+
+            [profiles.prod]
+            server = { port = 9443 }
+            rate_limit = { store = "redis" }
+        "#;
+
+        // when loading it for dev and for prod
+        let dev = Settings::from_toml(content, Profile::Dev).unwrap();
+        let prod = Settings::from_toml(content, Profile::Prod).unwrap();
+
+        // then expect the keys left out to keep their defaults and the profile table to win
+        assert_eq!(dev.profile, Profile::Dev);
+        assert_eq!(dev.server.port, 9090);
+        assert_eq!(dev.server.host, "0.0.0.0");
+        assert_eq!(dev.auth.tokens.access_token_ttl, 600);
+        assert_eq!(dev.auth.tokens.refresh_token_ttl, TokenSettings::default().refresh_token_ttl);
+        assert_eq!(dev.rate_limit.store, RateLimitStoreKind::Memory);
+        assert_eq!(prod.profile, Profile::Prod);
+        assert_eq!(prod.server.port, 9443);
+        assert_eq!(prod.auth.tokens.access_token_ttl, 600);
+        assert_eq!(prod.rate_limit.routes, vec![String::from("POST /api/v1/users/login=5/60")]);
+        assert_eq!(prod.rate_limit.store, RateLimitStoreKind::Redis);
+        assert_eq!(prod.log.format, LogFormat::Json);
+    }
+
+    #[test]
+    fn test_should_refuse_file_with_wrong_types() {
+        // given a file with a port written as text
+        let content = "[server]\nport = \"eighty\"";
+
+        // when loading it
+        let result = Settings::from_toml(content, Profile::Dev);
+
+        // then expect an error
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_should_override_settings_from_env() {
+        // given settings and variables for a few of them, one blank
+        let mut settings = valid_settings(Profile::Test);
+        let mut errors = Vec::new();
+
+        // when applying the environment
+        with_env(
+            &[("SERVER_PORT", "9191"), ("ACCESS_TOKEN_TTL", "120"), ("GRPC_PORT", "50051"), ("REVOCATION_CACHE_TTL", "5"), ("APP_NAME", "  ")],
+            || settings.apply_env(&mut errors),
+        );
+
+        // then expect the set variables to win and the blank one to be ignored
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(settings.server.port, 9191);
+        assert_eq!(settings.auth.tokens.access_token_ttl, 120);
+        assert_eq!(settings.grpc.port, Some(50051));
+        assert_eq!(settings.auth.caches.revocation_ttl, 5);
+        assert_eq!(settings.app.name, Settings::defaults(Profile::Test).app.name);
+    }
+
+    #[test]
+    fn test_should_report_every_unparsable_variable() {
+        // given two variables that are not numbers
+        let mut settings = valid_settings(Profile::Test);
+        let mut errors = Vec::new();
+
+        // when applying the environment
+        with_env(&[("SERVER_PORT", "http"), ("LOGIN_MAX_DELAY", "-1")], || settings.apply_env(&mut errors));
+
+        // then expect both named
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert!(errors[0].starts_with("SERVER_PORT: "));
+        assert!(errors[1].starts_with("LOGIN_MAX_DELAY: "));
+    }
+
+    #[test]
+    fn test_should_split_and_trim_lists() {
+        // given a comma separated variable with spaces and an empty entry
+        let mut allowed_origins = vec![String::from("https://app.example.com")];
+
+        // when overriding the list
+        with_env(&[("TEST_OVERRIDE_LIST", " https://a.example.com , ,https://b.example.com ")], || {
+            override_list(&mut allowed_origins, "TEST_OVERRIDE_LIST")
+        });
+
+        // then expect the list replaced by the trimmed entries
+        assert_eq!(allowed_origins, vec![String::from("https://a.example.com"), String::from("https://b.example.com")]);
+    }
+
+    #[test]
+    fn test_should_read_oidc_providers_from_env() {
+        // given two providers, one configured fully and one with a dash in its name
+        let mut settings = valid_settings(Profile::Test);
+        let mut errors = Vec::new();
+
+        // when applying the environment
+        with_env(
+            &[
+                ("OIDC_PROVIDERS", "corp, partner-sso"),
+                ("OIDC_CORP_ISSUER", "https://idp.example.com"),
+                ("OIDC_CORP_CLIENT_ID", "tasktracker"),
+                ("OIDC_CORP_CLIENT_SECRET", "secret"),
+                ("OIDC_CORP_TRUST_EMAIL", "true"),
+                ("OIDC_PARTNER_SSO_ISSUER", "https://sso.partner.test"),
+                ("OIDC_PARTNER_SSO_CLIENT_ID", "partner"),
+                ("OIDC_PARTNER_SSO_SCOPES", "openid email"),
+            ],
+            || settings.apply_env(&mut errors),
+        );
+
+        // then expect each provider read from its own variables, with the defaults filling the gaps
+        assert!(errors.is_empty(), "{:?}", errors);
+        let providers = &settings.oidc.providers;
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].name, "corp");
+        assert_eq!(providers[0].issuer, "https://idp.example.com");
+        assert_eq!(providers[0].client_secret, Some(String::from("secret")));
+        assert_eq!(providers[0].scopes, default_oidc_scopes());
+        assert!(providers[0].trust_email);
+        assert_eq!(providers[1].name, "partner-sso");
+        assert_eq!(providers[1].client_id, "partner");
+        assert_eq!(providers[1].client_secret, None);
+        assert_eq!(providers[1].scopes, "openid email");
+        assert!(!providers[1].trust_email);
+    }
+
+    #[test]
+    fn test_should_accept_valid_settings() {
+        // given complete settings for every profile
+        for profile in [Profile::Dev, Profile::Test, Profile::Prod] {
+            // when validating them
+            let errors = valid_settings(profile).validate();
+
+            // then expect no error
+            assert!(errors.is_empty(), "{}: {:?}", profile, errors);
+        }
+    }
+
+    #[test]
+    fn test_should_report_each_invalid_setting() {
+        // given settings broken one way at a time, with the start of the error each should give
+        let cases: Vec<(&str, fn(&mut Settings))> = vec![
+            ("app.base_url", |s| s.app.base_url = String::from("not a url")),
+            ("grpc.port", |s| s.grpc.port = Some(s.server.port)),
+            ("database.url", |s| s.database.url = String::new()),
+            ("database.name", |s| s.database.name = String::new()),
+            ("database.pool_max_size", |s| s.database.pool_max_size = 0),
+            ("auth.jwt_secret (JWT_SECRET) or", |s| s.auth.jwt_secret = None),
+            ("auth.jwt_secret (JWT_SECRET) must be at least", |s| {
+                s.profile = Profile::Prod;
+                s.auth.jwt_secret = Some(String::from("short"));
+            }),
+            ("auth.tokens.access_token_ttl", |s| s.auth.tokens.access_token_ttl = 0),
+            ("auth.tokens.refresh_token_ttl must be", |s| s.auth.tokens.refresh_token_ttl = 0),
+            ("auth.tokens.email_verification_ttl", |s| s.auth.tokens.email_verification_ttl = 0),
+            ("auth.tokens.password_reset_ttl", |s| s.auth.tokens.password_reset_ttl = 0),
+            ("auth.tokens.account_unlock_ttl", |s| s.auth.tokens.account_unlock_ttl = 0),
+            ("auth.tokens.refresh_token_ttl must not", |s| s.auth.tokens.refresh_token_ttl = s.auth.tokens.access_token_ttl - 1),
+            ("auth.login_throttle.failure_window", |s| s.auth.login_throttle.failure_window = 0),
+            ("auth.login_throttle.lock_duration", |s| s.auth.login_throttle.lock_duration = 0),
+            ("log.level", |s| s.log.level = String::from("tasktracker=loud")),
+            ("log.otlp_endpoint", |s| s.log.otlp_endpoint = Some(String::from("collector"))),
+            ("mail.smtp_host", |s| s.mail.mailer = MailerKind::Smtp),
+            ("rate_limit: ", |s| s.rate_limit.default = String::from("fast")),
+            ("rate_limit: ", |s| s.rate_limit.routes = vec![String::from("/api/v1/tasks")]),
+            ("rate_limit: ", |s| s.rate_limit.trusted_proxies = vec![String::from("proxy.internal")]),
+            ("rate_limit.redis_url", |s| s.rate_limit.store = RateLimitStoreKind::Redis),
+            ("cors: ", |s| {
+                s.cors.allowed_origins = vec![String::from("*")];
+                s.cors.allow_credentials = true;
+            }),
+            ("cors: ", |s| s.cors.allowed_methods = vec![String::from("G E T")]),
+            ("cors: ", |s| s.cors.allowed_headers = vec![String::from("Bad Header")]),
+            ("metrics.token", |s| {
+                s.profile = Profile::Prod;
+                s.metrics.token = None;
+            }),
+            ("oidc provider \"corp\"", |s| {
+                s.oidc.providers = vec![OidcProviderSettings {
+                    name: String::from("corp"),
+                    issuer: String::new(),
+                    client_id: String::from("tasktracker"),
+                    client_secret: None,
+                    scopes: default_oidc_scopes(),
+                    redirect_uri: None,
+                    trust_email: false,
+                }]
+            }),
+        ];
+
+        for (expected, break_settings) in cases {
+            let mut settings = valid_settings(Profile::Test);
+            break_settings(&mut settings);
+
+            // when validating them
+            let errors = settings.validate();
+
+            // then expect that error among them
+            assert!(errors.iter().any(|error| error.starts_with(expected)), "{}: {:?}", expected, errors);
+        }
+    }
+}
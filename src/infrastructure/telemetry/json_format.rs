@@ -0,0 +1,204 @@
+use std::fmt;
+
+use chrono::{SecondsFormat, Utc};
+use serde_json::{Map, Value};
+use tracing::{
+    field::{Field, Visit},
+    span::Record,
+    Event, Subscriber,
+};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::{
+    field::RecordFields,
+    fmt::{format::Writer, FmtContext, FormatEvent, FormatFields, FormattedFields},
+    registry::LookupSpan,
+};
+
+// Fields added by the `log` bridge, the normalized metadata already carries them
+const LOG_BRIDGE_PREFIX: &str = "log.";
+
+// Collects the fields of an event or span into a JSON object
+#[derive(Default)]
+pub struct JsonVisitor {
+    pub fields: Map<String, Value>,
+}
+
+impl JsonVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if !field.name().starts_with(LOG_BRIDGE_PREFIX) {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for JsonVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.insert(field, Value::from(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::from(format!("{:?}", value)));
+    }
+}
+
+// Span fields kept as JSON text, so each log line can nest them without parsing key=value pairs back
+pub struct JsonFields;
+
+impl<'writer> FormatFields<'writer> for JsonFields {
+    fn format_fields<R: RecordFields>(&self, mut writer: Writer<'writer>, fields: R) -> fmt::Result {
+        let mut visitor = JsonVisitor::default();
+        fields.record(&mut visitor);
+        write!(writer, "{}", Value::Object(visitor.fields))
+    }
+
+    // Values recorded after the span was created (the response status...) are merged into the same object
+    fn add_fields(&self, current: &'writer mut FormattedFields<Self>, fields: &Record<'_>) -> fmt::Result {
+        let mut visitor = JsonVisitor {
+            fields: serde_json::from_str(&current.fields).unwrap_or_default(),
+        };
+        fields.record(&mut visitor);
+        current.fields = Value::Object(visitor.fields).to_string();
+        Ok(())
+    }
+}
+
+// One JSON object per line: timestamp, level, target, message, the event fields,
+// the fields of every enclosing span merged under "span" and their names under "spans"
+pub struct JsonFormat;
+
+impl<S> FormatEvent<S, JsonFields> for JsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, JsonFields>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+
+        let mut line = Map::new();
+        line.insert(String::from("timestamp"), Value::from(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)));
+        line.insert(String::from("level"), Value::from(metadata.level().as_str()));
+        line.insert(String::from("target"), Value::from(metadata.target()));
+        if let Some(message) = visitor.fields.remove("message") {
+            line.insert(String::from("message"), message);
+        }
+        if !visitor.fields.is_empty() {
+            line.insert(String::from("fields"), Value::Object(visitor.fields));
+        }
+
+        if let Some(scope) = ctx.event_scope() {
+            let mut span_fields = Map::new();
+            let mut span_names = Vec::new();
+            for span in scope.from_root() {
+                span_names.push(Value::from(span.name()));
+                if let Some(fields) = span.extensions().get::<FormattedFields<JsonFields>>() {
+                    if let Ok(Value::Object(fields)) = serde_json::from_str::<Value>(&fields.fields) {
+                        span_fields.extend(fields);
+                    }
+                }
+            }
+            line.insert(String::from("span"), Value::Object(span_fields));
+            line.insert(String::from("spans"), Value::Array(span_names));
+        }
+
+        writeln!(writer, "{}", Value::Object(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{field, info, info_span};
+    use tracing_subscriber::{fmt, layer::SubscriberExt};
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Runs `f` under the JSON format alone and returns every line it logged, parsed
+    fn logged_lines(f: impl FnOnce()) -> Vec<Value> {
+        let output = Output::default();
+        let writer = output.clone();
+        let layer = fmt::layer().fmt_fields(JsonFields).event_format(JsonFormat).with_writer(move || writer.clone());
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), f);
+
+        let content = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        content.lines().map(|line| serde_json::from_str(line).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_should_write_one_json_object_per_event() {
+        // given an event with a message and typed fields outside any span
+
+        // when logging it
+        let lines = logged_lines(|| info!(user = "id1", attempts = 3, locked = false, ratio = 0.5, "Signed in"));
+
+        // then expect one line with the metadata, the message and the fields apart
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert!(chrono::DateTime::parse_from_rfc3339(line["timestamp"].as_str().unwrap()).is_ok());
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], module_path!());
+        assert_eq!(line["message"], "Signed in");
+        assert_eq!(line["fields"], serde_json::json!({ "user": "id1", "attempts": 3, "locked": false, "ratio": 0.5 }));
+        assert!(line.get("span").is_none());
+        assert!(line.get("spans").is_none());
+    }
+
+    #[test]
+    fn test_should_merge_fields_of_enclosing_spans() {
+        // given an event inside a request span, whose status is recorded later, and a nested span
+        let lines = logged_lines(|| {
+            let request = info_span!("request", request_id = "abc", status = field::Empty);
+            let _request = request.enter();
+            request.record("status", 200);
+            let use_case = info_span!("use_case", name = "GetTasks");
+            let _use_case = use_case.enter();
+
+            // when logging an event without fields
+            info!("Tasks retrieved");
+        });
+
+        // then expect the fields of both spans under "span" and their names from the root
+        let line = &lines[0];
+        assert_eq!(line["message"], "Tasks retrieved");
+        assert!(line.get("fields").is_none());
+        assert_eq!(line["span"], serde_json::json!({ "request_id": "abc", "status": 200, "name": "GetTasks" }));
+        assert_eq!(line["spans"], serde_json::json!(["request", "use_case"]));
+    }
+}
@@ -0,0 +1,26 @@
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use crate::infrastructure::settings::{LogFormat, LogSettings};
+
+pub mod json_format;
+pub mod otlp;
+
+use json_format::{JsonFields, JsonFormat};
+use otlp::OtlpLayer;
+
+// Installs the process wide subscriber, `log` records of the dependencies go through it too.
+// Only the first call wins, the integration tests start a server per test in the same process.
+pub fn init(settings: &LogSettings) -> Result<(), std::io::Error> {
+    let filter = EnvFilter::try_new(&settings.level).map_err(std::io::Error::other)?;
+    let output = match settings.format {
+        LogFormat::Json => fmt::layer().fmt_fields(JsonFields).event_format(JsonFormat).boxed(),
+        LogFormat::Pretty => fmt::layer().pretty().boxed(),
+    };
+    let otlp = match &settings.otlp_endpoint {
+        Some(endpoint) => Some(OtlpLayer::new(endpoint, &settings.service_name)?),
+        None => None,
+    };
+
+    let _already_set = tracing_subscriber::registry().with(filter).with(output).with(otlp).try_init();
+    Ok(())
+}
@@ -0,0 +1,360 @@
+use std::{
+    sync::mpsc::{self, Receiver, RecvTimeoutError, SyncSender},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use rand::RngCore;
+use serde_json::{json, Map, Value};
+use tracing::{
+    span::{Attributes, Id, Record},
+    subscriber::NoSubscriber,
+    Event, Level, Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use super::json_format::JsonVisitor;
+
+// Spans waiting for the exporter, new ones are dropped while the collector can't keep up
+const QUEUE_SIZE: usize = 4096;
+const MAX_BATCH_SIZE: usize = 512;
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+// W3C trace context field read from the root span, see the request id middleware
+pub const TRACEPARENT_FIELD: &str = "traceparent";
+
+// OTLP status codes
+const STATUS_UNSET: u8 = 0;
+const STATUS_ERROR: u8 = 2;
+// OTLP span kinds
+const KIND_INTERNAL: u8 = 1;
+const KIND_SERVER: u8 = 2;
+
+struct SpanData {
+    trace_id: String,
+    span_id: String,
+    parent_span_id: Option<String>,
+    start: SystemTime,
+    attributes: Map<String, Value>,
+    error: Option<String>,
+}
+
+// Exports closed spans to an OpenTelemetry collector with the OTLP/HTTP JSON encoding. Root spans start a trace,
+// or continue the one of their `traceparent` field, children share the trace of their parent.
+pub struct OtlpLayer {
+    sender: SyncSender<Value>,
+}
+
+impl OtlpLayer {
+    pub fn new(endpoint: &str, service_name: &str) -> Result<Self, std::io::Error> {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_SIZE);
+        let url = format!("{}/v1/traces", endpoint.trim_end_matches('/'));
+        let service_name = service_name.to_string();
+        thread::Builder::new()
+            .name(String::from("otlp-exporter"))
+            .spawn(move || export_loop(&url, &service_name, receiver))?;
+        Ok(OtlpLayer { sender })
+    }
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut visitor = JsonVisitor::default();
+        attrs.record(&mut visitor);
+        let traceparent = visitor.fields.remove(TRACEPARENT_FIELD);
+
+        let parent = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanData>().map(|data| (data.trace_id.clone(), data.span_id.clone())));
+        let (trace_id, parent_span_id) = match parent {
+            Some((trace_id, parent_span_id)) => (trace_id, Some(parent_span_id)),
+            None => match traceparent.as_ref().and_then(Value::as_str).and_then(parse_traceparent) {
+                Some((trace_id, parent_span_id)) => (trace_id, Some(parent_span_id)),
+                None => (random_hex(16), None),
+            },
+        };
+
+        span.extensions_mut().insert(SpanData {
+            trace_id,
+            span_id: random_hex(8),
+            parent_span_id,
+            start: SystemTime::now(),
+            attributes: visitor.fields,
+            error: None,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            let mut visitor = JsonVisitor {
+                fields: std::mem::take(&mut data.attributes),
+            };
+            values.record(&mut visitor);
+            data.attributes = visitor.fields;
+        }
+    }
+
+    // An error logged inside a span marks it as failed
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        if *event.metadata().level() != Level::ERROR {
+            return;
+        }
+        let Some(span) = ctx.event_span(event) else { return };
+        let mut visitor = JsonVisitor::default();
+        event.record(&mut visitor);
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            data.error = Some(visitor.fields.get("message").and_then(Value::as_str).unwrap_or("error").to_string());
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else { return };
+
+        let kind = if span.parent().is_none() { KIND_SERVER } else { KIND_INTERNAL };
+        let status = match &data.error {
+            Some(message) => json!({ "code": STATUS_ERROR, "message": message }),
+            None => json!({ "code": STATUS_UNSET }),
+        };
+        let mut otlp_span = json!({
+            "traceId": data.trace_id,
+            "spanId": data.span_id,
+            "name": span.name(),
+            "kind": kind,
+            "startTimeUnixNano": unix_nanos(data.start),
+            "endTimeUnixNano": unix_nanos(SystemTime::now()),
+            "attributes": attributes(data.attributes),
+            "status": status,
+        });
+        if let Some(parent_span_id) = data.parent_span_id {
+            otlp_span["parentSpanId"] = Value::from(parent_span_id);
+        }
+
+        let _dropped_when_full = self.sender.try_send(otlp_span);
+    }
+}
+
+fn export_loop(url: &str, service_name: &str, receiver: Receiver<Value>) {
+    let runtime = match tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            tracing::error!("Cannot start the OTLP exporter: {}", e);
+            return;
+        }
+    };
+    let client = reqwest::Client::new();
+    let mut batch = Vec::new();
+    let mut failing = false;
+
+    loop {
+        let disconnected = match receiver.recv_timeout(FLUSH_INTERVAL) {
+            Ok(span) => {
+                batch.push(span);
+                if batch.len() < MAX_BATCH_SIZE {
+                    continue;
+                }
+                false
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => true,
+        };
+
+        if !batch.is_empty() {
+            let body = export_request(service_name, std::mem::take(&mut batch));
+            // the export itself must not be traced, it would feed the queue it is draining
+            let result = tracing::subscriber::with_default(NoSubscriber::default(), || {
+                runtime.block_on(async { client.post(url).timeout(EXPORT_TIMEOUT).json(&body).send().await?.error_for_status() })
+            });
+            // a collector being down costs the spans, not the service, and is only reported once
+            match result {
+                Ok(_) if failing => {
+                    failing = false;
+                    tracing::info!("OTLP export to {} recovered", url);
+                }
+                Err(e) if !failing => {
+                    failing = true;
+                    tracing::warn!("OTLP export to {} failed, spans are dropped until it recovers: {}", url, e);
+                }
+                _ => {}
+            }
+        }
+
+        if disconnected {
+            return;
+        }
+    }
+}
+
+fn export_request(service_name: &str, spans: Vec<Value>) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [{ "key": "service.name", "value": { "stringValue": service_name } }]
+            },
+            "scopeSpans": [{
+                "scope": { "name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION") },
+                "spans": spans
+            }]
+        }]
+    })
+}
+
+// OTLP JSON attributes are typed key/value pairs
+fn attributes(fields: Map<String, Value>) -> Vec<Value> {
+    fields
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Bool(value) => json!({ "boolValue": value }),
+                Value::Number(number) if number.is_i64() || number.is_u64() => json!({ "intValue": number.to_string() }),
+                Value::Number(number) => json!({ "doubleValue": number }),
+                Value::String(value) => json!({ "stringValue": value }),
+                other => json!({ "stringValue": other.to_string() }),
+            };
+            json!({ "key": key, "value": value })
+        })
+        .collect()
+}
+
+// "00-<32 hex trace id>-<16 hex parent span id>-<2 hex flags>", all-zero ids are invalid
+fn parse_traceparent(traceparent: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = traceparent.trim().split('-').collect();
+    let is_id = |value: &str, len: usize| value.len() == len && value.chars().all(|c| c.is_ascii_hexdigit()) && value.chars().any(|c| c != '0');
+    match parts.as_slice() {
+        [version, trace_id, span_id, flags] if *version != "ff" && version.len() == 2 && flags.len() == 2 && is_id(trace_id, 32) && is_id(span_id, 16) => {
+            Some((trace_id.to_lowercase(), span_id.to_lowercase()))
+        }
+        _ => None,
+    }
+}
+
+fn random_hex(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buffer);
+    buffer.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// OTLP JSON writes 64 bits integers as strings
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::{error, info_span};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+    const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+    #[test]
+    fn test_should_parse_traceparent() {
+        // given a traceparent in upper case with surrounding spaces
+        let traceparent = format!(" 00-{}-{}-01 ", TRACE_ID.to_uppercase(), PARENT_SPAN_ID);
+
+        // when parsing it
+        let ids = parse_traceparent(&traceparent);
+
+        // then expect the lower case trace and parent span ids
+        assert_eq!(ids, Some((String::from(TRACE_ID), String::from(PARENT_SPAN_ID))));
+    }
+
+    #[test]
+    fn test_should_refuse_invalid_traceparents() {
+        // given traceparents with a forbidden version, zero ids, bad lengths, non hex digits or missing parts
+        for traceparent in [
+            format!("ff-{}-{}-01", TRACE_ID, PARENT_SPAN_ID),
+            format!("00-{}-{}-01", "0".repeat(32), PARENT_SPAN_ID),
+            format!("00-{}-{}-01", TRACE_ID, "0".repeat(16)),
+            format!("00-{}-{}-01", &TRACE_ID[1..], PARENT_SPAN_ID),
+            format!("00-{}-{}-1", TRACE_ID, PARENT_SPAN_ID),
+            format!("000-{}-{}-01", TRACE_ID, PARENT_SPAN_ID),
+            format!("00-{}z-{}-01", &TRACE_ID[1..], PARENT_SPAN_ID),
+            format!("00-{}-{}", TRACE_ID, PARENT_SPAN_ID),
+            String::new(),
+        ] {
+            // when parsing them
+            // then expect nothing
+            assert_eq!(parse_traceparent(&traceparent), None, "{}", traceparent);
+        }
+    }
+
+    #[test]
+    fn test_should_type_attributes() {
+        // given fields of every JSON type
+        let fields = json!({ "flag": true, "count": 3, "ratio": 0.5, "route": "/api/v1/tasks", "list": [1] });
+
+        // when turning them into OTLP attributes
+        let attributes = attributes(fields.as_object().unwrap().clone());
+
+        // then expect integers written as strings and anything else as text
+        assert_eq!(
+            attributes,
+            vec![
+                json!({ "key": "count", "value": { "intValue": "3" } }),
+                json!({ "key": "flag", "value": { "boolValue": true } }),
+                json!({ "key": "list", "value": { "stringValue": "[1]" } }),
+                json!({ "key": "ratio", "value": { "doubleValue": 0.5 } }),
+                json!({ "key": "route", "value": { "stringValue": "/api/v1/tasks" } }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_should_export_spans_of_one_trace() {
+        // given the layer feeding a queue, a request span continuing a remote trace and a failing child span
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_SIZE);
+        let subscriber = tracing_subscriber::registry().with(OtlpLayer { sender });
+
+        // when both close
+        tracing::subscriber::with_default(subscriber, || {
+            let traceparent = format!("00-{}-{}-01", TRACE_ID, PARENT_SPAN_ID);
+            let request = info_span!("request", traceparent = traceparent.as_str(), route = "/api/v1/tasks");
+            let _request = request.enter();
+            let use_case = info_span!("use_case");
+            let _use_case = use_case.enter();
+            error!("Cannot load tasks");
+        });
+        let use_case = receiver.try_recv().unwrap();
+        let request = receiver.try_recv().unwrap();
+
+        // then expect both in the remote trace, the child under the request and marked failed
+        assert_eq!(request["traceId"], TRACE_ID);
+        assert_eq!(request["parentSpanId"], PARENT_SPAN_ID);
+        assert_eq!(request["kind"], KIND_SERVER);
+        assert_eq!(request["status"], json!({ "code": STATUS_UNSET }));
+        assert_eq!(request["attributes"], json!([{ "key": "route", "value": { "stringValue": "/api/v1/tasks" } }]));
+        assert_eq!(use_case["traceId"], TRACE_ID);
+        assert_eq!(use_case["parentSpanId"], request["spanId"]);
+        assert_eq!(use_case["kind"], KIND_INTERNAL);
+        assert_eq!(use_case["status"], json!({ "code": STATUS_ERROR, "message": "Cannot load tasks" }));
+        assert_eq!(use_case["spanId"].as_str().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_should_start_a_trace_without_valid_traceparent() {
+        // given the layer feeding a queue and a request span with a malformed traceparent
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_SIZE);
+        let subscriber = tracing_subscriber::registry().with(OtlpLayer { sender });
+
+        // when it closes
+        tracing::subscriber::with_default(subscriber, || {
+            let _request = info_span!("request", traceparent = "garbage").entered();
+        });
+        let request = receiver.try_recv().unwrap();
+
+        // then expect a new trace without parent
+        let trace_id = request["traceId"].as_str().unwrap();
+        assert_eq!(trace_id.len(), 32);
+        assert_ne!(trace_id, TRACE_ID);
+        assert!(request.get("parentSpanId").is_none());
+    }
+}
@@ -1,10 +1,13 @@
 use actix_web::dev::Server;
+use infrastructure::settings::Settings;
 use std::net::TcpListener;
 
 pub mod adapters;
 pub mod application;
 pub mod domain;
 pub mod infrastructure;
+#[cfg(test)]
+mod test_support;
 
 extern crate dotenv;
 extern crate log;
@@ -13,7 +16,12 @@ extern crate log;
 extern crate diesel;
 extern crate r2d2;
 
-pub fn run(listener: TcpListener, db_name: &str) -> Result<Server, std::io::Error> {
-    infrastructure::server(listener, db_name)
+pub fn run(listener: TcpListener, settings: Settings) -> Result<Server, std::io::Error> {
+    infrastructure::server(listener, None, settings)
+}
+
+// Same as `run`, the gRPC services are also served on `grpc_listener`
+pub fn run_with_grpc(listener: TcpListener, grpc_listener: Option<TcpListener>, settings: Settings) -> Result<Server, std::io::Error> {
+    infrastructure::server(listener, grpc_listener, settings)
 }
 
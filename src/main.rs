@@ -1,7 +1,7 @@
 use std::env;
 use std::net::TcpListener;
 
-use tasktracker_backend::run;
+use tasktracker_backend::{infrastructure::settings::Settings, run_with_grpc};
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -14,8 +14,9 @@ async fn main() -> std::io::Result<()> {
 
     dotenv::from_filename(environment_file).ok();
 
-    let listener = TcpListener::bind("0.0.0.0:8080").expect("Failed to bind random port");
-    let database_name = dotenv::var("DATABASE_NAME").expect("DATABASE_NAME must be set");
+    let settings = Settings::load()?;
+    let listener = TcpListener::bind(settings.bind_address())?;
+    let grpc_listener = settings.grpc_bind_address().map(TcpListener::bind).transpose()?;
 
-    run(listener, &database_name)?.await
+    run_with_grpc(listener, grpc_listener, settings)?.await
 }
@@ -0,0 +1,118 @@
+// Fixtures shared by the unit tests
+use chrono::{NaiveDateTime, Utc};
+
+use crate::{
+    adapters::spi::oidc::oidc_client::MockOidcClient,
+    domain::task_entity::{TaskEntity, TaskPriority, TaskStatus, TaskStatusToDo, TaskType},
+};
+
+// A task of user "id1" with neutral values, tests set only the fields they look at
+pub struct TaskEntityBuilder {
+    task: TaskEntity,
+}
+
+impl TaskEntityBuilder {
+    pub fn new(id: &str) -> Self {
+        let now = Utc::now().naive_utc();
+        TaskEntityBuilder {
+            task: TaskEntity::new(
+                String::from(id),
+                String::from("id1"),
+                String::new(),
+                String::from("Task"),
+                TaskType::None,
+                TaskPriority::None,
+                TaskStatus::None,
+                String::new(),
+                0,
+                0,
+                Vec::new(),
+                now,
+                now,
+            ),
+        }
+    }
+
+    pub fn user_id(mut self, user_id: &str) -> Self {
+        self.task.user_id = String::from(user_id);
+        self
+    }
+
+    pub fn project_id(mut self, project_id: &str) -> Self {
+        self.task.project_id = String::from(project_id);
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.task.title = String::from(title);
+        self
+    }
+
+    pub fn typ(mut self, typ: TaskType) -> Self {
+        self.task.typ = typ;
+        self
+    }
+
+    pub fn priority(mut self, priority: TaskPriority) -> Self {
+        self.task.priority = priority;
+        self
+    }
+
+    pub fn status(mut self, status: TaskStatus) -> Self {
+        self.task.status = status;
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.task.description = String::from(description);
+        self
+    }
+
+    pub fn duration(mut self, duration: i32) -> Self {
+        self.task.duration = duration;
+        self
+    }
+
+    pub fn due_date(mut self, due_date: i64) -> Self {
+        self.task.due_date = due_date;
+        self
+    }
+
+    pub fn task_list(mut self, task_list: &[&str]) -> Self {
+        self.task.task_list = task_list.iter().map(|item| String::from(*item)).collect();
+        self
+    }
+
+    // Also the update time, the task was never edited
+    pub fn created_at(mut self, created_at: NaiveDateTime) -> Self {
+        self.task.created_at = created_at;
+        self.task.updated_at = created_at;
+        self
+    }
+
+    pub fn build(self) -> TaskEntity {
+        self.task
+    }
+}
+
+// The task "id1" of `user_id` the ownership checks are run against
+pub fn task_owned_by(user_id: &str) -> TaskEntity {
+    TaskEntityBuilder::new("id1")
+        .user_id(user_id)
+        .project_id("id1")
+        .title("task1")
+        .typ(TaskType::Work)
+        .priority(TaskPriority::Low)
+        .status(TaskStatus::ToDo(TaskStatusToDo::NotStarted))
+        .duration(1)
+        .due_date(321472382)
+        .build()
+}
+
+// An identity provider client knowing only the "corp" provider, which does not vouch for emails
+pub fn oidc_client_with_provider() -> MockOidcClient {
+    let mut oidc_client = MockOidcClient::new();
+    oidc_client.expect_provider_names().returning(|| vec![String::from("corp")]);
+    oidc_client.expect_trusts_email().returning(|_| false);
+    oidc_client
+}
@@ -1,6 +1,7 @@
 use diesel::prelude::*;
 use serde::Deserialize;
 use tasktracker_backend::adapters::spi::db::schema::*;
+use tasktracker_backend::domain::task_entity::{TaskPriority, TaskStatus, TaskType};
 use uuid::Uuid;
 use chrono::NaiveDateTime;
 
@@ -10,13 +11,13 @@ pub struct TaskJson {
     pub id: Uuid,
     pub user_id: Uuid,
     pub title: String,
-    pub typ: Option<String>,
-    pub priority: Option<String>,
-    pub status: Option<String>,
+    pub typ: TaskType,
+    pub priority: TaskPriority,
+    pub status: TaskStatus,
     pub description: String,
     pub duration: Option<i32>,
     pub due_date: Option<i64>,
-    pub project_id: Option<i32>,
+    pub project_id: Uuid,
     pub task_list: Option<Vec<String>>,
     pub updated_at: NaiveDateTime,
     pub created_at: NaiveDateTime,
@@ -1,2 +1,21 @@
 pub mod fixtures;
 pub mod test_tasks;
+pub mod test_task_ownership;
+pub mod test_oidc_login;
+pub mod test_login_throttle;
+pub mod test_rate_limit;
+pub mod test_cors;
+pub mod test_health;
+pub mod test_metrics;
+pub mod test_request_id;
+pub mod test_validation;
+pub mod test_task_priority;
+pub mod test_openapi;
+pub mod test_graphql;
+pub mod test_grpc;
+pub mod test_task_transfer;
+pub mod test_imports;
+pub mod test_calendar;
+pub mod test_refresh_tokens;
+pub mod test_api_keys;
+pub mod test_user_roles;
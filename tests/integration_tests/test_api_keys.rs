@@ -0,0 +1,83 @@
+use reqwest::{Client, Method, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn test_should_not_manage_credentials_or_sessions_with_an_api_key() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given an API key of quinn holding every permission of the role
+    let access_token = register_and_login(&client, &api_address, "quinn").await;
+    let response = client
+        .post(format!("{}/api/v1/api_keys/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "name": "ci" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+    let api_key = content_json["data"]["key"].as_str().unwrap().to_string();
+    let routes = [
+        (Method::POST, "/api/v1/users/logout", json!({})),
+        (Method::POST, "/api/v1/users/logout_all", json!({})),
+        (Method::PATCH, "/api/v1/users/one_own", json!({ "password": "An0ther-Passw0rd!" })),
+        (Method::GET, "/api/v1/sessions/all_own", json!({})),
+        (Method::DELETE, "/api/v1/sessions/one_own", json!({ "id": "8f2b0c52-4f8e-4c2e-9a8e-3f1e4b2c1d0a" })),
+        (Method::POST, "/api/v1/mfa/enroll_own", json!({})),
+        (Method::POST, "/api/v1/mfa/confirm_own", json!({ "code": "123456" })),
+        (Method::POST, "/api/v1/mfa/disable_own", json!({ "code": "123456" })),
+        (Method::GET, "/api/v1/api_keys/all_own", json!({})),
+        (Method::POST, "/api/v1/api_keys/one_own", json!({ "name": "another" })),
+    ];
+
+    for (method, path, body) in routes {
+        // when the route is called with the key
+        let response = client
+            .request(method, format!("{}{}", &api_address, path))
+            .header("X-Api-Key", &api_key)
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // then it is turned down
+        assert_eq!(response.status(), StatusCode::FORBIDDEN, "{}", path);
+        let content_json = response.json::<Value>().await.unwrap();
+        assert_eq!(content_json["error_code"], "forbidden", "{}", path);
+    }
+
+    // and the signed in user still can
+    let response = client
+        .get(format!("{}/api/v1/sessions/all_own", &api_address))
+        .bearer_auth(&access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::OK);
+}
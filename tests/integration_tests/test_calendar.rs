@@ -0,0 +1,145 @@
+use reqwest::{header::CONTENT_TYPE, Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+// Creates the feed of the caller, returns its token
+async fn create_feed(client: &Client, api_address: &str, access_token: &str) -> String {
+    let response = client
+        .post(format!("{}/api/v1/calendar/feed_own", api_address))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["code"], 201);
+    let token = content_json["data"]["token"].as_str().unwrap().to_string();
+    assert!(content_json["data"]["feed_url"].as_str().unwrap().ends_with(&format!("/api/v1/calendar/feed/{}.ics", token)));
+
+    token
+}
+
+// Fetches a feed the way a calendar app does, without credentials
+async fn get_feed(client: &Client, api_address: &str, token: &str, query: &str) -> (StatusCode, String) {
+    let response = client
+        .get(format!("{}/api/v1/calendar/feed/{}.ics{}", api_address, token, query))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert!(response.headers()[CONTENT_TYPE].to_str().unwrap().starts_with("text/calendar"));
+    }
+
+    (status, response.text().await.unwrap())
+}
+
+#[actix_rt::test]
+async fn test_should_serve_the_due_tasks_of_a_feed_without_credentials() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a task with a due date, one without and a calendar feed
+    let access_token = register_and_login(&client, &api_address, "nora").await;
+    let tasks = json!([
+        { "title": "Pay rent", "priority": "High", "due_date": 1700086400000i64, "duration": 30 },
+        { "title": "Someday" }
+    ]);
+    let response = client
+        .post(format!("{}/api/v1/tasks/import", &api_address))
+        .bearer_auth(&access_token)
+        .json(&tasks)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.json::<Value>().await.unwrap()["code"], 201);
+    let token = create_feed(&client, &api_address, &access_token).await;
+
+    // when a calendar app reads it
+    let (status, ics) = get_feed(&client, &api_address, &token, "").await;
+
+    // then only the due task is listed, as a todo
+    assert_eq!(status, StatusCode::OK);
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert_eq!(ics.matches("BEGIN:VTODO").count(), 1);
+    assert!(ics.contains("SUMMARY:Pay rent\r\n"));
+    assert!(ics.contains("DUE:20231115T221320Z\r\n"));
+    assert!(ics.contains("PRIORITY:1\r\n"));
+    assert!(!ics.contains("Someday"));
+
+    // and as an event lasting the task duration
+    let (status, ics) = get_feed(&client, &api_address, &token, "?kind=event").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(ics.contains("DTSTART:20231115T221320Z\r\nDTEND:20231115T224320Z\r\n"));
+
+    // and filtering on another project leaves it out
+    let (status, ics) = get_feed(&client, &api_address, &token, "?project_id=11111111-1111-1111-1111-111111111111").await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!ics.contains("BEGIN:VTODO"));
+}
+
+#[actix_rt::test]
+async fn test_should_regenerate_and_revoke_a_feed_with_its_token_only() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a feed
+    let access_token = register_and_login(&client, &api_address, "otto").await;
+    let token = create_feed(&client, &api_address, &access_token).await;
+
+    // when regenerating it without signing in
+    let response = client
+        .post(format!("{}/api/v1/calendar/feed/{}/regenerate", &api_address, token))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    // then the old URL stops working and the new one answers
+    assert_eq!(content_json["code"], 201);
+    let new_token = content_json["data"]["token"].as_str().unwrap().to_string();
+    assert_eq!(get_feed(&client, &api_address, &token, "").await.0, StatusCode::NOT_FOUND);
+    assert_eq!(get_feed(&client, &api_address, &new_token, "").await.0, StatusCode::OK);
+
+    // and revoking it leaves no working URL
+    let response = client
+        .delete(format!("{}/api/v1/calendar/feed/{}", &api_address, new_token))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.json::<Value>().await.unwrap()["code"], 200);
+    assert_eq!(get_feed(&client, &api_address, &new_token, "").await.0, StatusCode::NOT_FOUND);
+    let response = client
+        .delete(format!("{}/api/v1/calendar/feed_own", &api_address))
+        .bearer_auth(&access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.json::<Value>().await.unwrap()["code"], 404);
+}
@@ -0,0 +1,146 @@
+use reqwest::{Client, Method, StatusCode};
+use serde_json::{json, Value};
+
+use tasktracker_backend::infrastructure::settings::Settings;
+
+use crate::utils::utils_setup::{setup, spawn_app_with};
+
+const ALLOWED_ORIGINS: [&str; 2] = ["https://app.tasktracker.test", "https://*.preview.tasktracker.test"];
+
+fn allow_origins(settings: &mut Settings) {
+    settings.cors.allowed_origins = ALLOWED_ORIGINS.map(String::from).to_vec();
+}
+
+async fn register_and_login(client: &Client, api_address: &str) -> String {
+    let email = "carol@tasktracker.test";
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": "carol", "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+async fn create_own_task(client: &Client, api_address: &str, access_token: &str) -> String {
+    let response = client
+        .post(format!("{}/api/v1/tasks/one_own", api_address))
+        .bearer_auth(access_token)
+        .json(&json!({ "title": "Shared task" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["task_id"].as_str().unwrap().to_string()
+}
+
+async fn preflight(client: &Client, url: &str, origin: &str, method: &str) -> reqwest::Response {
+    client
+        .request(Method::OPTIONS, url)
+        .header("Origin", origin)
+        .header("Access-Control-Request-Method", method)
+        .header("Access-Control-Request-Headers", "authorization, content-type")
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[actix_rt::test]
+async fn test_should_allow_patch_and_delete_from_allowed_origin() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app_with(&_ctx.db_name, allow_origins);
+    let client = Client::new();
+    let url = format!("{}/api/v1/tasks/one_own", &api_address);
+
+    // given a task of a signed in user
+    let access_token = register_and_login(&client, &api_address).await;
+    let task_id = create_own_task(&client, &api_address, &access_token).await;
+
+    // when the frontend, listed or matching a wildcard, asks before updating then deleting it
+    for (origin, method) in [("https://app.tasktracker.test", "PATCH"), ("https://pr-42.preview.tasktracker.test", "DELETE")] {
+        let response = preflight(&client, &url, origin, method).await;
+
+        // then expect the preflight to allow it
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()["access-control-allow-origin"], origin);
+        assert!(response.headers()["access-control-allow-methods"].to_str().unwrap().contains(method));
+    }
+
+    let update_response = client
+        .patch(&url)
+        .header("Origin", "https://app.tasktracker.test")
+        .bearer_auth(&access_token)
+        .json(&json!({ "task_id": task_id, "title": "Renamed task" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let delete_response = client
+        .delete(&url)
+        .header("Origin", "https://pr-42.preview.tasktracker.test")
+        .bearer_auth(&access_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // and the requests themselves to go through with the origin echoed back
+    assert!(update_response.status().is_success());
+    assert_eq!(update_response.headers()["access-control-allow-origin"], "https://app.tasktracker.test");
+    assert!(delete_response.status().is_success());
+    assert_eq!(delete_response.headers()["access-control-allow-origin"], "https://pr-42.preview.tasktracker.test");
+}
+
+#[actix_rt::test]
+async fn test_should_reject_patch_and_delete_from_other_origin() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app_with(&_ctx.db_name, allow_origins);
+    let client = Client::new();
+    let url = format!("{}/api/v1/tasks/one_own", &api_address);
+
+    // given a task of a signed in user
+    let access_token = register_and_login(&client, &api_address).await;
+    let task_id = create_own_task(&client, &api_address, &access_token).await;
+
+    // when a page of another site, or one only resembling the wildcard, asks to delete it
+    for origin in ["https://evil.test", "https://preview.tasktracker.test.evil.test"] {
+        let response = preflight(&client, &url, origin, "DELETE").await;
+
+        // then expect the preflight to be refused
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(!response.headers().contains_key("access-control-allow-origin"));
+    }
+
+    // and a request sent anyway to never reach the task
+    let delete_response = client
+        .delete(&url)
+        .header("Origin", "https://evil.test")
+        .bearer_auth(&access_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(delete_response.status(), StatusCode::BAD_REQUEST);
+    assert!(!delete_response.headers().contains_key("access-control-allow-origin"));
+
+    let response = client
+        .get(&url)
+        .bearer_auth(&access_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+}
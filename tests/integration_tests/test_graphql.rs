@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use async_graphql::futures_util::{SinkExt, StreamExt};
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, http::HeaderValue, Message};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+async fn graphql(client: &Client, api_address: &str, access_token: &str, query: &str, variables: Value) -> Value {
+    let response = client
+        .post(format!("{}/graphql", api_address))
+        .bearer_auth(access_token)
+        .json(&json!({ "query": query, "variables": variables }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json::<Value>().await.unwrap()
+}
+
+const CREATE_TASK: &str = "mutation ($input: CreateTaskInput!) { createTask(input: $input) { id title priority } }";
+
+#[actix_rt::test]
+async fn test_should_require_authentication() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when querying without a token
+    let response = client
+        .post(format!("{}/graphql", &api_address))
+        .json(&json!({ "query": "{ me { username } }" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect the same refusal as the REST routes
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_rt::test]
+async fn test_should_resolve_tasks_with_assignees_page_by_page() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given two tasks created through mutations
+    let access_token = register_and_login(&client, &api_address, "ivan").await;
+    for (title, priority) in [("Low one", "LOW"), ("High one", "HIGH")] {
+        let content_json = graphql(&client, &api_address, &access_token, CREATE_TASK, json!({ "input": { "title": title, "priority": priority } })).await;
+        assert_eq!(content_json["data"]["createTask"]["title"], title);
+    }
+
+    // when listing them one per page, with the user and their assignee in the same round trip
+    let query = "query ($after: String) {
+        me { username }
+        tasks(first: 1, after: $after, sort: PRIORITY_DESC) {
+            edges { node { title priority assignee { username } project { name } } }
+            pageInfo { hasNextPage endCursor }
+        }
+    }";
+    let first_page = graphql(&client, &api_address, &access_token, query, json!({})).await;
+    let cursor = first_page["data"]["tasks"]["pageInfo"]["endCursor"].clone();
+    let second_page = graphql(&client, &api_address, &access_token, query, json!({ "after": cursor })).await;
+
+    // then expect the highest priority first and every field resolved
+    assert_eq!(first_page["data"]["me"]["username"], "ivan");
+    assert_eq!(
+        first_page["data"]["tasks"]["edges"],
+        json!([{ "node": { "title": "High one", "priority": "HIGH", "assignee": { "username": "ivan" }, "project": null } }])
+    );
+    assert_eq!(first_page["data"]["tasks"]["pageInfo"]["hasNextPage"], true);
+    assert_eq!(second_page["data"]["tasks"]["edges"][0]["node"]["title"], "Low one");
+    assert_eq!(second_page["data"]["tasks"]["pageInfo"]["hasNextPage"], false);
+}
+
+#[actix_rt::test]
+async fn test_should_report_invalid_input_and_hidden_tasks() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a task owned by judy
+    let judy_token = register_and_login(&client, &api_address, "judy").await;
+    let mallory_token = register_and_login(&client, &api_address, "mallory").await;
+    let content_json = graphql(&client, &api_address, &judy_token, CREATE_TASK, json!({ "input": { "title": "Private" } })).await;
+    let task_id = content_json["data"]["createTask"]["id"].clone();
+
+    // when creating a task with invalid fields
+    let content_json = graphql(&client, &api_address, &judy_token, CREATE_TASK, json!({ "input": { "title": " ", "duration": -1 } })).await;
+
+    // then expect each field reported under its GraphQL name
+    let extensions = &content_json["errors"][0]["extensions"];
+    assert_eq!(extensions["code"], "validation_failed");
+    assert_eq!(extensions["status"], 422);
+    let fields: Vec<&str> = extensions["fields"].as_array().unwrap().iter().map(|field| field["field"].as_str().unwrap()).collect();
+    assert_eq!(fields, vec!["duration", "title"]);
+
+    // and judy's task to be missing for mallory
+    let content_json = graphql(&client, &api_address, &mallory_token, "query ($id: ID!) { task(id: $id) { title } }", json!({ "id": task_id })).await;
+    assert_eq!(content_json["errors"][0]["extensions"]["code"], "not_found");
+}
+
+#[actix_rt::test]
+async fn test_should_push_changes_of_readable_tasks_to_subscribers() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given kim subscribed to task changes
+    let kim_token = register_and_login(&client, &api_address, "kim").await;
+    let leo_token = register_and_login(&client, &api_address, "leo").await;
+    let mut request = format!("{}/graphql/ws", api_address.replacen("http", "ws", 1)).into_client_request().unwrap();
+    request.headers_mut().insert("Authorization", HeaderValue::from_str(&format!("Bearer {}", kim_token)).unwrap());
+    request.headers_mut().insert("Sec-WebSocket-Protocol", HeaderValue::from_static("graphql-transport-ws"));
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.expect("Failed to open websocket.");
+
+    socket.send(Message::Text(json!({ "type": "connection_init" }).to_string())).await.unwrap();
+    let ack = socket.next().await.unwrap().unwrap();
+    assert_eq!(serde_json::from_str::<Value>(ack.to_text().unwrap()).unwrap()["type"], "connection_ack");
+    let subscription = "subscription { taskChanged { kind task { title assignee { username } } } }";
+    socket
+        .send(Message::Text(json!({ "id": "1", "type": "subscribe", "payload": { "query": subscription } }).to_string()))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // when leo then kim create a task, kim through REST
+    graphql(&client, &api_address, &leo_token, CREATE_TASK, json!({ "input": { "title": "Leo's task" } })).await;
+    client
+        .post(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&kim_token)
+        .json(&json!({ "title": "Kim's task" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect only kim's task to be pushed
+    let message = tokio::time::timeout(Duration::from_secs(5), socket.next()).await.expect("No change pushed").unwrap().unwrap();
+    let content_json = serde_json::from_str::<Value>(message.to_text().unwrap()).unwrap();
+    assert_eq!(content_json["type"], "next");
+    assert_eq!(
+        content_json["payload"]["data"]["taskChanged"],
+        json!({ "kind": "CREATED", "task": { "title": "Kim's task", "assignee": { "username": "kim" } } })
+    );
+}
+
+#[actix_rt::test]
+async fn test_should_end_subscriptions_of_revoked_tokens() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given mia subscribed to task changes
+    let old_token = register_and_login(&client, &api_address, "mia").await;
+    let mut request = format!("{}/graphql/ws", api_address.replacen("http", "ws", 1)).into_client_request().unwrap();
+    request.headers_mut().insert("Authorization", HeaderValue::from_str(&format!("Bearer {}", old_token)).unwrap());
+    request.headers_mut().insert("Sec-WebSocket-Protocol", HeaderValue::from_static("graphql-transport-ws"));
+    let (mut socket, _) = tokio_tungstenite::connect_async(request).await.expect("Failed to open websocket.");
+
+    socket.send(Message::Text(json!({ "type": "connection_init" }).to_string())).await.unwrap();
+    socket.next().await.unwrap().unwrap();
+    let subscription = "subscription { taskChanged { kind task { title } } }";
+    socket
+        .send(Message::Text(json!({ "id": "1", "type": "subscribe", "payload": { "query": subscription } }).to_string()))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // when mia logs out that token, then signs in again and creates a task
+    client
+        .post(format!("{}/api/v1/users/logout", &api_address))
+        .bearer_auth(&old_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let new_token = register_and_login(&client, &api_address, "mia").await;
+    graphql(&client, &api_address, &new_token, CREATE_TASK, json!({ "input": { "title": "Mia's task" } })).await;
+
+    // then expect the subscription to end instead of pushing the change
+    let message = tokio::time::timeout(Duration::from_secs(5), socket.next()).await.expect("Subscription still open").unwrap().unwrap();
+    let content_json = serde_json::from_str::<Value>(message.to_text().unwrap()).unwrap();
+    assert_eq!(content_json["type"], "complete");
+    assert_eq!(content_json["id"], "1");
+}
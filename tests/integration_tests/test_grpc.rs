@@ -0,0 +1,190 @@
+use reqwest::Client;
+use serde_json::{json, Value};
+use tasktracker_backend::adapters::api::grpc::proto::{
+    tasks_service_client::TasksServiceClient, users_service_client::UsersServiceClient, CreateTaskRequest, GetMeRequest, GetTaskRequest, ListTasksRequest, TaskPriority, TaskSort,
+    UpdateTaskRequest,
+};
+use tonic::{metadata::MetadataValue, transport::Channel, Code, Request};
+use tonic_types::StatusExt;
+
+use crate::utils::utils_setup::{setup, spawn_app_with_grpc, spawn_grpc_app_with};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+fn with_metadata<T>(message: T, key: &'static str, value: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request.metadata_mut().insert(key, MetadataValue::try_from(value).unwrap());
+    request
+}
+
+async fn tasks_client(grpc_address: &str) -> TasksServiceClient<Channel> {
+    TasksServiceClient::connect(grpc_address.to_string()).await.expect("Failed to connect.")
+}
+
+#[actix_rt::test]
+async fn test_should_require_authentication() {
+    // setup
+    let _ctx = setup();
+    let (_, grpc_address) = spawn_app_with_grpc(&_ctx.db_name);
+    let mut tasks = tasks_client(&grpc_address).await;
+
+    // when listing tasks without credentials
+    let status = tasks.list_tasks(ListTasksRequest::default()).await.unwrap_err();
+
+    // then expect the same refusal as the REST routes
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(status.message(), "Requires authentication");
+}
+
+#[actix_rt::test]
+async fn test_should_manage_tasks_with_a_bearer_token() {
+    // setup
+    let _ctx = setup();
+    let (api_address, grpc_address) = spawn_app_with_grpc(&_ctx.db_name);
+    let client = Client::new();
+    let mut tasks = tasks_client(&grpc_address).await;
+    let authorization = format!("Bearer {}", register_and_login(&client, &api_address, "nina").await);
+
+    // given two tasks created over gRPC
+    for (title, priority) in [("Low one", TaskPriority::Low), ("High one", TaskPriority::High)] {
+        let request = CreateTaskRequest {
+            title: title.to_string(),
+            priority: Some(priority.into()),
+            ..Default::default()
+        };
+        let task = tasks.create_task(with_metadata(request, "authorization", &authorization)).await.unwrap().into_inner();
+        assert_eq!(task.title, title);
+        assert_eq!(task.priority(), priority);
+    }
+
+    // when listing them by priority, then updating the first one
+    let request = ListTasksRequest {
+        sort: TaskSort::PriorityDesc.into(),
+        ..Default::default()
+    };
+    let listed = tasks.list_tasks(with_metadata(request, "authorization", &authorization)).await.unwrap().into_inner().tasks;
+    let request = UpdateTaskRequest {
+        id: listed[0].id.clone(),
+        title: Some(String::from("Renamed")),
+        ..Default::default()
+    };
+    tasks.update_task(with_metadata(request, "authorization", &authorization)).await.unwrap();
+
+    // then expect the highest priority first and the update visible through REST
+    assert_eq!(listed.iter().map(|task| task.title.as_str()).collect::<Vec<_>>(), vec!["High one", "Low one"]);
+    let response = client
+        .get(format!("{}/api/v1/tasks/one_own", &api_address))
+        .header("Authorization", &authorization)
+        .json(&json!({ "task_id": listed[0].id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["data"]["title"], "Renamed");
+}
+
+#[actix_rt::test]
+async fn test_should_accept_api_keys_and_hide_tasks_of_others() {
+    // setup
+    let _ctx = setup();
+    let (api_address, grpc_address) = spawn_app_with_grpc(&_ctx.db_name);
+    let client = Client::new();
+    let mut tasks = tasks_client(&grpc_address).await;
+    let mut users = UsersServiceClient::connect(grpc_address.clone()).await.expect("Failed to connect.");
+
+    // given an API key of oscar and a task of peggy
+    let oscar_token = register_and_login(&client, &api_address, "oscar").await;
+    let response = client
+        .post(format!("{}/api/v1/api_keys/one_own", &api_address))
+        .bearer_auth(&oscar_token)
+        .json(&json!({ "name": "ci" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+    let api_key = content_json["data"]["key"].as_str().unwrap().to_string();
+    let peggy_authorization = format!("Bearer {}", register_and_login(&client, &api_address, "peggy").await);
+    let request = CreateTaskRequest {
+        title: String::from("Private"),
+        ..Default::default()
+    };
+    let task = tasks.create_task(with_metadata(request, "authorization", &peggy_authorization)).await.unwrap().into_inner();
+
+    // when oscar calls with the key
+    let me = users.get_me(with_metadata(GetMeRequest {}, "x-api-key", &api_key)).await.unwrap().into_inner();
+    let status = tasks.get_task(with_metadata(GetTaskRequest { id: task.id }, "x-api-key", &api_key)).await.unwrap_err();
+
+    // then expect his account and peggy's task to be missing
+    assert_eq!(me.username, "oscar");
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.get_details_error_info().unwrap().reason, "not_found");
+}
+
+#[actix_rt::test]
+async fn test_should_report_invalid_fields() {
+    // setup
+    let _ctx = setup();
+    let (api_address, grpc_address) = spawn_app_with_grpc(&_ctx.db_name);
+    let client = Client::new();
+    let mut tasks = tasks_client(&grpc_address).await;
+    let authorization = format!("Bearer {}", register_and_login(&client, &api_address, "quinn").await);
+
+    // when creating a task with invalid fields
+    let request = CreateTaskRequest {
+        title: String::from(" "),
+        duration: Some(-1),
+        ..Default::default()
+    };
+    let status = tasks.create_task(with_metadata(request, "authorization", &authorization)).await.unwrap_err();
+
+    // then expect each field reported under its protobuf name
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.get_details_error_info().unwrap().reason, "validation_failed");
+    let fields: Vec<String> = status.get_details_bad_request().unwrap().field_violations.into_iter().map(|violation| violation.field).collect();
+    assert_eq!(fields, vec!["duration", "title"]);
+}
+
+#[actix_rt::test]
+async fn test_should_run_calls_through_the_request_id_and_rate_limit_layers() {
+    // setup
+    let _ctx = setup();
+    let (_, grpc_address) = spawn_grpc_app_with(&_ctx.db_name, |settings| {
+        settings.rate_limit.routes = vec![String::from("POST /tasktracker.v1.TasksService/ListTasks=2/60")];
+    });
+    let mut tasks = tasks_client(&grpc_address).await;
+
+    // given a call carrying its own request id
+    let status = tasks.list_tasks(with_metadata(ListTasksRequest::default(), "x-request-id", "grpc-call-1")).await.unwrap_err();
+
+    // when calling again past the limit of the method
+    let allowed = tasks.list_tasks(ListTasksRequest::default()).await.unwrap_err();
+    let limited = tasks.list_tasks(ListTasksRequest::default()).await.unwrap_err();
+
+    // then expect the id echoed, a generated one otherwise, and the third call refused like a 429
+    assert_eq!(status.metadata().get("x-request-id").unwrap(), "grpc-call-1");
+    assert_eq!(allowed.code(), Code::Unauthenticated);
+    assert!(allowed.metadata().get("x-request-id").is_some());
+    assert_eq!(limited.code(), Code::ResourceExhausted);
+    assert_eq!(limited.get_details_error_info().unwrap().reason, "too_many_requests");
+    assert!(limited.metadata().get("retry-after").is_some());
+}
@@ -0,0 +1,65 @@
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+#[actix_rt::test]
+async fn test_should_report_live_and_ready_without_authentication() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when probing a service whose database is migrated
+    let live_response = client.get(format!("{}/health/live", &api_address)).send().await.expect("Failed to execute request.");
+    let ready_response = client.get(format!("{}/health/ready", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect both to succeed with every check up
+    assert!(live_response.status().is_success());
+    assert_eq!(live_response.json::<Value>().await.unwrap()["status"], "ok");
+
+    assert!(ready_response.status().is_success());
+    let content_json = ready_response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["status"], "ok");
+    for check in content_json["checks"].as_array().unwrap() {
+        assert_eq!(check["status"], "up");
+    }
+}
+
+#[actix_rt::test]
+async fn test_should_not_be_ready_when_database_is_unreachable() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app("test_missing_database");
+    let client = Client::new();
+
+    // when probing a service pointed at a database that doesn't exist
+    let live_response = client.get(format!("{}/health/live", &api_address)).send().await.expect("Failed to execute request.");
+    let ready_response = client.get(format!("{}/health/ready", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect it alive but not ready
+    assert!(live_response.status().is_success());
+    assert_eq!(ready_response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let content_json = ready_response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["status"], "unavailable");
+    assert_eq!(content_json["checks"][0]["name"], "database");
+    assert_eq!(content_json["checks"][0]["status"], "down");
+}
+
+#[actix_rt::test]
+async fn test_should_return_version() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when asking for the version
+    let response = client.get(format!("{}/version", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect the crate version and build stamps
+    assert!(response.status().is_success());
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!content_json["git_sha"].as_str().unwrap().is_empty());
+    assert!(!content_json["build_time"].as_str().unwrap().is_empty());
+}
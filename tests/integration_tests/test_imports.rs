@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+async fn get_import(client: &Client, api_address: &str, access_token: &str, job_id: &str) -> (StatusCode, Value) {
+    let response = client
+        .get(format!("{}/api/v1/imports/{}", api_address, job_id))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    (response.status(), response.json::<Value>().await.unwrap())
+}
+
+// Polls the job until it is no longer queued or running
+async fn finished_import(client: &Client, api_address: &str, access_token: &str, job_id: &str) -> Value {
+    for _ in 0..50 {
+        let (_, content_json) = get_import(client, api_address, access_token, job_id).await;
+        if !matches!(content_json["data"]["state"].as_str(), Some("queued" | "running")) {
+            return content_json["data"].clone();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("import {} did not finish", job_id);
+}
+
+#[actix_rt::test]
+async fn test_should_import_a_trello_board_in_the_background() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a Trello board with an archived card
+    let access_token = register_and_login(&client, &api_address, "mia").await;
+    let board = json!({
+        "name": "Launch",
+        "lists": [{ "id": "l1", "name": "Done" }],
+        "cards": [
+            { "id": "c1", "name": "Write docs", "idList": "l1", "labels": [{ "name": "Work" }] },
+            { "id": "c2", "name": "Old idea", "idList": "l1", "closed": true }
+        ],
+        "checklists": [{ "idCard": "c1", "checkItems": [{ "name": "outline", "state": "complete" }] }]
+    });
+
+    // when importing it
+    let response = client
+        .post(format!("{}/api/v1/imports?source=trello", &api_address))
+        .bearer_auth(&access_token)
+        .json(&board)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["code"], 202);
+    let job_id = content_json["data"]["job_id"].as_str().unwrap().to_string();
+
+    // then the job completes with the project, the task and the skipped card
+    let job = finished_import(&client, &api_address, &access_token, &job_id).await;
+    assert_eq!(job["state"], "completed");
+    assert_eq!((job["processed"].as_u64(), job["total"].as_u64()), (Some(1), Some(1)));
+    assert_eq!(job["project_ids"].as_array().unwrap().len(), 1);
+    assert_eq!(job["skipped"], json!([{ "item": "card \"Old idea\"", "reason": "archived" }]));
+
+    // and the task belongs to the caller, in the new project
+    let response = client
+        .get(format!("{}/api/v1/tasks/export?format=ndjson", &api_address))
+        .bearer_auth(&access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let task: Value = serde_json::from_str(response.text().await.unwrap().trim()).unwrap();
+    assert_eq!(task["title"], "Write docs");
+    assert_eq!((task["typ"].as_str(), task["status"].as_str()), (Some("work"), Some("completed")));
+    assert_eq!(task["task_list"], json!(["[x] outline"]));
+    assert_eq!(task["project_id"], job["project_ids"][0]);
+
+    // and another instance on the same database reports the job too
+    let other_instance_address = spawn_app(&_ctx.db_name);
+    let (status, content_json) = get_import(&client, &other_instance_address, &access_token, &job_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_json["data"]["skipped"], job["skipped"]);
+
+    // and another user can't see the job
+    let other_token = register_and_login(&client, &api_address, "nia").await;
+    let (status, _) = get_import(&client, &api_address, &other_token, &job_id).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_a_file_of_another_tool() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a CSV sent as a Trello board
+    let access_token = register_and_login(&client, &api_address, "ola").await;
+
+    // when importing it
+    let response = client
+        .post(format!("{}/api/v1/imports?source=trello", &api_address))
+        .bearer_auth(&access_token)
+        .body("Name,Status\nOne,Done\n")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then nothing is queued
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
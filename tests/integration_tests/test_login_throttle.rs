@@ -0,0 +1,159 @@
+use diesel::RunQueryDsl;
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tasktracker_backend::{
+    adapters::{
+        api::users::users_payloads::UserLoginAttemptPayload,
+        spi::db::{db_connection::DbConnection, db_login_attempts_repository::LoginAttemptsRepository},
+    },
+    application::repositories::login_attempts_repository_abstract::LoginAttemptsRepositoryAbstract,
+    infrastructure::settings::Settings,
+};
+
+use crate::utils::utils_setup::{setup, spawn_app, spawn_app_with};
+
+const PASSWORD: &str = "Str0ng-Passw0rd!";
+
+async fn register(client: &Client, api_address: &str, email: &str) {
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": "grace", "email": email, "password": PASSWORD, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+}
+
+async fn login(client: &Client, api_address: &str, email: &str, password: &str) -> reqwest::Response {
+    client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+// Locks a counter directly, reaching the limit through the API would sit through every delay
+fn lock(db_name: &str, scope: &str, subject: &str) {
+    let db_connection = DbConnection::new(&dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set"), db_name, 10);
+    let mut conn = db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    diesel::sql_query(format!(
+        "INSERT INTO login_throttles (scope, subject, failed_count, last_failed_at, locked_until) VALUES ('{}', '{}', 50, NOW(), NOW() + INTERVAL '15 minutes')",
+        scope, subject
+    ))
+    .execute(&mut conn)
+    .unwrap();
+}
+
+// Puts a counter right under the lock, as after nine failed attempts
+fn fail_nine_times(db_name: &str, scope: &str, subject: &str) {
+    let db_connection = DbConnection::new(&dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set"), db_name, 10);
+    let mut conn = db_connection.get_pool().get().expect("couldn't get db connection from pool");
+    diesel::sql_query(format!(
+        "INSERT INTO login_throttles (scope, subject, failed_count, last_failed_at) VALUES ('{}', '{}', 9, NOW())",
+        scope, subject
+    ))
+    .execute(&mut conn)
+    .unwrap();
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_attempts_in_flight_over_the_limit() {
+    // setup
+    let _ctx = setup();
+    let settings = Settings::load_with(|settings| settings.database.name = _ctx.db_name.clone()).expect("Failed to load settings");
+    let login_attempts_repository = LoginAttemptsRepository {
+        db_connection: Arc::new(DbConnection::new(&settings.database.url, &settings.database.name, 10)),
+        token_settings: settings.auth.tokens,
+        throttle_settings: settings.auth.login_throttle,
+    };
+    let attempt_payload = UserLoginAttemptPayload::new(String::from("judy@tasktracker.test"), None);
+
+    // given an account one failure away from the lock
+    fail_nine_times(&_ctx.db_name, "account", "judy@tasktracker.test");
+
+    // when two attempts come in before the password of either is checked
+    let first = login_attempts_repository.count_login_attempt(&attempt_payload).await.unwrap();
+    let second = login_attempts_repository.count_login_attempt(&attempt_payload).await.unwrap();
+
+    // then only the first one may check its password
+    assert!(!first.exhausted);
+    assert!(second.exhausted);
+    assert_eq!(second.locked_until, None);
+
+    // and its failure locks the account
+    login_attempts_repository.record_failed_login(&attempt_payload).await.unwrap();
+    let third = login_attempts_repository.count_login_attempt(&attempt_payload).await.unwrap();
+    assert!(third.locked_until.is_some());
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_locked_account_even_with_right_password() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a locked account and a locked unknown email
+    register(&client, &api_address, "grace@tasktracker.test").await;
+    lock(&_ctx.db_name, "account", "grace@tasktracker.test");
+    lock(&_ctx.db_name, "account", "nobody@tasktracker.test");
+
+    // when signing in to both
+    let registered_response = login(&client, &api_address, "Grace@tasktracker.test", PASSWORD).await;
+    let unknown_response = login(&client, &api_address, "nobody@tasktracker.test", PASSWORD).await;
+
+    // then expect the same answer, not revealing which one exists
+    assert_eq!(registered_response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(unknown_response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let registered_json = registered_response.json::<Value>().await.unwrap();
+    let unknown_json = unknown_response.json::<Value>().await.unwrap();
+    assert_eq!(registered_json["message"], unknown_json["message"]);
+}
+
+#[actix_rt::test]
+async fn test_should_forget_failures_after_successful_login() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a few wrong passwords
+    register(&client, &api_address, "heidi@tasktracker.test").await;
+    for _ in 0..3 {
+        let response = login(&client, &api_address, "heidi@tasktracker.test", "Wr0ng-Passw0rd!").await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // when the right one follows
+    let response = login(&client, &api_address, "heidi@tasktracker.test", PASSWORD).await;
+
+    // then expect to be signed in
+    assert!(response.status().is_success());
+    let content_json = response.json::<Value>().await.unwrap();
+    assert!(content_json["data"]["access_token"].is_string());
+}
+
+#[actix_rt::test]
+async fn test_should_keep_ip_locked_despite_forged_forwarded_header() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app_with(&_ctx.db_name, |settings| settings.rate_limit.trusted_proxies = vec![String::from("10.0.0.1")]);
+    let client = Client::new();
+
+    // given the IP of the test client locked, the client not being a trusted proxy
+    register(&client, &api_address, "ivan@tasktracker.test").await;
+    lock(&_ctx.db_name, "ip", "127.0.0.1");
+
+    // when signing in while claiming to be forwarded for another address
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .header("X-Forwarded-For", "203.0.113.7")
+        .json(&json!({ "email": "ivan@tasktracker.test", "password": PASSWORD }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect the lock of the real peer address to apply
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+}
@@ -0,0 +1,84 @@
+use reqwest::{header, Client, StatusCode};
+use serde_json::json;
+
+use crate::utils::utils_setup::{setup, spawn_app, spawn_app_with};
+
+async fn login(client: &Client, api_address: &str, password: &str) -> StatusCode {
+    client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": "dave@tasktracker.test", "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .status()
+}
+
+#[actix_rt::test]
+async fn test_should_expose_request_login_pool_and_task_metrics() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a user who signed in once with a wrong then the right password, and created a task
+    let password = "Str0ng-Passw0rd!";
+    client
+        .post(format!("{}/api/v1/users/register", &api_address))
+        .json(&json!({ "username": "dave", "email": "dave@tasktracker.test", "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(login(&client, &api_address, "wrong-password").await, StatusCode::UNAUTHORIZED);
+    let response = client
+        .post(format!("{}/api/v1/users/login", &api_address))
+        .json(&json!({ "email": "dave@tasktracker.test", "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let access_token = response.json::<serde_json::Value>().await.unwrap()["data"]["access_token"].as_str().unwrap().to_string();
+    client
+        .post(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "title": "Measured task" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // when Prometheus scrapes the service
+    let response = client.get(format!("{}/metrics", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect every family in the text format
+    assert!(response.status().is_success());
+    assert!(response.headers()[header::CONTENT_TYPE].to_str().unwrap().starts_with("text/plain; version=0.0.4"));
+    let body = response.text().await.unwrap();
+    assert!(body.contains("http_requests_total{method=\"POST\",route=\"/api/v1/users/login\",status=\"2xx\"} 1"));
+    assert!(body.contains("http_requests_total{method=\"POST\",route=\"/api/v1/users/login\",status=\"4xx\"} 1"));
+    assert!(body.contains("http_request_duration_seconds_count{method=\"POST\",route=\"/api/v1/tasks/one_own\"} 1"));
+    assert!(body.contains("login_attempts_total{result=\"success\"} 1"));
+    assert!(body.contains("login_attempts_total{result=\"failure\"} 1"));
+    assert!(body.contains("db_pool_connections{state=\"idle\"}"));
+    assert!(body.contains("db_pool_wait_seconds_count"));
+    // the three fixture tasks are open as well
+    assert!(body.contains("tasks_open{status=\"none\"} 4"));
+}
+
+#[actix_rt::test]
+async fn test_should_require_scrape_token_when_configured() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app_with(&_ctx.db_name, |settings| settings.metrics.token = Some(String::from("scrape-secret")));
+    let client = Client::new();
+
+    // when scraping without then with the token
+    let anonymous_response = client.get(format!("{}/metrics", &api_address)).send().await.expect("Failed to execute request.");
+    let response = client
+        .get(format!("{}/metrics", &api_address))
+        .bearer_auth("scrape-secret")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect only the second one to be served
+    assert_eq!(anonymous_response.status(), StatusCode::UNAUTHORIZED);
+    assert!(response.status().is_success());
+}
@@ -0,0 +1,153 @@
+use reqwest::{redirect::Policy, Client, StatusCode, Url};
+use serde_json::{json, Value};
+
+use crate::utils::{
+    mock_idp::{mock_idp, MOCK_IDP_PROVIDER},
+    utils_setup::{setup, spawn_app, spawn_app_with},
+};
+
+fn client_without_redirects() -> Client {
+    Client::builder().redirect(Policy::none()).build().unwrap()
+}
+
+// Follows the redirects up to the identity provider answer, returns the query the callback receives
+async fn sign_in_at_idp(client: &Client, api_address: &str, login_hint: &str) -> String {
+    let response = client
+        .get(format!("{}/api/v1/oidc/{}/authorize", api_address, MOCK_IDP_PROVIDER))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::FOUND);
+
+    let authorization_url = response.headers()["location"].to_str().unwrap().to_string();
+    let response = client
+        .get(format!("{}&login_hint={}", authorization_url, login_hint))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::FOUND);
+
+    let redirect_url = Url::parse(response.headers()["location"].to_str().unwrap()).unwrap();
+    redirect_url.query().unwrap().to_string()
+}
+
+async fn callback(client: &Client, api_address: &str, query: &str) -> reqwest::Response {
+    client
+        .get(format!("{}/api/v1/oidc/{}/callback?{}", api_address, MOCK_IDP_PROVIDER, query))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[actix_rt::test]
+async fn test_should_provision_account_then_sign_it_in_again() {
+    // setup
+    let _ctx = setup();
+    mock_idp();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = client_without_redirects();
+
+    // given a first sign-in of carol at the identity provider
+    let query = sign_in_at_idp(&client, &api_address, "carol").await;
+    let first_response = callback(&client, &api_address, &query).await;
+    assert!(first_response.status().is_success());
+    let first_json = first_response.json::<Value>().await.unwrap();
+    assert!(first_json["data"]["access_token"].is_string());
+
+    // when she signs in a second time
+    let query = sign_in_at_idp(&client, &api_address, "carol").await;
+    let second_json = callback(&client, &api_address, &query).await.json::<Value>().await.unwrap();
+
+    // then expect the same provisioned account
+    assert_eq!(first_json["data"]["user_id"], second_json["data"]["user_id"]);
+    assert_eq!(second_json["data"]["email"], "carol@idp.test");
+}
+
+async fn register(client: &Client, api_address: &str, username: &str) -> Value {
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": format!("{}@idp.test", username), "password": "Str0ng-Passw0rd!", "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json::<Value>()
+        .await
+        .unwrap()
+}
+
+#[actix_rt::test]
+async fn test_should_link_existing_account_when_provider_trusts_email() {
+    // setup
+    let _ctx = setup();
+    mock_idp();
+    let api_address = spawn_app_with(&_ctx.db_name, |settings| settings.oidc.providers.iter_mut().for_each(|provider| provider.trust_email = true));
+    let client = client_without_redirects();
+
+    // given dave registered with a password
+    let registered_json = register(&client, &api_address, "dave").await;
+
+    // when he signs in through the identity provider
+    let query = sign_in_at_idp(&client, &api_address, "dave").await;
+    let signed_in_json = callback(&client, &api_address, &query).await.json::<Value>().await.unwrap();
+
+    // then expect his existing account
+    assert_eq!(registered_json["data"]["user_id"], signed_in_json["data"]["user_id"]);
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_to_link_existing_account_by_default() {
+    // setup
+    let _ctx = setup();
+    mock_idp();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = client_without_redirects();
+
+    // given gina registered with a password
+    register(&client, &api_address, "gina").await;
+
+    // when someone signs in through the identity provider with that email
+    let query = sign_in_at_idp(&client, &api_address, "gina").await;
+    let response = callback(&client, &api_address, &query).await;
+
+    // then expect the account to stay out of reach
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let json = response.json::<Value>().await.unwrap();
+    assert!(json["data"].is_null() || json["data"]["access_token"].is_null());
+}
+
+#[actix_rt::test]
+async fn test_should_reject_replayed_callback() {
+    // setup
+    let _ctx = setup();
+    mock_idp();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = client_without_redirects();
+
+    // given a completed sign-in
+    let query = sign_in_at_idp(&client, &api_address, "erin").await;
+    assert!(callback(&client, &api_address, &query).await.status().is_success());
+
+    // when the same callback is replayed
+    let response = callback(&client, &api_address, &query).await;
+
+    // then expect it to be refused
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_identity_without_verified_email() {
+    // setup
+    let _ctx = setup();
+    mock_idp();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = client_without_redirects();
+
+    // given an identity whose email the provider did not verify
+    let query = sign_in_at_idp(&client, &api_address, "unverified-frank").await;
+
+    // when completing the sign-in
+    let response = callback(&client, &api_address, &query).await;
+
+    // then expect no account to be provisioned
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
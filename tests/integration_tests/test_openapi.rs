@@ -0,0 +1,71 @@
+use std::collections::BTreeSet;
+
+use regex::Regex;
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// (method, path) of every route registered by a controller file, under the scope it is mounted on
+fn controller_routes(file: &str, scope: &str) -> BTreeSet<(String, String)> {
+    let source = std::fs::read_to_string(format!("{}/src/adapters/api/{}", env!("CARGO_MANIFEST_DIR"), file)).unwrap();
+    let route = Regex::new(r#"#\[(get|post|patch|put|delete)\("([^"]*)"\)\]"#).unwrap();
+    route.captures_iter(&source).map(|c| (c[1].to_string(), format!("{}{}", scope, &c[2]))).collect()
+}
+
+fn documented_routes(spec: &Value, scope: &str) -> BTreeSet<(String, String)> {
+    spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .filter(|(path, _)| path.starts_with(scope))
+        .flat_map(|(path, operations)| operations.as_object().unwrap().keys().map(move |method| (method.clone(), path.clone())))
+        .collect()
+}
+
+#[actix_rt::test]
+async fn test_should_document_every_task_and_user_route() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when fetching the document
+    let response = client.get(format!("{}/openapi.json", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect an OpenAPI 3.1 document with the bearer scheme
+    assert_eq!(response.status(), StatusCode::OK);
+    let spec = response.json::<Value>().await.unwrap();
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3.1"));
+    assert_eq!(spec["components"]["securitySchemes"]["bearer_auth"]["scheme"], "bearer");
+
+    // and the documented routes to match the registered ones exactly, both ways
+    for (file, scope) in [("tasks/tasks_controllers.rs", "/api/v1/tasks"), ("users/users_controllers.rs", "/api/v1/users")] {
+        assert_eq!(
+            documented_routes(&spec, scope),
+            controller_routes(file, scope),
+            "routes of {} drifted from the OpenAPI document",
+            file
+        );
+    }
+
+    // and authenticated routes to declare it, public ones not
+    assert_eq!(spec["paths"]["/api/v1/tasks/one_own"]["post"]["security"][0]["bearer_auth"], serde_json::json!([]));
+    assert!(spec["paths"]["/api/v1/users/login"]["post"]["security"].is_null());
+    assert!(spec["paths"]["/api/v1/users/register"]["post"]["responses"]["422"].is_object());
+}
+
+#[actix_rt::test]
+async fn test_should_serve_swagger_ui() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when opening the explorer
+    let response = client.get(format!("{}/swagger-ui/", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect the page
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.text().await.unwrap().contains("swagger"));
+}
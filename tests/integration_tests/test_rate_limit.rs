@@ -0,0 +1,44 @@
+use reqwest::{Client, StatusCode};
+
+use crate::utils::utils_setup::{setup, spawn_app_with};
+
+// Route limited this tightly by the test server only
+const LIMITED_ROUTE: &str = "/.well-known/jwks.json";
+
+async fn get_limited_route(client: &Client, api_address: &str, forwarded_for: &str) -> reqwest::Response {
+    client
+        .get(format!("{}{}", api_address, LIMITED_ROUTE))
+        .header("X-Forwarded-For", forwarded_for)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+}
+
+#[actix_rt::test]
+async fn test_should_limit_route_per_client_ip() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app_with(&_ctx.db_name, |settings| {
+        settings.rate_limit.routes = vec![format!("GET {}=2/60", LIMITED_ROUTE)];
+        // the test client connects from loopback and stands in for the reverse proxy
+        settings.rate_limit.trusted_proxies = vec![String::from("127.0.0.1")];
+    });
+    let client = Client::new();
+
+    // given a client that used up its two requests
+    for remaining in ["1", "0"] {
+        let response = get_limited_route(&client, &api_address, "203.0.113.7").await;
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()["ratelimit-limit"], "2");
+        assert_eq!(response.headers()["ratelimit-remaining"], remaining);
+    }
+
+    // when it calls again, and another client behind the same proxy calls
+    let limited_response = get_limited_route(&client, &api_address, "203.0.113.7").await;
+    let other_response = get_limited_route(&client, &api_address, "198.51.100.4").await;
+
+    // then expect only the first one to wait
+    assert_eq!(limited_response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(limited_response.headers()["retry-after"].to_str().unwrap().parse::<u64>().unwrap() > 0);
+    assert!(other_response.status().is_success());
+}
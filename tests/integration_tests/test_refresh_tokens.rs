@@ -0,0 +1,93 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns (access token, refresh token)
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> (String, String) {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    (
+        content_json["data"]["access_token"].as_str().unwrap().to_string(),
+        content_json["data"]["refresh_token"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn refresh(client: &Client, api_address: &str, refresh_token: &str) -> (StatusCode, Value) {
+    let response = client
+        .post(format!("{}/api/v1/users/refresh", api_address))
+        .json(&json!({ "refresh_token": refresh_token }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    (response.status(), response.json::<Value>().await.unwrap())
+}
+
+async fn get_own_user(client: &Client, api_address: &str, access_token: &str) -> StatusCode {
+    let response = client
+        .get(format!("{}/api/v1/users/one_own", api_address))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    response.status()
+}
+
+#[actix_rt::test]
+async fn test_should_reject_access_tokens_of_a_session_right_after_refresh_token_reuse() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a session whose refresh token was rotated, with the revocation list already loaded
+    let (access_token, refresh_token) = register_and_login(&client, &api_address, "pia").await;
+    assert_eq!(get_own_user(&client, &api_address, &access_token).await, StatusCode::OK);
+    let (status, _) = refresh(&client, &api_address, &refresh_token).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // when the rotated refresh token is replayed
+    let (status, content_json) = refresh(&client, &api_address, &refresh_token).await;
+
+    // then the session is revoked and its access token stops working at once
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(content_json["error_code"], "unauthorized");
+    assert_eq!(get_own_user(&client, &api_address, &access_token).await, StatusCode::UNAUTHORIZED);
+}
+
+#[actix_rt::test]
+async fn test_should_not_swap_access_and_refresh_tokens() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a signed in user
+    let (access_token, refresh_token) = register_and_login(&client, &api_address, "noa").await;
+
+    // when the refresh token is sent as a bearer token and the access token to the refresh endpoint
+    let bearer_status = get_own_user(&client, &api_address, &refresh_token).await;
+    let (refresh_status, _) = refresh(&client, &api_address, &access_token).await;
+
+    // then both are turned down
+    assert_eq!(bearer_status, StatusCode::UNAUTHORIZED);
+    assert_eq!(refresh_status, StatusCode::UNAUTHORIZED);
+    assert_eq!(get_own_user(&client, &api_address, &access_token).await, StatusCode::OK);
+}
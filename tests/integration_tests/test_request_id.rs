@@ -0,0 +1,50 @@
+use reqwest::{Client, StatusCode};
+use uuid::Uuid;
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+#[actix_rt::test]
+async fn test_should_generate_request_id() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when calling the api without a request id, once on a route and once on an unknown path
+    let response = client.get(format!("{}/health/live", &api_address)).send().await.expect("Failed to execute request.");
+    let not_found_response = client.get(format!("{}/api/v1/nowhere", &api_address)).send().await.expect("Failed to execute request.");
+
+    // then expect a new id on both responses
+    let request_id = response.headers().get("x-request-id").expect("missing X-Request-Id").to_str().unwrap().to_string();
+    assert!(Uuid::parse_str(&request_id).is_ok());
+    assert_eq!(not_found_response.status(), StatusCode::NOT_FOUND);
+    let other_request_id = not_found_response.headers().get("x-request-id").expect("missing X-Request-Id").to_str().unwrap();
+    assert_ne!(request_id, other_request_id);
+}
+
+#[actix_rt::test]
+async fn test_should_propagate_request_id() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when calling the api with an id from a proxy and with one holding a line break attempt
+    let response = client
+        .get(format!("{}/health/live", &api_address))
+        .header("X-Request-Id", "edge-4f1c:42")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let forged_response = client
+        .get(format!("{}/health/live", &api_address))
+        .header("X-Request-Id", "abc\" level=error")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect the valid id echoed back and the other one replaced
+    assert_eq!(response.headers().get("x-request-id").unwrap(), "edge-4f1c:42");
+    let replaced = forged_response.headers().get("x-request-id").unwrap().to_str().unwrap();
+    assert!(Uuid::parse_str(replaced).is_ok());
+}
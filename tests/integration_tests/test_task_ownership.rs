@@ -0,0 +1,189 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns (user id, access token)
+async fn register_and_login(client: &Client, api_address: &str, username: &str, role: &str) -> (String, String) {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": role }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    (
+        content_json["data"]["user_id"].as_str().unwrap().to_string(),
+        content_json["data"]["access_token"].as_str().unwrap().to_string(),
+    )
+}
+
+async fn create_own_task(client: &Client, api_address: &str, access_token: &str) -> String {
+    let response = client
+        .post(format!("{}/api/v1/tasks/one_own", api_address))
+        .bearer_auth(access_token)
+        .json(&json!({ "title": "Private task" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["task_id"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn test_should_hide_task_of_another_user() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a task owned by alice
+    let (_, alice_token) = register_and_login(&client, &api_address, "alice", "customer").await;
+    let (_, bob_token) = register_and_login(&client, &api_address, "bob", "customer").await;
+    let task_id = create_own_task(&client, &api_address, &alice_token).await;
+
+    // when bob gets it by id
+    let response = client
+        .get(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&bob_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect the same answer as for a task that doesn't exist
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["error_code"], "not_found");
+
+    let response = client
+        .get(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&bob_token)
+        .json(&json!({ "task_id": "00000000-0000-0000-0000-000000000000" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[actix_rt::test]
+async fn test_should_not_update_or_delete_task_of_another_user() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a task owned by alice
+    let (_, alice_token) = register_and_login(&client, &api_address, "alice", "customer").await;
+    let (_, bob_token) = register_and_login(&client, &api_address, "bob", "customer").await;
+    let task_id = create_own_task(&client, &api_address, &alice_token).await;
+
+    // when bob updates then deletes it
+    let update_response = client
+        .patch(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&bob_token)
+        .json(&json!({ "task_id": task_id, "title": "Taken over" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let delete_response = client
+        .delete(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&bob_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect both to be refused as not found and the task to be unchanged for alice
+    assert_eq!(update_response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(delete_response.status(), StatusCode::NOT_FOUND);
+
+    let response = client
+        .get(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&alice_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["data"]["title"], "Private task");
+}
+
+#[actix_rt::test]
+async fn test_should_not_create_task_for_another_user() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given two customers
+    let (alice_id, _) = register_and_login(&client, &api_address, "alice", "customer").await;
+    let (_, bob_token) = register_and_login(&client, &api_address, "bob", "customer").await;
+
+    // when bob creates a task on alice's behalf
+    let response = client
+        .post(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&bob_token)
+        .json(&json!({ "user_id": alice_id, "title": "Planted task" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect it to be forbidden
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[actix_rt::test]
+async fn test_should_let_admin_read_and_delete_any_task() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a task owned by alice
+    let (_, alice_token) = register_and_login(&client, &api_address, "alice", "customer").await;
+    let (_, admin_token) = register_and_login(&client, &api_address, "admin", "admin").await;
+    let task_id = create_own_task(&client, &api_address, &alice_token).await;
+
+    // when an admin gets then deletes it
+    let get_response = client
+        .get(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&admin_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let delete_response = client
+        .delete(format!("{}/api/v1/tasks/one", &api_address))
+        .bearer_auth(&admin_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect both to succeed and the task to be gone for alice
+    assert!(get_response.status().is_success());
+    assert!(delete_response.status().is_success());
+
+    let response = client
+        .get(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&alice_token)
+        .json(&json!({ "task_id": task_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,117 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str) -> String {
+    let email = "heidi@tasktracker.test";
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": "heidi", "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+async fn list_own_titles(client: &Client, api_address: &str, access_token: &str, sort: &str) -> Vec<String> {
+    let response = client
+        .get(format!("{}/api/v1/tasks/all_by_user_id_own", api_address))
+        .bearer_auth(access_token)
+        .json(&json!({ "sort": sort }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"].as_array().unwrap().iter().map(|task| task["title"].as_str().unwrap().to_string()).collect()
+}
+
+#[actix_rt::test]
+async fn test_should_sort_tasks_by_priority_rank() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given tasks whose priorities sort differently by name and by rank
+    let access_token = register_and_login(&client, &api_address).await;
+    for (title, priority) in [("medium", Some("Medium")), ("high", Some("High")), ("unset", None), ("low", Some("Low"))] {
+        let response = client
+            .post(format!("{}/api/v1/tasks/one_own", &api_address))
+            .bearer_auth(&access_token)
+            .json(&json!({ "title": title, "priority": priority }))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert!(response.status().is_success());
+    }
+
+    // when listing them by priority, both ways
+    let descending = list_own_titles(&client, &api_address, &access_token, "priority_desc").await;
+    let ascending = list_own_titles(&client, &api_address, &access_token, "priority_asc").await;
+
+    // then expect the rank order, a task without priority ranking lowest
+    assert_eq!(descending, vec!["high", "medium", "low", "unset"]);
+    assert_eq!(ascending, vec!["unset", "low", "medium", "high"]);
+}
+
+#[actix_rt::test]
+async fn test_should_store_and_update_nested_status() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a task created as a bug to do
+    let access_token = register_and_login(&client, &api_address).await;
+    let response = client
+        .post(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "title": "Fix login", "typ": "Work", "status": { "ToDo": "Bug" } }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["data"]["typ"], "Work");
+    assert_eq!(content_json["data"]["priority"], "None");
+    assert_eq!(content_json["data"]["status"], json!({ "ToDo": "Bug" }));
+    let task_id = content_json["data"]["task_id"].as_str().unwrap().to_string();
+
+    // when moving it to testing
+    let response = client
+        .patch(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "task_id": task_id, "status": { "InProgress": "Testing" } }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect the status changed and the priority left alone
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["data"]["status"], json!({ "InProgress": "Testing" }));
+    assert_eq!(content_json["data"]["priority"], "None");
+
+    // and an unknown value to be refused before reaching the database
+    let response = client
+        .post(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "title": "Unknown", "priority": "Urgent" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,135 @@
+use reqwest::{header::CONTENT_TYPE, Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+async fn import(client: &Client, api_address: &str, access_token: &str, query: &str, content_type: &str, body: &str) -> (StatusCode, Value) {
+    let response = client
+        .post(format!("{}/api/v1/tasks/import{}", api_address, query))
+        .bearer_auth(access_token)
+        .header(CONTENT_TYPE, content_type)
+        .body(body.to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    (response.status(), response.json::<Value>().await.unwrap())
+}
+
+async fn export(client: &Client, api_address: &str, access_token: &str, format: &str) -> String {
+    let response = client
+        .get(format!("{}/api/v1/tasks/export?format={}", api_address, format))
+        .bearer_auth(access_token)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-disposition"].to_str().unwrap().contains(&format!("tasks.{}", format)));
+
+    response.text().await.unwrap()
+}
+
+#[actix_rt::test]
+async fn test_should_import_a_csv_file_and_export_it_back() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a CSV file with its own column names
+    let access_token = register_and_login(&client, &api_address, "judy").await;
+    let body = "Name,Urgency,Checklist\nWrite docs,High,\"outline\nreview\"\nShip release,low,\n";
+    let mapping = "?mapping=%7B%22Name%22%3A%22title%22%2C%22Urgency%22%3A%22priority%22%2C%22Checklist%22%3A%22task_list%22%7D";
+
+    // when importing it
+    let (status, content_json) = import(&client, &api_address, &access_token, mapping, "text/csv", body).await;
+
+    // then both tasks are created for the caller
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_json["code"], 201);
+    assert_eq!(content_json["data"]["task_ids"].as_array().unwrap().len(), 2);
+
+    // and come back in every export format
+    let csv = export(&client, &api_address, &access_token, "csv").await;
+    assert!(csv.starts_with("task_id,user_id,project_id,title,typ,priority,status"));
+    assert!(csv.contains("Write docs,none,high,none,,0,0,\"outline\nreview\""));
+    let ndjson = export(&client, &api_address, &access_token, "ndjson").await;
+    let tasks: Vec<Value> = ndjson.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(tasks.len(), 2);
+    let docs = tasks.iter().find(|task| task["title"] == "Write docs").unwrap();
+    assert_eq!(docs["task_list"], json!(["outline", "review"]));
+    let release = tasks.iter().find(|task| task["title"] == "Ship release").unwrap();
+    assert_eq!(release["priority"], "low");
+    let json_file = export(&client, &api_address, &access_token, "json").await;
+    assert_eq!(serde_json::from_str::<Value>(&json_file).unwrap().as_array().unwrap().len(), 2);
+}
+
+#[actix_rt::test]
+async fn test_should_report_invalid_rows_and_import_nothing() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a JSON file with a blank title and an unknown status
+    let access_token = register_and_login(&client, &api_address, "kim").await;
+    let body = r#"[{"title":"Fine"},{"title":" "},{"title":"Odd","status":"maybe"}]"#;
+
+    // when checking it with a dry run
+    let (status, content_json) = import(&client, &api_address, &access_token, "?dry_run=true", "application/json", body).await;
+
+    // then every problem is reported by row
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_json["code"], 200);
+    assert_eq!(content_json["data"]["valid_rows"], 1);
+    let fields: Vec<&str> = content_json["data"]["errors"].as_array().unwrap().iter().map(|error| error["field"].as_str().unwrap()).collect();
+    assert_eq!(fields, vec!["rows[2].title", "rows[3].status"]);
+
+    // when importing it for real
+    let (status, content_json) = import(&client, &api_address, &access_token, "?format=json", "text/plain", body).await;
+
+    // then the import fails as a whole
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(content_json["data"].as_array().unwrap().len(), 2);
+    let ndjson = export(&client, &api_address, &access_token, "ndjson").await;
+    assert!(ndjson.is_empty());
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_an_unknown_format() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a body without a known Content-Type
+    let access_token = register_and_login(&client, &api_address, "lou").await;
+
+    // when importing it
+    let (status, _) = import(&client, &api_address, &access_token, "", "text/plain", "title\nOne\n").await;
+
+    // then the format has to be named
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
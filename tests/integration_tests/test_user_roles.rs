@@ -0,0 +1,52 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str, username: &str) -> String {
+    let email = format!("{}@tasktracker.test", username);
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": username, "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn test_should_keep_the_role_when_updating_the_own_account() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a customer
+    let access_token = register_and_login(&client, &api_address, "rosa").await;
+
+    // when they update their account claiming to be a super admin promoting themselves
+    let response = client
+        .patch(format!("{}/api/v1/users/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "username": "rosalind", "role": "super_admin", "role_promote": "promote" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then the account is updated and the role left as it was
+    assert_eq!(response.status(), StatusCode::OK);
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["data"]["username"], "rosalind");
+    assert_eq!(content_json["data"]["role"], "customer");
+}
@@ -0,0 +1,122 @@
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::utils::utils_setup::{setup, spawn_app};
+
+// Registers a user and signs them in, returns the access token
+async fn register_and_login(client: &Client, api_address: &str) -> String {
+    let email = "grace@tasktracker.test";
+    let password = "Str0ng-Passw0rd!";
+
+    client
+        .post(format!("{}/api/v1/users/register", api_address))
+        .json(&json!({ "username": "grace", "email": email, "password": password, "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .post(format!("{}/api/v1/users/login", api_address))
+        .json(&json!({ "email": email, "password": password }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let content_json = response.json::<Value>().await.unwrap();
+
+    content_json["data"]["access_token"].as_str().unwrap().to_string()
+}
+
+#[actix_rt::test]
+async fn test_should_report_every_invalid_task_field() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // given a signed in user
+    let access_token = register_and_login(&client, &api_address).await;
+
+    // when creating a task with a blank title, a negative duration and a malformed project id
+    let response = client
+        .post(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "title": "  ", "duration": -5, "project_id": "not-a-uuid" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect each failure listed, sorted by field
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["error_code"], "validation_failed");
+    assert_eq!(
+        content_json["data"],
+        json!([
+            { "field": "duration", "code": "out_of_range", "message": "must not be negative" },
+            { "field": "project_id", "code": "invalid_uuid", "message": "must be a valid UUID" },
+            { "field": "title", "code": "required", "message": "must not be empty" },
+        ])
+    );
+
+    // and a malformed task id to be refused rather than looked up
+    let response = client
+        .get(format!("{}/api/v1/tasks/one_own", &api_address))
+        .bearer_auth(&access_token)
+        .json(&json!({ "task_id": "42" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let content_json = response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["data"][0]["field"], "task_id");
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_registration_with_invalid_fields() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+
+    // when registering with a username too short, an invalid email and a short password
+    let response = client
+        .post(format!("{}/api/v1/users/register", &api_address))
+        .json(&json!({ "username": "al", "email": "al@", "password": "short", "role": "customer" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect no account and the three fields reported
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let content_json = response.json::<Value>().await.unwrap();
+    let codes: Vec<&str> = content_json["data"].as_array().unwrap().iter().map(|field| field["code"].as_str().unwrap()).collect();
+    assert_eq!(codes, vec!["invalid_email", "weak_password", "invalid_username"]);
+}
+
+#[actix_rt::test]
+async fn test_should_refuse_malformed_optional_body() {
+    // setup
+    let _ctx = setup();
+    let api_address = spawn_app(&_ctx.db_name);
+    let client = Client::new();
+    let url = format!("{}/api/v1/tasks/all_by_user_id_own", &api_address);
+
+    // given a signed in user
+    let access_token = register_and_login(&client, &api_address).await;
+
+    // when listing own tasks without a body, then with a body that isn't JSON
+    let empty_response = client.get(&url).bearer_auth(&access_token).send().await.expect("Failed to execute request.");
+    let malformed_response = client
+        .get(&url)
+        .bearer_auth(&access_token)
+        .header("Content-Type", "application/json")
+        .body("{\"user_id\": ")
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // then expect the body left out to be fine and the malformed one refused
+    assert!(empty_response.status().is_success());
+    assert_eq!(malformed_response.status(), StatusCode::BAD_REQUEST);
+    let content_json = malformed_response.json::<Value>().await.unwrap();
+    assert_eq!(content_json["error_code"], "bad_request");
+}
@@ -1,5 +1,4 @@
 pub mod integration_tests;
 pub mod utils;
 
-#[macro_use]
 extern crate diesel_migrations;
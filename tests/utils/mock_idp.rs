@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    env,
+    net::TcpListener,
+    sync::{mpsc, Mutex, OnceLock},
+    thread,
+};
+
+use actix_web::{get, http::header, post, web, App, HttpResponse, HttpServer};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use openssl::rsa::Rsa;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+pub const MOCK_IDP_PROVIDER: &str = "mock";
+pub const MOCK_IDP_CLIENT_ID: &str = "tasktracker";
+const MOCK_IDP_KID: &str = "mock-key";
+
+static MOCK_IDP: OnceLock<String> = OnceLock::new();
+
+struct MockIdpState {
+    issuer: String,
+    encoding_key: EncodingKey,
+    jwk: Value,
+    codes: Mutex<HashMap<String, PendingCode>>,
+}
+
+struct PendingCode {
+    login_hint: String,
+    nonce: String,
+    code_challenge: String,
+}
+
+#[derive(Deserialize)]
+struct AuthorizeQuery {
+    client_id: String,
+    redirect_uri: String,
+    state: String,
+    nonce: String,
+    code_challenge: String,
+    code_challenge_method: String,
+    // picks the signed in user, "unverified-" names get an unverified email
+    login_hint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenForm {
+    grant_type: String,
+    code: String,
+    client_id: String,
+    code_verifier: String,
+}
+
+// Identity provider signing in whoever `login_hint` names, shared by every test and registered as the "mock" provider
+pub fn mock_idp() -> &'static str {
+    MOCK_IDP.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
+            let issuer = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+
+            let rsa = Rsa::generate(2048).unwrap();
+            let state = web::Data::new(MockIdpState {
+                issuer: issuer.clone(),
+                encoding_key: EncodingKey::from_rsa_pem(&rsa.private_key_to_pem().unwrap()).unwrap(),
+                jwk: json!({
+                    "kty": "RSA",
+                    "use": "sig",
+                    "alg": "RS256",
+                    "kid": MOCK_IDP_KID,
+                    "n": URL_SAFE_NO_PAD.encode(rsa.n().to_vec()),
+                    "e": URL_SAFE_NO_PAD.encode(rsa.e().to_vec()),
+                }),
+                codes: Mutex::new(HashMap::new()),
+            });
+
+            actix_rt::System::new().block_on(async move {
+                let server = HttpServer::new(move || App::new().app_data(state.clone()).service(discovery).service(jwks).service(authorize).service(token))
+                    .listen(listener)
+                    .unwrap()
+                    .run();
+                sender.send(issuer).unwrap();
+                server.await.unwrap();
+            });
+        });
+
+        let issuer = receiver.recv().unwrap();
+        env::set_var("OIDC_PROVIDERS", MOCK_IDP_PROVIDER);
+        env::set_var("OIDC_MOCK_ISSUER", &issuer);
+        env::set_var("OIDC_MOCK_CLIENT_ID", MOCK_IDP_CLIENT_ID);
+        env::set_var("OIDC_MOCK_REDIRECT_URI", "http://127.0.0.1/sso/callback");
+        issuer
+    })
+}
+
+#[get("/.well-known/openid-configuration")]
+async fn discovery(state: web::Data<MockIdpState>) -> HttpResponse {
+    HttpResponse::Ok().json(json!({
+        "issuer": state.issuer,
+        "authorization_endpoint": format!("{}/authorize", state.issuer),
+        "token_endpoint": format!("{}/token", state.issuer),
+        "jwks_uri": format!("{}/jwks", state.issuer),
+    }))
+}
+
+#[get("/jwks")]
+async fn jwks(state: web::Data<MockIdpState>) -> HttpResponse {
+    HttpResponse::Ok().json(json!({ "keys": [state.jwk] }))
+}
+
+#[get("/authorize")]
+async fn authorize(state: web::Data<MockIdpState>, query: web::Query<AuthorizeQuery>) -> HttpResponse {
+    if query.client_id != MOCK_IDP_CLIENT_ID || query.code_challenge_method != "S256" {
+        return HttpResponse::BadRequest().finish();
+    }
+
+    let code = Uuid::new_v4().to_string();
+    state.codes.lock().unwrap().insert(
+        code.clone(),
+        PendingCode {
+            login_hint: query.login_hint.clone().unwrap_or_else(|| String::from("alice")),
+            nonce: query.nonce.clone(),
+            code_challenge: query.code_challenge.clone(),
+        },
+    );
+
+    HttpResponse::Found()
+        .insert_header((header::LOCATION, format!("{}?code={}&state={}", query.redirect_uri, code, query.state)))
+        .finish()
+}
+
+#[post("/token")]
+async fn token(state: web::Data<MockIdpState>, form: web::Form<TokenForm>) -> HttpResponse {
+    let pending = match state.codes.lock().unwrap().remove(&form.code) {
+        Some(pending) => pending,
+        None => return HttpResponse::BadRequest().json(json!({ "error": "invalid_grant" })),
+    };
+    let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(form.code_verifier.as_bytes()));
+    if form.grant_type != "authorization_code" || form.client_id != MOCK_IDP_CLIENT_ID || code_challenge != pending.code_challenge {
+        return HttpResponse::BadRequest().json(json!({ "error": "invalid_grant" }));
+    }
+
+    let now = Utc::now().timestamp();
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(String::from(MOCK_IDP_KID));
+    let claims = json!({
+        "iss": state.issuer,
+        "aud": MOCK_IDP_CLIENT_ID,
+        "sub": format!("sub-{}", pending.login_hint),
+        "iat": now,
+        "exp": now + 300,
+        "nonce": pending.nonce,
+        "email": format!("{}@idp.test", pending.login_hint),
+        "email_verified": !pending.login_hint.starts_with("unverified-"),
+        "name": pending.login_hint,
+    });
+
+    HttpResponse::Ok().json(json!({
+        "access_token": Uuid::new_v4().to_string(),
+        "token_type": "Bearer",
+        "id_token": encode(&header, &claims, &state.encoding_key).unwrap(),
+    }))
+}
@@ -1,3 +1,4 @@
+pub mod mock_idp;
 pub mod test_context;
 pub mod utils_file;
 pub mod utils_setup;
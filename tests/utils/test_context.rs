@@ -1,5 +1,3 @@
-use std::error::Error;
-
 use crate::integration_tests::fixtures::fixtures_run;
 use diesel::RunQueryDsl;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
@@ -7,53 +5,49 @@ use fixtures_run::execute_imports;
 use tasktracker_backend::adapters::spi::db::db_connection::DbConnection;
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("./migrations");
 
-// embed_migrations!("./migrations");
-
 pub struct TestContextPostgreSQL {
     pub base_url: String,
     pub db_name: String,
 }
 
 impl TestContextPostgreSQL {
-    pub fn new(base_url: &str, db_name: &str) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+    pub fn new(base_url: &str, db_name: &str) -> Self {
         // connect to "postgres" db to be able to create our test database.
-        let db_connection_postgres_db = DbConnection { db_name: "postgres".to_string() };
+        let db_connection_postgres_db = DbConnection::new(base_url, "postgres", 10);
         let mut conn_postgres_db = db_connection_postgres_db.get_pool().get().expect("couldn't get db connection from pool");
 
         let query = diesel::sql_query(format!("CREATE DATABASE {};", db_name).as_str());
-        query.execute(&conn_postgres_db).unwrap_or_else(|_| panic!("couldn't create database {}", db_name));
+        query.execute(&mut conn_postgres_db).unwrap_or_else(|_| panic!("couldn't create database {}", db_name));
 
         // connect to the "test" db created above
-        let db_connection_test_db = DbConnection { db_name: db_name.to_string() };
+        let db_connection_test_db = DbConnection::new(base_url, db_name, 10);
         let mut conn_test_db = db_connection_test_db.get_pool().get().expect("couldn't get db connection from pool");
 
         // create data model
-        // embedded_migrations::run_with_output(&conn_test_db, &mut std::io::stdout()).expect("couldn't run migration");
-        conn_test_db.run_pending_migrations(MIGRATIONS)?;
+        conn_test_db.run_pending_migrations(MIGRATIONS).expect("couldn't run migrations");
 
         // insert fixtures
         execute_imports(&db_connection_test_db);
 
-        // Self {
-        //     base_url: base_url.to_string(),
-        //     db_name: db_name.to_string(),
-        // }
-        Ok(())
+        Self {
+            base_url: base_url.to_string(),
+            db_name: db_name.to_string(),
+        }
     }
 }
 
 impl Drop for TestContextPostgreSQL {
     fn drop(&mut self) {
         // connect to "postgres" db to be able to drop our "tests" databases.
-        let db_connection_postgres_db = DbConnection { db_name: "postgres".to_string() };
-        let conn_postgres_db = db_connection_postgres_db.get_pool().get().expect("couldn't get db connection from pool");
+        let db_connection_postgres_db = DbConnection::new(&self.base_url, "postgres", 10);
+        let mut conn_postgres_db = db_connection_postgres_db.get_pool().get().expect("couldn't get db connection from pool");
 
         // disconnect user before dropping the db
         let disconnect_users = format!("SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}';", self.db_name);
-        diesel::sql_query(disconnect_users.as_str()).execute(&conn_postgres_db).unwrap();
+        diesel::sql_query(disconnect_users.as_str()).execute(&mut conn_postgres_db).unwrap();
 
-        // drop!
-        let query = diesel::sql_query(format!("DROP DATABASE {};", self.db_name).as_str());
-        query.execute(&conn_postgres_db).unwrap_or_else(|_| panic!("couldn't drop test database {}", self.db_name));
+        // drop! FORCE also closes connections the app's pool reopened since the statement above
+        let query = diesel::sql_query(format!("DROP DATABASE {} WITH (FORCE);", self.db_name).as_str());
+        query.execute(&mut conn_postgres_db).unwrap_or_else(|_| panic!("couldn't drop test database {}", self.db_name));
     }
 }
@@ -1,50 +1,53 @@
-use actix_web::{web, App, HttpResponse, HttpServer};
-use std::net::TcpStream;
 use std::{env, net::TcpListener};
+use tasktracker_backend::infrastructure::settings::Settings;
 use uuid::Uuid;
 
 use super::test_context::TestContextPostgreSQL;
-use crate::utils::utils_file::read_from_file;
 
 pub fn spawn_app(db_name: &str) -> String {
+    spawn_app_with(db_name, |_| {})
+}
+
+// Same as `spawn_app`, `overrides` adjusts the settings of this server only, unlike environment variables
+pub fn spawn_app_with(db_name: &str, overrides: impl FnOnce(&mut Settings)) -> String {
     // Let the OS assign a port (:0)
     let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
 
     let port = listener.local_addr().unwrap().port();
-    let server = tasktracker_backend::run(listener, db_name).expect("Failed to bind address");
-
-    let _ = tokio::spawn(server);
+    let settings = Settings::load_with(|settings| {
+        settings.database.name = db_name.to_string();
+        overrides(settings);
+    })
+    .expect("Failed to load settings");
+    let server = tasktracker_backend::run(listener, settings).expect("Failed to bind address");
 
-    if TcpStream::connect("localhost:3333").is_ok() {
-        println!("Http source faked server already running");
-    } else {
-        spawn_http_spi();
-    }
+    tokio::spawn(server);
 
     format!("http://127.0.0.1:{}", port)
 }
 
-pub fn spawn_http_spi() -> String {
-    async fn facts_route() -> HttpResponse {
-        let json = read_from_file::<CatFactsApiModel>("tests/integration_tests/fixtures/cat_facts.json").unwrap();
-        HttpResponse::Ok().json(json)
-    }
+// Same as `spawn_app`, with the gRPC services on a second port. Returns the HTTP and the gRPC addresses.
+pub fn spawn_app_with_grpc(db_name: &str) -> (String, String) {
+    spawn_grpc_app_with(db_name, |_| {})
+}
 
-    async fn random_fact_route() -> HttpResponse {
-        HttpResponse::Ok().json(CatFactApiModel {
-            fact: String::from("In the 1930s, two Russian biologists discovered that color change in Siamese kittens depend on their body temperature. Siamese cats carry albino genes that work only when the body temperature is above 98° F. If these kittens are left in a very warm room, their points won’t darken and they will stay a creamy white."),
-            length: 315,
-        })
-    }
+// Same as `spawn_app_with_grpc`, `overrides` adjusts the settings of this server only
+pub fn spawn_grpc_app_with(db_name: &str, overrides: impl FnOnce(&mut Settings)) -> (String, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
+    let grpc_listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind random port");
 
-    let s1 = HttpServer::new(move || App::new().route("facts", web::get().to(facts_route)).route("fact", web::get().to(random_fact_route)))
-        .bind("0.0.0.0:3333")
-        .expect("woops")
-        .run();
+    let port = listener.local_addr().unwrap().port();
+    let grpc_port = grpc_listener.local_addr().unwrap().port();
+    let settings = Settings::load_with(|settings| {
+        settings.database.name = db_name.to_string();
+        overrides(settings);
+    })
+    .expect("Failed to load settings");
+    let server = tasktracker_backend::run_with_grpc(listener, Some(grpc_listener), settings).expect("Failed to bind address");
 
-    let _ = tokio::spawn(s1);
+    tokio::spawn(server);
 
-    "http://127.0.0.1:3333".to_string()
+    (format!("http://127.0.0.1:{}", port), format!("http://127.0.0.1:{}", grpc_port))
 }
 
 pub fn setup() -> TestContextPostgreSQL {